@@ -1,7 +1,8 @@
 use crate::database::{
-    DatabaseConnection, ConnectionConfig, QueryResult, TableInfo, DatabaseType as LegacyDatabaseType, 
-    mysql::MySQLConnection, redis::RedisConnection, postgresql::PostgreSQLConnection, mongodb::MongoDBConnection,
-    enhanced_types::*, test_adapter::{AdapterConverter}
+    adapter::DatabaseAdapterFactory, enhanced_types::*, redis_pubsub::RedisStreamHandle,
+    redis_batch::RedisTransactionSession,
+    redis_memory::MemoryAnalysisHandle, mongodb_documents::ExtendedJsonMode,
+    mongodb_change_streams::ChangeStreamHandle, mongodb_gridfs::GridFSTransferHandle,
 };
 use crate::ai::{AIService, AIConfig, AIProvider, deepseek::DeepSeekService};
 use crate::mcp::{MCP};
@@ -29,10 +30,20 @@ fn format_bytes(bytes: u64) -> String {
 }
 
 pub struct AppState {
-    pub connections: Mutex<HashMap<String, Box<dyn DatabaseConnection>>>,
+    pub connections: Mutex<HashMap<String, Box<dyn DatabaseAdapter>>>,
     pub connection_configs: Mutex<HashMap<String, ConnectionConfig>>,
     pub ai_service: Mutex<Option<Box<dyn AIService>>>,
     pub mcp_server: Mutex<Option<MCP>>,
+    /// 运行中的Redis订阅和MONITOR，按订阅ID索引
+    pub redis_streams: Mutex<HashMap<String, RedisStreamHandle>>,
+    /// 运行中的Redis内存分析任务，按任务ID索引
+    pub redis_analysis_jobs: Mutex<HashMap<String, MemoryAnalysisHandle>>,
+    /// 进行中的Redis事务会话，按会话ID索引
+    pub redis_transactions: Mutex<HashMap<String, RedisTransactionSession>>,
+    /// 运行中的MongoDB变更流，按监听ID索引
+    pub mongodb_watches: Mutex<HashMap<String, ChangeStreamHandle>>,
+    /// 运行中的GridFS上传/下载任务，按传输ID索引
+    pub gridfs_transfers: Mutex<HashMap<String, GridFSTransferHandle>>,
 }
 
 impl Default for AppState {
@@ -42,11 +53,17 @@ impl Default for AppState {
             connection_configs: Mutex::new(HashMap::new()),
             ai_service: Mutex::new(None),
             mcp_server: Mutex::new(None),
+            redis_streams: Mutex::new(HashMap::new()),
+            redis_analysis_jobs: Mutex::new(HashMap::new()),
+            redis_transactions: Mutex::new(HashMap::new()),
+            mongodb_watches: Mutex::new(HashMap::new()),
+            gridfs_transfers: Mutex::new(HashMap::new()),
         }
     }
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn connect_database(
     db_type: String,
     host: String,
@@ -54,36 +71,24 @@ pub async fn connect_database(
     username: Option<String>,
     password: Option<String>,
     database: Option<String>,
+    options: Option<HashMap<String, String>>,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
     let connection_id = Uuid::new_v4().to_string();
     
     // 重构配置对象
     let config = ConnectionConfig {
-        db_type: match db_type.as_str() {
-            "MySQL" => LegacyDatabaseType::MySQL,
-            "Redis" => LegacyDatabaseType::Redis,
-            "PostgreSQL" => LegacyDatabaseType::PostgreSQL,
-            "MongoDB" => LegacyDatabaseType::MongoDB,
-            _ => return Err("Unsupported database type".to_string()),
-        },
+        db_type: DatabaseType::from_name(&db_type)
+            .ok_or("Unsupported database type")?,
         host,
         port,
         username,
         password,
         database,
-        options: std::collections::HashMap::new(),
-    };
-    
-    let mut connection: Box<dyn DatabaseConnection> = match config.db_type {
-        LegacyDatabaseType::MySQL => Box::new(MySQLConnection::new()),
-        LegacyDatabaseType::Redis => Box::new(RedisConnection::new()),
-        LegacyDatabaseType::PostgreSQL => Box::new(PostgreSQLConnection::new()),
-        LegacyDatabaseType::MongoDB => Box::new(MongoDBConnection::new()),
-        _ => return Err("Unsupported database type".to_string()),
+        options: options.unwrap_or_default(),
     };
     
-    connection.connect(&config).await
+    let connection = DatabaseAdapterFactory::connect(&config).await
         .map_err(|e| format!("连接失败: {}", e))?;
         
     state.connections.lock().await
@@ -104,7 +109,7 @@ pub async fn execute_query(
     let connection = connections.get(&connection_id)
         .ok_or("连接未找到")?;
         
-    connection.execute(&query).await
+    connection.execute_raw(&query).await
         .map_err(|e| format!("查询执行失败: {}", e))
 }
 
@@ -112,11 +117,26 @@ pub async fn execute_query(
 pub async fn get_database_schema(
     connection_id: String,
     state: State<'_, AppState>,
-) -> Result<Vec<TableInfo>, String> {
+) -> Result<Vec<TableSchema>, String> {
     let connections = state.connections.lock().await;
     let connection = connections.get(&connection_id)
         .ok_or("连接未找到")?;
         
+    connection.get_schema().await
+        .map(|schema| schema.tables)
+        .map_err(|e| format!("获取数据库结构失败: {}", e))
+}
+
+/// 获取包含视图、存储过程和函数的完整数据库模式
+#[tauri::command]
+pub async fn get_database_schema_enhanced(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<DatabaseSchema, String> {
+    let connections = state.connections.lock().await;
+    let connection = connections.get(&connection_id)
+        .ok_or("连接未找到")?;
+
     connection.get_schema().await
         .map_err(|e| format!("获取数据库结构失败: {}", e))
 }
@@ -129,38 +149,21 @@ pub async fn test_database_connection(
     username: Option<String>,
     password: Option<String>,
     database: Option<String>,
+    options: Option<HashMap<String, String>>,
 ) -> Result<bool, String> {
     // 重构配置对象
     let config = ConnectionConfig {
-        db_type: match db_type.as_str() {
-            "MySQL" => LegacyDatabaseType::MySQL,
-            "Redis" => LegacyDatabaseType::Redis,
-            "PostgreSQL" => LegacyDatabaseType::PostgreSQL,
-            "MongoDB" => LegacyDatabaseType::MongoDB,
-            _ => return Err("Unsupported database type".to_string()),
-        },
+        db_type: DatabaseType::from_name(&db_type)
+            .ok_or("Unsupported database type")?,
         host,
         port,
         username,
         password,
         database,
-        options: std::collections::HashMap::new(),
-    };
-    let mut connection: Box<dyn DatabaseConnection> = match config.db_type {
-        LegacyDatabaseType::MySQL => Box::new(MySQLConnection::new()),
-        LegacyDatabaseType::Redis => Box::new(RedisConnection::new()),
-        LegacyDatabaseType::PostgreSQL => Box::new(PostgreSQLConnection::new()),
-        LegacyDatabaseType::MongoDB => Box::new(MongoDBConnection::new()),
-        _ => return Err("Unsupported database type".to_string()),
+        options: options.unwrap_or_default(),
     };
-    
-    match connection.connect(&config).await {
-        Ok(_) => {
-            let _ = connection.disconnect().await;
-            Ok(true)
-        }
-        Err(e) => Err(format!("连接测试失败: {}", e)),
-    }
+    DatabaseAdapterFactory::test_connection(&config).await
+        .map_err(|e| format!("连接测试失败: {}", e))
 }
 
 #[tauri::command]
@@ -177,6 +180,28 @@ pub async fn disconnect_database(
     // 同时清理连接配置
     state.connection_configs.lock().await.remove(&connection_id);
     
+    // 停止该连接上的订阅和MONITOR
+    state.redis_streams.lock().await
+        .retain(|_, handle| handle.info.connection_id != connection_id);
+    state.redis_analysis_jobs.lock().await
+        .retain(|_, job| {
+            if job.connection_id == connection_id {
+                job.cancel();
+            }
+            job.connection_id != connection_id
+        });
+    state.redis_transactions.lock().await
+        .retain(|_, session| session.info.connection_id != connection_id);
+    state.mongodb_watches.lock().await
+        .retain(|_, handle| handle.info.connection_id != connection_id);
+    state.gridfs_transfers.lock().await
+        .retain(|_, transfer| {
+            if transfer.connection_id == connection_id {
+                transfer.cancel();
+            }
+            transfer.connection_id != connection_id
+        });
+    
     Ok(())
 }
 
@@ -317,19 +342,7 @@ pub async fn get_databases_2(
     let database_names = connection.get_databases().await
         .map_err(|e| format!("获取数据库列表失败: {}", e))?;
     
-    // 从连接配置中获取数据库类型
-    let configs = state.connection_configs.lock().await;
-    let config = configs.get(&connection_id)
-        .ok_or("连接配置未找到")?;
-    
-    // 转换数据库类型
-    let db_type = match config.db_type {
-        LegacyDatabaseType::MySQL => DatabaseType::MySQL,
-        LegacyDatabaseType::PostgreSQL => DatabaseType::PostgreSQL,
-        LegacyDatabaseType::Redis => DatabaseType::Redis,
-        LegacyDatabaseType::MongoDB => DatabaseType::MongoDB,
-        LegacyDatabaseType::SQLite => DatabaseType::SQLite,
-    };
+    let db_type = connection.db_type();
     
     // 构建轻量级数据库信息（不获取具体表信息）
     let mut databases = Vec::new();
@@ -399,19 +412,7 @@ pub async fn get_database_tables(
     let connection = connections.get_mut(&connection_id)
         .ok_or("连接未找到")?;
     
-    // 从连接配置中获取数据库类型
-    let configs = state.connection_configs.lock().await;
-    let config = configs.get(&connection_id)
-        .ok_or("连接配置未找到")?;
-    
-    // 转换数据库类型
-    let db_type = match config.db_type {
-        LegacyDatabaseType::MySQL => DatabaseType::MySQL,
-        LegacyDatabaseType::PostgreSQL => DatabaseType::PostgreSQL,
-        LegacyDatabaseType::Redis => DatabaseType::Redis,
-        LegacyDatabaseType::MongoDB => DatabaseType::MongoDB,
-        LegacyDatabaseType::SQLite => DatabaseType::SQLite,
-    };
+    let db_type = connection.db_type();
     
     // 根据数据库类型处理不同的结构
     let (tables, views, procedures, functions, redis_keys, mongodb_collections) = match db_type {
@@ -425,7 +426,7 @@ pub async fn get_database_tables(
             
             println!("Redis: 成功切换到数据库 {}", database_name);
                 
-            let keys_result = connection.execute("KEYS *").await
+            let keys_result = connection.execute_raw("KEYS *").await
                 .map_err(|e| format!("获取Redis键列表失败: {}", e))?;
             
             println!("Redis: 在数据库 {} 中找到 {} 个键", database_name, keys_result.rows.len());
@@ -461,7 +462,8 @@ pub async fn get_database_tables(
             
             // 获取表结构
             let tables_info = connection.get_schema().await
-                .map_err(|e| format!("获取表结构失败: {}", e))?;
+                .map_err(|e| format!("获取表结构失败: {}", e))?
+                .tables;
             
             let tables: Vec<TableNode> = tables_info.into_iter().map(|table| {
                 let size_bytes = 1024 * 50; // 50KB 默认大小
@@ -484,7 +486,8 @@ pub async fn get_database_tables(
                 .map_err(|e| format!("选择数据库失败: {}", e))?;
             
             let tables_info = connection.get_schema().await
-                .map_err(|e| format!("获取表结构失败: {}", e))?;
+                .map_err(|e| format!("获取表结构失败: {}", e))?
+                .tables;
             
             let tables: Vec<TableNode> = tables_info.into_iter().map(|table| {
                 let size_bytes = 1024 * 10;
@@ -687,94 +690,77 @@ pub async fn mcp_health_check(state: State<'_, AppState>) -> Result<serde_json::
 
 // ===== 新增：增强版数据库命令 =====
 
-/// 执行查询并返回增强结果（测试版）
+/// 执行查询并返回增强结果
 #[tauri::command]
 pub async fn execute_query_enhanced(
     connection_id: String,
     query: String,
+    extended_json: Option<ExtendedJsonMode>,
     state: State<'_, AppState>,
 ) -> Result<EnhancedQueryResult, String> {
     let connections = state.connections.lock().await;
     let connection = connections.get(&connection_id)
         .ok_or("连接未找到")?;
     
-    // 从连接配置中获取数据库类型
-    let configs = state.connection_configs.lock().await;
-    let config = configs.get(&connection_id)
-        .ok_or("连接配置未找到")?;
-    
-    // 转换数据库类型从Legacy到Enhanced
-    let db_type = match config.db_type {
-        LegacyDatabaseType::MySQL => DatabaseType::MySQL,
-        LegacyDatabaseType::PostgreSQL => DatabaseType::PostgreSQL,
-        LegacyDatabaseType::Redis => DatabaseType::Redis,
-        LegacyDatabaseType::MongoDB => DatabaseType::MongoDB,
-        LegacyDatabaseType::SQLite => DatabaseType::SQLite,
-    };
-    
-    // 使用测试适配器包装现有连接
-    let legacy_result = connection.execute(&query).await
-        .map_err(|e| format!("查询执行失败: {}", e))?;
-    
-    // 转换为增强结果
-    let enhanced_result = AdapterConverter::from_legacy_result(legacy_result, db_type);
+    // MongoDB 可以指定扩展 JSON 模式
+    if let (Some(mongodb), Some(mode)) = (connection.as_mongodb(), extended_json) {
+        return mongodb.query_documents(&query, mode).await
+            .map_err(|e| format!("查询执行失败: {}", e));
+    }
     
-    Ok(enhanced_result)
+    connection.execute(&query).await
+        .map_err(|e| format!("查询执行失败: {}", e))
 }
 
 /// 获取UI配置信息
 #[tauri::command]
 pub async fn get_ui_config(
-    _connection_id: String,
+    connection_id: String,
     db_type: String,
-    _state: State<'_, AppState>,
+    state: State<'_, AppState>,
 ) -> Result<DatabaseUIConfig, String> {
-    let parsed_db_type = match db_type.as_str() {
-        "MySQL" => DatabaseType::MySQL,
-        "PostgreSQL" => DatabaseType::PostgreSQL,
-        "Redis" => DatabaseType::Redis,
-        "MongoDB" => DatabaseType::MongoDB,
-        "SQLite" => DatabaseType::SQLite,
-        _ => return Err("不支持的数据库类型".to_string()),
-    };
-    
-    Ok(AdapterConverter::get_ui_config_for_db_type(parsed_db_type))
+    let parsed_db_type = DatabaseType::from_name(&db_type)
+        .ok_or("不支持的数据库类型")?;
+
+    // 已连接且类型一致时由适配器提供配置，否则返回该类型的默认配置
+    let connections = state.connections.lock().await;
+    match connections.get(&connection_id) {
+        Some(connection) if connection.db_type() == parsed_db_type => Ok(connection.get_ui_config()),
+        _ => Ok(DatabaseUIConfig::for_db_type(&parsed_db_type)),
+    }
 }
 
-/// 获取查询建议（基础版）
+/// 获取查询建议，未连接时按数据库类型返回默认建议
 #[tauri::command]
 pub async fn get_query_suggestions(
-    _connection_id: String,
-    _context: String,
-    _state: State<'_, AppState>,
+    connection_id: String,
+    context: String,
+    db_type: Option<String>,
+    state: State<'_, AppState>,
 ) -> Result<Vec<QuerySuggestion>, String> {
-    // 返回基础查询建议
-    Ok(vec![
-        QuerySuggestion {
-            text: "SELECT * FROM".to_string(),
-            description: "查询表数据".to_string(),
-            category: SuggestionCategory::Keyword,
-            score: 100,
-        },
-        QuerySuggestion {
-            text: "INSERT INTO".to_string(),
-            description: "插入数据".to_string(),
-            category: SuggestionCategory::Keyword,
-            score: 90,
-        },
-        QuerySuggestion {
-            text: "UPDATE".to_string(),
-            description: "更新数据".to_string(),
-            category: SuggestionCategory::Keyword,
-            score: 85,
-        },
-        QuerySuggestion {
-            text: "DELETE FROM".to_string(),
-            description: "删除数据".to_string(),
-            category: SuggestionCategory::Keyword,
-            score: 80,
-        },
-    ])
+    let connections = state.connections.lock().await;
+    if let Some(connection) = connections.get(&connection_id) {
+        return Ok(connection.get_query_suggestions(&context));
+    }
+
+    let parsed_db_type = db_type.as_deref()
+        .and_then(DatabaseType::from_name)
+        .ok_or("连接未找到")?;
+    Ok(QuerySuggestion::defaults_for(&parsed_db_type))
+}
+
+/// 获取实时统计信息，不支持的数据库返回 None
+#[tauri::command]
+pub async fn get_real_time_stats(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<DatabaseStats>, String> {
+    let connections = state.connections.lock().await;
+    let connection = connections.get(&connection_id)
+        .ok_or("连接未找到")?;
+
+    connection.get_real_time_stats().await
+        .map_err(|e| format!("获取实时统计失败: {}", e))
 }
 
 // ===== 新增：数据库结构导航API =====
@@ -798,14 +784,7 @@ pub async fn get_database_structure(
     let config = configs.get(&connection_id)
         .ok_or("连接配置未找到")?;
     
-    // 转换数据库类型从Legacy到Enhanced
-    let db_type = match config.db_type {
-        LegacyDatabaseType::MySQL => DatabaseType::MySQL,
-        LegacyDatabaseType::PostgreSQL => DatabaseType::PostgreSQL,
-        LegacyDatabaseType::Redis => DatabaseType::Redis,
-        LegacyDatabaseType::MongoDB => DatabaseType::MongoDB,
-        LegacyDatabaseType::SQLite => DatabaseType::SQLite,
-    };
+    let db_type = config.db_type.clone();
     
     let mut database_nodes = Vec::new();
     
@@ -818,7 +797,7 @@ pub async fn get_database_structure(
                 connection.use_database(&db_name).await
                     .map_err(|e| format!("切换Redis数据库失败: {}", e))?;
                     
-                let keys_result = connection.execute("KEYS *").await
+                let keys_result = connection.execute_raw("KEYS *").await
                     .map_err(|e| format!("获取Redis键列表失败: {}", e))?;
                 
                 // 先获取键的总数
@@ -851,7 +830,8 @@ pub async fn get_database_structure(
             DatabaseType::MySQL | DatabaseType::PostgreSQL => {
                 // 获取表信息
                 let tables_info = connection.get_schema().await
-                    .map_err(|e| format!("获取表结构失败: {}", e))?;
+                    .map_err(|e| format!("获取表结构失败: {}", e))?
+                    .tables;
                 
                 let tables: Vec<TableNode> = tables_info.into_iter().map(|table| {
                     // 对于SQL数据库，使用合理的默认值
@@ -873,7 +853,8 @@ pub async fn get_database_structure(
             _ => {
                 // 其他数据库类型，使用默认处理
                 let tables_info = connection.get_schema().await
-                    .map_err(|e| format!("获取表结构失败: {}", e))?;
+                    .map_err(|e| format!("获取表结构失败: {}", e))?
+                    .tables;
                 
                 let tables: Vec<TableNode> = tables_info.into_iter().map(|table| {
                     let (row_count, size_bytes) = (None, 1024 * 10); // 10KB 默认大小
@@ -974,55 +955,15 @@ pub async fn get_redis_structure(
 #[tauri::command]
 pub async fn get_mongodb_structure(
     connection_id: String,
-    _database_name: String,
+    database_name: String,
     state: State<'_, AppState>,
 ) -> Result<MongoCollectionInfo, String> {
     let connections = state.connections.lock().await;
-    let _connection = connections.get(&connection_id)
-        .ok_or("连接未找到")?;
+    let connection = connections.get(&connection_id)
+        .ok_or("连接未找到")?
+        .as_mongodb()
+        .ok_or("该连接不是MongoDB连接")?;
 
-    // 模拟MongoDB集合信息
-    Ok(MongoCollectionInfo {
-        collections: vec![
-            MongoCollectionNode {
-                name: "users".to_string(),
-                document_count: Some(1500),
-                size: Some(1024 * 512), // 512KB
-                indexes: vec![
-                    IndexInfo {
-                        name: "_id_".to_string(),
-                        columns: vec!["_id".to_string()],
-                        unique: true,
-                        index_type: "btree".to_string(),
-                    },
-                    IndexInfo {
-                        name: "email_1".to_string(),
-                        columns: vec!["email".to_string()],
-                        unique: true,
-                        index_type: "btree".to_string(),
-                    },
-                ],
-            },
-            MongoCollectionNode {
-                name: "products".to_string(),
-                document_count: Some(800),
-                size: Some(1024 * 256), // 256KB
-                indexes: vec![
-                    IndexInfo {
-                        name: "_id_".to_string(),
-                        columns: vec!["_id".to_string()],
-                        unique: true,
-                        index_type: "btree".to_string(),
-                    },
-                ],
-            },
-        ],
-        gridfs_buckets: vec![
-            GridFSBucketNode {
-                name: "uploads".to_string(),
-                file_count: Some(42),
-                total_size: Some(1024 * 1024 * 50), // 50MB
-            },
-        ],
-    })
+    connection.collection_structure(Some(&database_name)).await
+        .map_err(|e| format!("获取MongoDB结构失败: {}", e))
 }
\ No newline at end of file
@@ -0,0 +1,138 @@
+use super::*;
+use async_trait::async_trait;
+
+// 只实现必需方法的模拟适配器，用于验证默认实现
+struct MockAdapter {
+    db_type: DatabaseType,
+    connected: bool,
+}
+
+#[async_trait]
+impl DatabaseAdapter for MockAdapter {
+    fn db_type(&self) -> DatabaseType {
+        self.db_type.clone()
+    }
+
+    async fn connect(&mut self, _config: &ConnectionConfig) -> Result<()> {
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.connected = false;
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    async fn execute_raw(&self, _query: &str) -> Result<QueryResult> {
+        Ok(QueryResult {
+            columns: vec!["id".to_string(), "name".to_string()],
+            rows: vec![
+                vec!["1".to_string(), "Alice".to_string()],
+                vec!["2".to_string(), "NULL".to_string()],
+            ],
+            affected_rows: 2,
+            execution_time: 50,
+        })
+    }
+
+    async fn get_schema(&self) -> Result<DatabaseSchema> {
+        Ok(DatabaseSchema::new("test_db", vec![TableSchema::new("users", vec![
+            ColumnInfo::new("id", "INT", false, true),
+            ColumnInfo::new("name", "VARCHAR(255)", true, false),
+        ])]))
+    }
+}
+
+fn mock(db_type: DatabaseType) -> MockAdapter {
+    MockAdapter { db_type, connected: false }
+}
+
+#[tokio::test]
+async fn test_default_execute() {
+    let result = mock(DatabaseType::MySQL).execute("SELECT * FROM users").await.unwrap();
+
+    assert_eq!(result.db_type, DatabaseType::MySQL);
+    assert_eq!(result.execution_time, 50);
+    assert_eq!(result.metadata.query, "SELECT * FROM users");
+    match result.data {
+        QueryData::Relational { columns, rows, affected_rows, .. } => {
+            assert_eq!(columns.len(), 2);
+            assert_eq!(columns[1].name, "name");
+            assert!(matches!(&rows[0][1], CellValue::String(name) if name == "Alice"));
+            assert!(rows[1][1].is_null());
+            assert_eq!(affected_rows, 2);
+        }
+        other => panic!("expected relational data, got {:?}", other),
+    }
+
+    // 表格结果总是使用表格视图，即使数据库默认是键值视图
+    let result = mock(DatabaseType::Redis).execute("GET a").await.unwrap();
+    assert_eq!(result.ui_config.display_mode, DisplayMode::Table);
+    assert_eq!(result.ui_config.editor_config.language, "redis");
+}
+
+#[tokio::test]
+async fn test_default_methods() {
+    let mut adapter = mock(DatabaseType::MySQL);
+    assert!(adapter.get_databases().await.unwrap().is_empty());
+    assert!(adapter.use_database("other").await.is_ok());
+    assert!(adapter.get_real_time_stats().await.unwrap().is_none());
+    assert!(adapter.as_redis().is_none() && adapter.as_mongodb().is_none());
+
+    let schema = adapter.get_schema().await.unwrap();
+    assert_eq!(schema.database_name, "test_db");
+    assert_eq!(schema.tables[0].columns.len(), 2);
+    assert!(schema.tables[0].columns[0].primary_key);
+
+    let suggestions = adapter.get_query_suggestions("");
+    assert_eq!(suggestions[0].text, "SELECT * FROM");
+    let suggestions = mock(DatabaseType::Redis).get_query_suggestions("");
+    assert!(suggestions.iter().all(|s| s.category == SuggestionCategory::Command));
+}
+
+#[test]
+fn test_ui_config_for_db_type() {
+    let mysql = mock(DatabaseType::MySQL).get_ui_config();
+    assert_eq!(mysql.display_mode, DisplayMode::Table);
+    assert!(mysql.supported_operations.contains(&Operation::Select));
+    assert!(mysql.export_formats.contains(&ExportFormat::Excel));
+    assert!(!mysql.monitoring_capable);
+    assert_eq!(mysql.theme.unwrap().primary_color, "#00758f");
+
+    let redis = mock(DatabaseType::Redis).get_ui_config();
+    assert_eq!(redis.display_mode, DisplayMode::KeyValue);
+    assert!(redis.supported_operations.contains(&Operation::Monitor));
+    assert!(redis.monitoring_capable);
+    assert_eq!(redis.theme.unwrap().icon, "🔴");
+
+    let mongodb = mock(DatabaseType::MongoDB).get_ui_config();
+    assert_eq!(mongodb.display_mode, DisplayMode::Document);
+    assert_eq!(mongodb.editor_config.language, "mongodb");
+    assert!(mongodb.export_formats.contains(&ExportFormat::MongoDB));
+}
+
+#[test]
+fn test_create_adapter() {
+    for db_type in [DatabaseType::MySQL, DatabaseType::PostgreSQL, DatabaseType::Redis, DatabaseType::MongoDB] {
+        let adapter = DatabaseAdapterFactory::create_adapter(&db_type).unwrap();
+        assert_eq!(adapter.db_type(), db_type);
+        assert!(!adapter.is_connected());
+    }
+    let redis = DatabaseAdapterFactory::create_adapter(&DatabaseType::Redis).unwrap();
+    assert!(redis.as_redis().is_some() && redis.as_mongodb().is_none());
+
+    let error = DatabaseAdapterFactory::create_adapter(&DatabaseType::SQLite).err().unwrap();
+    assert!(error.to_string().contains("暂不支持"));
+}
+
+#[test]
+fn test_database_type_from_name() {
+    assert_eq!(DatabaseType::from_name("MySQL"), Some(DatabaseType::MySQL));
+    assert_eq!(DatabaseType::from_name("PostgreSQL"), Some(DatabaseType::PostgreSQL));
+    assert_eq!(DatabaseType::from_name("MongoDB"), Some(DatabaseType::MongoDB));
+    assert_eq!(DatabaseType::from_name("mysql"), None);
+}
@@ -1,3 +1,5 @@
+use super::mongodb::MongoDBConnection;
+use super::redis::RedisConnection;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -64,6 +66,48 @@ pub struct QueryMetadata {
     pub warnings: Vec<String>,
 }
 
+impl EnhancedQueryResult {
+    /// 把表格结果包装为 Relational 数据，字符串 "NULL" 视为空值
+    pub fn from_table(db_type: DatabaseType, query: &str, result: QueryResult) -> Self {
+        let mut ui_config = DatabaseUIConfig::for_db_type(&db_type);
+        ui_config.display_mode = DisplayMode::Table;
+
+        EnhancedQueryResult {
+            db_type,
+            data: QueryData::Relational {
+                columns: result.columns.into_iter()
+                    .map(|name| ColumnInfo::new(name, "VARCHAR", true, false))
+                    .collect(),
+                rows: result.rows.into_iter()
+                    .map(|row| row.into_iter()
+                        .map(|cell| if cell == "NULL" { CellValue::Null } else { CellValue::String(cell) })
+                        .collect())
+                    .collect(),
+                total_rows: None,
+                affected_rows: result.affected_rows,
+                schema_info: None,
+            },
+            metadata: QueryMetadata {
+                query: query.to_string(),
+                timestamp: Utc::now(),
+                execution_plan: None,
+                warnings: vec![],
+            },
+            execution_time: result.execution_time,
+            ui_config,
+        }
+    }
+}
+
+/// 表格形式的查询结果，所有值已格式化为字符串
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub affected_rows: u64,
+    pub execution_time: u64, // milliseconds
+}
+
 // ===== 单元格值类型 =====
 
 /// 表示数据库中不同类型的值
@@ -119,6 +163,18 @@ pub enum DatabaseType {
 }
 
 impl DatabaseType {
+    /// 根据前端传入的名称识别类型，例如 "MySQL"、"MongoDB"
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "MySQL" => DatabaseType::MySQL,
+            "PostgreSQL" => DatabaseType::PostgreSQL,
+            "Redis" => DatabaseType::Redis,
+            "MongoDB" => DatabaseType::MongoDB,
+            "SQLite" => DatabaseType::SQLite,
+            _ => return None,
+        })
+    }
+
     /// 获取显示名称
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -249,9 +305,33 @@ pub struct MongoCollectionInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MongoCollectionNode {
     pub name: String,
+    /// collection、view 或 timeseries
+    pub collection_type: String,
     pub document_count: Option<u64>,
     pub size: Option<u64>,
-    pub indexes: Vec<IndexInfo>,
+    /// collStats 统计，视图没有
+    pub stats: Option<CollectionStats>,
+    pub indexes: Vec<MongoIndexInfo>,
+}
+
+/// MongoDB 索引信息，columns/unique/index_type 与 IndexInfo 保持一致
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MongoIndexInfo {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+    /// btree、text、2dsphere、2d、hashed、wildcard 等
+    pub index_type: String,
+    /// 键模式，例如 {"email": 1, "created_at": -1}
+    pub key: serde_json::Value,
+    pub sparse: bool,
+    pub hidden: bool,
+    /// TTL 索引的过期秒数
+    pub ttl_seconds: Option<u64>,
+    /// 部分索引的过滤条件
+    pub partial_filter: Option<serde_json::Value>,
+    /// 索引大小（字节）
+    pub size: Option<u64>,
 }
 
 /// GridFS 桶节点
@@ -260,6 +340,7 @@ pub struct GridFSBucketNode {
     pub name: String,
     pub file_count: Option<u64>,
     pub total_size: Option<u64>,
+    pub chunk_count: Option<u64>,
 }
 
 /// 大小信息
@@ -300,6 +381,23 @@ pub struct ColumnInfo {
     pub extra: HashMap<String, String>,
 }
 
+impl ColumnInfo {
+    /// 只有基本信息的列，其余元数据留空
+    pub fn new(name: impl Into<String>, data_type: impl Into<String>, nullable: bool, primary_key: bool) -> Self {
+        Self {
+            name: name.into(),
+            data_type: data_type.into(),
+            nullable,
+            primary_key,
+            auto_increment: false,
+            default_value: None,
+            charset: None,
+            comment: None,
+            extra: HashMap::new(),
+        }
+    }
+}
+
 /// 表模式信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableSchema {
@@ -312,6 +410,21 @@ pub struct TableSchema {
     pub charset: Option<String>,
 }
 
+impl TableSchema {
+    /// 只有列信息的表，索引和外键留空
+    pub fn new(name: impl Into<String>, columns: Vec<ColumnInfo>) -> Self {
+        Self {
+            name: name.into(),
+            columns,
+            indexes: vec![],
+            foreign_keys: vec![],
+            table_comment: None,
+            engine: None,
+            charset: None,
+        }
+    }
+}
+
 /// 索引信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexInfo {
@@ -356,6 +469,39 @@ pub enum RedisDataType {
     Stream,
     HyperLogLog,
     Bitmap,
+    // Redis Stack 模块类型
+    Json,
+    TimeSeries,
+    BloomFilter,
+    CuckooFilter,
+    CountMinSketch,
+    TopK,
+    TDigest,
+    /// 其他模块注册的类型，保留 TYPE 返回的原始名称
+    Module(String),
+}
+
+impl RedisDataType {
+    /// 根据 TYPE 命令的返回值识别类型，键不存在（none）时返回 None
+    pub fn from_type_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "string" => RedisDataType::String,
+            "hash" => RedisDataType::Hash,
+            "list" => RedisDataType::List,
+            "set" => RedisDataType::Set,
+            "zset" => RedisDataType::ZSet,
+            "stream" => RedisDataType::Stream,
+            "ReJSON-RL" => RedisDataType::Json,
+            "TSDB-TYPE" => RedisDataType::TimeSeries,
+            "MBbloom--" => RedisDataType::BloomFilter,
+            "MBbloomCF" => RedisDataType::CuckooFilter,
+            "CMSk-TYPE" => RedisDataType::CountMinSketch,
+            "TopK-TYPE" => RedisDataType::TopK,
+            "TDIS-TYPE" => RedisDataType::TDigest,
+            "none" | "" => return None,
+            other => RedisDataType::Module(other.to_string()),
+        })
+    }
 }
 
 /// Redis 值类型
@@ -370,6 +516,16 @@ pub enum RedisValue {
     Stream { entries: Vec<StreamEntry> },
     HyperLogLog { cardinality: u64 },
     Bitmap { bits: String },
+    Json { document: serde_json::Value },
+    TimeSeries { samples: Vec<TimeSeriesSample> },
+}
+
+/// 时间序列的一个采样点
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeSeriesSample {
+    /// Unix 毫秒时间戳
+    pub timestamp: i64,
+    pub value: f64,
 }
 
 /// Redis Stream 条目
@@ -476,6 +632,7 @@ pub enum Operation {
     UpdateMany,
     DeleteOne,
     DeleteMany,
+    BulkWrite,
     Aggregate,
     CreateCollection,
     DropCollection,
@@ -518,31 +675,186 @@ pub struct DatabaseTheme {
     pub icon: String,
 }
 
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+fn theme(primary_color: &str, accent_color: &str, icon: &str) -> Option<DatabaseTheme> {
+    Some(DatabaseTheme {
+        primary_color: primary_color.to_string(),
+        accent_color: accent_color.to_string(),
+        icon: icon.to_string(),
+    })
+}
+
+impl DatabaseUIConfig {
+    /// 各数据库类型的默认界面配置
+    pub fn for_db_type(db_type: &DatabaseType) -> Self {
+        match db_type {
+            DatabaseType::MySQL => DatabaseUIConfig {
+                display_mode: DisplayMode::Table,
+                supported_operations: vec![
+                    Operation::Select, Operation::Insert, Operation::Update, Operation::Delete,
+                    Operation::CreateTable, Operation::DropTable, Operation::AlterTable,
+                    Operation::CreateIndex, Operation::DropIndex,
+                ],
+                editor_config: EditorConfig {
+                    language: "mysql".to_string(),
+                    auto_complete: true,
+                    syntax_highlighting: true,
+                    keywords: strings(&[
+                        "SELECT", "FROM", "WHERE", "INSERT", "UPDATE", "DELETE",
+                        "CREATE", "DROP", "ALTER", "TABLE", "INDEX", "DATABASE",
+                    ]),
+                    functions: strings(&["COUNT", "SUM", "AVG", "MAX", "MIN", "NOW"]),
+                    operators: strings(&["=", "!=", "<", ">", "<=", ">=", "LIKE", "IN"]),
+                },
+                export_formats: vec![ExportFormat::CSV, ExportFormat::Excel, ExportFormat::JSON, ExportFormat::SQL],
+                monitoring_capable: false,
+                theme: theme("#00758f", "#f29111", "🗄️"),
+            },
+            DatabaseType::PostgreSQL => DatabaseUIConfig {
+                display_mode: DisplayMode::Table,
+                supported_operations: vec![
+                    Operation::Select, Operation::Insert, Operation::Update, Operation::Delete,
+                    Operation::CreateTable, Operation::DropTable, Operation::AlterTable,
+                    Operation::CreateIndex, Operation::DropIndex,
+                ],
+                editor_config: EditorConfig {
+                    language: "postgresql".to_string(),
+                    auto_complete: true,
+                    syntax_highlighting: true,
+                    keywords: strings(&[
+                        "SELECT", "FROM", "WHERE", "INSERT", "UPDATE", "DELETE",
+                        "CREATE", "DROP", "ALTER", "TABLE", "INDEX", "RETURNING", "WITH",
+                    ]),
+                    functions: strings(&["COUNT", "SUM", "AVG", "MAX", "MIN", "NOW", "COALESCE"]),
+                    operators: strings(&["=", "!=", "<", ">", "<=", ">=", "LIKE", "ILIKE", "IN"]),
+                },
+                export_formats: vec![ExportFormat::CSV, ExportFormat::Excel, ExportFormat::JSON, ExportFormat::SQL],
+                monitoring_capable: false,
+                theme: theme("#336791", "#ffffff", "🐘"),
+            },
+            DatabaseType::Redis => DatabaseUIConfig {
+                display_mode: DisplayMode::KeyValue,
+                supported_operations: vec![
+                    Operation::Get, Operation::Set, Operation::Keys, Operation::Monitor,
+                    Operation::FlushDB, Operation::Info,
+                ],
+                editor_config: EditorConfig {
+                    language: "redis".to_string(),
+                    auto_complete: true,
+                    syntax_highlighting: true,
+                    keywords: strings(&["GET", "SET", "KEYS", "DEL", "EXPIRE", "TTL", "HGETALL", "LRANGE", "SMEMBERS", "INFO"]),
+                    functions: vec![],
+                    operators: vec![],
+                },
+                export_formats: vec![ExportFormat::JSON, ExportFormat::Redis],
+                monitoring_capable: true,
+                theme: theme("#d82c20", "#ffffff", "🔴"),
+            },
+            DatabaseType::MongoDB => DatabaseUIConfig {
+                display_mode: DisplayMode::Document,
+                supported_operations: vec![
+                    Operation::Find, Operation::InsertOne, Operation::InsertMany,
+                    Operation::UpdateOne, Operation::UpdateMany, Operation::DeleteOne, Operation::DeleteMany,
+                    Operation::BulkWrite, Operation::Aggregate, Operation::CreateIndex, Operation::DropIndex,
+                    Operation::CreateCollection, Operation::DropCollection,
+                ],
+                editor_config: EditorConfig {
+                    language: "mongodb".to_string(),
+                    auto_complete: true,
+                    syntax_highlighting: true,
+                    keywords: strings(&["db", "find", "findOne", "aggregate", "insertOne", "insertMany", "updateOne", "updateMany", "deleteOne", "deleteMany"]),
+                    functions: vec![],
+                    operators: strings(&["$eq", "$ne", "$gt", "$gte", "$lt", "$lte", "$in", "$nin", "$and", "$or", "$regex"]),
+                },
+                export_formats: vec![ExportFormat::JSON, ExportFormat::MongoDB],
+                monitoring_capable: false,
+                theme: theme("#47a248", "#ffffff", "🍃"),
+            },
+            DatabaseType::SQLite => DatabaseUIConfig {
+                display_mode: DisplayMode::Table,
+                supported_operations: vec![Operation::Select],
+                editor_config: EditorConfig {
+                    language: "sqlite".to_string(),
+                    auto_complete: false,
+                    syntax_highlighting: false,
+                    keywords: vec![],
+                    functions: vec![],
+                    operators: vec![],
+                },
+                export_formats: vec![ExportFormat::CSV],
+                monitoring_capable: false,
+                theme: None,
+            },
+        }
+    }
+}
+
 // ===== 数据库适配器 Trait =====
 
-/// 数据库适配器接口
+/// 数据库适配器接口，所有数据库连接都实现该接口
 #[async_trait]
 pub trait DatabaseAdapter: Send + Sync {
-    /// 执行查询并返回增强的结果
-    async fn execute(&self, query: &str) -> anyhow::Result<EnhancedQueryResult>;
-    
-    /// 获取数据库模式信息
+    /// 数据库类型
+    fn db_type(&self) -> DatabaseType;
+
+    /// 建立连接
+    async fn connect(&mut self, config: &ConnectionConfig) -> anyhow::Result<()>;
+
+    /// 断开连接
+    async fn disconnect(&mut self) -> anyhow::Result<()>;
+
+    /// 是否已连接
+    fn is_connected(&self) -> bool;
+
+    /// 执行查询并返回表格形式的结果
+    async fn execute_raw(&self, query: &str) -> anyhow::Result<QueryResult>;
+
+    /// 执行查询并返回增强的结果，默认把表格结果包装为 Relational 数据
+    async fn execute(&self, query: &str) -> anyhow::Result<EnhancedQueryResult> {
+        let result = self.execute_raw(query).await?;
+        Ok(EnhancedQueryResult::from_table(self.db_type(), query, result))
+    }
+
+    /// 获取当前数据库的模式信息，未选择数据库时 tables 为数据库列表
     async fn get_schema(&self) -> anyhow::Result<DatabaseSchema>;
-    
+
+    /// 获取数据库列表
+    async fn get_databases(&self) -> anyhow::Result<Vec<String>> {
+        Ok(vec![])
+    }
+
+    /// 切换当前数据库
+    async fn use_database(&mut self, _database_name: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     /// 获取查询建议
-    fn get_query_suggestions(&self, context: &str) -> Vec<QuerySuggestion>;
-    
+    fn get_query_suggestions(&self, _context: &str) -> Vec<QuerySuggestion> {
+        QuerySuggestion::defaults_for(&self.db_type())
+    }
+
     /// 获取UI配置
-    fn get_ui_config(&self) -> DatabaseUIConfig;
-    
-    /// 获取实时统计信息（可选）
-    async fn get_real_time_stats(&self) -> anyhow::Result<Option<DatabaseStats>>;
-    
-    /// 测试连接
-    async fn test_connection(&self) -> anyhow::Result<bool>;
-    
-    /// 关闭连接
-    async fn close(&self) -> anyhow::Result<()>;
+    fn get_ui_config(&self) -> DatabaseUIConfig {
+        DatabaseUIConfig::for_db_type(&self.db_type())
+    }
+
+    /// 获取实时统计信息，不支持时返回 None
+    async fn get_real_time_stats(&self) -> anyhow::Result<Option<DatabaseStats>> {
+        Ok(None)
+    }
+
+    /// 获取Redis连接，供Redis特定的扩展命令使用
+    fn as_redis(&self) -> Option<&RedisConnection> {
+        None
+    }
+
+    /// 获取MongoDB连接，供MongoDB特定的扩展命令使用
+    fn as_mongodb(&self) -> Option<&MongoDBConnection> {
+        None
+    }
 }
 
 /// 数据库模式
@@ -555,6 +867,19 @@ pub struct DatabaseSchema {
     pub functions: Vec<FunctionInfo>,
 }
 
+impl DatabaseSchema {
+    /// 只有表的模式，视图、存储过程和函数留空
+    pub fn new(database_name: impl Into<String>, tables: Vec<TableSchema>) -> Self {
+        Self {
+            database_name: database_name.into(),
+            tables,
+            views: vec![],
+            procedures: vec![],
+            functions: vec![],
+        }
+    }
+}
+
 /// 视图信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ViewInfo {
@@ -588,6 +913,34 @@ pub struct QuerySuggestion {
     pub score: u32,
 }
 
+impl QuerySuggestion {
+    fn new(text: &str, description: &str, category: SuggestionCategory, score: u32) -> Self {
+        Self { text: text.to_string(), description: description.to_string(), category, score }
+    }
+
+    /// 各数据库类型的基础查询建议
+    pub fn defaults_for(db_type: &DatabaseType) -> Vec<Self> {
+        match db_type {
+            DatabaseType::MySQL | DatabaseType::PostgreSQL | DatabaseType::SQLite => vec![
+                Self::new("SELECT * FROM", "查询表数据", SuggestionCategory::Keyword, 100),
+                Self::new("INSERT INTO", "插入数据", SuggestionCategory::Keyword, 90),
+                Self::new("UPDATE", "更新数据", SuggestionCategory::Keyword, 85),
+                Self::new("DELETE FROM", "删除数据", SuggestionCategory::Keyword, 80),
+            ],
+            DatabaseType::Redis => vec![
+                Self::new("GET", "获取键值", SuggestionCategory::Command, 100),
+                Self::new("SET", "设置键值", SuggestionCategory::Command, 95),
+                Self::new("KEYS", "查找键", SuggestionCategory::Command, 90),
+            ],
+            DatabaseType::MongoDB => vec![
+                Self::new("db.collection.find({})", "查询文档", SuggestionCategory::Command, 100),
+                Self::new("db.collection.aggregate([])", "执行聚合管道", SuggestionCategory::Command, 90),
+                Self::new("db.collection.insertOne({})", "插入文档", SuggestionCategory::Command, 85),
+            ],
+        }
+    }
+}
+
 /// 建议类别
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum SuggestionCategory {
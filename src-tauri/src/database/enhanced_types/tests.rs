@@ -367,4 +367,16 @@ mod tests {
             _ => panic!("Expected zset value"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_redis_data_type_from_type_name() {
+        assert!(matches!(RedisDataType::from_type_name("zset"), Some(RedisDataType::ZSet)));
+        assert!(matches!(RedisDataType::from_type_name("ReJSON-RL"), Some(RedisDataType::Json)));
+        assert!(matches!(RedisDataType::from_type_name("TSDB-TYPE"), Some(RedisDataType::TimeSeries)));
+        assert!(matches!(
+            RedisDataType::from_type_name("graphdata"),
+            Some(RedisDataType::Module(name)) if name == "graphdata"
+        ));
+        assert!(RedisDataType::from_type_name("none").is_none());
+    }
+}
@@ -1,75 +1,37 @@
-use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+// 所有数据库连接实现 enhanced_types 中的 DatabaseAdapter，
+// 连接配置、数据库类型和结果类型也只在那里定义一次
+pub use enhanced_types::{
+    ColumnInfo, ConnectionConfig, DatabaseAdapter, DatabaseSchema, DatabaseType, QueryResult, TableSchema,
+};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConnectionConfig {
-    pub db_type: DatabaseType,
-    pub host: String,
-    pub port: u16,
-    pub username: Option<String>,
-    pub password: Option<String>,
-    pub database: Option<String>,
-    #[serde(default)]
-    pub options: HashMap<String, String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub enum DatabaseType {
-    MySQL,
-    Redis,
-    // 预留扩展
-    PostgreSQL,
-    SQLite,
-    MongoDB,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QueryResult {
-    pub columns: Vec<String>,
-    pub rows: Vec<Vec<String>>,
-    pub affected_rows: u64,
-    pub execution_time: u64, // milliseconds
-}
-
-#[async_trait]
-pub trait DatabaseConnection: Send + Sync {
-    async fn connect(&mut self, config: &ConnectionConfig) -> anyhow::Result<()>;
-    async fn disconnect(&mut self) -> anyhow::Result<()>;
-    async fn execute(&self, query: &str) -> anyhow::Result<QueryResult>;
-    async fn get_schema(&self) -> anyhow::Result<Vec<TableInfo>>;
-    async fn get_databases(&self) -> anyhow::Result<Vec<String>> { 
-        // 默认实现返回空列表
-        Ok(vec![])
-    }
-    async fn use_database(&mut self, _database_name: &str) -> anyhow::Result<()> {
-        // 默认实现不执行任何操作
-        Ok(())
-    }
-    fn is_connected(&self) -> bool;
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TableInfo {
-    pub name: String,
-    pub columns: Vec<ColumnInfo>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ColumnInfo {
-    pub name: String,
-    pub data_type: String,
-    pub nullable: bool,
-    pub primary_key: bool,
-}
+pub mod adapter;
+pub mod enhanced_types;
 
-// 原有模块
 pub mod mysql;
 pub mod redis;
+pub mod redis_admin;
+pub mod redis_batch;
+pub mod redis_codec;
+pub mod redis_keyspace;
+pub mod redis_memory;
+pub mod redis_modules;
+pub mod redis_pubsub;
+pub mod redis_scripting;
+pub mod redis_streams;
+pub mod redis_value;
 pub mod postgresql;
 pub mod mongodb;
-
-// 新增的增强模块
-pub mod enhanced_types;
-pub mod test_adapter;
\ No newline at end of file
+pub mod mongodb_admin;
+pub mod mongodb_aggregate;
+pub mod mongodb_bulk;
+pub mod mongodb_change_streams;
+pub mod mongodb_connection;
+pub mod mongodb_documents;
+pub mod mongodb_explain;
+pub mod mongodb_gridfs;
+pub mod mongodb_indexes;
+pub mod mongodb_schema;
+pub mod mongodb_structure;
+pub mod mongodb_validation;
+pub mod mongodb_shell;
+pub mod mongodb_sql;
@@ -1,12 +1,30 @@
-use super::{DatabaseConnection, ConnectionConfig, QueryResult, TableInfo, ColumnInfo};
-use mongodb::{Client, options::ClientOptions, Database, Collection, bson::{doc, Bson, Document}};
+use super::{ColumnInfo, ConnectionConfig, DatabaseAdapter, DatabaseSchema, DatabaseType, QueryResult, TableSchema};
+use super::enhanced_types::EnhancedQueryResult;
+use super::mongodb_aggregate::{check_pipeline, collect_documents};
+use super::mongodb_bulk::{bulk_table, parse_bulk_operation, BulkOperation, BulkWriteOptions};
+use super::mongodb_connection::{connection_uri, redact_uri};
+use super::mongodb_documents::ExtendedJsonMode;
+use super::mongodb_schema::SchemaCache;
+use super::mongodb_shell::{as_i64, parse_statement, parse_value, ShellCall, ShellStatement};
+use mongodb::{
+    Client, Database, Collection, IndexModel,
+    bson::{doc, Bson, Document},
+    options::{
+        AggregateOptions, ClientOptions, CountOptions, DeleteOptions, FindOneAndUpdateOptions, IndexOptions,
+        InsertOneOptions, ReplaceOptions, UpdateModifications, UpdateOptions,
+    },
+};
 use futures::StreamExt;
 use async_trait::async_trait;
 
+#[cfg(test)]
+mod tests;
+
 pub struct MongoDBConnection {
     client: Option<Client>,
     database: Option<Database>,
     current_database: Option<String>,
+    schema_cache: std::sync::Mutex<SchemaCache>, // get_schema 抽样推断的集合结构
 }
 
 impl MongoDBConnection {
@@ -15,8 +33,14 @@ impl MongoDBConnection {
             client: None,
             database: None,
             current_database: None,
+            schema_cache: std::sync::Mutex::new(SchemaCache::new()),
         }
     }
+
+    /// 抽样推断结构的缓存，按库名和集合名索引
+    pub(crate) fn schema_cache(&self) -> std::sync::MutexGuard<'_, SchemaCache> {
+        self.schema_cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
     
     
     // 将BSON值转换为字符串的辅助函数
@@ -59,81 +83,600 @@ impl MongoDBConnection {
         let trimmed = query.trim();
         
         // 检查是否是MongoDB Shell风格的命令
-        if trimmed.starts_with("db.") {
-            let parts: Vec<&str> = trimmed.split('.').collect();
-            if parts.len() >= 3 {
-                let collection_name = parts[1];
-                let operation = parts[2];
-                
-                if operation.starts_with("find(") {
-                    let filter_str = operation.strip_prefix("find(")
-                        .and_then(|s| s.strip_suffix(")"))
-                        .unwrap_or("{}");
-                    
-                    let filter: Document = if filter_str.trim() == "{}" || filter_str.trim().is_empty() {
-                        doc! {}
-                    } else {
-                        // 简化JSON解析，如果失败就使用空过滤器
-                        match serde_json::from_str::<serde_json::Value>(filter_str) {
-                            Ok(json_val) => {
-                                match bson::to_document(&json_val) {
-                                    Ok(doc) => doc,
-                                    Err(_) => doc! {}
-                                }
-                            },
-                            Err(_) => doc! {}
-                        }
-                    };
-                    
-                    return Ok(MongoOperation::Find {
-                        collection: collection_name.to_string(),
-                        filter,
-                        limit: Some(100), // 默认限制
-                    });
-                } else if operation.starts_with("insertOne(") {
-                    let doc_str = operation.strip_prefix("insertOne(")
-                        .and_then(|s| s.strip_suffix(")"))
-                        .unwrap_or("{}");
-                    
-                    let document: Document = serde_json::from_str(doc_str)
-                        .map_err(|e| anyhow::anyhow!("无效的文档格式: {}", e))?;
-                    
-                    return Ok(MongoOperation::InsertOne {
-                        collection: collection_name.to_string(),
-                        document,
-                    });
-                } else if operation.starts_with("updateOne(") || operation.starts_with("updateMany(") {
-                    // 简化处理更新操作
-                    return Ok(MongoOperation::Update {
-                        collection: collection_name.to_string(),
-                        filter: doc! {},
-                        update: doc! {},
-                        many: operation.starts_with("updateMany"),
-                    });
-                } else if operation.starts_with("deleteOne(") || operation.starts_with("deleteMany(") {
-                    return Ok(MongoOperation::Delete {
-                        collection: collection_name.to_string(),
-                        filter: doc! {},
-                        many: operation.starts_with("deleteMany"),
-                    });
-                }
-            }
+        if trimmed.starts_with("db.") || trimmed.starts_with("db[") {
+            return shell_operation(parse_statement(trimmed)?);
         }
         
-        // 如果不是标准的MongoDB命令，尝试作为JSON解析
+        // 如果不是标准的MongoDB命令，尝试作为过滤条件解析
         if trimmed.starts_with('{') && trimmed.ends_with('}') {
-            let filter: Document = serde_json::from_str(trimmed)
-                .map_err(|e| anyhow::anyhow!("无效的JSON查询: {}", e))?;
+            let filter = document_arg(Some(parse_value(trimmed)?), "查询")?;
             
             return Ok(MongoOperation::Find {
                 collection: "default".to_string(),
                 filter,
-                limit: Some(100),
+                projection: None,
+                sort: None,
+                skip: None,
+                limit: None,
             });
         }
         
         Err(anyhow::anyhow!("不支持的MongoDB操作: {}", query))
     }
+
+    /// 客户端，供扩展模块使用
+    pub(crate) fn client(&self) -> anyhow::Result<&Client> {
+        self.client.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected"))
+    }
+
+    /// 当前数据库，供扩展模块使用
+    pub(crate) fn database(&self) -> anyhow::Result<&Database> {
+        self.database.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("未选择数据库"))
+    }
+
+    /// 执行查询，find/aggregate 返回原始文档，其余操作返回表格结果
+    pub(crate) async fn run_query(&self, query: &str) -> anyhow::Result<QueryOutput> {
+        let _client = self.client.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Not connected"))?;
+        
+        let start = std::time::Instant::now();
+        
+        // 解析MongoDB操作
+        let operation = self.parse_mongodb_query(query)?;
+        
+        match operation {
+            MongoOperation::Find { collection, filter, projection, sort, skip, limit } => {
+                let db = self.database.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
+                
+                let coll: Collection<Document> = db.collection(&collection);
+                
+                let find_options = mongodb::options::FindOptions::builder()
+                    .projection(projection)
+                    .sort(sort)
+                    .skip(skip)
+                    .limit(limit.unwrap_or(DEFAULT_FIND_LIMIT))
+                    .build();
+                
+                let cursor = coll.find(filter, find_options).await?;
+                let documents: Vec<Document> = cursor.collect::<Vec<_>>().await
+                    .into_iter()
+                    .collect::<Result<Vec<_>, _>>()?;
+                
+                Ok(QueryOutput::Documents(documents))
+            }
+            MongoOperation::InsertOne { collection, document, options } => {
+                let db = self.database.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
+                
+                let coll: Collection<Document> = db.collection(&collection);
+                let result = coll.insert_one(document, options).await?;
+                
+                Ok(QueryOutput::Table(QueryResult {
+                    columns: vec!["inserted_id".to_string()],
+                    rows: vec![vec![result.inserted_id.to_string()]],
+                    affected_rows: 1,
+                    execution_time: start.elapsed().as_millis() as u64,
+                }))
+            }
+            MongoOperation::Update { collection, filter, update, options, many } => {
+                let db = self.database.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
+                
+                let coll: Collection<Document> = db.collection(&collection);
+                let result = if many {
+                    coll.update_many(filter, update, options).await?
+                } else {
+                    coll.update_one(filter, update, options).await?
+                };
+                
+                Ok(QueryOutput::Table(QueryResult {
+                    columns: vec!["matched_count".to_string(), "modified_count".to_string()],
+                    rows: vec![vec![result.matched_count.to_string(), result.modified_count.to_string()]],
+                    affected_rows: result.modified_count,
+                    execution_time: start.elapsed().as_millis() as u64,
+                }))
+            }
+            MongoOperation::Delete { collection, filter, options, many } => {
+                let db = self.database.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
+                
+                let coll: Collection<Document> = db.collection(&collection);
+                let result = if many {
+                    coll.delete_many(filter, options).await?
+                } else {
+                    coll.delete_one(filter, options).await?
+                };
+                
+                Ok(QueryOutput::Table(QueryResult {
+                    columns: vec!["deleted_count".to_string()],
+                    rows: vec![vec![result.deleted_count.to_string()]],
+                    affected_rows: result.deleted_count,
+                    execution_time: start.elapsed().as_millis() as u64,
+                }))
+            }
+            MongoOperation::InsertMany { collection, documents, options } => {
+                let operations = documents.into_iter()
+                    .map(|document| BulkOperation::Insert { document })
+                    .collect();
+                let result = self.bulk_write(&collection, operations, &options).await?;
+                Ok(QueryOutput::Table(bulk_table(&result)))
+            }
+            MongoOperation::BulkWrite { collection, operations, options } => {
+                let result = self.bulk_write(&collection, operations, &options).await?;
+                Ok(QueryOutput::Table(bulk_table(&result)))
+            }
+            MongoOperation::ReplaceOne { collection, filter, replacement, options } => {
+                let db = self.database.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
+                
+                let coll: Collection<Document> = db.collection(&collection);
+                let result = coll.replace_one(filter, replacement, options).await?;
+                
+                Ok(QueryOutput::Table(QueryResult {
+                    columns: vec!["matched_count".to_string(), "modified_count".to_string(), "upserted_id".to_string()],
+                    rows: vec![vec![
+                        result.matched_count.to_string(),
+                        result.modified_count.to_string(),
+                        result.upserted_id.as_ref().map(Self::bson_to_string).unwrap_or_else(|| "null".to_string()),
+                    ]],
+                    affected_rows: result.modified_count,
+                    execution_time: start.elapsed().as_millis() as u64,
+                }))
+            }
+            MongoOperation::FindOneAndUpdate { collection, filter, update, options } => {
+                let db = self.database.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
+                
+                let coll: Collection<Document> = db.collection(&collection);
+                let document = coll.find_one_and_update(filter, update, options).await?;
+                
+                let mut result = Self::documents_result(document.as_slice(), start.elapsed().as_millis() as u64);
+                result.affected_rows = result.rows.len() as u64;
+                Ok(QueryOutput::Table(result))
+            }
+            MongoOperation::CountDocuments { collection, filter, options } => {
+                let db = self.database.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
+                
+                let coll: Collection<Document> = db.collection(&collection);
+                let count = coll.count_documents(filter, options).await?;
+                
+                Ok(QueryOutput::Table(QueryResult {
+                    columns: vec!["count".to_string()],
+                    rows: vec![vec![count.to_string()]],
+                    affected_rows: 0,
+                    execution_time: start.elapsed().as_millis() as u64,
+                }))
+            }
+            MongoOperation::Distinct { collection, field, filter } => {
+                let db = self.database.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
+                
+                let coll: Collection<Document> = db.collection(&collection);
+                let values = coll.distinct(&field, filter, None).await?;
+                
+                Ok(QueryOutput::Table(QueryResult {
+                    columns: vec![field],
+                    rows: values.iter().map(|value| vec![Self::bson_to_string(value)]).collect(),
+                    affected_rows: 0,
+                    execution_time: start.elapsed().as_millis() as u64,
+                }))
+            }
+            MongoOperation::Aggregate { collection, pipeline, options } => {
+                let db = self.database.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
+                
+                let coll: Collection<Document> = db.collection(&collection);
+                let cursor = coll.aggregate(pipeline, options).await?;
+                // 与 find 一样最多返回默认条数
+                let (documents, _) = collect_documents(cursor, DEFAULT_FIND_LIMIT as usize).await?;
+                
+                Ok(QueryOutput::Documents(documents))
+            }
+            MongoOperation::CreateIndex { collection, keys, options } => {
+                let db = self.database.as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
+                
+                let coll: Collection<Document> = db.collection(&collection);
+                let index = IndexModel::builder().keys(keys).options(options).build();
+                let result = coll.create_index(index, None).await?;
+                
+                Ok(QueryOutput::Table(QueryResult {
+                    columns: vec!["index_name".to_string()],
+                    rows: vec![vec![result.index_name]],
+                    affected_rows: 0,
+                    execution_time: start.elapsed().as_millis() as u64,
+                }))
+            }
+        }
+    }
+
+    // 将文档列表转换为表格，列为所有文档字段的并集
+    fn documents_result(documents: &[Document], execution_time: u64) -> QueryResult {
+        let mut all_keys = std::collections::HashSet::new();
+        for doc in documents {
+            for key in doc.keys() {
+                all_keys.insert(key.clone());
+            }
+        }
+        
+        let mut columns: Vec<String> = all_keys.into_iter().collect();
+        columns.sort();
+        
+        let rows: Vec<Vec<String>> = documents.iter()
+            .map(|doc| {
+                columns.iter()
+                    .map(|col| {
+                        match doc.get(col) {
+                            Some(value) => Self::bson_to_string(value),
+                            None => "null".to_string(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        
+        QueryResult {
+            columns,
+            rows,
+            affected_rows: 0,
+            execution_time,
+        }
+    }
+}
+
+/// 确认空过滤条件的选项名，执行前会从选项中移除
+const CONFIRM_OPTION: &str = "confirm";
+
+/// find 未指定 limit 时的默认条数
+pub(super) const DEFAULT_FIND_LIMIT: i64 = 100;
+
+/// 将解析后的 shell 语句转换为具体操作
+fn shell_operation(statement: ShellStatement) -> anyhow::Result<MongoOperation> {
+    let ShellStatement { collection, calls } = statement;
+    let mut calls = calls.into_iter();
+    let call = calls.next().ok_or_else(|| anyhow::anyhow!("缺少集合方法"))?;
+    let chained: Vec<ShellCall> = calls.collect();
+    let method = call.name.clone();
+
+    if method != "find" {
+        if let Some(extra) = chained.iter().find(|c| !matches!(c.name.as_str(), "pretty" | "toArray")) {
+            return Err(anyhow::anyhow!("{}() 不支持链式调用 .{}()", method, extra.name));
+        }
+    }
+
+    let operation = match method.as_str() {
+        "find" | "findOne" => {
+            let [filter, projection] = arguments(call, 0, 2)?;
+            let mut projection = match projection {
+                Some(projection) => Some(document_arg(Some(projection), "投影")?),
+                None => None,
+            };
+            let mut sort = None;
+            let mut skip = None;
+            let mut limit = if method == "findOne" { Some(1) } else { None };
+
+            for modifier in chained {
+                let name = modifier.name.clone();
+                match name.as_str() {
+                    "sort" => sort = Some(document_arg(single_argument(modifier)?, "排序条件")?),
+                    "project" | "projection" => projection = Some(document_arg(single_argument(modifier)?, "投影")?),
+                    "skip" => {
+                        let value = single_argument(modifier)?.as_ref().and_then(as_i64)
+                            .and_then(|v| u64::try_from(v).ok())
+                            .ok_or_else(|| anyhow::anyhow!("skip() 需要非负整数"))?;
+                        skip = Some(value);
+                    }
+                    "limit" => {
+                        let value = single_argument(modifier)?.as_ref().and_then(as_i64)
+                            .ok_or_else(|| anyhow::anyhow!("limit() 需要整数"))?;
+                        limit = Some(value.abs());
+                    }
+                    "pretty" | "toArray" => {}
+                    other => return Err(anyhow::anyhow!("find() 不支持链式调用 .{}()", other)),
+                }
+            }
+
+            MongoOperation::Find {
+                collection,
+                filter: optional_document(filter, "过滤条件")?,
+                projection,
+                sort,
+                skip,
+                limit: limit.filter(|l| *l > 0),
+            }
+        }
+        "insertOne" => {
+            let [document, options] = arguments(call, 1, 2)?;
+            let (options, _) = parse_options(options)?;
+            MongoOperation::InsertOne {
+                collection,
+                document: document_arg(document, "文档")?,
+                options: deserialize_options(options, "插入选项")?,
+            }
+        }
+        "insertMany" => {
+            let [documents, options] = arguments(call, 1, 2)?;
+            let documents = match documents {
+                Some(Bson::Array(items)) if !items.is_empty() => items.into_iter()
+                    .map(|item| document_arg(Some(item), "文档"))
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+                _ => return Err(anyhow::anyhow!("insertMany() 需要非空的文档数组")),
+            };
+            let (options, _) = parse_options(options)?;
+            MongoOperation::InsertMany {
+                collection,
+                documents,
+                options: deserialize_options(options, "插入选项")?,
+            }
+        }
+        "bulkWrite" => {
+            let [operations, options] = arguments(call, 1, 2)?;
+            let options: BulkWriteOptions = deserialize_options(optional_document(options, "选项")?, "批量写入选项")?;
+            let operations = match operations {
+                Some(Bson::Array(items)) if !items.is_empty() => items.into_iter()
+                    .map(|item| parse_bulk_operation(document_arg(Some(item), "批量操作")?, options.confirm))
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+                _ => return Err(anyhow::anyhow!("bulkWrite() 需要非空的操作数组")),
+            };
+            MongoOperation::BulkWrite {
+                collection,
+                operations,
+                options,
+            }
+        }
+        "updateOne" | "updateMany" => {
+            let [filter, update, options] = arguments(call, 2, 3)?;
+            let filter = document_arg(filter, "过滤条件")?;
+            let update = parse_update(update)?;
+            let (options, confirmed) = parse_options(options)?;
+            check_empty_filter(&filter, confirmed, &method)?;
+            MongoOperation::Update {
+                collection,
+                filter,
+                update,
+                options: deserialize_options(options, "更新选项")?,
+                many: method == "updateMany",
+            }
+        }
+        "replaceOne" => {
+            let [filter, replacement, options] = arguments(call, 2, 3)?;
+            let filter = document_arg(filter, "过滤条件")?;
+            let replacement = document_arg(replacement, "替换文档")?;
+            if let Some(key) = replacement.keys().find(|key| key.starts_with('$')) {
+                return Err(anyhow::anyhow!("替换文档不能包含更新操作符 '{}'，部分更新请使用 updateOne", key));
+            }
+            let (options, confirmed) = parse_options(options)?;
+            check_empty_filter(&filter, confirmed, &method)?;
+            MongoOperation::ReplaceOne {
+                collection,
+                filter,
+                replacement,
+                options: deserialize_options(options, "替换选项")?,
+            }
+        }
+        "deleteOne" | "deleteMany" => {
+            let [filter, options] = arguments(call, 1, 2)?;
+            let filter = document_arg(filter, "过滤条件")?;
+            let (options, confirmed) = parse_options(options)?;
+            check_empty_filter(&filter, confirmed, &method)?;
+            MongoOperation::Delete {
+                collection,
+                filter,
+                options: deserialize_options(options, "删除选项")?,
+                many: method == "deleteMany",
+            }
+        }
+        "findOneAndUpdate" => {
+            let [filter, update, options] = arguments(call, 2, 3)?;
+            let filter = document_arg(filter, "过滤条件")?;
+            let update = parse_update(update)?;
+            let (mut options, confirmed) = parse_options(options)?;
+            check_empty_filter(&filter, confirmed, &method)?;
+            // mongosh 的 returnNewDocument 对应驱动的 returnDocument
+            if let Some(return_new) = options.remove("returnNewDocument") {
+                let after = return_new.as_bool()
+                    .ok_or_else(|| anyhow::anyhow!("returnNewDocument 选项必须是布尔值"))?;
+                options.insert("returnDocument", if after { "after" } else { "before" });
+            }
+            MongoOperation::FindOneAndUpdate {
+                collection,
+                filter,
+                update,
+                options: deserialize_options(options, "更新选项")?,
+            }
+        }
+        "countDocuments" => {
+            let [filter, options] = arguments(call, 0, 2)?;
+            let (options, _) = parse_options(options)?;
+            MongoOperation::CountDocuments {
+                collection,
+                filter: optional_document(filter, "过滤条件")?,
+                options: deserialize_options(options, "计数选项")?,
+            }
+        }
+        "distinct" => {
+            let [field, filter] = arguments(call, 1, 2)?;
+            let field = match field {
+                Some(Bson::String(field)) if !field.is_empty() => field,
+                _ => return Err(anyhow::anyhow!("distinct() 需要字段名")),
+            };
+            MongoOperation::Distinct {
+                collection,
+                field,
+                filter: optional_document(filter, "过滤条件")?,
+            }
+        }
+        "aggregate" => {
+            let [pipeline, options] = arguments(call, 0, 2)?;
+            let pipeline = match pipeline {
+                None => vec![],
+                Some(Bson::Array(stages)) => stages.into_iter()
+                    .map(|stage| document_arg(Some(stage), "聚合阶段"))
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+                Some(_) => return Err(anyhow::anyhow!("aggregate() 需要阶段数组")),
+            };
+            let (options, confirmed) = parse_options(options)?;
+            if let Some(target) = check_pipeline(&pipeline)?.write_target {
+                if !confirmed {
+                    return Err(anyhow::anyhow!(
+                        "管道会写入集合 {}。如确认执行，请在选项中加入 {{{}: true}}", target, CONFIRM_OPTION
+                    ));
+                }
+            }
+            MongoOperation::Aggregate {
+                collection,
+                pipeline,
+                options: deserialize_options(options, "聚合选项")?,
+            }
+        }
+        "createIndex" => {
+            let [keys, options] = arguments(call, 1, 2)?;
+            let keys = document_arg(keys, "索引键")?;
+            if keys.is_empty() {
+                return Err(anyhow::anyhow!("索引键不能为空"));
+            }
+            let (options, _) = parse_options(options)?;
+            MongoOperation::CreateIndex {
+                collection,
+                keys,
+                options: if options.is_empty() { None } else { Some(deserialize_options(options, "索引选项")?) },
+            }
+        }
+        other => return Err(anyhow::anyhow!("不支持的集合方法: {}()", other)),
+    };
+    Ok(operation)
+}
+
+/// 检查参数个数并按位置取出，缺少的参数为 None
+fn arguments<const N: usize>(call: ShellCall, min: usize, max: usize) -> anyhow::Result<[Option<Bson>; N]> {
+    debug_assert!(max <= N);
+    if call.args.len() < min || call.args.len() > max {
+        let expected = if min == max { min.to_string() } else { format!("{} 到 {}", min, max) };
+        return Err(anyhow::anyhow!("{}() 需要 {} 个参数，实际为 {} 个", call.name, expected, call.args.len()));
+    }
+    let mut args = call.args.into_iter();
+    Ok(std::array::from_fn(|_| args.next()))
+}
+
+fn single_argument(call: ShellCall) -> anyhow::Result<Option<Bson>> {
+    let [value] = arguments(call, 1, 1)?;
+    Ok(value)
+}
+
+fn document_arg(value: Option<Bson>, what: &str) -> anyhow::Result<Document> {
+    match value {
+        Some(Bson::Document(document)) => Ok(document),
+        Some(_) => Err(anyhow::anyhow!("{}必须是文档", what)),
+        None => Err(anyhow::anyhow!("缺少{}", what)),
+    }
+}
+
+/// 省略时视为空文档
+fn optional_document(value: Option<Bson>, what: &str) -> anyhow::Result<Document> {
+    match value {
+        None => Ok(Document::new()),
+        value => document_arg(value, what),
+    }
+}
+
+fn deserialize_options<T: serde::de::DeserializeOwned + Default>(options: Document, what: &str) -> anyhow::Result<T> {
+    if options.is_empty() {
+        return Ok(T::default());
+    }
+    bson::from_document(options).map_err(|e| anyhow::anyhow!("无效的{}: {}", what, e))
+}
+
+/// 更新内容可以是操作符文档或聚合管道
+pub(crate) fn parse_update(value: Option<Bson>) -> anyhow::Result<UpdateModifications> {
+    if let Some(Bson::Array(stages)) = value {
+        let pipeline = stages.into_iter()
+            .map(|stage| match stage {
+                Bson::Document(stage) => Ok(stage),
+                _ => Err(anyhow::anyhow!("更新管道的每个阶段都必须是文档")),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if pipeline.is_empty() {
+            return Err(anyhow::anyhow!("更新管道不能为空"));
+        }
+        return Ok(UpdateModifications::Pipeline(pipeline));
+    }
+
+    let update = document_arg(value, "更新内容")?;
+    if update.is_empty() {
+        return Err(anyhow::anyhow!("更新内容不能为空"));
+    }
+    if let Some(key) = update.keys().find(|key| !key.starts_with('$')) {
+        return Err(anyhow::anyhow!("更新内容只能包含 $set、$inc 等更新操作符，发现字段 '{}'；替换整个文档请使用 replaceOne", key));
+    }
+    Ok(UpdateModifications::Document(update))
+}
+
+/// 解析选项参数，并取出其中的确认标记
+fn parse_options(value: Option<Bson>) -> anyhow::Result<(Document, bool)> {
+    let mut options = optional_document(value, "选项")?;
+    let confirmed = match options.remove(CONFIRM_OPTION) {
+        Some(Bson::Boolean(confirmed)) => confirmed,
+        Some(_) => return Err(anyhow::anyhow!("{} 选项必须是布尔值", CONFIRM_OPTION)),
+        None => false,
+    };
+    Ok((options, confirmed))
+}
+
+/// 空过滤条件会作用于整个集合，必须显式确认
+pub(crate) fn check_empty_filter(filter: &Document, confirmed: bool, operation: &str) -> anyhow::Result<()> {
+    if filter.is_empty() && !confirmed {
+        return Err(anyhow::anyhow!(
+            "{} 使用了空过滤条件，将作用于集合中的所有文档。如确认执行，请在选项中加入 {{{}: true}}",
+            operation, CONFIRM_OPTION
+        ));
+    }
+    Ok(())
+}
+
+/// 查询输出：文档由调用方决定展平为表格还是转换为扩展 JSON
+pub(crate) enum QueryOutput {
+    Documents(Vec<Document>),
+    Table(QueryResult),
+}
+
+/// 构造 explain 命令中被解释的命令，只支持读操作
+pub(crate) fn explainable_command(statement: ShellStatement) -> anyhow::Result<Document> {
+    match shell_operation(statement)? {
+        MongoOperation::Find { collection, filter, projection, sort, skip, limit } => {
+            let mut command = doc! { "find": collection, "filter": filter };
+            if let Some(projection) = projection {
+                command.insert("projection", projection);
+            }
+            if let Some(sort) = sort {
+                command.insert("sort", sort);
+            }
+            if let Some(skip) = skip {
+                command.insert("skip", skip as i64);
+            }
+            if let Some(limit) = limit {
+                command.insert("limit", limit);
+            }
+            Ok(command)
+        }
+        MongoOperation::Aggregate { collection, pipeline, options } => {
+            let mut command = doc! { "aggregate": collection, "pipeline": pipeline, "cursor": {} };
+            if let Some(allow_disk_use) = options.allow_disk_use {
+                command.insert("allowDiskUse", allow_disk_use);
+            }
+            Ok(command)
+        }
+        MongoOperation::CountDocuments { collection, filter, .. } => {
+            Ok(doc! { "count": collection, "query": filter })
+        }
+        MongoOperation::Distinct { collection, field, filter } => {
+            Ok(doc! { "distinct": collection, "key": field, "query": filter })
+        }
+        _ => Err(anyhow::anyhow!("只支持 find、aggregate、countDocuments 和 distinct 的执行计划")),
+    }
 }
 
 // MongoDB操作枚举
@@ -142,49 +685,88 @@ enum MongoOperation {
     Find {
         collection: String,
         filter: Document,
+        projection: Option<Document>,
+        sort: Option<Document>,
+        skip: Option<u64>,
+        /// 用户指定的条数，未指定或为 0 时为 None，执行时使用 DEFAULT_FIND_LIMIT
         limit: Option<i64>,
     },
     InsertOne {
         collection: String,
         document: Document,
+        options: InsertOneOptions,
+    },
+    InsertMany {
+        collection: String,
+        documents: Vec<Document>,
+        options: BulkWriteOptions,
+    },
+    BulkWrite {
+        collection: String,
+        operations: Vec<BulkOperation>,
+        options: BulkWriteOptions,
     },
     Update {
         collection: String,
         filter: Document,
-        update: Document,
+        update: UpdateModifications,
+        options: UpdateOptions,
         many: bool,
     },
+    ReplaceOne {
+        collection: String,
+        filter: Document,
+        replacement: Document,
+        options: ReplaceOptions,
+    },
     Delete {
         collection: String,
         filter: Document,
+        options: DeleteOptions,
         many: bool,
     },
+    FindOneAndUpdate {
+        collection: String,
+        filter: Document,
+        update: UpdateModifications,
+        options: FindOneAndUpdateOptions,
+    },
+    CountDocuments {
+        collection: String,
+        filter: Document,
+        options: CountOptions,
+    },
+    Distinct {
+        collection: String,
+        field: String,
+        filter: Document,
+    },
+    Aggregate {
+        collection: String,
+        pipeline: Vec<Document>,
+        options: AggregateOptions,
+    },
+    CreateIndex {
+        collection: String,
+        keys: Document,
+        options: Option<IndexOptions>,
+    },
 }
 
 #[async_trait]
-impl DatabaseConnection for MongoDBConnection {
+impl DatabaseAdapter for MongoDBConnection {
+    fn db_type(&self) -> DatabaseType {
+        DatabaseType::MongoDB
+    }
+
     async fn connect(&mut self, config: &ConnectionConfig) -> anyhow::Result<()> {
         use tracing::{info, error, debug};
         
-        info!("正在连接MongoDB数据库 - 主机: {}, 端口: {}", config.host, config.port);
-
-        // 构建MongoDB连接字符串
-        let mut uri = if let Some(username) = &config.username {
-            if let Some(password) = &config.password {
-                format!("mongodb://{}:{}@{}:{}", username, password, config.host, config.port)
-            } else {
-                format!("mongodb://{}@{}:{}", username, config.host, config.port)
-            }
-        } else {
-            format!("mongodb://{}:{}", config.host, config.port)
-        };
-        
-        // 添加认证数据库（如果指定）
-        if let Some(auth_db) = config.options.get("authSource") {
-            uri = format!("{}?authSource={}", uri, auth_db);
-        }
-        
-        debug!("MongoDB连接URI: {}", uri.replace(config.password.as_ref().unwrap_or(&"".to_string()).as_str(), "***"));
+        info!("正在连接MongoDB数据库 - 主机: {}, 端口: {}", redact_uri(&config.host), config.port);
+
+        // 完整连接串或由多主机、副本集、读偏好、认证和 TLS 设置拼成的连接串
+        let uri = connection_uri(config)?;
+        debug!("MongoDB连接URI: {}", redact_uri(&uri));
 
         let client_options = ClientOptions::parse(&uri).await
             .map_err(|e| {
@@ -224,123 +806,27 @@ impl DatabaseConnection for MongoDBConnection {
         self.client = None;
         self.database = None;
         self.current_database = None;
+        self.schema_cache().clear();
         Ok(())
     }
 
-    async fn execute(&self, query: &str) -> anyhow::Result<QueryResult> {
-        let _client = self.client.as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Not connected"))?;
-        
+    async fn execute_raw(&self, query: &str) -> anyhow::Result<QueryResult> {
         let start = std::time::Instant::now();
-        
-        // 解析MongoDB操作
-        let operation = self.parse_mongodb_query(query)?;
-        
-        match operation {
-            MongoOperation::Find { collection, filter, limit } => {
-                let db = self.database.as_ref()
-                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
-                
-                let coll: Collection<Document> = db.collection(&collection);
-                
-                let find_options = if let Some(limit_val) = limit {
-                    mongodb::options::FindOptions::builder()
-                        .limit(limit_val)
-                        .build()
-                } else {
-                    mongodb::options::FindOptions::default()
-                };
-                
-                let cursor = coll.find(filter, find_options).await?;
-                let documents: Vec<Document> = cursor.collect::<Vec<_>>().await
-                    .into_iter()
-                    .collect::<Result<Vec<_>, _>>()?;
-                
-                // 转换为表格格式  
-                let mut all_keys = std::collections::HashSet::new();
-                for doc in &documents {
-                    for key in doc.keys() {
-                        all_keys.insert(key.clone());
-                    }
-                }
-                
-                let mut columns: Vec<String> = all_keys.into_iter().collect();
-                columns.sort();
-                
-                let rows: Vec<Vec<String>> = documents.iter()
-                    .map(|doc| {
-                        columns.iter()
-                            .map(|col| {
-                                match doc.get(col) {
-                                    Some(value) => Self::bson_to_string(value),
-                                    None => "null".to_string(),
-                                }
-                            })
-                            .collect()
-                    })
-                    .collect();
-                
-                Ok(QueryResult {
-                    columns,
-                    rows,
-                    affected_rows: 0,
-                    execution_time: start.elapsed().as_millis() as u64,
-                })
-            }
-            MongoOperation::InsertOne { collection, document } => {
-                let db = self.database.as_ref()
-                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
-                
-                let coll: Collection<Document> = db.collection(&collection);
-                let result = coll.insert_one(document, None).await?;
-                
-                Ok(QueryResult {
-                    columns: vec!["inserted_id".to_string()],
-                    rows: vec![vec![result.inserted_id.to_string()]],
-                    affected_rows: 1,
-                    execution_time: start.elapsed().as_millis() as u64,
-                })
-            }
-            MongoOperation::Update { collection, filter, update, many } => {
-                let db = self.database.as_ref()
-                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
-                
-                let coll: Collection<Document> = db.collection(&collection);
-                let result = if many {
-                    coll.update_many(filter, update, None).await?
-                } else {
-                    coll.update_one(filter, update, None).await?
-                };
-                
-                Ok(QueryResult {
-                    columns: vec!["matched_count".to_string(), "modified_count".to_string()],
-                    rows: vec![vec![result.matched_count.to_string(), result.modified_count.to_string()]],
-                    affected_rows: result.modified_count,
-                    execution_time: start.elapsed().as_millis() as u64,
-                })
-            }
-            MongoOperation::Delete { collection, filter, many } => {
-                let db = self.database.as_ref()
-                    .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
-                
-                let coll: Collection<Document> = db.collection(&collection);
-                let result = if many {
-                    coll.delete_many(filter, None).await?
-                } else {
-                    coll.delete_one(filter, None).await?
-                };
-                
-                Ok(QueryResult {
-                    columns: vec!["deleted_count".to_string()],
-                    rows: vec![vec![result.deleted_count.to_string()]],
-                    affected_rows: result.deleted_count,
-                    execution_time: start.elapsed().as_millis() as u64,
-                })
+        match self.run_query(query).await? {
+            // 表格视图：文档展平为字符串列
+            QueryOutput::Documents(documents) => {
+                Ok(Self::documents_result(&documents, start.elapsed().as_millis() as u64))
             }
+            QueryOutput::Table(result) => Ok(result),
         }
     }
 
-    async fn get_schema(&self) -> anyhow::Result<Vec<TableInfo>> {
+    async fn execute(&self, query: &str) -> anyhow::Result<EnhancedQueryResult> {
+        // 文档视图保留嵌套结构
+        self.query_documents(query, ExtendedJsonMode::Relaxed).await
+    }
+
+    async fn get_schema(&self) -> anyhow::Result<DatabaseSchema> {
         let _client = self.client.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Not connected"))?;
             
@@ -355,20 +841,13 @@ impl DatabaseConnection for MongoDBConnection {
                     continue;
                 }
                 
-                table_infos.push(TableInfo {
-                    name: format!("{}", db_name),
-                    columns: vec![
-                        ColumnInfo {
-                            name: "database".to_string(),
-                            data_type: "DATABASE".to_string(),
-                            nullable: false,
-                            primary_key: false,
-                        }
-                    ],
-                });
+                table_infos.push(TableSchema::new(
+                    db_name,
+                    vec![ColumnInfo::new("database", "DATABASE", false, false)],
+                ));
             }
             
-            return Ok(table_infos);
+            return Ok(DatabaseSchema::new("", table_infos));
         }
         
         // 获取集合信息
@@ -376,30 +855,9 @@ impl DatabaseConnection for MongoDBConnection {
             .ok_or_else(|| anyhow::anyhow!("未选择数据库"))?;
         
         let collection_names = db.list_collection_names(None).await?;
+        let table_infos = self.sampled_table_schemas(collection_names).await;
         
-        let mut table_infos = Vec::new();
-        for collection_name in collection_names {
-            // 为每个集合创建基本的"表"信息
-            table_infos.push(TableInfo {
-                name: collection_name,
-                columns: vec![
-                    ColumnInfo {
-                        name: "_id".to_string(),
-                        data_type: "ObjectId".to_string(),
-                        nullable: false,
-                        primary_key: true,
-                    },
-                    ColumnInfo {
-                        name: "document".to_string(),
-                        data_type: "Document".to_string(),
-                        nullable: false,
-                        primary_key: false,
-                    },
-                ],
-            });
-        }
-        
-        Ok(table_infos)
+        Ok(DatabaseSchema::new(self.current_database.clone().unwrap_or_default(), table_infos))
     }
 
     async fn get_databases(&self) -> anyhow::Result<Vec<String>> {
@@ -423,4 +881,8 @@ impl DatabaseConnection for MongoDBConnection {
     fn is_connected(&self) -> bool {
         self.client.is_some()
     }
+
+    fn as_mongodb(&self) -> Option<&MongoDBConnection> {
+        Some(self)
+    }
 }
\ No newline at end of file
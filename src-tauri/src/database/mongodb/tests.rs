@@ -0,0 +1,170 @@
+use super::*;
+use mongodb::bson::doc;
+
+fn parse(query: &str) -> anyhow::Result<MongoOperation> {
+    MongoDBConnection::new().parse_mongodb_query(query)
+}
+
+#[test]
+fn test_delete_uses_filter() {
+    match parse("db.users.deleteMany({age: 5})").unwrap() {
+        MongoOperation::Delete { collection, filter, many, .. } => {
+            assert_eq!(collection, "users");
+            assert_eq!(filter, doc! { "age": 5 });
+            assert!(many);
+        }
+        other => panic!("unexpected operation: {:?}", other),
+    }
+}
+
+#[test]
+fn test_update_uses_filter_update_and_options() {
+    let operation = parse(r#"db.users.updateOne({"profile.name": "a, b"}, {$set: {active: true}}, {upsert: true});"#).unwrap();
+    match operation {
+        MongoOperation::Update { filter, update, options, many, .. } => {
+            assert_eq!(filter, doc! { "profile.name": "a, b" });
+            match update {
+                UpdateModifications::Document(update) => assert_eq!(update, doc! { "$set": { "active": true } }),
+                other => panic!("unexpected update: {:?}", other),
+            }
+            assert_eq!(options.upsert, Some(true));
+            assert!(!many);
+        }
+        other => panic!("unexpected operation: {:?}", other),
+    }
+
+    let operation = parse(r#"db.users.updateMany({a: 1}, [{$set: {b: "$a"}}])"#).unwrap();
+    assert!(matches!(operation, MongoOperation::Update { update: UpdateModifications::Pipeline(_), many: true, .. }));
+}
+
+#[test]
+fn test_extended_json_filter() {
+    match parse(r#"db.users.deleteOne({"_id": {"$oid": "507f1f77bcf86cd799439011"}})"#).unwrap() {
+        MongoOperation::Delete { filter, .. } => {
+            assert!(matches!(filter.get("_id"), Some(Bson::ObjectId(_))));
+        }
+        other => panic!("unexpected operation: {:?}", other),
+    }
+}
+
+#[test]
+fn test_empty_filter_requires_confirmation() {
+    assert!(parse("db.users.deleteMany({})").is_err());
+    assert!(parse("db.users.updateMany({}, {$set: {a: 1}})").is_err());
+    assert!(parse("db.users.replaceOne({}, {a: 1})").is_err());
+
+    match parse("db.users.deleteMany({}, {confirm: true})").unwrap() {
+        MongoOperation::Delete { filter, .. } => assert!(filter.is_empty()),
+        other => panic!("unexpected operation: {:?}", other),
+    }
+    assert!(parse(r#"db.users.deleteMany({}, {confirm: "yes"})"#).is_err());
+}
+
+#[test]
+fn test_invalid_arguments_are_rejected() {
+    // 解析失败不能退化为空过滤条件
+    assert!(parse("db.users.deleteMany({age: })").is_err());
+    assert!(parse("db.users.deleteMany()").is_err());
+    assert!(parse("db.users.updateOne({a: 1})").is_err());
+    assert!(parse("db.users.updateOne({a: 1}, {b: 2})").is_err());
+    assert!(parse("db.users.updateOne({a: 1}, {})").is_err());
+    assert!(parse("db.users.replaceOne({a: 1}, {$set: {b: 2}})").is_err());
+    assert!(parse("db.users.insertOne({a: 1}).limit(1)").is_err());
+    assert!(parse("db.users.dropEverything()").is_err());
+}
+
+#[test]
+fn test_find_modifiers() {
+    let operation = parse("db.getCollection('orders').find({status: 'A'}, {total: 1}).sort({total: -1}).skip(10).limit(5)").unwrap();
+    match operation {
+        MongoOperation::Find { collection, filter, projection, sort, skip, limit } => {
+            assert_eq!(collection, "orders");
+            assert_eq!(filter, doc! { "status": "A" });
+            assert_eq!(projection, Some(doc! { "total": 1 }));
+            assert_eq!(sort, Some(doc! { "total": -1 }));
+            assert_eq!(skip, Some(10));
+            assert_eq!(limit, Some(5));
+        }
+        other => panic!("unexpected operation: {:?}", other),
+    }
+
+    assert!(matches!(parse("db.orders.find()").unwrap(), MongoOperation::Find { limit: None, .. }));
+    assert!(matches!(parse("db.orders.find().limit(0)").unwrap(), MongoOperation::Find { limit: None, .. }));
+    assert!(matches!(parse("db.orders.findOne({a: 1})").unwrap(), MongoOperation::Find { limit: Some(1), .. }));
+}
+
+#[test]
+fn test_other_methods() {
+    match parse("db.users.insertOne({a: 1}, {writeConcern: {w: 'majority'}, bypassDocumentValidation: true})").unwrap() {
+        MongoOperation::InsertOne { options, .. } => {
+            assert_eq!(options.bypass_document_validation, Some(true));
+            assert!(options.write_concern.is_some());
+        }
+        other => panic!("unexpected operation: {:?}", other),
+    }
+    assert!(parse("db.users.insertOne({a: 1}, {bypassDocumentValidation: 'yes'})").is_err());
+    assert!(matches!(
+        parse("db.users.insertMany([{a: 1}, {a: 2}], {ordered: false})").unwrap(),
+        MongoOperation::InsertMany { ref documents, .. } if documents.len() == 2
+    ));
+    assert!(parse("db.users.insertMany([])").is_err());
+
+    match parse("db.users.findOneAndUpdate({a: 1}, {$inc: {n: 1}}, {returnNewDocument: true, upsert: true})").unwrap() {
+        MongoOperation::FindOneAndUpdate { options, .. } => {
+            assert!(matches!(options.return_document, Some(mongodb::options::ReturnDocument::After)));
+            assert_eq!(options.upsert, Some(true));
+        }
+        other => panic!("unexpected operation: {:?}", other),
+    }
+
+    assert!(matches!(
+        parse("db.users.countDocuments({a: 1})").unwrap(),
+        MongoOperation::CountDocuments { ref filter, .. } if filter == &doc! { "a": 1 }
+    ));
+    assert!(matches!(
+        parse("db.users.distinct('city', {active: true})").unwrap(),
+        MongoOperation::Distinct { ref field, .. } if field == "city"
+    ));
+    assert!(matches!(
+        parse("db.users.aggregate([{$match: {a: 1}}, {$group: {_id: '$b', n: {$sum: 1}}}])").unwrap(),
+        MongoOperation::Aggregate { ref pipeline, .. } if pipeline.len() == 2
+    ));
+    match parse("db.users.createIndex({email: 1}, {unique: true, name: 'email_1'})").unwrap() {
+        MongoOperation::CreateIndex { keys, options, .. } => {
+            assert_eq!(keys, doc! { "email": 1 });
+            let options = options.unwrap();
+            assert_eq!(options.unique, Some(true));
+            assert_eq!(options.name.as_deref(), Some("email_1"));
+        }
+        other => panic!("unexpected operation: {:?}", other),
+    }
+}
+
+#[test]
+fn test_aggregate_options_and_write_confirmation() {
+    match parse("db.users.aggregate([{$match: {a: 1}}], {allowDiskUse: true, maxTimeMS: 5000})").unwrap() {
+        MongoOperation::Aggregate { options, .. } => {
+            assert_eq!(options.allow_disk_use, Some(true));
+            assert_eq!(options.max_time, Some(std::time::Duration::from_millis(5000)));
+        }
+        other => panic!("unexpected operation: {:?}", other),
+    }
+
+    assert!(parse("db.users.aggregate([{$match: {a: 1}}, {$out: 'archive'}])").is_err());
+    assert!(parse("db.users.aggregate([{$match: {a: 1}}, {$out: 'archive'}], {confirm: true})").is_ok());
+}
+
+#[test]
+fn test_bulk_write() {
+    match parse("db.users.bulkWrite([{insertOne: {document: {a: 1}}}, {deleteMany: {filter: {a: 2}}}], {ordered: false})").unwrap() {
+        MongoOperation::BulkWrite { collection, operations, options } => {
+            assert_eq!(collection, "users");
+            assert_eq!(operations.len(), 2);
+            assert_eq!(options.ordered, Some(false));
+        }
+        other => panic!("unexpected operation: {:?}", other),
+    }
+    assert!(parse("db.users.bulkWrite([])").is_err());
+    assert!(parse("db.users.bulkWrite([{deleteMany: {filter: {}}}])").is_err());
+    assert!(parse("db.users.bulkWrite([{deleteMany: {filter: {}}}], {confirm: true})").is_ok());
+}
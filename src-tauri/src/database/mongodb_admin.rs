@@ -0,0 +1,591 @@
+// MongoDB 管理：数据库、集合（含固定集合、时间序列集合和视图）、用户和角色
+//
+// MongoDB 没有单独的建库命令，数据库在创建第一个集合时才会出现，
+// 因此创建数据库需要同时给出第一个集合。用户和角色定义在各自的数据库中，
+// 权限列表同时包含直接授予和经角色继承的部分。
+
+use super::mongodb::MongoDBConnection;
+use super::mongodb_documents::{from_extended_json, to_extended_json, ExtendedJsonMode};
+use super::mongodb_shell::as_i64;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    Database,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// 不允许删除的系统数据库
+const SYSTEM_DATABASES: [&str; 3] = ["admin", "local", "config"];
+
+/// 数据库名中不允许出现的字符
+const INVALID_DATABASE_CHARS: &[char] = &['/', '\\', '.', ' ', '"', '$', '*', '<', '>', ':', '|', '?'];
+
+/// 数据库列表项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseInfo {
+    pub name: String,
+    pub size_on_disk: u64,
+    pub empty: bool,
+}
+
+/// 创建集合请求，固定集合、时间序列集合和视图三者互斥
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateCollectionRequest {
+    /// 为空时使用当前数据库
+    #[serde(default)]
+    pub database: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub capped: bool,
+    /// 固定集合的最大字节数
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// 固定集合的最大文档数
+    #[serde(default)]
+    pub max: Option<u64>,
+    /// 时间序列集合的时间字段
+    #[serde(default)]
+    pub time_field: Option<String>,
+    #[serde(default)]
+    pub meta_field: Option<String>,
+    /// seconds | minutes | hours
+    #[serde(default)]
+    pub granularity: Option<String>,
+    /// 时间序列数据的过期秒数
+    #[serde(default)]
+    pub expire_after_seconds: Option<u64>,
+    /// 视图的源集合
+    #[serde(default)]
+    pub view_on: Option<String>,
+    /// 视图的聚合管道
+    #[serde(default)]
+    pub pipeline: Option<Vec<serde_json::Value>>,
+    #[serde(default)]
+    pub validator: Option<serde_json::Value>,
+    #[serde(default)]
+    pub validation_level: Option<String>,
+    #[serde(default)]
+    pub validation_action: Option<String>,
+    #[serde(default)]
+    pub collation: Option<serde_json::Value>,
+}
+
+/// 角色引用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleRef {
+    pub role: String,
+    pub db: String,
+}
+
+/// 权限作用的资源：某个集合、整个数据库（collection 为空）、集群或任意资源
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivilegeResource {
+    #[serde(default)]
+    pub db: Option<String>,
+    #[serde(default)]
+    pub collection: Option<String>,
+    #[serde(default)]
+    pub cluster: bool,
+    #[serde(default)]
+    pub any_resource: bool,
+}
+
+/// 一条权限：资源和允许的操作
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Privilege {
+    pub resource: PrivilegeResource,
+    pub actions: Vec<String>,
+}
+
+/// 用户信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserInfo {
+    pub user: String,
+    pub db: String,
+    pub roles: Vec<RoleRef>,
+    pub mechanisms: Vec<String>,
+    pub custom_data: Option<serde_json::Value>,
+    /// 经全部角色（含继承）得到的权限
+    pub privileges: Vec<Privilege>,
+}
+
+/// 角色信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleInfo {
+    pub role: String,
+    pub db: String,
+    pub is_builtin: bool,
+    /// 直接继承的角色
+    pub roles: Vec<RoleRef>,
+    /// 直接授予的权限
+    pub privileges: Vec<Privilege>,
+    /// 包含继承角色在内的全部权限
+    pub inherited_privileges: Vec<Privilege>,
+}
+
+/// 创建用户请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateUserRequest {
+    /// 用户所在的认证数据库，为空时使用当前数据库
+    #[serde(default)]
+    pub database: Option<String>,
+    pub user: String,
+    pub password: String,
+    #[serde(default)]
+    pub roles: Vec<RoleRef>,
+    #[serde(default)]
+    pub mechanisms: Option<Vec<String>>,
+    #[serde(default)]
+    pub custom_data: Option<serde_json::Value>,
+}
+
+/// 修改用户请求，未提供的项保持不变，roles 会整体替换
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateUserRequest {
+    #[serde(default)]
+    pub database: Option<String>,
+    pub user: String,
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub roles: Option<Vec<RoleRef>>,
+    #[serde(default)]
+    pub custom_data: Option<serde_json::Value>,
+}
+
+/// 创建或修改角色请求，修改时 privileges 和 roles 为空表示保持不变
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleRequest {
+    #[serde(default)]
+    pub database: Option<String>,
+    pub role: String,
+    #[serde(default)]
+    pub privileges: Option<Vec<Privilege>>,
+    #[serde(default)]
+    pub roles: Option<Vec<RoleRef>>,
+}
+
+impl MongoDBConnection {
+    /// name 为空时使用当前数据库
+    fn target_database(&self, name: Option<&str>) -> anyhow::Result<Database> {
+        match name.filter(|name| !name.is_empty()) {
+            Some(name) => Ok(self.client()?.database(name)),
+            None => Ok(self.database()?.clone()),
+        }
+    }
+
+    /// 列出数据库及其占用空间
+    pub async fn list_database_infos(&self) -> anyhow::Result<Vec<DatabaseInfo>> {
+        let specs = self.client()?.list_databases(None, None).await
+            .map_err(|e| anyhow::anyhow!("获取数据库列表失败: {}", e))?;
+        Ok(specs.into_iter()
+            .map(|spec| DatabaseInfo { name: spec.name, size_on_disk: spec.size_on_disk, empty: spec.empty })
+            .collect())
+    }
+
+    /// 创建数据库，MongoDB 需要同时创建第一个集合
+    pub async fn create_database(&self, name: &str, collection: &str) -> anyhow::Result<()> {
+        check_database_name(name)?;
+        self.create_collection(&CreateCollectionRequest {
+            database: Some(name.to_string()),
+            name: collection.to_string(),
+            ..Default::default()
+        }).await
+    }
+
+    /// 删除数据库，系统数据库不允许删除
+    pub async fn drop_database(&self, name: &str) -> anyhow::Result<()> {
+        if SYSTEM_DATABASES.contains(&name) {
+            return Err(anyhow::anyhow!("系统数据库 {} 不能删除", name));
+        }
+        self.client()?.database(name).drop(None).await
+            .map_err(|e| anyhow::anyhow!("删除数据库 {} 失败: {}", name, e))
+    }
+
+    /// 创建普通集合、固定集合、时间序列集合或视图
+    pub async fn create_collection(&self, request: &CreateCollectionRequest) -> anyhow::Result<()> {
+        let command = create_collection_command(request)?;
+        self.target_database(request.database.as_deref())?
+            .run_command(command, None).await
+            .map_err(|e| anyhow::anyhow!("创建集合 {} 失败: {}", request.name, e))?;
+        Ok(())
+    }
+
+    /// 删除集合或视图
+    pub async fn drop_collection(&self, database: Option<&str>, name: &str) -> anyhow::Result<()> {
+        if name.starts_with("system.") {
+            return Err(anyhow::anyhow!("系统集合 {} 不能删除", name));
+        }
+        self.target_database(database)?
+            .collection::<Document>(name)
+            .drop(None).await
+            .map_err(|e| anyhow::anyhow!("删除集合 {} 失败: {}", name, e))
+    }
+
+    /// 重命名集合，drop_target 为 true 时覆盖已存在的目标集合
+    pub async fn rename_collection(
+        &self,
+        database: Option<&str>,
+        from: &str,
+        to: &str,
+        drop_target: bool,
+    ) -> anyhow::Result<()> {
+        let db = self.target_database(database)?;
+        let command = rename_command(db.name(), from, to, drop_target)?;
+        self.client()?.database("admin").run_command(command, None).await
+            .map_err(|e| anyhow::anyhow!("重命名集合 {} 失败: {}", from, e))?;
+        Ok(())
+    }
+
+    /// 列出数据库中的用户及其权限
+    pub async fn list_users(&self, database: Option<&str>) -> anyhow::Result<Vec<UserInfo>> {
+        let reply = self.target_database(database)?
+            .run_command(doc! { "usersInfo": 1, "showPrivileges": true }, None).await
+            .map_err(|e| anyhow::anyhow!("获取用户列表失败: {}", e))?;
+        let mut users: Vec<UserInfo> = documents(&reply, "users").map(parse_user).collect();
+        users.sort_by(|a, b| a.user.cmp(&b.user));
+        Ok(users)
+    }
+
+    pub async fn create_user(&self, request: &CreateUserRequest) -> anyhow::Result<()> {
+        let command = create_user_command(request)?;
+        self.target_database(request.database.as_deref())?
+            .run_command(command, None).await
+            .map_err(|e| anyhow::anyhow!("创建用户 {} 失败: {}", request.user, e))?;
+        Ok(())
+    }
+
+    pub async fn update_user(&self, request: &UpdateUserRequest) -> anyhow::Result<()> {
+        let command = update_user_command(request)?;
+        self.target_database(request.database.as_deref())?
+            .run_command(command, None).await
+            .map_err(|e| anyhow::anyhow!("修改用户 {} 失败: {}", request.user, e))?;
+        Ok(())
+    }
+
+    pub async fn drop_user(&self, database: Option<&str>, user: &str) -> anyhow::Result<()> {
+        self.target_database(database)?
+            .run_command(doc! { "dropUser": user }, None).await
+            .map_err(|e| anyhow::anyhow!("删除用户 {} 失败: {}", user, e))?;
+        Ok(())
+    }
+
+    /// 列出数据库中的角色，show_builtin 为 true 时包含内置角色
+    pub async fn list_roles(&self, database: Option<&str>, show_builtin: bool) -> anyhow::Result<Vec<RoleInfo>> {
+        let command = doc! { "rolesInfo": 1, "showPrivileges": true, "showBuiltinRoles": show_builtin };
+        let reply = self.target_database(database)?
+            .run_command(command, None).await
+            .map_err(|e| anyhow::anyhow!("获取角色列表失败: {}", e))?;
+        let mut roles: Vec<RoleInfo> = documents(&reply, "roles").map(parse_role).collect();
+        roles.sort_by(|a, b| a.is_builtin.cmp(&b.is_builtin).then_with(|| a.role.cmp(&b.role)));
+        Ok(roles)
+    }
+
+    pub async fn create_role(&self, request: &RoleRequest) -> anyhow::Result<()> {
+        let command = role_command("createRole", request)?;
+        self.target_database(request.database.as_deref())?
+            .run_command(command, None).await
+            .map_err(|e| anyhow::anyhow!("创建角色 {} 失败: {}", request.role, e))?;
+        Ok(())
+    }
+
+    pub async fn update_role(&self, request: &RoleRequest) -> anyhow::Result<()> {
+        let command = role_command("updateRole", request)?;
+        self.target_database(request.database.as_deref())?
+            .run_command(command, None).await
+            .map_err(|e| anyhow::anyhow!("修改角色 {} 失败: {}", request.role, e))?;
+        Ok(())
+    }
+
+    pub async fn drop_role(&self, database: Option<&str>, role: &str) -> anyhow::Result<()> {
+        self.target_database(database)?
+            .run_command(doc! { "dropRole": role }, None).await
+            .map_err(|e| anyhow::anyhow!("删除角色 {} 失败: {}", role, e))?;
+        Ok(())
+    }
+}
+
+pub(crate) fn check_database_name(name: &str) -> anyhow::Result<()> {
+    if name.is_empty() {
+        return Err(anyhow::anyhow!("数据库名不能为空"));
+    }
+    if name.len() >= 64 {
+        return Err(anyhow::anyhow!("数据库名不能超过 63 个字节"));
+    }
+    if let Some(c) = name.chars().find(|c| INVALID_DATABASE_CHARS.contains(c) || *c == '\0') {
+        return Err(anyhow::anyhow!("数据库名不能包含字符 '{}'", c));
+    }
+    Ok(())
+}
+
+fn check_collection_name(name: &str) -> anyhow::Result<()> {
+    if name.is_empty() {
+        return Err(anyhow::anyhow!("集合名不能为空"));
+    }
+    if name.contains('$') || name.contains('\0') {
+        return Err(anyhow::anyhow!("集合名不能包含 $ 或空字符"));
+    }
+    if name.starts_with("system.") {
+        return Err(anyhow::anyhow!("集合名不能以 system. 开头"));
+    }
+    Ok(())
+}
+
+fn json_document(value: &serde_json::Value, what: &str) -> anyhow::Result<Document> {
+    from_extended_json(value.clone()).map_err(|e| anyhow::anyhow!("{}{}", what, e))
+}
+
+pub(crate) fn create_collection_command(request: &CreateCollectionRequest) -> anyhow::Result<Document> {
+    check_collection_name(&request.name)?;
+    let time_series = request.time_field.is_some();
+    let view = request.view_on.is_some();
+    if [request.capped, time_series, view].iter().filter(|kind| **kind).count() > 1 {
+        return Err(anyhow::anyhow!("固定集合、时间序列集合和视图不能同时指定"));
+    }
+
+    let mut command = doc! { "create": request.name.as_str() };
+    if request.capped {
+        let size = request.size.filter(|size| *size > 0)
+            .ok_or_else(|| anyhow::anyhow!("固定集合必须指定 size（最大字节数）"))?;
+        command.insert("capped", true);
+        command.insert("size", size as i64);
+        if let Some(max) = request.max {
+            command.insert("max", max as i64);
+        }
+    } else if request.size.is_some() || request.max.is_some() {
+        return Err(anyhow::anyhow!("size 和 max 只适用于固定集合"));
+    }
+
+    if let Some(time_field) = &request.time_field {
+        if time_field.is_empty() {
+            return Err(anyhow::anyhow!("时间序列集合的 timeField 不能为空"));
+        }
+        let mut timeseries = doc! { "timeField": time_field.as_str() };
+        if let Some(meta_field) = request.meta_field.as_deref().filter(|field| !field.is_empty()) {
+            if meta_field == time_field {
+                return Err(anyhow::anyhow!("metaField 不能与 timeField 相同"));
+            }
+            timeseries.insert("metaField", meta_field);
+        }
+        if let Some(granularity) = &request.granularity {
+            if !matches!(granularity.as_str(), "seconds" | "minutes" | "hours") {
+                return Err(anyhow::anyhow!("granularity 只能是 seconds、minutes 或 hours"));
+            }
+            timeseries.insert("granularity", granularity.as_str());
+        }
+        command.insert("timeseries", timeseries);
+        if let Some(expire) = request.expire_after_seconds {
+            command.insert("expireAfterSeconds", expire as i64);
+        }
+    } else if request.meta_field.is_some() || request.granularity.is_some() || request.expire_after_seconds.is_some() {
+        return Err(anyhow::anyhow!("metaField、granularity 和 expireAfterSeconds 只适用于时间序列集合"));
+    }
+
+    if let Some(view_on) = &request.view_on {
+        if view_on.is_empty() {
+            return Err(anyhow::anyhow!("视图的源集合不能为空"));
+        }
+        if request.validator.is_some() {
+            return Err(anyhow::anyhow!("视图不支持校验规则"));
+        }
+        let pipeline = request.pipeline.iter().flatten()
+            .map(|stage| json_document(stage, "视图管道阶段").map(Bson::Document))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        command.insert("viewOn", view_on.as_str());
+        command.insert("pipeline", pipeline);
+    } else if request.pipeline.is_some() {
+        return Err(anyhow::anyhow!("pipeline 只适用于视图"));
+    }
+
+    if let Some(validator) = &request.validator {
+        command.insert("validator", json_document(validator, "校验规则")?);
+    }
+    if let Some(level) = &request.validation_level {
+        command.insert("validationLevel", level.as_str());
+    }
+    if let Some(action) = &request.validation_action {
+        command.insert("validationAction", action.as_str());
+    }
+    if let Some(collation) = &request.collation {
+        command.insert("collation", json_document(collation, "排序规则")?);
+    }
+    Ok(command)
+}
+
+pub(crate) fn rename_command(database: &str, from: &str, to: &str, drop_target: bool) -> anyhow::Result<Document> {
+    check_collection_name(to)?;
+    if from == to {
+        return Err(anyhow::anyhow!("新集合名与原集合名相同"));
+    }
+    Ok(doc! {
+        "renameCollection": format!("{}.{}", database, from),
+        "to": format!("{}.{}", database, to),
+        "dropTarget": drop_target,
+    })
+}
+
+fn role_refs(roles: &[RoleRef]) -> Vec<Bson> {
+    roles.iter()
+        .map(|role| Bson::Document(doc! { "role": role.role.as_str(), "db": role.db.as_str() }))
+        .collect()
+}
+
+pub(crate) fn create_user_command(request: &CreateUserRequest) -> anyhow::Result<Document> {
+    if request.user.is_empty() {
+        return Err(anyhow::anyhow!("用户名不能为空"));
+    }
+    if request.password.is_empty() {
+        return Err(anyhow::anyhow!("密码不能为空"));
+    }
+    let mut command = doc! {
+        "createUser": request.user.as_str(),
+        "pwd": request.password.as_str(),
+        "roles": role_refs(&request.roles),
+    };
+    if let Some(mechanisms) = &request.mechanisms {
+        command.insert("mechanisms", mechanisms.clone());
+    }
+    if let Some(custom_data) = &request.custom_data {
+        command.insert("customData", json_document(custom_data, "customData ")?);
+    }
+    Ok(command)
+}
+
+pub(crate) fn update_user_command(request: &UpdateUserRequest) -> anyhow::Result<Document> {
+    let mut command = doc! { "updateUser": request.user.as_str() };
+    if let Some(password) = &request.password {
+        if password.is_empty() {
+            return Err(anyhow::anyhow!("密码不能为空"));
+        }
+        command.insert("pwd", password.as_str());
+    }
+    if let Some(roles) = &request.roles {
+        command.insert("roles", role_refs(roles));
+    }
+    if let Some(custom_data) = &request.custom_data {
+        command.insert("customData", json_document(custom_data, "customData ")?);
+    }
+    if command.len() == 1 {
+        return Err(anyhow::anyhow!("没有需要修改的用户设置"));
+    }
+    Ok(command)
+}
+
+fn privilege_document(privilege: &Privilege) -> anyhow::Result<Document> {
+    if privilege.actions.is_empty() {
+        return Err(anyhow::anyhow!("权限至少需要一个操作"));
+    }
+    let resource = &privilege.resource;
+    let resource = if resource.any_resource {
+        doc! { "anyResource": true }
+    } else if resource.cluster {
+        doc! { "cluster": true }
+    } else {
+        // db 和 collection 为空字符串分别表示所有数据库和所有集合
+        doc! {
+            "db": resource.db.clone().unwrap_or_default(),
+            "collection": resource.collection.clone().unwrap_or_default(),
+        }
+    };
+    Ok(doc! { "resource": resource, "actions": privilege.actions.clone() })
+}
+
+pub(crate) fn role_command(name: &str, request: &RoleRequest) -> anyhow::Result<Document> {
+    if request.role.is_empty() {
+        return Err(anyhow::anyhow!("角色名不能为空"));
+    }
+    let mut command = Document::new();
+    command.insert(name, request.role.as_str());
+    let creating = name == "createRole";
+    match &request.privileges {
+        Some(privileges) => {
+            let privileges = privileges.iter()
+                .map(|privilege| privilege_document(privilege).map(Bson::Document))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            command.insert("privileges", privileges);
+        }
+        None if creating => {
+            command.insert("privileges", Bson::Array(vec![]));
+        }
+        None => {}
+    }
+    match &request.roles {
+        Some(roles) => {
+            command.insert("roles", role_refs(roles));
+        }
+        None if creating => {
+            command.insert("roles", Bson::Array(vec![]));
+        }
+        None => {}
+    }
+    if !creating && command.len() == 1 {
+        return Err(anyhow::anyhow!("没有需要修改的角色设置"));
+    }
+    Ok(command)
+}
+
+fn documents<'a>(reply: &'a Document, key: &str) -> impl Iterator<Item = &'a Document> {
+    reply.get_array(key).into_iter().flatten().filter_map(Bson::as_document)
+}
+
+fn parse_role_refs(document: &Document, key: &str) -> Vec<RoleRef> {
+    documents(document, key)
+        .map(|role| RoleRef {
+            role: role.get_str("role").unwrap_or_default().to_string(),
+            db: role.get_str("db").unwrap_or_default().to_string(),
+        })
+        .collect()
+}
+
+pub(crate) fn parse_privileges(document: &Document, key: &str) -> Vec<Privilege> {
+    documents(document, key)
+        .map(|privilege| {
+            let resource = privilege.get_document("resource").ok();
+            let text = |name: &str| resource.and_then(|resource| resource.get_str(name).ok()).map(str::to_string);
+            let flag = |name: &str| resource.and_then(|resource| resource.get_bool(name).ok()).unwrap_or(false);
+            Privilege {
+                resource: PrivilegeResource {
+                    db: text("db"),
+                    collection: text("collection"),
+                    cluster: flag("cluster"),
+                    any_resource: flag("anyResource"),
+                },
+                actions: privilege.get_array("actions").into_iter().flatten()
+                    .filter_map(Bson::as_str)
+                    .map(str::to_string)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn parse_user(user: &Document) -> UserInfo {
+    UserInfo {
+        user: user.get_str("user").unwrap_or_default().to_string(),
+        db: user.get_str("db").unwrap_or_default().to_string(),
+        roles: parse_role_refs(user, "roles"),
+        mechanisms: user.get_array("mechanisms").into_iter().flatten()
+            .filter_map(Bson::as_str)
+            .map(str::to_string)
+            .collect(),
+        custom_data: user.get_document("customData").ok()
+            .map(|data| to_extended_json(data.clone(), ExtendedJsonMode::Relaxed)),
+        privileges: parse_privileges(user, "inheritedPrivileges"),
+    }
+}
+
+pub(crate) fn parse_role(role: &Document) -> RoleInfo {
+    RoleInfo {
+        role: role.get_str("role").unwrap_or_default().to_string(),
+        db: role.get_str("db").unwrap_or_default().to_string(),
+        is_builtin: role.get("isBuiltin").and_then(|value| match value {
+            Bson::Boolean(builtin) => Some(*builtin),
+            other => as_i64(other).map(|v| v != 0),
+        }).unwrap_or(false),
+        roles: parse_role_refs(role, "roles"),
+        privileges: parse_privileges(role, "privileges"),
+        inherited_privileges: parse_privileges(role, "inheritedPrivileges"),
+    }
+}
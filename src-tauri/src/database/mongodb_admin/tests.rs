@@ -0,0 +1,167 @@
+use super::*;
+use serde_json::json;
+
+fn collection(name: &str) -> CreateCollectionRequest {
+    CreateCollectionRequest { name: name.to_string(), ..Default::default() }
+}
+
+#[test]
+fn test_check_database_name() {
+    assert!(check_database_name("shop").is_ok());
+    assert!(check_database_name("").is_err());
+    assert!(check_database_name("a.b").is_err());
+    assert!(check_database_name("a b").is_err());
+    assert!(check_database_name(&"x".repeat(64)).is_err());
+}
+
+#[test]
+fn test_create_plain_and_capped_collection() {
+    assert_eq!(create_collection_command(&collection("orders")).unwrap(), doc! { "create": "orders" });
+
+    let capped = CreateCollectionRequest { capped: true, size: Some(4096), max: Some(100), ..collection("log") };
+    assert_eq!(
+        create_collection_command(&capped).unwrap(),
+        doc! { "create": "log", "capped": true, "size": 4096_i64, "max": 100_i64 },
+    );
+    assert!(create_collection_command(&CreateCollectionRequest { capped: true, ..collection("log") }).is_err());
+    assert!(create_collection_command(&CreateCollectionRequest { size: Some(1), ..collection("log") }).is_err());
+    assert!(create_collection_command(&collection("system.js")).is_err());
+    assert!(create_collection_command(&collection("a$b")).is_err());
+}
+
+#[test]
+fn test_create_time_series_collection() {
+    let request = CreateCollectionRequest {
+        time_field: Some("ts".to_string()),
+        meta_field: Some("sensor".to_string()),
+        granularity: Some("minutes".to_string()),
+        expire_after_seconds: Some(86400),
+        ..collection("readings")
+    };
+    let command = create_collection_command(&request).unwrap();
+    assert_eq!(
+        command.get_document("timeseries").unwrap(),
+        &doc! { "timeField": "ts", "metaField": "sensor", "granularity": "minutes" },
+    );
+    assert_eq!(command.get_i64("expireAfterSeconds").unwrap(), 86400);
+
+    let bad = CreateCollectionRequest { granularity: Some("days".to_string()), ..request.clone() };
+    assert!(create_collection_command(&bad).is_err());
+    let mixed = CreateCollectionRequest { capped: true, size: Some(10), ..request };
+    assert!(create_collection_command(&mixed).is_err());
+    let stray = CreateCollectionRequest { granularity: Some("hours".to_string()), ..collection("x") };
+    assert!(create_collection_command(&stray).is_err());
+}
+
+#[test]
+fn test_create_view() {
+    let request = CreateCollectionRequest {
+        view_on: Some("orders".to_string()),
+        pipeline: Some(vec![json!({"$match": {"status": "A"}})]),
+        ..collection("active_orders")
+    };
+    let command = create_collection_command(&request).unwrap();
+    assert_eq!(command.get_str("viewOn").unwrap(), "orders");
+    assert_eq!(command.get_array("pipeline").unwrap().len(), 1);
+
+    let with_validator = CreateCollectionRequest { validator: Some(json!({"a": 1})), ..request };
+    assert!(create_collection_command(&with_validator).is_err());
+    let pipeline_only = CreateCollectionRequest { pipeline: Some(vec![]), ..collection("x") };
+    assert!(create_collection_command(&pipeline_only).is_err());
+}
+
+#[test]
+fn test_rename_command() {
+    assert_eq!(
+        rename_command("shop", "orders", "orders_old", false).unwrap(),
+        doc! { "renameCollection": "shop.orders", "to": "shop.orders_old", "dropTarget": false },
+    );
+    assert!(rename_command("shop", "orders", "orders", false).is_err());
+    assert!(rename_command("shop", "orders", "", false).is_err());
+}
+
+#[test]
+fn test_user_commands() {
+    let request = CreateUserRequest {
+        database: None,
+        user: "app".to_string(),
+        password: "secret".to_string(),
+        roles: vec![RoleRef { role: "readWrite".to_string(), db: "shop".to_string() }],
+        mechanisms: None,
+        custom_data: Some(json!({"team": "web"})),
+    };
+    let command = create_user_command(&request).unwrap();
+    assert_eq!(command.get_str("createUser").unwrap(), "app");
+    assert_eq!(command.get_array("roles").unwrap()[0], Bson::Document(doc! { "role": "readWrite", "db": "shop" }));
+    assert_eq!(command.get_document("customData").unwrap(), &doc! { "team": "web" });
+    assert!(create_user_command(&CreateUserRequest { password: String::new(), ..request }).is_err());
+
+    let update = UpdateUserRequest { database: None, user: "app".to_string(), password: None, roles: None, custom_data: None };
+    assert!(update_user_command(&update).is_err());
+    let update = UpdateUserRequest { roles: Some(vec![]), ..update };
+    assert_eq!(update_user_command(&update).unwrap(), doc! { "updateUser": "app", "roles": [] });
+}
+
+#[test]
+fn test_role_commands() {
+    let request = RoleRequest {
+        database: None,
+        role: "reporting".to_string(),
+        privileges: Some(vec![
+            Privilege {
+                resource: PrivilegeResource { db: Some("shop".to_string()), collection: None, ..Default::default() },
+                actions: vec!["find".to_string()],
+            },
+            Privilege {
+                resource: PrivilegeResource { cluster: true, ..Default::default() },
+                actions: vec!["serverStatus".to_string()],
+            },
+        ]),
+        roles: None,
+    };
+    let command = role_command("createRole", &request).unwrap();
+    let privileges = command.get_array("privileges").unwrap();
+    assert_eq!(
+        privileges[0],
+        Bson::Document(doc! { "resource": { "db": "shop", "collection": "" }, "actions": ["find"] }),
+    );
+    assert_eq!(privileges[1].as_document().unwrap().get_document("resource").unwrap(), &doc! { "cluster": true });
+    assert_eq!(command.get_array("roles").unwrap().len(), 0);
+
+    let empty = RoleRequest { privileges: None, ..request.clone() };
+    assert!(role_command("updateRole", &empty).is_err());
+    let no_actions = RoleRequest {
+        privileges: Some(vec![Privilege { resource: PrivilegeResource::default(), actions: vec![] }]),
+        ..request
+    };
+    assert!(role_command("createRole", &no_actions).is_err());
+}
+
+#[test]
+fn test_parse_user_and_role() {
+    let user = parse_user(&doc! {
+        "user": "app",
+        "db": "shop",
+        "roles": [{ "role": "read", "db": "shop" }],
+        "mechanisms": ["SCRAM-SHA-256"],
+        "inheritedPrivileges": [
+            { "resource": { "db": "shop", "collection": "" }, "actions": ["find", "listCollections"] },
+        ],
+    });
+    assert_eq!(user.roles, vec![RoleRef { role: "read".to_string(), db: "shop".to_string() }]);
+    assert_eq!(user.mechanisms, vec!["SCRAM-SHA-256"]);
+    assert_eq!(user.privileges[0].resource.db.as_deref(), Some("shop"));
+    assert_eq!(user.privileges[0].actions.len(), 2);
+
+    let role = parse_role(&doc! {
+        "role": "clusterMonitor",
+        "db": "admin",
+        "isBuiltin": true,
+        "roles": [],
+        "privileges": [{ "resource": { "cluster": true }, "actions": ["serverStatus"] }],
+        "inheritedPrivileges": [],
+    });
+    assert!(role.is_builtin);
+    assert!(role.privileges[0].resource.cluster);
+    assert!(role.inherited_privileges.is_empty());
+}
@@ -0,0 +1,272 @@
+// MongoDB 聚合管道：执行、逐阶段预览以及写入阶段检查
+//
+// 预览时依次执行管道的前 1..n 个阶段并追加 $limit，展示每个阶段的样例输出。
+// $out/$merge 会写入集合，预览中不会执行，正式执行时需要显式确认。
+
+use super::mongodb::{MongoDBConnection, DEFAULT_FIND_LIMIT};
+use super::mongodb_documents::{to_extended_json, ExtendedJsonMode};
+use futures::StreamExt;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    options::AggregateOptions,
+    Collection, Cursor,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests;
+
+/// 预览时每个阶段默认返回的样例条数
+const DEFAULT_PREVIEW_SIZE: i64 = 20;
+
+/// 聚合请求，管道阶段使用（扩展）JSON 表示
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateRequest {
+    pub collection: String,
+    pub pipeline: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub allow_disk_use: Option<bool>,
+    #[serde(default)]
+    pub max_time_ms: Option<u64>,
+    #[serde(default)]
+    pub batch_size: Option<u32>,
+    /// 最多返回的文档数，默认与 find 相同
+    #[serde(default)]
+    pub max_documents: Option<i64>,
+    /// 管道包含 $out/$merge 时必须为 true
+    #[serde(default)]
+    pub confirm_write: bool,
+}
+
+/// 聚合结果，文档为宽松模式的扩展 JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateResult {
+    pub documents: Vec<serde_json::Value>,
+    /// 结果超过 max_documents，只返回了前面的部分
+    pub truncated: bool,
+    pub warnings: Vec<String>,
+    pub execution_time: u64,
+}
+
+/// 单个阶段的预览
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StagePreview {
+    pub index: usize,
+    /// 阶段操作符，例如 $match
+    pub operator: String,
+    pub documents: Vec<serde_json::Value>,
+    pub execution_time: u64,
+    /// 写入阶段不会执行
+    pub skipped: bool,
+    pub error: Option<String>,
+}
+
+/// 整个管道的预览
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelinePreview {
+    pub stages: Vec<StagePreview>,
+    pub warnings: Vec<String>,
+}
+
+/// 管道检查结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct PipelineCheck {
+    pub warnings: Vec<String>,
+    /// $out/$merge 的目标集合
+    pub write_target: Option<String>,
+}
+
+impl MongoDBConnection {
+    /// 执行聚合管道
+    pub async fn aggregate(&self, request: &AggregateRequest) -> anyhow::Result<AggregateResult> {
+        let start = std::time::Instant::now();
+        let pipeline = parse_pipeline(&request.pipeline)?;
+        let check = check_pipeline(&pipeline)?;
+        if let Some(target) = &check.write_target {
+            if !request.confirm_write {
+                return Err(anyhow::anyhow!("管道会写入集合 {}，请确认后再执行", target));
+            }
+        }
+
+        let options = AggregateOptions::builder()
+            .allow_disk_use(request.allow_disk_use)
+            .max_time(request.max_time_ms.map(Duration::from_millis))
+            .batch_size(request.batch_size)
+            .build();
+        let max_documents = request.max_documents.filter(|n| *n > 0).unwrap_or(DEFAULT_FIND_LIMIT);
+        let (documents, truncated) = self.run_pipeline(&request.collection, pipeline, options, max_documents as usize).await?;
+
+        let mut warnings = check.warnings;
+        if truncated {
+            warnings.push(format!("结果超过 {} 条，只返回前 {} 条", max_documents, max_documents));
+        }
+        Ok(AggregateResult {
+            documents: documents.into_iter().map(|document| to_extended_json(document, ExtendedJsonMode::Relaxed)).collect(),
+            truncated,
+            warnings,
+            execution_time: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// 逐阶段预览，某个阶段失败后其余阶段不再执行
+    pub async fn preview_pipeline(
+        &self,
+        request: &AggregateRequest,
+        sample_size: Option<i64>,
+    ) -> anyhow::Result<PipelinePreview> {
+        let pipeline = parse_pipeline(&request.pipeline)?;
+        let check = check_pipeline(&pipeline)?;
+        let sample_size = sample_size.filter(|n| *n > 0).unwrap_or(DEFAULT_PREVIEW_SIZE);
+
+        let mut stages = Vec::with_capacity(pipeline.len());
+        let mut failed = false;
+        for (index, stage) in pipeline.iter().enumerate() {
+            let operator = stage_operator(stage);
+            if failed || is_write_stage(&operator) {
+                stages.push(StagePreview {
+                    index,
+                    operator,
+                    documents: vec![],
+                    execution_time: 0,
+                    skipped: true,
+                    error: None,
+                });
+                continue;
+            }
+
+            let start = std::time::Instant::now();
+            let mut prefix: Vec<Document> = pipeline[..=index].to_vec();
+            prefix.push(doc! { "$limit": sample_size });
+            let options = AggregateOptions::builder()
+                .allow_disk_use(request.allow_disk_use)
+                .max_time(request.max_time_ms.map(Duration::from_millis))
+                .build();
+
+            let (documents, error) = match self.run_pipeline(&request.collection, prefix, options, sample_size as usize).await {
+                Ok((documents, _)) => (documents.into_iter().map(|document| to_extended_json(document, ExtendedJsonMode::Relaxed)).collect(), None),
+                Err(e) => {
+                    failed = true;
+                    (vec![], Some(e.to_string()))
+                }
+            };
+            stages.push(StagePreview {
+                index,
+                operator,
+                documents,
+                execution_time: start.elapsed().as_millis() as u64,
+                skipped: false,
+                error,
+            });
+        }
+
+        Ok(PipelinePreview { stages, warnings: check.warnings })
+    }
+
+    async fn run_pipeline(
+        &self,
+        collection: &str,
+        pipeline: Vec<Document>,
+        options: AggregateOptions,
+        max_documents: usize,
+    ) -> anyhow::Result<(Vec<Document>, bool)> {
+        let coll: Collection<Document> = self.database()?.collection(collection);
+        let cursor = coll.aggregate(pipeline, options).await
+            .map_err(|e| anyhow::anyhow!("聚合执行失败: {}", e))?;
+        collect_documents(cursor, max_documents).await
+            .map_err(|e| anyhow::anyhow!("读取聚合结果失败: {}", e))
+    }
+}
+
+/// 最多读取 max_documents 个文档，第二项表示是否还有未读取的结果；
+/// 提前结束时游标被丢弃，驱动会关闭服务端游标
+pub(crate) async fn collect_documents(
+    mut cursor: Cursor<Document>,
+    max_documents: usize,
+) -> mongodb::error::Result<(Vec<Document>, bool)> {
+    let mut documents = Vec::new();
+    while let Some(document) = cursor.next().await {
+        if documents.len() == max_documents {
+            return Ok((documents, true));
+        }
+        documents.push(document?);
+    }
+    Ok((documents, false))
+}
+
+/// 将扩展 JSON 阶段转换为 BSON 文档
+pub(crate) fn parse_pipeline(stages: &[serde_json::Value]) -> anyhow::Result<Vec<Document>> {
+    stages.iter().enumerate().map(|(index, stage)| {
+        match Bson::try_from(stage.clone()) {
+            Ok(Bson::Document(stage)) => Ok(stage),
+            Ok(_) => Err(anyhow::anyhow!("第 {} 个阶段必须是文档", index + 1)),
+            Err(e) => Err(anyhow::anyhow!("第 {} 个阶段无效: {}", index + 1, e)),
+        }
+    }).collect()
+}
+
+fn stage_operator(stage: &Document) -> String {
+    stage.keys().next().cloned().unwrap_or_default()
+}
+
+fn is_write_stage(operator: &str) -> bool {
+    matches!(operator, "$out" | "$merge")
+}
+
+/// 检查阶段结构，并对写入或跨集合读取的阶段给出提示
+pub(crate) fn check_pipeline(pipeline: &[Document]) -> anyhow::Result<PipelineCheck> {
+    let mut check = PipelineCheck::default();
+    for (index, stage) in pipeline.iter().enumerate() {
+        if stage.len() != 1 {
+            return Err(anyhow::anyhow!("第 {} 个阶段必须且只能包含一个操作符", index + 1));
+        }
+        let operator = stage_operator(stage);
+        if !operator.starts_with('$') {
+            return Err(anyhow::anyhow!("第 {} 个阶段的操作符 '{}' 必须以 $ 开头", index + 1, operator));
+        }
+        let value = stage.get(&operator).unwrap_or(&Bson::Null);
+
+        match operator.as_str() {
+            "$out" | "$merge" => {
+                if index + 1 != pipeline.len() {
+                    return Err(anyhow::anyhow!("{} 必须是管道的最后一个阶段", operator));
+                }
+                let target = write_target(&operator, value);
+                check.warnings.push(if operator == "$out" {
+                    format!("$out 会用聚合结果替换集合 {} 的全部内容", target)
+                } else {
+                    format!("$merge 会将聚合结果写入集合 {}，可能更新或插入文档", target)
+                });
+                check.write_target = Some(target);
+            }
+            "$lookup" | "$graphLookup" => {
+                let from = value.as_document()
+                    .and_then(|spec| spec.get_str("from").ok())
+                    .unwrap_or("?");
+                check.warnings.push(format!(
+                    "第 {} 个阶段 {} 会读取集合 {}，请确认关联字段上有索引，否则每个输入文档都会扫描该集合",
+                    index + 1, operator, from
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(check)
+}
+
+/// $out: "coll" | {db, coll}；$merge: "coll" | {into: "coll" | {db, coll}}
+fn write_target(operator: &str, value: &Bson) -> String {
+    let target = match (operator, value) {
+        ("$merge", Bson::Document(spec)) => spec.get("into").unwrap_or(&Bson::Null),
+        _ => value,
+    };
+    match target {
+        Bson::String(name) => name.clone(),
+        Bson::Document(spec) => match (spec.get_str("db"), spec.get_str("coll")) {
+            (Ok(db), Ok(coll)) => format!("{}.{}", db, coll),
+            (Err(_), Ok(coll)) => coll.to_string(),
+            _ => "?".to_string(),
+        },
+        _ => "?".to_string(),
+    }
+}
@@ -0,0 +1,50 @@
+use super::*;
+use serde_json::json;
+
+#[test]
+fn test_parse_pipeline() {
+    let pipeline = parse_pipeline(&[
+        json!({"$match": {"_id": {"$oid": "507f1f77bcf86cd799439011"}}}),
+        json!({"$limit": 5}),
+    ]).unwrap();
+    assert_eq!(pipeline.len(), 2);
+    let id = pipeline[0].get_document("$match").unwrap().get("_id");
+    assert!(matches!(id, Some(Bson::ObjectId(_))));
+
+    assert!(parse_pipeline(&[json!([1, 2])]).is_err());
+}
+
+#[test]
+fn test_check_pipeline_warnings() {
+    let pipeline = vec![
+        doc! { "$lookup": { "from": "orders", "localField": "_id", "foreignField": "user_id", "as": "orders" } },
+        doc! { "$merge": { "into": { "db": "reports", "coll": "user_orders" } } },
+    ];
+    let check = check_pipeline(&pipeline).unwrap();
+    assert_eq!(check.write_target.as_deref(), Some("reports.user_orders"));
+    assert_eq!(check.warnings.len(), 2);
+    assert!(check.warnings[0].contains("orders"));
+
+    let check = check_pipeline(&[doc! { "$match": { "a": 1 } }, doc! { "$out": "archive" }]).unwrap();
+    assert_eq!(check.write_target.as_deref(), Some("archive"));
+
+    let check = check_pipeline(&[doc! { "$match": { "a": 1 } }]).unwrap();
+    assert_eq!(check, PipelineCheck::default());
+}
+
+#[test]
+fn test_check_pipeline_errors() {
+    assert!(check_pipeline(&[doc! { "$out": "x" }, doc! { "$match": {} }]).is_err());
+    assert!(check_pipeline(&[doc! { "$match": {}, "$limit": 1 }]).is_err());
+    assert!(check_pipeline(&[doc! { "match": {} }]).is_err());
+}
+
+#[test]
+fn test_request_defaults() {
+    let request: AggregateRequest = serde_json::from_value(json!({
+        "collection": "users",
+        "pipeline": [{"$match": {}}],
+    })).unwrap();
+    assert!(!request.confirm_write);
+    assert_eq!(request.max_time_ms, None);
+}
@@ -0,0 +1,445 @@
+// MongoDB 批量写入：insertMany 和 bulkWrite
+//
+// 驱动 2.x 的 insert_many 在部分失败时只返回错误，拿不到其余文档的结果，
+// 因此这里按 insert/update/delete 写命令分批执行：连续的同类操作合并为一个命令，
+// 服务端返回的 writeErrors 和 upserted 按批内下标映射回原始操作序号。
+// 插入前为缺少 _id 的文档生成 ObjectId，这样成功插入的文档都能给出 _id。
+
+use super::mongodb::{check_empty_filter, parse_update, MongoDBConnection};
+use super::mongodb_documents::{from_extended_json, to_extended_json, ExtendedJsonMode};
+use super::mongodb_shell::as_i64;
+use super::QueryResult;
+use mongodb::{
+    bson::{doc, oid::ObjectId, Bson, Document},
+    options::{UpdateModifications, WriteConcern},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod tests;
+
+/// 每个写命令最多包含的操作数
+const MAX_BATCH_OPERATIONS: usize = 1000;
+
+/// 每个写命令的大致字节上限，服务端单个命令文档不能超过 16MB
+const MAX_BATCH_BYTES: usize = 8 * 1024 * 1024;
+
+/// 批量写入中的一个操作，replaceOne 以不带操作符的更新表示
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BulkOperation {
+    Insert {
+        document: Document,
+    },
+    Update {
+        filter: Document,
+        update: Bson,
+        multi: bool,
+        upsert: bool,
+        /// arrayFilters、collation、hint
+        options: Document,
+    },
+    Delete {
+        filter: Document,
+        multi: bool,
+        /// collation、hint
+        options: Document,
+    },
+}
+
+/// 批量写入选项
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkWriteOptions {
+    /// 有序写入遇到第一个错误即停止，默认为 true
+    #[serde(default)]
+    pub ordered: Option<bool>,
+    #[serde(default)]
+    pub bypass_document_validation: Option<bool>,
+    #[serde(default)]
+    pub write_concern: Option<WriteConcern>,
+    /// 允许 update/replace/delete 使用空过滤条件
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+impl BulkWriteOptions {
+    fn is_ordered(&self) -> bool {
+        self.ordered.unwrap_or(true)
+    }
+}
+
+/// 批量写入请求，操作使用 mongosh 的写法，例如
+/// {"insertOne": {"document": {...}}}、{"updateMany": {"filter": {...}, "update": {...}}}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkWriteRequest {
+    pub collection: String,
+    pub operations: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub options: BulkWriteOptions,
+}
+
+/// 插入或 upsert 产生的 _id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkWriteId {
+    /// 操作在请求中的序号
+    pub index: usize,
+    pub id: serde_json::Value,
+}
+
+/// 单个操作的写入错误
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkWriteFailure {
+    pub index: usize,
+    pub code: i32,
+    pub message: String,
+    /// 服务端的 errInfo，例如校验失败的详情
+    pub details: Option<serde_json::Value>,
+}
+
+/// 批量写入结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkWriteResult {
+    pub inserted_count: u64,
+    pub matched_count: u64,
+    pub modified_count: u64,
+    pub deleted_count: u64,
+    pub upserted_count: u64,
+    pub inserted_ids: Vec<BulkWriteId>,
+    pub upserted_ids: Vec<BulkWriteId>,
+    pub write_errors: Vec<BulkWriteFailure>,
+    pub write_concern_error: Option<String>,
+    /// 有序写入因错误而未执行的操作数
+    pub skipped: u64,
+    pub execution_time: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BatchKind {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl BatchKind {
+    fn command(self) -> &'static str {
+        match self {
+            BatchKind::Insert => "insert",
+            BatchKind::Update => "update",
+            BatchKind::Delete => "delete",
+        }
+    }
+
+    fn field(self) -> &'static str {
+        match self {
+            BatchKind::Insert => "documents",
+            BatchKind::Update => "updates",
+            BatchKind::Delete => "deletes",
+        }
+    }
+}
+
+/// 一个写命令包含的操作，indexes 是每个条目在请求中的序号
+#[derive(Debug)]
+pub(crate) struct BulkBatch {
+    kind: BatchKind,
+    indexes: Vec<usize>,
+    entries: Vec<Document>,
+    bytes: usize,
+}
+
+impl MongoDBConnection {
+    /// 执行批量写入，写入错误记录在结果中而不是作为错误返回
+    pub(crate) async fn bulk_write(
+        &self,
+        collection: &str,
+        operations: Vec<BulkOperation>,
+        options: &BulkWriteOptions,
+    ) -> anyhow::Result<BulkWriteResult> {
+        let start = std::time::Instant::now();
+        let db = self.database()?;
+        let total = operations.len();
+        let ordered = options.is_ordered();
+        let mut result = BulkWriteResult::default();
+
+        for batch in plan_batches(operations) {
+            let executed = batch.indexes[0];
+            let reply = db.run_command(batch_command(collection, &batch, options)?, None).await
+                .map_err(|e| anyhow::anyhow!("批量写入失败（此前已执行 {} 个操作）: {}", executed, e))?;
+            if merge_reply(&mut result, &batch, &reply, ordered) {
+                let failed = result.write_errors.last().map_or(total, |failure| failure.index + 1);
+                result.skipped = (total - failed) as u64;
+                break;
+            }
+        }
+
+        result.execution_time = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    /// 执行前端提交的批量写入请求
+    pub async fn execute_bulk_write(&self, request: BulkWriteRequest) -> anyhow::Result<BulkWriteResult> {
+        if request.operations.is_empty() {
+            return Err(anyhow::anyhow!("批量写入至少需要一个操作"));
+        }
+        let operations = request.operations.into_iter()
+            .enumerate()
+            .map(|(index, operation)| {
+                from_extended_json(operation)
+                    .and_then(|operation| parse_bulk_operation(operation, request.options.confirm))
+                    .map_err(|e| anyhow::anyhow!("第 {} 个操作无效: {}", index, e))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.bulk_write(&request.collection, operations, &request.options).await
+    }
+
+    /// 批量插入扩展 JSON 文档
+    pub async fn insert_documents(
+        &self,
+        collection: &str,
+        documents: Vec<serde_json::Value>,
+        options: &BulkWriteOptions,
+    ) -> anyhow::Result<BulkWriteResult> {
+        if documents.is_empty() {
+            return Err(anyhow::anyhow!("没有要插入的文档"));
+        }
+        let operations = documents.into_iter()
+            .enumerate()
+            .map(|(index, document)| {
+                from_extended_json(document)
+                    .map(|document| BulkOperation::Insert { document })
+                    .map_err(|e| anyhow::anyhow!("第 {} 个文档无效: {}", index, e))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        self.bulk_write(collection, operations, options).await
+    }
+}
+
+fn update_bson(update: UpdateModifications) -> anyhow::Result<Bson> {
+    match update {
+        UpdateModifications::Document(update) => Ok(Bson::Document(update)),
+        UpdateModifications::Pipeline(pipeline) => Ok(Bson::Array(pipeline.into_iter().map(Bson::Document).collect())),
+        _ => Err(anyhow::anyhow!("不支持的更新内容")),
+    }
+}
+
+/// 解析 mongosh 写法的单个批量操作
+pub(crate) fn parse_bulk_operation(model: Document, confirmed: bool) -> anyhow::Result<BulkOperation> {
+    if model.len() != 1 {
+        return Err(anyhow::anyhow!("每个批量操作只能包含一个操作名，例如 {{insertOne: {{document: ...}}}}"));
+    }
+    let (name, body) = model.into_iter().next().unwrap_or_default();
+    let mut body = match body {
+        Bson::Document(body) => body,
+        _ => return Err(anyhow::anyhow!("{} 的内容必须是文档", name)),
+    };
+
+    let allowed: &[&str] = match name.as_str() {
+        "insertOne" => &["document"],
+        "updateOne" | "updateMany" => &["filter", "update", "upsert", "arrayFilters", "collation", "hint"],
+        "replaceOne" => &["filter", "replacement", "upsert", "collation", "hint"],
+        "deleteOne" | "deleteMany" => &["filter", "collation", "hint"],
+        other => return Err(anyhow::anyhow!("不支持的批量操作: {}", other)),
+    };
+    if let Some(key) = body.keys().find(|key| !allowed.contains(&key.as_str())) {
+        return Err(anyhow::anyhow!("{} 不支持字段 '{}'", name, key));
+    }
+
+    if name == "insertOne" {
+        return match body.remove("document") {
+            Some(Bson::Document(document)) => Ok(BulkOperation::Insert { document }),
+            _ => Err(anyhow::anyhow!("insertOne 需要 document 文档")),
+        };
+    }
+
+    let filter = match body.remove("filter") {
+        Some(Bson::Document(filter)) => filter,
+        _ => return Err(anyhow::anyhow!("{} 需要 filter 文档", name)),
+    };
+    check_empty_filter(&filter, confirmed, &name)?;
+    let upsert = match body.remove("upsert") {
+        None => false,
+        Some(Bson::Boolean(upsert)) => upsert,
+        Some(_) => return Err(anyhow::anyhow!("upsert 必须是布尔值")),
+    };
+
+    match name.as_str() {
+        "updateOne" | "updateMany" => {
+            let update = update_bson(parse_update(body.remove("update"))?)?;
+            Ok(BulkOperation::Update { filter, update, multi: name == "updateMany", upsert, options: body })
+        }
+        "replaceOne" => {
+            let replacement = match body.remove("replacement") {
+                Some(Bson::Document(replacement)) => replacement,
+                _ => return Err(anyhow::anyhow!("replaceOne 需要 replacement 文档")),
+            };
+            if let Some(key) = replacement.keys().find(|key| key.starts_with('$')) {
+                return Err(anyhow::anyhow!("替换文档不能包含更新操作符 '{}'，部分更新请使用 updateOne", key));
+            }
+            Ok(BulkOperation::Update { filter, update: Bson::Document(replacement), multi: false, upsert, options: body })
+        }
+        _ => Ok(BulkOperation::Delete { filter, multi: name == "deleteMany", options: body }),
+    }
+}
+
+/// 将操作转换为写命令中的条目
+fn batch_entry(operation: BulkOperation) -> (BatchKind, Document) {
+    match operation {
+        BulkOperation::Insert { document } => {
+            if document.contains_key("_id") {
+                return (BatchKind::Insert, document);
+            }
+            let mut with_id = doc! { "_id": ObjectId::new() };
+            with_id.extend(document);
+            (BatchKind::Insert, with_id)
+        }
+        BulkOperation::Update { filter, update, multi, upsert, options } => {
+            let mut entry = doc! { "q": filter, "u": update, "multi": multi, "upsert": upsert };
+            entry.extend(options);
+            (BatchKind::Update, entry)
+        }
+        BulkOperation::Delete { filter, multi, options } => {
+            let mut entry = doc! { "q": filter, "limit": if multi { 0 } else { 1 } };
+            entry.extend(options);
+            (BatchKind::Delete, entry)
+        }
+    }
+}
+
+/// 按顺序把连续的同类操作合并成批，每批不超过条数和大小限制
+pub(crate) fn plan_batches(operations: Vec<BulkOperation>) -> Vec<BulkBatch> {
+    let mut batches: Vec<BulkBatch> = Vec::new();
+    for (index, operation) in operations.into_iter().enumerate() {
+        let (kind, entry) = batch_entry(operation);
+        let bytes = bson::to_vec(&entry).map(|bytes| bytes.len()).unwrap_or(0);
+        match batches.last_mut() {
+            Some(batch) if batch.kind == kind
+                && batch.entries.len() < MAX_BATCH_OPERATIONS
+                && batch.bytes + bytes <= MAX_BATCH_BYTES =>
+            {
+                batch.indexes.push(index);
+                batch.entries.push(entry);
+                batch.bytes += bytes;
+            }
+            _ => batches.push(BulkBatch { kind, indexes: vec![index], entries: vec![entry], bytes }),
+        }
+    }
+    batches
+}
+
+pub(crate) fn batch_command(collection: &str, batch: &BulkBatch, options: &BulkWriteOptions) -> anyhow::Result<Document> {
+    let mut command = Document::new();
+    command.insert(batch.kind.command(), collection);
+    command.insert(batch.kind.field(), batch.entries.clone());
+    command.insert("ordered", options.is_ordered());
+    if let (Some(bypass), false) = (options.bypass_document_validation, batch.kind == BatchKind::Delete) {
+        command.insert("bypassDocumentValidation", bypass);
+    }
+    if let Some(write_concern) = &options.write_concern {
+        command.insert("writeConcern", bson::to_bson(write_concern)
+            .map_err(|e| anyhow::anyhow!("写关注无效: {}", e))?);
+    }
+    Ok(command)
+}
+
+fn count(document: &Document, key: &str) -> u64 {
+    document.get(key).and_then(as_i64).map_or(0, |v| v.max(0) as u64)
+}
+
+/// 合并一个写命令的返回，有序写入出现写入错误时返回 true 表示停止
+pub(crate) fn merge_reply(result: &mut BulkWriteResult, batch: &BulkBatch, reply: &Document, ordered: bool) -> bool {
+    let n = count(reply, "n");
+    let mut failed = HashSet::new();
+    for error in reply.get_array("writeErrors").into_iter().flatten().filter_map(Bson::as_document) {
+        let position = error.get("index").and_then(as_i64).unwrap_or(0).max(0) as usize;
+        failed.insert(position);
+        result.write_errors.push(BulkWriteFailure {
+            index: batch.indexes.get(position).copied().unwrap_or(position),
+            code: error.get("code").and_then(as_i64).unwrap_or(0) as i32,
+            message: error.get_str("errmsg").unwrap_or_default().to_string(),
+            details: error.get_document("errInfo").ok()
+                .map(|info| to_extended_json(info.clone(), ExtendedJsonMode::Relaxed)),
+        });
+    }
+    let first_failure = failed.iter().min().copied();
+
+    match batch.kind {
+        BatchKind::Insert => {
+            result.inserted_count += n;
+            for (position, entry) in batch.entries.iter().enumerate() {
+                let not_reached = ordered && first_failure.is_some_and(|first| position > first);
+                if failed.contains(&position) || not_reached {
+                    continue;
+                }
+                if let Some(id) = entry.get("_id") {
+                    result.inserted_ids.push(BulkWriteId {
+                        index: batch.indexes[position],
+                        id: id.clone().into_relaxed_extjson(),
+                    });
+                }
+            }
+        }
+        BatchKind::Update => {
+            let mut upserted = 0;
+            for entry in reply.get_array("upserted").into_iter().flatten().filter_map(Bson::as_document) {
+                let position = entry.get("index").and_then(as_i64).unwrap_or(0).max(0) as usize;
+                upserted += 1;
+                result.upserted_ids.push(BulkWriteId {
+                    index: batch.indexes.get(position).copied().unwrap_or(position),
+                    id: entry.get("_id").cloned().unwrap_or(Bson::Null).into_relaxed_extjson(),
+                });
+            }
+            result.upserted_count += upserted;
+            result.matched_count += n.saturating_sub(upserted);
+            result.modified_count += count(reply, "nModified");
+        }
+        BatchKind::Delete => result.deleted_count += n,
+    }
+
+    if let Ok(error) = reply.get_document("writeConcernError") {
+        result.write_concern_error = Some(error.get_str("errmsg").unwrap_or("写关注错误").to_string());
+    }
+    ordered && first_failure.is_some()
+}
+
+/// shell 语句的结果表：每个插入、upsert 和失败的操作一行，最后一行为汇总
+pub(crate) fn bulk_table(result: &BulkWriteResult) -> QueryResult {
+    let mut rows: Vec<(usize, Vec<String>)> = Vec::new();
+    for id in &result.inserted_ids {
+        rows.push((id.index, vec![id.index.to_string(), "inserted".to_string(), id.id.to_string(), String::new()]));
+    }
+    for id in &result.upserted_ids {
+        rows.push((id.index, vec![id.index.to_string(), "upserted".to_string(), id.id.to_string(), String::new()]));
+    }
+    for failure in &result.write_errors {
+        rows.push((failure.index, vec![
+            failure.index.to_string(),
+            "error".to_string(),
+            String::new(),
+            format!("[{}] {}", failure.code, failure.message),
+        ]));
+    }
+    rows.sort_by_key(|(index, _)| *index);
+
+    let mut summary = format!(
+        "插入 {}，匹配 {}，修改 {}，删除 {}，upsert {}，失败 {}",
+        result.inserted_count, result.matched_count, result.modified_count,
+        result.deleted_count, result.upserted_count, result.write_errors.len(),
+    );
+    if result.skipped > 0 {
+        summary.push_str(&format!("，未执行 {}", result.skipped));
+    }
+    if let Some(error) = &result.write_concern_error {
+        summary.push_str(&format!("，写关注错误: {}", error));
+    }
+
+    QueryResult {
+        columns: vec!["index".to_string(), "result".to_string(), "id".to_string(), "message".to_string()],
+        rows: rows.into_iter()
+            .map(|(_, row)| row)
+            .chain(std::iter::once(vec![String::new(), "summary".to_string(), String::new(), summary]))
+            .collect(),
+        affected_rows: result.inserted_count + result.modified_count + result.deleted_count + result.upserted_count,
+        execution_time: result.execution_time,
+    }
+}
@@ -0,0 +1,160 @@
+use super::*;
+
+fn operation(model: Document) -> anyhow::Result<BulkOperation> {
+    parse_bulk_operation(model, false)
+}
+
+fn ops(models: Vec<Document>) -> Vec<BulkOperation> {
+    models.into_iter().map(|model| operation(model).unwrap()).collect()
+}
+
+#[test]
+fn test_parse_bulk_operations() {
+    assert_eq!(
+        operation(doc! { "insertOne": { "document": { "a": 1 } } }).unwrap(),
+        BulkOperation::Insert { document: doc! { "a": 1 } },
+    );
+    assert_eq!(
+        operation(doc! { "updateMany": { "filter": { "a": 1 }, "update": { "$set": { "b": 2 } }, "upsert": true } }).unwrap(),
+        BulkOperation::Update {
+            filter: doc! { "a": 1 },
+            update: Bson::Document(doc! { "$set": { "b": 2 } }),
+            multi: true,
+            upsert: true,
+            options: Document::new(),
+        },
+    );
+    assert!(matches!(
+        operation(doc! { "replaceOne": { "filter": { "_id": 1 }, "replacement": { "a": 2 } } }).unwrap(),
+        BulkOperation::Update { multi: false, upsert: false, .. }
+    ));
+    assert!(matches!(
+        operation(doc! { "deleteOne": { "filter": { "a": 1 }, "hint": "a_1" } }).unwrap(),
+        BulkOperation::Delete { multi: false, ref options, .. } if options == &doc! { "hint": "a_1" }
+    ));
+}
+
+#[test]
+fn test_parse_bulk_operation_errors() {
+    assert!(operation(doc! { "insertOne": { "document": {} }, "deleteOne": { "filter": {} } }).is_err());
+    assert!(operation(doc! { "dropCollection": {} }).is_err());
+    assert!(operation(doc! { "insertOne": { "doc": { "a": 1 } } }).is_err());
+    assert!(operation(doc! { "updateOne": { "filter": { "a": 1 }, "update": { "b": 2 } } }).is_err());
+    assert!(operation(doc! { "replaceOne": { "filter": { "a": 1 }, "replacement": { "$set": { "b": 2 } } } }).is_err());
+    assert!(operation(doc! { "deleteMany": { "filter": {} } }).is_err());
+    assert!(parse_bulk_operation(doc! { "deleteMany": { "filter": {} } }, true).is_ok());
+}
+
+#[test]
+fn test_plan_batches_groups_consecutive_operations() {
+    let batches = plan_batches(ops(vec![
+        doc! { "insertOne": { "document": { "a": 1 } } },
+        doc! { "insertOne": { "document": { "_id": 7, "a": 2 } } },
+        doc! { "deleteOne": { "filter": { "a": 1 } } },
+        doc! { "insertOne": { "document": { "a": 3 } } },
+    ]));
+    assert_eq!(batches.len(), 3);
+    assert_eq!(batches[0].indexes, vec![0, 1]);
+    assert_eq!(batches[1].kind, BatchKind::Delete);
+    assert_eq!(batches[1].entries[0], doc! { "q": { "a": 1 }, "limit": 1 });
+    assert_eq!(batches[2].indexes, vec![3]);
+
+    // 缺少 _id 的文档会生成 ObjectId 并放在第一个字段
+    let generated = &batches[0].entries[0];
+    assert_eq!(generated.keys().next().map(String::as_str), Some("_id"));
+    assert!(generated.get_object_id("_id").is_ok());
+    assert_eq!(batches[0].entries[1].get_i32("_id").unwrap(), 7);
+}
+
+#[test]
+fn test_plan_batches_splits_large_batches() {
+    let models = (0..MAX_BATCH_OPERATIONS + 1)
+        .map(|i| doc! { "insertOne": { "document": { "_id": i as i64 } } })
+        .collect();
+    let batches = plan_batches(ops(models));
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[1].indexes, vec![MAX_BATCH_OPERATIONS]);
+}
+
+#[test]
+fn test_batch_command() {
+    let batches = plan_batches(ops(vec![doc! { "deleteMany": { "filter": { "a": 1 } } }]));
+    let options = BulkWriteOptions {
+        ordered: Some(false),
+        bypass_document_validation: Some(true),
+        ..Default::default()
+    };
+    let command = batch_command("users", &batches[0], &options).unwrap();
+    assert_eq!(command.get_str("delete").unwrap(), "users");
+    assert!(!command.get_bool("ordered").unwrap());
+    assert!(!command.contains_key("bypassDocumentValidation"));
+}
+
+#[test]
+fn test_merge_insert_reply_with_ordered_error() {
+    let batches = plan_batches(ops(vec![
+        doc! { "deleteOne": { "filter": { "a": 1 } } },
+        doc! { "insertOne": { "document": { "_id": 1 } } },
+        doc! { "insertOne": { "document": { "_id": 1 } } },
+        doc! { "insertOne": { "document": { "_id": 2 } } },
+    ]));
+    let mut result = BulkWriteResult::default();
+    let reply = doc! {
+        "n": 1,
+        "writeErrors": [{ "index": 1, "code": 11000, "errmsg": "E11000 duplicate key error" }],
+        "ok": 1.0,
+    };
+    assert!(merge_reply(&mut result, &batches[1], &reply, true));
+    assert_eq!(result.inserted_count, 1);
+    assert_eq!(result.inserted_ids.len(), 1);
+    assert_eq!(result.inserted_ids[0].index, 1);
+    assert_eq!(result.write_errors[0].index, 2);
+    assert_eq!(result.write_errors[0].code, 11000);
+}
+
+#[test]
+fn test_merge_unordered_reply_continues() {
+    let batches = plan_batches(ops(vec![
+        doc! { "insertOne": { "document": { "_id": 1 } } },
+        doc! { "insertOne": { "document": { "_id": 1 } } },
+        doc! { "insertOne": { "document": { "_id": 2 } } },
+    ]));
+    let mut result = BulkWriteResult::default();
+    let reply = doc! { "n": 2, "writeErrors": [{ "index": 1, "code": 11000, "errmsg": "dup" }] };
+    assert!(!merge_reply(&mut result, &batches[0], &reply, false));
+    let indexes: Vec<usize> = result.inserted_ids.iter().map(|id| id.index).collect();
+    assert_eq!(indexes, vec![0, 2]);
+}
+
+#[test]
+fn test_merge_update_reply_with_upserts() {
+    let batches = plan_batches(ops(vec![
+        doc! { "insertOne": { "document": { "_id": 1 } } },
+        doc! { "updateOne": { "filter": { "a": 1 }, "update": { "$set": { "b": 1 } } } },
+        doc! { "updateOne": { "filter": { "a": 2 }, "update": { "$set": { "b": 1 } }, "upsert": true } },
+    ]));
+    let mut result = BulkWriteResult::default();
+    let reply = doc! { "n": 2, "nModified": 1, "upserted": [{ "index": 1, "_id": 42 }] };
+    assert!(!merge_reply(&mut result, &batches[1], &reply, true));
+    assert_eq!(result.matched_count, 1);
+    assert_eq!(result.modified_count, 1);
+    assert_eq!(result.upserted_count, 1);
+    assert_eq!(result.upserted_ids[0].index, 2);
+    assert_eq!(result.upserted_ids[0].id, serde_json::json!(42));
+}
+
+#[test]
+fn test_bulk_table() {
+    let result = BulkWriteResult {
+        inserted_count: 1,
+        inserted_ids: vec![BulkWriteId { index: 0, id: serde_json::json!(1) }],
+        write_errors: vec![BulkWriteFailure { index: 1, code: 11000, message: "dup".to_string(), details: None }],
+        skipped: 1,
+        ..Default::default()
+    };
+    let table = bulk_table(&result);
+    assert_eq!(table.rows.len(), 3);
+    assert_eq!(table.rows[1][3], "[11000] dup");
+    assert!(table.rows[2][3].contains("未执行 1"));
+    assert_eq!(table.affected_rows, 1);
+}
@@ -0,0 +1,421 @@
+// MongoDB 变更流推送
+//
+// 可以在集合、数据库或整个集群上开启变更流（需要副本集或分片集群）。
+// 后台任务按批次把事件交给 ChangeStreamSink（由命令层转发为 Tauri 事件），
+// 每批都带上最新的 resume token；流出错时用该 token 自动重新打开，
+// 前端保存的 token 也可以在重新连接后传回，从断开处继续。
+
+use super::mongodb::MongoDBConnection;
+use super::mongodb_aggregate::parse_pipeline;
+use super::mongodb_documents::{to_extended_json, ExtendedJsonMode};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use mongodb::{
+    bson::{self, Bson, Document},
+    change_stream::{event::ResumeToken, ChangeStream},
+    options::{ChangeStreamOptions, FullDocumentBeforeChangeType, FullDocumentType},
+    Client, Collection, Database,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+#[cfg(test)]
+mod tests;
+
+/// 出错后自动恢复的最大尝试次数
+const MAX_RESUME_ATTEMPTS: u32 = 5;
+
+/// 变更流的范围
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeStreamScope {
+    Collection,
+    Database,
+    Cluster,
+}
+
+/// 开启变更流的请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeStreamRequest {
+    pub scope: ChangeStreamScope,
+    /// 为空时使用当前数据库，cluster 范围忽略
+    #[serde(default)]
+    pub database: Option<String>,
+    /// collection 范围必填
+    #[serde(default)]
+    pub collection: Option<String>,
+    /// 过滤管道，只允许 $match、$project 等不改变事件顺序的阶段
+    #[serde(default)]
+    pub pipeline: Vec<serde_json::Value>,
+    /// default | updateLookup | whenAvailable | required
+    #[serde(default)]
+    pub full_document: Option<String>,
+    /// off | whenAvailable | required
+    #[serde(default)]
+    pub full_document_before_change: Option<String>,
+    /// 从该 token 之后继续
+    #[serde(default)]
+    pub resume_token: Option<serde_json::Value>,
+    /// 单个事件最多携带的变更数
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+    /// 批量推送的最长等待时间（毫秒）
+    #[serde(default = "default_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+}
+
+fn default_max_batch_size() -> usize {
+    100
+}
+
+fn default_flush_interval_ms() -> u64 {
+    200
+}
+
+/// 推送到前端的单条变更
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    /// insert、update、replace、delete、drop 等
+    pub operation_type: String,
+    /// 数据库.集合
+    pub namespace: Option<String>,
+    pub document_key: Option<serde_json::Value>,
+    /// 完整的变更事件，宽松模式扩展 JSON
+    pub event: serde_json::Value,
+}
+
+/// 一次推送的变更批次
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeStreamBatch {
+    pub watch_id: String,
+    pub events: Vec<ChangeEvent>,
+    /// 本批次之后的 resume token
+    pub resume_token: Option<serde_json::Value>,
+}
+
+/// 变更流信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeStreamInfo {
+    pub watch_id: String,
+    pub connection_id: String,
+    pub scope: ChangeStreamScope,
+    pub database: Option<String>,
+    pub collection: Option<String>,
+    pub started_at: DateTime<Utc>,
+}
+
+/// 变更接收端，由命令层实现（转发为 Tauri 事件）
+pub trait ChangeStreamSink: Send + Sync + 'static {
+    /// 推送一批变更
+    fn on_batch(&self, batch: ChangeStreamBatch);
+    /// 变更流结束，error 为 None 表示正常停止
+    fn on_closed(&self, watch_id: &str, error: Option<String>, resume_token: Option<serde_json::Value>);
+}
+
+/// 变更流的监听目标
+#[derive(Clone)]
+pub enum WatchTarget {
+    Collection(Collection<Document>),
+    Database(Database),
+    Cluster(Client),
+}
+
+impl WatchTarget {
+    pub fn database_name(&self) -> Option<String> {
+        match self {
+            WatchTarget::Collection(coll) => Some(coll.namespace().db),
+            WatchTarget::Database(db) => Some(db.name().to_string()),
+            WatchTarget::Cluster(_) => None,
+        }
+    }
+
+    async fn open(&self, pipeline: &[Document], options: ChangeStreamOptions) -> anyhow::Result<ChangeStream<Document>> {
+        let pipeline = pipeline.to_vec();
+        let stream = match self {
+            WatchTarget::Collection(coll) => coll.watch(pipeline, options).await.map(ChangeStream::with_type),
+            WatchTarget::Database(db) => db.watch(pipeline, options).await.map(ChangeStream::with_type),
+            WatchTarget::Cluster(client) => client.watch(pipeline, options).await.map(ChangeStream::with_type),
+        };
+        stream.map_err(|e| anyhow::anyhow!("打开变更流失败: {}", e))
+    }
+}
+
+impl MongoDBConnection {
+    /// 解析变更流的监听目标
+    pub fn watch_target(&self, request: &ChangeStreamRequest) -> anyhow::Result<WatchTarget> {
+        let database = || -> anyhow::Result<Database> {
+            match request.database.as_deref().filter(|name| !name.is_empty()) {
+                Some(name) => Ok(self.client()?.database(name)),
+                None => Ok(self.database()?.clone()),
+            }
+        };
+        Ok(match request.scope {
+            ChangeStreamScope::Collection => {
+                let collection = request.collection.as_deref()
+                    .filter(|name| !name.is_empty())
+                    .ok_or_else(|| anyhow::anyhow!("集合范围的变更流需要指定集合"))?;
+                WatchTarget::Collection(database()?.collection(collection))
+            }
+            ChangeStreamScope::Database => WatchTarget::Database(database()?),
+            ChangeStreamScope::Cluster => WatchTarget::Cluster(self.client()?.clone()),
+        })
+    }
+}
+
+// ===== 变更流句柄 =====
+
+/// 运行中的变更流，drop 或调用 stop 后后台任务退出
+pub struct ChangeStreamHandle {
+    pub info: ChangeStreamInfo,
+    stop_tx: Option<oneshot::Sender<()>>,
+    resume_token: Arc<Mutex<Option<serde_json::Value>>>,
+    finished: Arc<AtomicBool>,
+}
+
+impl ChangeStreamHandle {
+    /// 最新的 resume token
+    pub fn resume_token(&self) -> Option<serde_json::Value> {
+        self.resume_token.lock().ok().and_then(|token| token.clone())
+    }
+
+    /// 服务端关闭或恢复失败后后台任务已退出
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    /// 停止变更流
+    pub fn stop(mut self) {
+        if let Some(tx) = self.stop_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+impl Drop for ChangeStreamHandle {
+    fn drop(&mut self) {
+        if let Some(tx) = self.stop_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+/// 打开变更流，变更通过 sink 推送
+pub async fn start_watch(
+    target: WatchTarget,
+    info: ChangeStreamInfo,
+    request: ChangeStreamRequest,
+    sink: Arc<dyn ChangeStreamSink>,
+) -> anyhow::Result<ChangeStreamHandle> {
+    let pipeline = parse_pipeline(&request.pipeline)?;
+    check_change_stream_pipeline(&pipeline)?;
+    let initial_token = request.resume_token.clone().map(parse_resume_token).transpose()?;
+    let options = change_stream_options(&request, initial_token)?;
+    let stream = target.open(&pipeline, options.clone()).await?;
+
+    let (stop_tx, stop_rx) = oneshot::channel::<()>();
+    let resume_token = Arc::new(Mutex::new(request.resume_token.clone()));
+    let finished = Arc::new(AtomicBool::new(false));
+    let worker = Worker {
+        target,
+        pipeline,
+        options,
+        watch_id: info.watch_id.clone(),
+        max_batch_size: request.max_batch_size.max(1),
+        flush_interval: Duration::from_millis(request.flush_interval_ms.max(10)),
+        resume_token: resume_token.clone(),
+        finished: finished.clone(),
+        sink,
+    };
+    tokio::spawn(worker.run(stream, stop_rx));
+
+    Ok(ChangeStreamHandle {
+        info,
+        stop_tx: Some(stop_tx),
+        resume_token,
+        finished,
+    })
+}
+
+struct Worker {
+    target: WatchTarget,
+    pipeline: Vec<Document>,
+    options: ChangeStreamOptions,
+    watch_id: String,
+    max_batch_size: usize,
+    flush_interval: Duration,
+    resume_token: Arc<Mutex<Option<serde_json::Value>>>,
+    finished: Arc<AtomicBool>,
+    sink: Arc<dyn ChangeStreamSink>,
+}
+
+enum Step {
+    Stop,
+    Next(Option<mongodb::error::Result<Document>>),
+    Tick,
+}
+
+impl Worker {
+    async fn run(self, mut stream: ChangeStream<Document>, mut stop_rx: oneshot::Receiver<()>) {
+        let mut batch = Vec::with_capacity(self.max_batch_size);
+        let mut ticker = tokio::time::interval(self.flush_interval);
+
+        let error = loop {
+            let step = tokio::select! {
+                _ = &mut stop_rx => Step::Stop,
+                next = stream.next() => Step::Next(next),
+                _ = ticker.tick() => Step::Tick,
+            };
+
+            match step {
+                Step::Stop => break None,
+                Step::Next(Some(Ok(event))) => {
+                    batch.push(change_event(event));
+                    self.save_token(&stream);
+                    if batch.len() >= self.max_batch_size {
+                        self.flush(&mut batch);
+                    }
+                }
+                Step::Next(Some(Err(e))) => {
+                    self.flush(&mut batch);
+                    tracing::warn!("变更流 {} 出错，尝试恢复: {}", self.watch_id, e);
+                    match self.resume(&mut stop_rx).await {
+                        Ok(Some(resumed)) => stream = resumed,
+                        Ok(None) => break None,
+                        Err(resume_error) => break Some(format!("{}（恢复失败: {}）", e, resume_error)),
+                    }
+                }
+                // 集合或数据库被删除、重命名后服务端会关闭变更流
+                Step::Next(None) => break Some("变更流已失效".to_string()),
+                Step::Tick => {
+                    // 没有新事件时 token 也会前进（postBatchResumeToken）
+                    self.save_token(&stream);
+                    self.flush(&mut batch);
+                }
+            }
+        };
+
+        self.flush(&mut batch);
+        let token = self.resume_token.lock().ok().and_then(|token| token.clone());
+        self.finished.store(true, Ordering::Relaxed);
+        self.sink.on_closed(&self.watch_id, error, token);
+    }
+
+    fn save_token(&self, stream: &ChangeStream<Document>) {
+        if let Some(token) = stream.resume_token().and_then(|token| resume_token_json(&token)) {
+            if let Ok(mut current) = self.resume_token.lock() {
+                *current = Some(token);
+            }
+        }
+    }
+
+    fn flush(&self, batch: &mut Vec<ChangeEvent>) {
+        if batch.is_empty() {
+            return;
+        }
+        self.sink.on_batch(ChangeStreamBatch {
+            watch_id: self.watch_id.clone(),
+            events: std::mem::replace(batch, Vec::with_capacity(self.max_batch_size)),
+            resume_token: self.resume_token.lock().ok().and_then(|token| token.clone()),
+        });
+    }
+
+    /// 按指数退避从最新的 token 重新打开，收到停止信号时返回 Ok(None)
+    async fn resume(&self, stop_rx: &mut oneshot::Receiver<()>) -> anyhow::Result<Option<ChangeStream<Document>>> {
+        let mut last_error = anyhow::anyhow!("没有可用的 resume token");
+        for attempt in 0..MAX_RESUME_ATTEMPTS {
+            let delay = Duration::from_secs(1 << attempt);
+            tokio::select! {
+                _ = &mut *stop_rx => return Ok(None),
+                _ = tokio::time::sleep(delay) => {}
+            }
+
+            let token = self.resume_token.lock().ok().and_then(|token| token.clone());
+            let mut options = self.options.clone();
+            if let Some(token) = token {
+                options.resume_after = Some(parse_resume_token(token)?);
+                options.start_after = None;
+                options.start_at_operation_time = None;
+            }
+            match self.target.open(&self.pipeline, options).await {
+                Ok(stream) => return Ok(Some(stream)),
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+}
+
+// ===== 选项与事件转换 =====
+
+pub(crate) fn change_stream_options(
+    request: &ChangeStreamRequest,
+    resume_token: Option<ResumeToken>,
+) -> anyhow::Result<ChangeStreamOptions> {
+    let full_document = match request.full_document.as_deref() {
+        None | Some("") | Some("default") => None,
+        Some("updateLookup") => Some(FullDocumentType::UpdateLookup),
+        Some("whenAvailable") => Some(FullDocumentType::WhenAvailable),
+        Some("required") => Some(FullDocumentType::Required),
+        Some(other) => return Err(anyhow::anyhow!("不支持的 fullDocument 模式: {}", other)),
+    };
+    let full_document_before_change = match request.full_document_before_change.as_deref() {
+        None | Some("") => None,
+        Some("off") => Some(FullDocumentBeforeChangeType::Off),
+        Some("whenAvailable") => Some(FullDocumentBeforeChangeType::WhenAvailable),
+        Some("required") => Some(FullDocumentBeforeChangeType::Required),
+        Some(other) => return Err(anyhow::anyhow!("不支持的 fullDocumentBeforeChange 模式: {}", other)),
+    };
+
+    Ok(ChangeStreamOptions::builder()
+        .full_document(full_document)
+        .full_document_before_change(full_document_before_change)
+        .resume_after(resume_token)
+        .build())
+}
+
+/// 变更流管道只允许这些阶段
+const CHANGE_STREAM_STAGES: &[&str] = &[
+    "$match", "$project", "$addFields", "$set", "$unset", "$replaceRoot", "$replaceWith", "$redact",
+];
+
+pub(crate) fn check_change_stream_pipeline(pipeline: &[Document]) -> anyhow::Result<()> {
+    for (index, stage) in pipeline.iter().enumerate() {
+        let operator = stage.keys().next().map(String::as_str).unwrap_or_default();
+        if stage.len() != 1 || !CHANGE_STREAM_STAGES.contains(&operator) {
+            return Err(anyhow::anyhow!(
+                "第 {} 个阶段 {} 不能用于变更流，只支持 {}",
+                index + 1, operator, CHANGE_STREAM_STAGES.join("、")
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_resume_token(value: serde_json::Value) -> anyhow::Result<ResumeToken> {
+    let token = Bson::try_from(value).map_err(|e| anyhow::anyhow!("resume token 无效: {}", e))?;
+    bson::from_bson(token).map_err(|e| anyhow::anyhow!("resume token 无效: {}", e))
+}
+
+pub(crate) fn resume_token_json(token: &ResumeToken) -> Option<serde_json::Value> {
+    bson::to_bson(token).ok().map(Bson::into_relaxed_extjson)
+}
+
+pub(crate) fn change_event(event: Document) -> ChangeEvent {
+    let namespace = event.get_document("ns").ok().map(|ns| {
+        match (ns.get_str("db"), ns.get_str("coll")) {
+            (Ok(db), Ok(coll)) => format!("{}.{}", db, coll),
+            (Ok(db), Err(_)) => db.to_string(),
+            _ => String::new(),
+        }
+    });
+    ChangeEvent {
+        operation_type: event.get_str("operationType").unwrap_or_default().to_string(),
+        namespace: namespace.filter(|ns| !ns.is_empty()),
+        document_key: event.get_document("documentKey").ok()
+            .map(|key| to_extended_json(key.clone(), ExtendedJsonMode::Relaxed)),
+        event: to_extended_json(event, ExtendedJsonMode::Relaxed),
+    }
+}
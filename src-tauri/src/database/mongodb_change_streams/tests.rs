@@ -0,0 +1,75 @@
+use super::*;
+use mongodb::bson::{doc, oid::ObjectId};
+
+fn request(value: serde_json::Value) -> ChangeStreamRequest {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_request_defaults() {
+    let req = request(serde_json::json!({ "scope": "collection", "collection": "orders" }));
+    assert_eq!(req.scope, ChangeStreamScope::Collection);
+    assert_eq!(req.max_batch_size, 100);
+    assert_eq!(req.flush_interval_ms, 200);
+    assert!(req.pipeline.is_empty());
+}
+
+#[test]
+fn test_change_stream_options() {
+    let req = request(serde_json::json!({
+        "scope": "database",
+        "full_document": "updateLookup",
+        "full_document_before_change": "whenAvailable",
+    }));
+    let options = change_stream_options(&req, None).unwrap();
+    assert!(matches!(options.full_document, Some(FullDocumentType::UpdateLookup)));
+    assert!(matches!(options.full_document_before_change, Some(FullDocumentBeforeChangeType::WhenAvailable)));
+
+    let default = request(serde_json::json!({ "scope": "cluster", "full_document": "default" }));
+    assert!(change_stream_options(&default, None).unwrap().full_document.is_none());
+
+    let invalid = request(serde_json::json!({ "scope": "cluster", "full_document": "always" }));
+    assert!(change_stream_options(&invalid, None).is_err());
+}
+
+#[test]
+fn test_resume_token_round_trip() {
+    let json = serde_json::json!({ "_data": "826540F1A2000000012B022C0100296E5A1004" });
+    let token = parse_resume_token(json.clone()).unwrap();
+    assert_eq!(resume_token_json(&token), Some(json));
+
+    let req = request(serde_json::json!({ "scope": "cluster" }));
+    let options = change_stream_options(&req, Some(token.clone())).unwrap();
+    assert_eq!(options.resume_after, Some(token));
+}
+
+#[test]
+fn test_check_change_stream_pipeline() {
+    let allowed = vec![
+        doc! { "$match": { "operationType": { "$in": ["insert", "update"] } } },
+        doc! { "$project": { "fullDocument.password": 0 } },
+    ];
+    assert!(check_change_stream_pipeline(&allowed).is_ok());
+    assert!(check_change_stream_pipeline(&[doc! { "$group": { "_id": "$ns" } }]).is_err());
+    assert!(check_change_stream_pipeline(&[doc! { "$lookup": { "from": "users" } }]).is_err());
+}
+
+#[test]
+fn test_change_event() {
+    let id = ObjectId::new();
+    let event = change_event(doc! {
+        "_id": { "_data": "8265" },
+        "operationType": "insert",
+        "ns": { "db": "shop", "coll": "orders" },
+        "documentKey": { "_id": id },
+        "fullDocument": { "_id": id, "total": 10 },
+    });
+    assert_eq!(event.operation_type, "insert");
+    assert_eq!(event.namespace.as_deref(), Some("shop.orders"));
+    assert_eq!(event.document_key, Some(serde_json::json!({ "_id": { "$oid": id.to_hex() } })));
+    assert_eq!(event.event["fullDocument"]["total"], 10);
+
+    let dropped = change_event(doc! { "operationType": "dropDatabase", "ns": { "db": "shop" } });
+    assert_eq!(dropped.namespace.as_deref(), Some("shop"));
+    assert!(dropped.document_key.is_none());
+}
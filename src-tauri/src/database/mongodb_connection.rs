@@ -0,0 +1,328 @@
+// MongoDB 连接串与拓扑信息
+//
+// 连接选项可以是完整的 URI（options["uri"]，或 host 直接填写 mongodb:// / mongodb+srv://），
+// 也可以是结构化的设置：host 中用逗号分隔多个主机，其余设置放在 options 中，
+// 由这里拼成标准连接串后交给驱动解析，两种方式最终走同一套驱动校验。
+
+use super::mongodb::MongoDBConnection;
+use super::mongodb_shell::as_i64;
+use super::ConnectionConfig;
+use mongodb::bson::{doc, Bson, Document};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests;
+
+/// 结构化设置对应的 URI 参数名，options 中的键不区分大小写
+const URI_OPTIONS: [&str; 13] = [
+    "replicaSet",
+    "readPreference",
+    "readPreferenceTags",
+    "directConnection",
+    "authSource",
+    "authMechanism",
+    "appName",
+    "tls",
+    "tlsCAFile",
+    "tlsCertificateKeyFile",
+    "tlsAllowInvalidCertificates",
+    "connectTimeoutMS",
+    "serverSelectionTimeoutMS",
+];
+
+/// 取值为布尔的 URI 参数
+const BOOLEAN_OPTIONS: [&str; 3] = ["directConnection", "tls", "tlsAllowInvalidCertificates"];
+
+/// 副本集成员
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologyMember {
+    pub host: String,
+    /// PRIMARY、SECONDARY、ARBITER 等
+    pub state: String,
+    pub healthy: bool,
+    /// 当前连接的节点
+    pub is_self: bool,
+    pub uptime_seconds: Option<u64>,
+    pub ping_ms: Option<u64>,
+    /// 相对主节点的复制延迟
+    pub lag_seconds: Option<u64>,
+}
+
+/// 连接后的拓扑信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologyInfo {
+    /// standalone | replicaSet | sharded
+    pub kind: String,
+    pub set_name: Option<String>,
+    pub primary: Option<String>,
+    pub me: Option<String>,
+    pub members: Vec<TopologyMember>,
+    pub version: Option<String>,
+    /// 没有 replSetGetStatus 权限时成员信息只来自 hello，没有健康状态和延迟
+    pub detailed: bool,
+}
+
+impl MongoDBConnection {
+    /// 通过 hello、buildInfo 和 replSetGetStatus 获取拓扑信息
+    pub async fn topology(&self) -> anyhow::Result<TopologyInfo> {
+        let admin = self.client()?.database("admin");
+        let hello = admin.run_command(doc! { "hello": 1 }, None).await
+            .map_err(|e| anyhow::anyhow!("获取拓扑信息失败: {}", e))?;
+        let mut topology = parse_hello(&hello);
+
+        if let Ok(build) = admin.run_command(doc! { "buildInfo": 1 }, None).await {
+            topology.version = build.get_str("version").ok().map(str::to_string);
+        }
+        if topology.kind == "replicaSet" {
+            match admin.run_command(doc! { "replSetGetStatus": 1 }, None).await {
+                Ok(status) => {
+                    topology.members = parse_replica_status(&status);
+                    topology.detailed = true;
+                }
+                Err(e) => tracing::warn!("获取副本集状态失败，只显示 hello 中的成员: {}", e),
+            }
+        }
+        Ok(topology)
+    }
+}
+
+/// 连接字符串，优先使用完整 URI，否则按结构化设置拼接
+pub(crate) fn connection_uri(config: &ConnectionConfig) -> anyhow::Result<String> {
+    let uri = option(&config.options, "uri")
+        .or_else(|| Some(config.host.trim()).filter(|host| is_uri(host)));
+    if let Some(uri) = uri {
+        if !is_uri(uri) {
+            return Err(anyhow::anyhow!("连接串必须以 mongodb:// 或 mongodb+srv:// 开头"));
+        }
+        return Ok(with_credentials(uri, config));
+    }
+
+    let hosts: Vec<String> = config.host.split(',')
+        .map(str::trim)
+        .filter(|host| !host.is_empty())
+        .map(|host| if has_port(host) { host.to_string() } else { format!("{}:{}", host, config.port) })
+        .collect();
+    if hosts.is_empty() {
+        return Err(anyhow::anyhow!("缺少主机地址"));
+    }
+
+    let params = uri_params(&config.options)?;
+    if hosts.len() > 1 && params.iter().any(|(key, value)| *key == "directConnection" && value == "true") {
+        return Err(anyhow::anyhow!("directConnection 只能用于单个主机"));
+    }
+    let x509 = params.iter().any(|(key, value)| *key == "authMechanism" && value == "MONGODB-X509");
+
+    // 不在路径中带数据库名，否则未指定 authSource 时会改为在该库认证
+    let mut uri = format!("mongodb://{}{}/", credentials(config, x509), hosts.join(","));
+    if !params.is_empty() {
+        let query: Vec<String> = params.iter()
+            .map(|(key, value)| format!("{}={}", key, percent_encode(value)))
+            .collect();
+        uri.push('?');
+        uri.push_str(&query.join("&"));
+    }
+    Ok(uri)
+}
+
+fn is_uri(value: &str) -> bool {
+    value.starts_with("mongodb://") || value.starts_with("mongodb+srv://")
+}
+
+/// IPv6 地址写作 [::1] 或 [::1]:27017
+fn has_port(host: &str) -> bool {
+    match host.rfind(']') {
+        Some(end) => host[end..].contains(':'),
+        None => host.contains(':'),
+    }
+}
+
+fn option<'a>(options: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    options.iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+/// 从 options 中取出 URI 参数并校验取值，顺序与 URI_OPTIONS 一致
+pub(crate) fn uri_params(options: &HashMap<String, String>) -> anyhow::Result<Vec<(&'static str, String)>> {
+    let mut params = Vec::new();
+    for name in URI_OPTIONS {
+        let Some(value) = option(options, name) else { continue };
+        if BOOLEAN_OPTIONS.contains(&name) {
+            let value = value.to_ascii_lowercase();
+            if value != "true" && value != "false" {
+                return Err(anyhow::anyhow!("{} 只能是 true 或 false", name));
+            }
+            params.push((name, value));
+            continue;
+        }
+        match name {
+            "readPreferenceTags" => {
+                // 多组标签用分号分隔，按顺序作为候选，例如 "dc:ny,rack:1;dc:sf"
+                for tags in value.split(';').map(str::trim) {
+                    if !tags.is_empty() && tags.split(',').any(|tag| !tag.contains(':')) {
+                        return Err(anyhow::anyhow!("readPreferenceTags 格式应为 键:值,键:值"));
+                    }
+                    params.push((name, tags.to_string()));
+                }
+            }
+            "readPreference" => {
+                if !matches!(value, "primary" | "primaryPreferred" | "secondary" | "secondaryPreferred" | "nearest") {
+                    return Err(anyhow::anyhow!(
+                        "readPreference 只能是 primary、primaryPreferred、secondary、secondaryPreferred 或 nearest"
+                    ));
+                }
+                params.push((name, value.to_string()));
+            }
+            "authMechanism" => {
+                let mechanism = value.to_ascii_uppercase();
+                if !matches!(
+                    mechanism.as_str(),
+                    "SCRAM-SHA-1" | "SCRAM-SHA-256" | "MONGODB-X509" | "PLAIN" | "GSSAPI" | "MONGODB-AWS"
+                ) {
+                    return Err(anyhow::anyhow!("不支持的认证机制: {}", value));
+                }
+                params.push((name, mechanism));
+            }
+            "connectTimeoutMS" | "serverSelectionTimeoutMS" => {
+                value.parse::<u64>().map_err(|_| anyhow::anyhow!("{} 必须是毫秒数", name))?;
+                params.push((name, value.to_string()));
+            }
+            _ => params.push((name, value.to_string())),
+        }
+    }
+
+    let read_preference = params.iter().find(|(key, _)| *key == "readPreference").map(|(_, value)| value.as_str());
+    if params.iter().any(|(key, _)| *key == "readPreferenceTags") && matches!(read_preference, None | Some("primary")) {
+        return Err(anyhow::anyhow!("readPreference 为 primary 时不能指定标签"));
+    }
+    let x509 = params.iter().any(|(key, value)| *key == "authMechanism" && value == "MONGODB-X509");
+    if x509 && !params.iter().any(|(key, _)| *key == "tlsCertificateKeyFile") {
+        return Err(anyhow::anyhow!("X.509 认证需要指定客户端证书 tlsCertificateKeyFile"));
+    }
+    Ok(params)
+}
+
+/// 用户名和密码部分，X.509 认证只带用户名（证书主题）
+fn credentials(config: &ConnectionConfig, x509: bool) -> String {
+    match (config.username.as_deref().filter(|user| !user.is_empty()), config.password.as_deref()) {
+        (Some(user), Some(password)) if !x509 && !password.is_empty() => {
+            format!("{}:{}@", percent_encode(user), percent_encode(password))
+        }
+        (Some(user), _) => format!("{}@", percent_encode(user)),
+        (None, _) => String::new(),
+    }
+}
+
+/// 完整 URI 中没有用户信息时补上单独填写的用户名和密码
+fn with_credentials(uri: &str, config: &ConnectionConfig) -> String {
+    let (scheme, rest) = uri.split_once("://").unwrap_or(("mongodb", uri));
+    let authority = rest.split(['/', '?']).next().unwrap_or_default();
+    let credentials = credentials(config, false);
+    if authority.contains('@') || credentials.is_empty() {
+        return uri.to_string();
+    }
+    format!("{}://{}{}", scheme, credentials, rest)
+}
+
+/// 按 RFC 3986 编码非保留字符以外的字节
+pub(crate) fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// 日志中隐藏连接串里的密码
+pub(crate) fn redact_uri(uri: &str) -> String {
+    let Some((scheme, rest)) = uri.split_once("://") else { return uri.to_string() };
+    let authority_end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let Some(at) = rest[..authority_end].rfind('@') else { return uri.to_string() };
+    match rest[..at].split_once(':') {
+        Some((user, _)) => format!("{}://{}:***{}", scheme, user, &rest[at..]),
+        None => uri.to_string(),
+    }
+}
+
+fn strings(document: &Document, key: &str) -> Vec<String> {
+    document.get_array(key).into_iter().flatten()
+        .filter_map(Bson::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+/// 解析 hello 的返回，成员只有地址和角色
+pub(crate) fn parse_hello(hello: &Document) -> TopologyInfo {
+    let set_name = hello.get_str("setName").ok().map(str::to_string);
+    let kind = if hello.get_str("msg").ok() == Some("isdbgrid") {
+        "sharded"
+    } else if set_name.is_some() {
+        "replicaSet"
+    } else {
+        "standalone"
+    };
+    let primary = hello.get_str("primary").ok().map(str::to_string);
+    let me = hello.get_str("me").ok().map(str::to_string);
+
+    let member = |host: String, state: &str| TopologyMember {
+        is_self: me.as_deref() == Some(host.as_str()),
+        state: state.to_string(),
+        healthy: true,
+        uptime_seconds: None,
+        ping_ms: None,
+        lag_seconds: None,
+        host,
+    };
+    let mut members = Vec::new();
+    for host in strings(hello, "hosts") {
+        let state = if primary.as_deref() == Some(host.as_str()) { "PRIMARY" } else { "SECONDARY" };
+        members.push(member(host, state));
+    }
+    members.extend(strings(hello, "passives").into_iter().map(|host| member(host, "PASSIVE")));
+    members.extend(strings(hello, "arbiters").into_iter().map(|host| member(host, "ARBITER")));
+
+    TopologyInfo {
+        kind: kind.to_string(),
+        set_name,
+        primary,
+        me,
+        members,
+        version: None,
+        detailed: false,
+    }
+}
+
+/// 解析 replSetGetStatus 的成员，复制延迟以主节点的 optimeDate 为基准
+pub(crate) fn parse_replica_status(status: &Document) -> Vec<TopologyMember> {
+    let members: Vec<&Document> = status.get_array("members").into_iter().flatten()
+        .filter_map(Bson::as_document)
+        .collect();
+    let optime = |member: &Document| member.get_datetime("optimeDate").ok().map(|date| date.timestamp_millis());
+    let primary_optime = members.iter()
+        .find(|member| member.get_str("stateStr").ok() == Some("PRIMARY"))
+        .and_then(|member| optime(member));
+
+    members.iter()
+        .map(|member| {
+            let state = member.get_str("stateStr").unwrap_or("UNKNOWN").to_string();
+            let lag_seconds = match (state.as_str(), primary_optime, optime(member)) {
+                ("SECONDARY", Some(primary), Some(own)) => Some((primary - own).max(0) as u64 / 1000),
+                _ => None,
+            };
+            TopologyMember {
+                host: member.get_str("name").unwrap_or_default().to_string(),
+                healthy: member.get("health").and_then(as_i64).is_none_or(|health| health == 1),
+                is_self: member.get_bool("self").unwrap_or(false),
+                uptime_seconds: member.get("uptime").and_then(as_i64).map(|v| v.max(0) as u64),
+                ping_ms: member.get("pingMs").and_then(as_i64).map(|v| v.max(0) as u64),
+                lag_seconds,
+                state,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,143 @@
+// MongoDB 文档结果
+//
+// find/aggregate 的结果以扩展 JSON 文档返回，ObjectId、Date、Decimal128、Binary
+// 等类型不会被展平为字符串。宽松模式更易读，规范模式连数字类型
+// （Int32/Int64/Double）也保留；宽松模式的数字写回时会变成 Int32 或 Double，
+// 因此替换文档只接受规范模式。
+
+use super::enhanced_types::{DatabaseType, DatabaseUIConfig, EnhancedQueryResult, QueryData, QueryMetadata};
+use super::mongodb::{MongoDBConnection, QueryOutput};
+use super::mongodb_explain::split_explain;
+use super::QueryResult;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    Collection,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// 扩展 JSON 的输出模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtendedJsonMode {
+    /// 宽松模式：数字、字符串等直接输出，日期为 ISO-8601
+    #[default]
+    Relaxed,
+    /// 规范模式：所有非 JSON 原生类型都带类型包装，例如 {"$numberInt": "1"}
+    Canonical,
+}
+
+impl MongoDBConnection {
+    /// 执行查询并以文档形式返回结果，非文档结果（计数、写入结果等）按行转换为文档；
+    /// 以 .explain() 结尾的语句返回原始执行计划，解析后的计划放在 execution_plan 中
+    pub async fn query_documents(&self, query: &str, mode: ExtendedJsonMode) -> anyhow::Result<EnhancedQueryResult> {
+        let start = std::time::Instant::now();
+        if let Some((statement, verbosity)) = split_explain(query)? {
+            let explain = self.explain_statement(statement, verbosity).await?;
+            let plan = serde_json::to_string(&explain.plan)?;
+            let warnings = explain.plan.warnings.clone();
+            return Ok(document_result(query, vec![explain.raw], Some(plan), warnings, start));
+        }
+
+        let documents = match self.run_query(query).await? {
+            QueryOutput::Documents(documents) => documents.into_iter()
+                .map(|document| to_extended_json(document, mode))
+                .collect(),
+            QueryOutput::Table(result) => table_documents(result),
+        };
+        Ok(document_result(query, documents, None, vec![], start))
+    }
+
+    /// 按 _id 整体替换文档，document 必须是规范模式的扩展 JSON，返回修改的文档数
+    pub async fn replace_document(&self, collection: &str, document: serde_json::Value) -> anyhow::Result<u64> {
+        require_canonical(&document, "")?;
+        let document = from_extended_json(document)?;
+        let id = document.get("_id")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("文档缺少 _id，无法定位要替换的文档"))?;
+
+        let coll: Collection<Document> = self.database()?.collection(collection);
+        let result = coll.replace_one(doc! { "_id": id }, document, None).await
+            .map_err(|e| anyhow::anyhow!("替换文档失败: {}", e))?;
+        if result.matched_count == 0 {
+            return Err(anyhow::anyhow!("集合 {} 中没有找到该 _id 的文档", collection));
+        }
+        Ok(result.modified_count)
+    }
+}
+
+fn document_result(
+    query: &str,
+    documents: Vec<serde_json::Value>,
+    execution_plan: Option<String>,
+    warnings: Vec<String>,
+    start: std::time::Instant,
+) -> EnhancedQueryResult {
+    EnhancedQueryResult {
+        db_type: DatabaseType::MongoDB,
+        data: QueryData::Document {
+            documents,
+            collection_stats: None,
+            indexes: None,
+        },
+        metadata: QueryMetadata {
+            query: query.to_string(),
+            timestamp: chrono::Utc::now(),
+            execution_plan,
+            warnings,
+        },
+        execution_time: start.elapsed().as_millis() as u64,
+        ui_config: DatabaseUIConfig::for_db_type(&DatabaseType::MongoDB),
+    }
+}
+
+pub fn to_extended_json(document: Document, mode: ExtendedJsonMode) -> serde_json::Value {
+    match mode {
+        ExtendedJsonMode::Relaxed => Bson::Document(document).into_relaxed_extjson(),
+        ExtendedJsonMode::Canonical => Bson::Document(document).into_canonical_extjson(),
+    }
+}
+
+/// 解析扩展 JSON 文档，两种模式都可以识别
+pub fn from_extended_json(value: serde_json::Value) -> anyhow::Result<Document> {
+    match Bson::try_from(value) {
+        Ok(Bson::Document(document)) => Ok(document),
+        Ok(_) => Err(anyhow::anyhow!("必须是文档")),
+        Err(e) => Err(anyhow::anyhow!("无效的扩展 JSON: {}", e)),
+    }
+}
+
+/// 规范模式中的数字都带类型包装，出现裸数字说明是宽松模式，写回会改变数字类型
+fn require_canonical(value: &serde_json::Value, path: &str) -> anyhow::Result<()> {
+    match value {
+        serde_json::Value::Number(n) => Err(anyhow::anyhow!(
+            "字段 {} 的数字 {} 没有类型包装，请使用规范模式的扩展 JSON 编辑文档",
+            if path.is_empty() { "(根)" } else { path }, n
+        )),
+        serde_json::Value::Array(items) => items.iter()
+            .enumerate()
+            .try_for_each(|(index, item)| require_canonical(item, &field_path(path, &index.to_string()))),
+        serde_json::Value::Object(map) => map.iter()
+            .try_for_each(|(key, item)| require_canonical(item, &field_path(path, key))),
+        _ => Ok(()),
+    }
+}
+
+fn field_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() { key.to_string() } else { format!("{}.{}", parent, key) }
+}
+
+/// 将表格结果的每一行转换为一个文档
+fn table_documents(result: QueryResult) -> Vec<serde_json::Value> {
+    result.rows.into_iter()
+        .map(|row| {
+            let fields = result.columns.iter()
+                .cloned()
+                .zip(row.into_iter().map(serde_json::Value::String))
+                .collect();
+            serde_json::Value::Object(fields)
+        })
+        .collect()
+}
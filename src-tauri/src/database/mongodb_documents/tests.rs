@@ -0,0 +1,84 @@
+use super::*;
+use mongodb::bson::{oid::ObjectId, spec::BinarySubtype, Binary, DateTime, Decimal128};
+use std::str::FromStr;
+
+fn typed_document() -> Document {
+    doc! {
+        "_id": ObjectId::from_str("64b7f0c2a1b2c3d4e5f60718").unwrap(),
+        "created": DateTime::from_millis(1_700_000_000_000),
+        "price": Decimal128::from_str("19.99").unwrap(),
+        "payload": Binary { subtype: BinarySubtype::Generic, bytes: vec![1, 2, 3] },
+        "count": 3,
+        "total": 5_000_000_000_i64,
+        "tags": ["a", "b"],
+    }
+}
+
+#[test]
+fn test_relaxed_extended_json_keeps_types() {
+    let json = to_extended_json(typed_document(), ExtendedJsonMode::Relaxed);
+    assert_eq!(json["_id"]["$oid"], "64b7f0c2a1b2c3d4e5f60718");
+    assert!(json["created"]["$date"].is_string());
+    assert_eq!(json["price"]["$numberDecimal"], "19.99");
+    assert_eq!(json["payload"]["$binary"]["base64"], "AQID");
+    assert_eq!(json["count"], 3);
+    assert_eq!(json["tags"][1], "b");
+}
+
+#[test]
+fn test_canonical_extended_json_keeps_number_types() {
+    let json = to_extended_json(typed_document(), ExtendedJsonMode::Canonical);
+    assert_eq!(json["count"]["$numberInt"], "3");
+    assert_eq!(json["total"]["$numberLong"], "5000000000");
+    assert_eq!(json["created"]["$date"]["$numberLong"], "1700000000000");
+}
+
+#[test]
+fn test_extended_json_round_trip() {
+    let original = typed_document();
+    for mode in [ExtendedJsonMode::Relaxed, ExtendedJsonMode::Canonical] {
+        let json = to_extended_json(original.clone(), mode);
+        assert_eq!(from_extended_json(json).unwrap(), original);
+    }
+}
+
+#[test]
+fn test_require_canonical() {
+    let canonical = to_extended_json(typed_document(), ExtendedJsonMode::Canonical);
+    assert!(require_canonical(&canonical, "").is_ok());
+
+    // 宽松模式的 Int64 和整数值的 Double 写回后都会变成 Int32
+    let relaxed = to_extended_json(typed_document(), ExtendedJsonMode::Relaxed);
+    let error = require_canonical(&relaxed, "").unwrap_err().to_string();
+    assert!(error.contains("count") || error.contains("total"), "{}", error);
+
+    let nested = serde_json::json!({ "a": [{ "$numberInt": "1" }, { "b": 5.0 }] });
+    assert!(require_canonical(&nested, "").unwrap_err().to_string().contains("a.1.b"));
+}
+
+#[test]
+fn test_from_extended_json_requires_document() {
+    assert!(from_extended_json(serde_json::json!([1, 2])).is_err());
+    assert!(from_extended_json(serde_json::json!({ "_id": { "$oid": "bad" } })).is_err());
+}
+
+#[test]
+fn test_table_documents() {
+    let result = QueryResult {
+        columns: vec!["matched_count".to_string(), "modified_count".to_string()],
+        rows: vec![vec!["2".to_string(), "1".to_string()]],
+        affected_rows: 1,
+        execution_time: 0,
+    };
+    assert_eq!(
+        table_documents(result),
+        vec![serde_json::json!({ "matched_count": "2", "modified_count": "1" })]
+    );
+}
+
+#[test]
+fn test_mode_deserialize() {
+    let mode: ExtendedJsonMode = serde_json::from_str("\"canonical\"").unwrap();
+    assert_eq!(mode, ExtendedJsonMode::Canonical);
+    assert_eq!(ExtendedJsonMode::default(), ExtendedJsonMode::Relaxed);
+}
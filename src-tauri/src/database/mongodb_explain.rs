@@ -0,0 +1,455 @@
+// MongoDB 执行计划与数据库分析器
+//
+// explain 结果在不同版本和拓扑下结构差异很大：聚合的计划在 stages[0].$cursor 中，
+// 分片集群在 shards 下按分片给出，7.0 起 SBE 引擎把计划包在 queryPlan 里。
+// 这里统一解析为阶段树，并汇总使用的索引和扫描量。
+
+use super::mongodb::{explainable_command, MongoDBConnection};
+use super::mongodb_documents::{to_extended_json, ExtendedJsonMode};
+use super::mongodb_shell::{as_i64, parse_statement, ShellStatement};
+use futures::StreamExt;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    options::FindOptions,
+    Collection,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// 慢操作记录默认返回条数
+const DEFAULT_PROFILE_LIMIT: i64 = 100;
+
+/// 扫描文档数超过返回数的倍数时给出提示
+const EXAMINED_RATIO_WARNING: u64 = 100;
+
+/// explain 的详细程度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExplainVerbosity {
+    /// 只选择计划，不执行
+    QueryPlanner,
+    /// 执行胜出计划并统计扫描量和耗时
+    #[default]
+    ExecutionStats,
+    /// 同时执行候选计划
+    AllPlansExecution,
+}
+
+impl ExplainVerbosity {
+    fn as_str(self) -> &'static str {
+        match self {
+            ExplainVerbosity::QueryPlanner => "queryPlanner",
+            ExplainVerbosity::ExecutionStats => "executionStats",
+            ExplainVerbosity::AllPlansExecution => "allPlansExecution",
+        }
+    }
+
+    fn parse(value: &str) -> anyhow::Result<Self> {
+        match value {
+            "queryPlanner" => Ok(ExplainVerbosity::QueryPlanner),
+            "executionStats" => Ok(ExplainVerbosity::ExecutionStats),
+            "allPlansExecution" => Ok(ExplainVerbosity::AllPlansExecution),
+            _ => Err(anyhow::anyhow!(
+                "explain 级别只能是 queryPlanner、executionStats 或 allPlansExecution"
+            )),
+        }
+    }
+}
+
+/// 计划树中的一个阶段
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlanStage {
+    /// 例如 COLLSCAN、IXSCAN、FETCH、SORT
+    pub stage: String,
+    pub index_name: Option<String>,
+    pub key_pattern: Option<serde_json::Value>,
+    /// forward | backward
+    pub direction: Option<String>,
+    pub filter: Option<serde_json::Value>,
+    /// 以下统计仅在 executionStats 及以上级别存在
+    pub n_returned: Option<u64>,
+    pub keys_examined: Option<u64>,
+    pub docs_examined: Option<u64>,
+    pub execution_time_ms: Option<u64>,
+    pub children: Vec<PlanStage>,
+}
+
+/// 解析后的执行计划
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExplainPlan {
+    pub namespace: Option<String>,
+    pub verbosity: ExplainVerbosity,
+    pub winning_plan: Option<PlanStage>,
+    pub rejected_plans: usize,
+    pub indexes_used: Vec<String>,
+    /// 计划中包含全集合扫描
+    pub collection_scan: bool,
+    /// 聚合管道中 $cursor 之后的阶段名
+    pub pipeline_stages: Vec<String>,
+    pub n_returned: Option<u64>,
+    pub keys_examined: Option<u64>,
+    pub docs_examined: Option<u64>,
+    pub execution_time_ms: Option<u64>,
+    pub warnings: Vec<String>,
+}
+
+/// explain 结果，raw 为服务端原始返回的宽松模式扩展 JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplainResult {
+    pub plan: ExplainPlan,
+    pub raw: serde_json::Value,
+}
+
+/// 数据库分析器设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfilerStatus {
+    /// 0 关闭，1 记录慢操作，2 记录全部操作
+    pub level: i64,
+    pub slow_ms: Option<i64>,
+    pub sample_rate: Option<f64>,
+    pub filter: Option<serde_json::Value>,
+}
+
+/// system.profile 查询条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileQuery {
+    /// 命名空间，例如 shop.orders；只写集合名时补上当前数据库
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// query | insert | update | remove | command | getmore
+    #[serde(default)]
+    pub op: Option<String>,
+    #[serde(default)]
+    pub min_millis: Option<i64>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+/// 一条慢操作记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileEntry {
+    pub op: String,
+    pub namespace: String,
+    pub timestamp: Option<String>,
+    pub millis: u64,
+    /// 例如 COLLSCAN、IXSCAN { name: 1 }
+    pub plan_summary: Option<String>,
+    pub keys_examined: Option<u64>,
+    pub docs_examined: Option<u64>,
+    pub n_returned: Option<u64>,
+    pub response_length: Option<u64>,
+    pub client: Option<String>,
+    pub user: Option<String>,
+    pub app_name: Option<String>,
+    pub command: Option<serde_json::Value>,
+}
+
+impl MongoDBConnection {
+    /// 获取 find/aggregate/countDocuments/distinct 语句的执行计划，语句末尾的 .explain() 可省略
+    pub async fn explain(&self, query: &str, verbosity: Option<ExplainVerbosity>) -> anyhow::Result<ExplainResult> {
+        let (statement, requested) = match split_explain(query)? {
+            Some((statement, requested)) => (statement, Some(requested)),
+            None => (parse_statement(query.trim())?, None),
+        };
+        self.explain_statement(statement, verbosity.or(requested).unwrap_or_default()).await
+    }
+
+    pub(crate) async fn explain_statement(
+        &self,
+        statement: ShellStatement,
+        verbosity: ExplainVerbosity,
+    ) -> anyhow::Result<ExplainResult> {
+        let command = explainable_command(statement)?;
+
+        let raw = self.database()?
+            .run_command(doc! { "explain": command, "verbosity": verbosity.as_str() }, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("获取执行计划失败: {}", e))?;
+        Ok(ExplainResult {
+            plan: parse_explain(&raw, verbosity),
+            raw: to_extended_json(raw, ExtendedJsonMode::Relaxed),
+        })
+    }
+
+    /// 读取当前数据库的分析器设置
+    pub async fn profiler_status(&self) -> anyhow::Result<ProfilerStatus> {
+        let reply = self.database()?.run_command(doc! { "profile": -1 }, None).await
+            .map_err(|e| anyhow::anyhow!("读取分析器设置失败: {}", e))?;
+        Ok(parse_profiler_status(&reply))
+    }
+
+    /// 修改分析器级别和慢操作阈值，返回修改前的设置
+    pub async fn set_profiler(
+        &self,
+        level: i64,
+        slow_ms: Option<i64>,
+        sample_rate: Option<f64>,
+    ) -> anyhow::Result<ProfilerStatus> {
+        let command = profile_command(level, slow_ms, sample_rate)?;
+        let reply = self.database()?.run_command(command, None).await
+            .map_err(|e| anyhow::anyhow!("修改分析器设置失败: {}", e))?;
+        Ok(parse_profiler_status(&reply))
+    }
+
+    /// 按时间倒序读取 system.profile 中的记录
+    pub async fn profile_entries(&self, query: &ProfileQuery) -> anyhow::Result<Vec<ProfileEntry>> {
+        let db = self.database()?;
+        let filter = profile_filter(db.name(), query);
+        let options = FindOptions::builder()
+            .sort(doc! { "ts": -1 })
+            .limit(query.limit.filter(|n| *n > 0).unwrap_or(DEFAULT_PROFILE_LIMIT))
+            .build();
+
+        let coll: Collection<Document> = db.collection("system.profile");
+        let cursor = coll.find(filter, options).await
+            .map_err(|e| anyhow::anyhow!("查询 system.profile 失败: {}", e))?;
+        let entries = cursor.collect::<Vec<_>>().await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("读取慢操作记录失败: {}", e))?;
+        Ok(entries.iter().map(parse_profile_entry).collect())
+    }
+}
+
+/// 拆出语句末尾的 .explain(级别)，不是 explain 语句时返回 None
+pub(crate) fn split_explain(query: &str) -> anyhow::Result<Option<(ShellStatement, ExplainVerbosity)>> {
+    let trimmed = query.trim();
+    if !(trimmed.starts_with("db.") || trimmed.starts_with("db[")) {
+        return Ok(None);
+    }
+    let mut statement = match parse_statement(trimmed) {
+        Ok(statement) => statement,
+        Err(_) => return Ok(None),
+    };
+    if statement.calls.len() < 2 {
+        return Ok(None);
+    }
+    let call = match statement.calls.pop() {
+        Some(call) if call.name == "explain" => call,
+        _ => return Ok(None),
+    };
+    let verbosity = match call.args.first() {
+        None => ExplainVerbosity::QueryPlanner,
+        Some(Bson::String(value)) => ExplainVerbosity::parse(value)?,
+        Some(Bson::Boolean(true)) => ExplainVerbosity::AllPlansExecution,
+        Some(Bson::Boolean(false)) => ExplainVerbosity::QueryPlanner,
+        Some(_) => return Err(anyhow::anyhow!("explain() 的参数必须是字符串")),
+    };
+    Ok(Some((statement, verbosity)))
+}
+
+fn count(document: &Document, key: &str) -> Option<u64> {
+    document.get(key).and_then(as_i64).map(|v| v.max(0) as u64)
+}
+
+/// SBE 引擎的计划包在 queryPlan 中
+fn unwrap_plan(plan: &Document) -> &Document {
+    plan.get_document("queryPlan").unwrap_or(plan)
+}
+
+pub(crate) fn parse_plan_stage(stage: &Document) -> PlanStage {
+    let mut children = Vec::new();
+    if let Ok(child) = stage.get_document("inputStage") {
+        children.push(parse_plan_stage(child));
+    }
+    for key in ["inputStages", "shards"] {
+        if let Ok(inputs) = stage.get_array(key) {
+            children.extend(inputs.iter().filter_map(Bson::as_document).map(|input| {
+                // 分片汇总阶段下每个分片的计划在 winningPlan 或 executionStages 中
+                let input = input.get_document("executionStages")
+                    .or_else(|_| input.get_document("winningPlan"))
+                    .map(unwrap_plan)
+                    .unwrap_or(input);
+                parse_plan_stage(input)
+            }));
+        }
+    }
+
+    let json = |key: &str| stage.get_document(key).ok()
+        .map(|value| to_extended_json(value.clone(), ExtendedJsonMode::Relaxed));
+    PlanStage {
+        stage: stage.get_str("stage").unwrap_or("UNKNOWN").to_string(),
+        index_name: stage.get_str("indexName").ok().map(str::to_string),
+        key_pattern: json("keyPattern"),
+        direction: stage.get_str("direction").ok().map(str::to_string),
+        filter: json("filter"),
+        n_returned: count(stage, "nReturned"),
+        keys_examined: count(stage, "keysExamined"),
+        docs_examined: count(stage, "docsExamined"),
+        execution_time_ms: count(stage, "executionTimeMillisEstimate")
+            .or_else(|| count(stage, "executionTimeMillis")),
+        children,
+    }
+}
+
+fn collect_stages(stage: &PlanStage, indexes: &mut Vec<String>, collection_scan: &mut bool) {
+    if stage.stage == "COLLSCAN" {
+        *collection_scan = true;
+    }
+    if let Some(name) = &stage.index_name {
+        if !indexes.contains(name) {
+            indexes.push(name.clone());
+        }
+    }
+    for child in &stage.children {
+        collect_stages(child, indexes, collection_scan);
+    }
+}
+
+/// 找到包含 queryPlanner 的部分：顶层、聚合的 $cursor 阶段或第一个分片
+fn planner_section(raw: &Document) -> (Option<&Document>, Vec<String>, Vec<String>) {
+    let mut pipeline_stages = Vec::new();
+    let mut warnings = Vec::new();
+
+    if raw.contains_key("queryPlanner") {
+        return (Some(raw), pipeline_stages, warnings);
+    }
+    if let Ok(stages) = raw.get_array("stages") {
+        let mut section = None;
+        for stage in stages.iter().filter_map(Bson::as_document) {
+            match stage.get_document("$cursor") {
+                Ok(cursor) if section.is_none() => section = Some(cursor),
+                _ => pipeline_stages.extend(stage.keys().next().cloned()),
+            }
+        }
+        return (section, pipeline_stages, warnings);
+    }
+    if let Ok(shards) = raw.get_document("shards") {
+        if shards.len() > 1 {
+            warnings.push(format!("查询涉及 {} 个分片，只展示第一个分片的计划", shards.len()));
+        }
+        if let Some((_, Bson::Document(shard))) = shards.iter().next() {
+            let (section, stages, _) = planner_section(shard);
+            return (section, stages, warnings);
+        }
+    }
+    (None, pipeline_stages, warnings)
+}
+
+/// 解析 explain 命令的返回
+pub(crate) fn parse_explain(raw: &Document, verbosity: ExplainVerbosity) -> ExplainPlan {
+    let (section, pipeline_stages, mut warnings) = planner_section(raw);
+    let planner = section.and_then(|section| section.get_document("queryPlanner").ok());
+    let stats = section
+        .and_then(|section| section.get_document("executionStats").ok())
+        .or_else(|| raw.get_document("executionStats").ok());
+
+    // 有执行统计时用 executionStages，它与 winningPlan 结构相同且带计数
+    let tree = stats
+        .and_then(|stats| stats.get_document("executionStages").ok())
+        .or_else(|| planner.and_then(|planner| planner.get_document("winningPlan").ok()))
+        .map(unwrap_plan);
+    let winning_plan = tree.map(parse_plan_stage);
+
+    let mut indexes_used = Vec::new();
+    let mut collection_scan = false;
+    if let Some(plan) = &winning_plan {
+        collect_stages(plan, &mut indexes_used, &mut collection_scan);
+    }
+
+    let n_returned = stats.and_then(|stats| count(stats, "nReturned"));
+    let docs_examined = stats.and_then(|stats| count(stats, "totalDocsExamined"));
+    if collection_scan {
+        warnings.push("执行计划包含全集合扫描（COLLSCAN），可以考虑为过滤字段建立索引".to_string());
+    }
+    if let (Some(returned), Some(examined)) = (n_returned, docs_examined) {
+        if examined > returned.max(1) * EXAMINED_RATIO_WARNING {
+            warnings.push(format!("扫描了 {} 个文档只返回 {} 个，索引选择性较差", examined, returned));
+        }
+    }
+    if planner.is_none() && raw.get_array("stages").is_ok() {
+        warnings.push("聚合管道没有可用的查询计划，可能从 $collStats 等特殊阶段开始".to_string());
+    }
+
+    ExplainPlan {
+        namespace: planner.and_then(|planner| planner.get_str("namespace").ok()).map(str::to_string),
+        verbosity,
+        winning_plan,
+        rejected_plans: planner
+            .and_then(|planner| planner.get_array("rejectedPlans").ok())
+            .map_or(0, Vec::len),
+        indexes_used,
+        collection_scan,
+        pipeline_stages,
+        n_returned,
+        keys_examined: stats.and_then(|stats| count(stats, "totalKeysExamined")),
+        docs_examined,
+        execution_time_ms: stats.and_then(|stats| count(stats, "executionTimeMillis")),
+        warnings,
+    }
+}
+
+pub(crate) fn profile_command(level: i64, slow_ms: Option<i64>, sample_rate: Option<f64>) -> anyhow::Result<Document> {
+    if !(0..=2).contains(&level) {
+        return Err(anyhow::anyhow!("分析器级别只能是 0、1 或 2"));
+    }
+    let mut command = doc! { "profile": level };
+    if let Some(slow_ms) = slow_ms {
+        if slow_ms < 0 {
+            return Err(anyhow::anyhow!("slowms 不能为负数"));
+        }
+        command.insert("slowms", slow_ms);
+    }
+    if let Some(sample_rate) = sample_rate {
+        if !(0.0..=1.0).contains(&sample_rate) {
+            return Err(anyhow::anyhow!("sampleRate 必须在 0 到 1 之间"));
+        }
+        command.insert("sampleRate", sample_rate);
+    }
+    Ok(command)
+}
+
+pub(crate) fn parse_profiler_status(reply: &Document) -> ProfilerStatus {
+    ProfilerStatus {
+        level: reply.get("was").and_then(as_i64).unwrap_or(0),
+        slow_ms: reply.get("slowms").and_then(as_i64),
+        sample_rate: match reply.get("sampleRate") {
+            Some(Bson::Double(rate)) => Some(*rate),
+            Some(value) => as_i64(value).map(|v| v as f64),
+            None => None,
+        },
+        filter: reply.get_document("filter").ok()
+            .map(|filter| to_extended_json(filter.clone(), ExtendedJsonMode::Relaxed)),
+    }
+}
+
+pub(crate) fn profile_filter(database: &str, query: &ProfileQuery) -> Document {
+    let mut filter = Document::new();
+    if let Some(namespace) = query.namespace.as_deref().filter(|ns| !ns.is_empty()) {
+        let namespace = if namespace.contains('.') {
+            namespace.to_string()
+        } else {
+            format!("{}.{}", database, namespace)
+        };
+        filter.insert("ns", namespace);
+    }
+    if let Some(op) = query.op.as_deref().filter(|op| !op.is_empty()) {
+        filter.insert("op", op);
+    }
+    if let Some(min_millis) = query.min_millis {
+        filter.insert("millis", doc! { "$gte": min_millis });
+    }
+    filter
+}
+
+pub(crate) fn parse_profile_entry(entry: &Document) -> ProfileEntry {
+    let text = |key: &str| entry.get_str(key).ok().map(str::to_string);
+    ProfileEntry {
+        op: entry.get_str("op").unwrap_or_default().to_string(),
+        namespace: entry.get_str("ns").unwrap_or_default().to_string(),
+        timestamp: entry.get_datetime("ts").ok().and_then(|ts| ts.try_to_rfc3339_string().ok()),
+        millis: count(entry, "millis").unwrap_or(0),
+        plan_summary: text("planSummary"),
+        keys_examined: count(entry, "keysExamined"),
+        docs_examined: count(entry, "docsExamined"),
+        n_returned: count(entry, "nreturned"),
+        response_length: count(entry, "responseLength"),
+        client: text("client"),
+        user: text("user"),
+        app_name: text("appName"),
+        command: entry.get_document("command").ok()
+            .map(|command| to_extended_json(command.clone(), ExtendedJsonMode::Relaxed)),
+    }
+}
@@ -0,0 +1,183 @@
+use super::*;
+use mongodb::bson::DateTime;
+
+#[test]
+fn test_split_explain_detects_trailing_call() {
+    let (statement, verbosity) = split_explain("db.users.find({age: {$gt: 18}}).explain('executionStats')")
+        .unwrap()
+        .unwrap();
+    assert_eq!(statement.collection, "users");
+    assert_eq!(statement.calls.len(), 1);
+    assert_eq!(verbosity, ExplainVerbosity::ExecutionStats);
+
+    let (_, verbosity) = split_explain("db.users.aggregate([]).explain()").unwrap().unwrap();
+    assert_eq!(verbosity, ExplainVerbosity::QueryPlanner);
+    let (_, verbosity) = split_explain("db.users.find().explain(true)").unwrap().unwrap();
+    assert_eq!(verbosity, ExplainVerbosity::AllPlansExecution);
+}
+
+#[test]
+fn test_split_explain_ignores_other_queries() {
+    assert!(split_explain("db.users.find({})").unwrap().is_none());
+    assert!(split_explain("{name: 'a'}").unwrap().is_none());
+    assert!(split_explain("db.users.find().explain('verbose')").is_err());
+}
+
+#[test]
+fn test_explainable_command() {
+    let (statement, _) = split_explain("db.users.find({a: 1}).sort({b: -1}).limit(5).explain()")
+        .unwrap()
+        .unwrap();
+    let command = explainable_command(statement).unwrap();
+    assert_eq!(command.get_str("find").unwrap(), "users");
+    assert_eq!(command.get_document("filter").unwrap(), &doc! { "a": 1 });
+    assert_eq!(command.get_document("sort").unwrap(), &doc! { "b": -1 });
+    assert_eq!(command.get_i64("limit").unwrap(), 5);
+
+    // 未指定 limit 时按真实查询解释，不带执行时的默认条数
+    let (statement, _) = split_explain("db.users.find({a: 1}).explain()").unwrap().unwrap();
+    assert!(!explainable_command(statement).unwrap().contains_key("limit"));
+    let (statement, _) = split_explain("db.users.find().limit(0).explain()").unwrap().unwrap();
+    assert!(!explainable_command(statement).unwrap().contains_key("limit"));
+
+    let statement = parse_statement("db.users.deleteMany({})").unwrap();
+    assert!(explainable_command(statement).is_err());
+}
+
+#[test]
+fn test_parse_find_execution_stats() {
+    let raw = doc! {
+        "queryPlanner": {
+            "namespace": "shop.users",
+            "winningPlan": { "stage": "FETCH" },
+            "rejectedPlans": [{ "stage": "COLLSCAN" }],
+        },
+        "executionStats": {
+            "nReturned": 2,
+            "executionTimeMillis": 3,
+            "totalKeysExamined": 2,
+            "totalDocsExamined": 2,
+            "executionStages": {
+                "stage": "FETCH",
+                "nReturned": 2,
+                "docsExamined": 2,
+                "inputStage": {
+                    "stage": "IXSCAN",
+                    "indexName": "age_1",
+                    "keyPattern": { "age": 1 },
+                    "direction": "forward",
+                    "keysExamined": 2,
+                },
+            },
+        },
+    };
+    let plan = parse_explain(&raw, ExplainVerbosity::ExecutionStats);
+    assert_eq!(plan.namespace.as_deref(), Some("shop.users"));
+    assert_eq!(plan.rejected_plans, 1);
+    assert_eq!(plan.indexes_used, vec!["age_1"]);
+    assert!(!plan.collection_scan);
+    assert_eq!(plan.docs_examined, Some(2));
+    assert_eq!(plan.execution_time_ms, Some(3));
+
+    let stage = plan.winning_plan.unwrap();
+    assert_eq!(stage.stage, "FETCH");
+    assert_eq!(stage.children[0].stage, "IXSCAN");
+    assert_eq!(stage.children[0].keys_examined, Some(2));
+    assert_eq!(stage.children[0].key_pattern.as_ref().unwrap()["age"], 1);
+    assert!(plan.warnings.is_empty());
+}
+
+#[test]
+fn test_parse_aggregate_cursor_stage() {
+    let raw = doc! {
+        "stages": [
+            { "$cursor": {
+                "queryPlanner": {
+                    "namespace": "shop.orders",
+                    "winningPlan": { "queryPlan": { "stage": "COLLSCAN" } },
+                },
+            } },
+            { "$group": { "_id": "$status" } },
+            { "$sort": { "_id": 1 } },
+        ],
+    };
+    let plan = parse_explain(&raw, ExplainVerbosity::QueryPlanner);
+    assert_eq!(plan.winning_plan.unwrap().stage, "COLLSCAN");
+    assert!(plan.collection_scan);
+    assert_eq!(plan.pipeline_stages, vec!["$group", "$sort"]);
+    assert!(plan.warnings.iter().any(|w| w.contains("COLLSCAN")));
+}
+
+#[test]
+fn test_parse_sharded_plan_warns() {
+    let shard = doc! { "queryPlanner": { "winningPlan": { "stage": "IXSCAN", "indexName": "_id_" } } };
+    let raw = doc! { "shards": { "rs0": shard.clone(), "rs1": shard } };
+    let plan = parse_explain(&raw, ExplainVerbosity::QueryPlanner);
+    assert_eq!(plan.indexes_used, vec!["_id_"]);
+    assert!(plan.warnings.iter().any(|w| w.contains("2 个分片")));
+}
+
+#[test]
+fn test_parse_explain_warns_on_poor_selectivity() {
+    let raw = doc! {
+        "queryPlanner": { "winningPlan": { "stage": "IXSCAN", "indexName": "status_1" } },
+        "executionStats": { "nReturned": 1, "totalDocsExamined": 5000 },
+    };
+    let plan = parse_explain(&raw, ExplainVerbosity::ExecutionStats);
+    assert!(plan.warnings.iter().any(|w| w.contains("5000")));
+}
+
+#[test]
+fn test_profile_command_validation() {
+    assert_eq!(profile_command(1, Some(50), None).unwrap(), doc! { "profile": 1_i64, "slowms": 50_i64 });
+    assert!(profile_command(3, None, None).is_err());
+    assert!(profile_command(1, Some(-1), None).is_err());
+    assert!(profile_command(1, None, Some(1.5)).is_err());
+}
+
+#[test]
+fn test_parse_profiler_status() {
+    let status = parse_profiler_status(&doc! { "was": 1, "slowms": 100, "sampleRate": 1.0, "ok": 1.0 });
+    assert_eq!(status.level, 1);
+    assert_eq!(status.slow_ms, Some(100));
+    assert_eq!(status.sample_rate, Some(1.0));
+    assert!(status.filter.is_none());
+}
+
+#[test]
+fn test_profile_filter() {
+    let query = ProfileQuery {
+        namespace: Some("orders".to_string()),
+        op: Some("query".to_string()),
+        min_millis: Some(100),
+        limit: None,
+    };
+    assert_eq!(
+        profile_filter("shop", &query),
+        doc! { "ns": "shop.orders", "op": "query", "millis": { "$gte": 100_i64 } },
+    );
+    assert!(profile_filter("shop", &ProfileQuery::default()).is_empty());
+}
+
+#[test]
+fn test_parse_profile_entry() {
+    let entry = parse_profile_entry(&doc! {
+        "op": "query",
+        "ns": "shop.orders",
+        "ts": DateTime::from_millis(1_700_000_000_000),
+        "millis": 250,
+        "planSummary": "COLLSCAN",
+        "docsExamined": 10000,
+        "nreturned": 3,
+        "command": { "find": "orders", "filter": { "status": "A" } },
+        "appName": "crate",
+    });
+    assert_eq!(entry.namespace, "shop.orders");
+    assert_eq!(entry.millis, 250);
+    assert_eq!(entry.plan_summary.as_deref(), Some("COLLSCAN"));
+    assert_eq!(entry.docs_examined, Some(10000));
+    assert_eq!(entry.n_returned, Some(3));
+    assert!(entry.timestamp.unwrap().starts_with("2023-11-14"));
+    assert_eq!(entry.command.unwrap()["filter"]["status"], "A");
+    assert_eq!(entry.app_name.as_deref(), Some("crate"));
+}
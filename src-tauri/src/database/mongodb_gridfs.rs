@@ -0,0 +1,520 @@
+// MongoDB GridFS 文件浏览：分页列出、上传、下载、删除
+//
+// 列表直接查询 <桶>.files 集合，以便带出旧客户端写入的 contentType 等字段。
+// 上传和下载在后台任务中按块流式传输，通过 GridFSTransferSink 报告进度
+// （由命令层转发为 Tauri 事件），可以随时取消：取消上传会删除已写入的块，
+// 取消下载会删除未写完的本地文件。
+
+use super::mongodb::MongoDBConnection;
+use super::mongodb_documents::{to_extended_json, ExtendedJsonMode};
+use super::mongodb_shell::as_i64;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    gridfs::GridFsBucket,
+    options::{FindOptions, GridFsBucketOptions, GridFsUploadOptions},
+    Collection,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[cfg(test)]
+mod tests;
+
+/// 每次读写的缓冲区大小，与 GridFS 默认块大小一致
+const TRANSFER_BUFFER_SIZE: usize = 255 * 1024;
+
+/// 进度事件的最小间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 元数据中记录内容类型的字段
+const CONTENT_TYPE_FIELD: &str = "contentType";
+
+/// GridFS 文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridFSFile {
+    /// 文件 _id，宽松模式扩展 JSON
+    pub id: serde_json::Value,
+    pub filename: Option<String>,
+    pub length: u64,
+    pub chunk_size: u64,
+    pub upload_date: Option<DateTime<Utc>>,
+    pub content_type: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// 文件列表查询
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridFSFileQuery {
+    pub bucket: String,
+    /// 文件名包含的文本（不区分大小写）
+    #[serde(default)]
+    pub filename: Option<String>,
+    /// 额外的过滤条件，作用于 files 集合，例如 {"metadata.owner": "alice"}
+    #[serde(default)]
+    pub filter: Option<serde_json::Value>,
+    #[serde(default)]
+    pub skip: u64,
+    #[serde(default = "default_page_size")]
+    pub limit: i64,
+}
+
+fn default_page_size() -> i64 {
+    50
+}
+
+/// 一页文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridFSFilePage {
+    pub files: Vec<GridFSFile>,
+    /// 满足条件的文件总数
+    pub total: u64,
+    pub skip: u64,
+    pub limit: i64,
+}
+
+/// 上传请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridFSUploadRequest {
+    pub bucket: String,
+    pub local_path: String,
+    /// 为空时使用本地文件名
+    #[serde(default)]
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<serde_json::Value>,
+    #[serde(default)]
+    pub chunk_size_bytes: Option<u32>,
+}
+
+/// 下载请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridFSDownloadRequest {
+    pub bucket: String,
+    /// 文件 _id，扩展 JSON
+    pub file_id: serde_json::Value,
+    pub local_path: String,
+}
+
+/// 传输方向
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum GridFSTransferDirection {
+    Upload,
+    Download,
+}
+
+/// 传输进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridFSTransferProgress {
+    pub transfer_id: String,
+    pub direction: GridFSTransferDirection,
+    pub filename: String,
+    pub transferred: u64,
+    pub total: u64,
+}
+
+/// 传输结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridFSTransferResult {
+    pub transfer_id: String,
+    pub direction: GridFSTransferDirection,
+    pub file_id: serde_json::Value,
+    pub filename: String,
+    pub local_path: String,
+    pub bytes: u64,
+    /// 取消时上传的块和下载的本地文件都已清理
+    pub cancelled: bool,
+    pub duration_ms: u64,
+}
+
+/// 传输进度接收端，由命令层实现（转发为 Tauri 事件）
+pub trait GridFSTransferSink: Send + Sync + 'static {
+    fn on_progress(&self, progress: GridFSTransferProgress);
+    fn on_complete(&self, transfer_id: &str, result: Result<GridFSTransferResult, String>);
+}
+
+/// 运行中的传输任务
+pub struct GridFSTransferHandle {
+    pub connection_id: String,
+    cancelled: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+}
+
+impl GridFSTransferHandle {
+    /// 请求取消，任务在当前块结束后停止
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+}
+
+impl MongoDBConnection {
+    /// 当前数据库中的 GridFS 桶
+    pub fn gridfs_bucket(&self, bucket: &str) -> anyhow::Result<GridFsBucket> {
+        if bucket.is_empty() {
+            return Err(anyhow::anyhow!("GridFS 桶名不能为空"));
+        }
+        let options = GridFsBucketOptions::builder().bucket_name(bucket.to_string()).build();
+        Ok(self.database()?.gridfs_bucket(options))
+    }
+
+    /// 分页列出文件，按上传时间倒序
+    pub async fn list_gridfs_files(&self, query: &GridFSFileQuery) -> anyhow::Result<GridFSFilePage> {
+        let files: Collection<Document> = self.database()?.collection(&format!("{}.files", query.bucket));
+        let filter = file_filter(query)?;
+        let total = files.count_documents(filter.clone(), None).await
+            .map_err(|e| anyhow::anyhow!("统计 GridFS 文件失败: {}", e))?;
+
+        let options = FindOptions::builder()
+            .sort(doc! { "uploadDate": -1, "_id": -1 })
+            .skip(query.skip)
+            .limit(query.limit.max(1))
+            .build();
+        let cursor = files.find(filter, options).await
+            .map_err(|e| anyhow::anyhow!("列出 GridFS 文件失败: {}", e))?;
+        let documents = cursor.collect::<Vec<_>>().await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("读取 GridFS 文件信息失败: {}", e))?;
+
+        Ok(GridFSFilePage {
+            files: documents.into_iter().map(gridfs_file).collect(),
+            total,
+            skip: query.skip,
+            limit: query.limit.max(1),
+        })
+    }
+
+    /// 删除文件及其所有块
+    pub async fn delete_gridfs_file(&self, bucket: &str, file_id: serde_json::Value) -> anyhow::Result<()> {
+        let id = parse_file_id(file_id)?;
+        self.gridfs_bucket(bucket)?.delete(id).await
+            .map_err(|e| anyhow::anyhow!("删除 GridFS 文件失败: {}", e))
+    }
+}
+
+pub(crate) fn file_filter(query: &GridFSFileQuery) -> anyhow::Result<Document> {
+    let mut filter = match &query.filter {
+        Some(value) => match Bson::try_from(value.clone()) {
+            Ok(Bson::Document(filter)) => filter,
+            Ok(_) => return Err(anyhow::anyhow!("过滤条件必须是文档")),
+            Err(e) => return Err(anyhow::anyhow!("过滤条件无效: {}", e)),
+        },
+        None => Document::new(),
+    };
+    if let Some(name) = query.filename.as_deref().filter(|name| !name.is_empty()) {
+        filter.insert("filename", doc! { "$regex": regex::escape(name), "$options": "i" });
+    }
+    Ok(filter)
+}
+
+pub(crate) fn gridfs_file(document: Document) -> GridFSFile {
+    let metadata = document.get_document("metadata").ok();
+    let content_type = document.get_str(CONTENT_TYPE_FIELD).ok()
+        .or_else(|| metadata.and_then(|metadata| metadata.get_str(CONTENT_TYPE_FIELD).ok()))
+        .map(str::to_string);
+    let number = |name: &str| document.get(name).and_then(as_i64).map(|v| v.max(0) as u64).unwrap_or(0);
+
+    GridFSFile {
+        id: document.get("_id").cloned().unwrap_or(Bson::Null).into_relaxed_extjson(),
+        filename: document.get_str("filename").ok().map(str::to_string),
+        length: number("length"),
+        chunk_size: number("chunkSize"),
+        upload_date: document.get_datetime("uploadDate").ok()
+            .and_then(|date| DateTime::from_timestamp_millis(date.timestamp_millis())),
+        content_type,
+        metadata: metadata.map(|metadata| to_extended_json(metadata.clone(), ExtendedJsonMode::Relaxed)),
+    }
+}
+
+pub(crate) fn parse_file_id(value: serde_json::Value) -> anyhow::Result<Bson> {
+    Bson::try_from(value).map_err(|e| anyhow::anyhow!("文件 _id 无效: {}", e))
+}
+
+/// 上传时写入的元数据，内容类型放在 metadata.contentType
+pub(crate) fn upload_metadata(request: &GridFSUploadRequest) -> anyhow::Result<Option<Document>> {
+    let mut metadata = match &request.metadata {
+        Some(value) => match Bson::try_from(value.clone()) {
+            Ok(Bson::Document(metadata)) => metadata,
+            Ok(_) => return Err(anyhow::anyhow!("元数据必须是文档")),
+            Err(e) => return Err(anyhow::anyhow!("元数据无效: {}", e)),
+        },
+        None => Document::new(),
+    };
+    if let Some(content_type) = request.content_type.as_deref().filter(|t| !t.is_empty()) {
+        metadata.insert(CONTENT_TYPE_FIELD, content_type);
+    }
+    Ok((!metadata.is_empty()).then_some(metadata))
+}
+
+// ===== 传输任务 =====
+
+struct Transfer {
+    transfer_id: String,
+    direction: GridFSTransferDirection,
+    filename: String,
+    total: u64,
+    cancelled: Arc<AtomicBool>,
+    sink: Arc<dyn GridFSTransferSink>,
+    last_progress: Instant,
+}
+
+impl Transfer {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn progress(&mut self, transferred: u64, force: bool) {
+        if !force && self.last_progress.elapsed() < PROGRESS_INTERVAL {
+            return;
+        }
+        self.last_progress = Instant::now();
+        self.sink.on_progress(GridFSTransferProgress {
+            transfer_id: self.transfer_id.clone(),
+            direction: self.direction,
+            filename: self.filename.clone(),
+            transferred,
+            total: self.total,
+        });
+    }
+}
+
+fn spawn_transfer<F>(
+    connection_id: String,
+    transfer_id: String,
+    sink: Arc<dyn GridFSTransferSink>,
+    cancelled: Arc<AtomicBool>,
+    task: F,
+) -> GridFSTransferHandle
+where
+    F: std::future::Future<Output = anyhow::Result<GridFSTransferResult>> + Send + 'static,
+{
+    let finished = Arc::new(AtomicBool::new(false));
+    let task_finished = finished.clone();
+    tokio::spawn(async move {
+        let result = task.await.map_err(|e| e.to_string());
+        task_finished.store(true, Ordering::Relaxed);
+        sink.on_complete(&transfer_id, result);
+    });
+
+    GridFSTransferHandle {
+        connection_id,
+        cancelled,
+        finished,
+    }
+}
+
+/// 启动后台上传
+pub fn start_upload(
+    bucket: GridFsBucket,
+    transfer_id: String,
+    connection_id: String,
+    request: GridFSUploadRequest,
+    sink: Arc<dyn GridFSTransferSink>,
+) -> GridFSTransferHandle {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let task = upload(bucket, transfer_id.clone(), request, cancelled.clone(), sink.clone());
+    spawn_transfer(connection_id, transfer_id, sink, cancelled, task)
+}
+
+/// 启动后台下载
+pub fn start_download(
+    bucket: GridFsBucket,
+    transfer_id: String,
+    connection_id: String,
+    request: GridFSDownloadRequest,
+    sink: Arc<dyn GridFSTransferSink>,
+) -> GridFSTransferHandle {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let task = download(bucket, transfer_id.clone(), request, cancelled.clone(), sink.clone());
+    spawn_transfer(connection_id, transfer_id, sink, cancelled, task)
+}
+
+async fn upload(
+    bucket: GridFsBucket,
+    transfer_id: String,
+    request: GridFSUploadRequest,
+    cancelled: Arc<AtomicBool>,
+    sink: Arc<dyn GridFSTransferSink>,
+) -> anyhow::Result<GridFSTransferResult> {
+    use futures::io::AsyncWriteExt as _;
+
+    let start = Instant::now();
+    let path = PathBuf::from(&request.local_path);
+    let filename = match request.filename.as_deref().filter(|name| !name.is_empty()) {
+        Some(name) => name.to_string(),
+        None => local_file_name(&path)?,
+    };
+    let mut file = tokio::fs::File::open(&path).await
+        .map_err(|e| anyhow::anyhow!("打开本地文件 {} 失败: {}", request.local_path, e))?;
+    let total = file.metadata().await.map(|metadata| metadata.len()).unwrap_or(0);
+
+    let options = GridFsUploadOptions::builder()
+        .chunk_size_bytes(request.chunk_size_bytes)
+        .metadata(upload_metadata(&request)?)
+        .build();
+    let mut stream = bucket.open_upload_stream(&filename, options);
+    let file_id = stream.id().clone();
+
+    let mut transfer = Transfer {
+        transfer_id: transfer_id.clone(),
+        direction: GridFSTransferDirection::Upload,
+        filename: filename.clone(),
+        total,
+        cancelled,
+        sink,
+        last_progress: Instant::now(),
+    };
+    transfer.progress(0, true);
+
+    let mut buffer = vec![0u8; TRANSFER_BUFFER_SIZE];
+    let mut transferred = 0u64;
+    loop {
+        if transfer.is_cancelled() {
+            // 删除已经写入的块
+            stream.abort().await
+                .map_err(|e| anyhow::anyhow!("取消上传失败: {}", e))?;
+            break;
+        }
+        let read = file.read(&mut buffer).await
+            .map_err(|e| anyhow::anyhow!("读取本地文件失败: {}", e))?;
+        if read == 0 {
+            stream.close().await
+                .map_err(|e| anyhow::anyhow!("完成上传失败: {}", e))?;
+            break;
+        }
+        if let Err(e) = stream.write_all(&buffer[..read]).await {
+            let _ = stream.abort().await;
+            return Err(anyhow::anyhow!("上传失败: {}", e));
+        }
+        transferred += read as u64;
+        transfer.progress(transferred, false);
+    }
+    transfer.progress(transferred, true);
+
+    Ok(GridFSTransferResult {
+        transfer_id,
+        direction: GridFSTransferDirection::Upload,
+        file_id: file_id.into_relaxed_extjson(),
+        filename,
+        local_path: request.local_path,
+        bytes: transferred,
+        cancelled: transfer.is_cancelled(),
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+async fn download(
+    bucket: GridFsBucket,
+    transfer_id: String,
+    request: GridFSDownloadRequest,
+    cancelled: Arc<AtomicBool>,
+    sink: Arc<dyn GridFSTransferSink>,
+) -> anyhow::Result<GridFSTransferResult> {
+    use futures::io::AsyncReadExt as _;
+
+    let start = Instant::now();
+    let id = parse_file_id(request.file_id.clone())?;
+    let info = bucket.find(doc! { "_id": id.clone() }, None).await
+        .map_err(|e| anyhow::anyhow!("查找 GridFS 文件失败: {}", e))?
+        .next().await
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("读取 GridFS 文件信息失败: {}", e))?
+        .ok_or_else(|| anyhow::anyhow!("GridFS 文件不存在"))?;
+    let filename = info.filename.clone().unwrap_or_default();
+
+    let mut stream = bucket.open_download_stream(id).await
+        .map_err(|e| anyhow::anyhow!("打开下载流失败: {}", e))?;
+    // 先写入目标旁的临时文件，成功后再替换，失败或取消不会破坏已有的同名文件
+    let path = PathBuf::from(&request.local_path);
+    let partial = partial_path(&path, &transfer_id)?;
+    let mut file = tokio::fs::File::create(&partial).await
+        .map_err(|e| anyhow::anyhow!("创建本地文件 {} 失败: {}", partial.display(), e))?;
+
+    let mut transfer = Transfer {
+        transfer_id: transfer_id.clone(),
+        direction: GridFSTransferDirection::Download,
+        filename: filename.clone(),
+        total: info.length,
+        cancelled,
+        sink,
+        last_progress: Instant::now(),
+    };
+    transfer.progress(0, true);
+
+    let mut buffer = vec![0u8; TRANSFER_BUFFER_SIZE];
+    let mut transferred = 0u64;
+    // 返回是否被取消；最后一块读完之后才到达的取消请求不再生效
+    let outcome: anyhow::Result<bool> = async {
+        loop {
+            if transfer.is_cancelled() {
+                return Ok(true);
+            }
+            let read = stream.read(&mut buffer).await
+                .map_err(|e| anyhow::anyhow!("下载失败: {}", e))?;
+            if read == 0 {
+                file.flush().await
+                    .map_err(|e| anyhow::anyhow!("写入本地文件失败: {}", e))?;
+                return Ok(false);
+            }
+            file.write_all(&buffer[..read]).await
+                .map_err(|e| anyhow::anyhow!("写入本地文件失败: {}", e))?;
+            transferred += read as u64;
+            transfer.progress(transferred, false);
+        }
+    }.await;
+    drop(file);
+
+    let cancelled = match outcome {
+        Ok(false) => match tokio::fs::rename(&partial, &path).await {
+            Ok(()) => false,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&partial).await;
+                return Err(anyhow::anyhow!("保存本地文件 {} 失败: {}", request.local_path, e));
+            }
+        },
+        // 失败或取消时只删除临时文件
+        Ok(true) => {
+            let _ = tokio::fs::remove_file(&partial).await;
+            true
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(e);
+        }
+    };
+    transfer.progress(transferred, true);
+
+    Ok(GridFSTransferResult {
+        transfer_id,
+        direction: GridFSTransferDirection::Download,
+        file_id: request.file_id,
+        filename,
+        local_path: request.local_path,
+        bytes: transferred,
+        cancelled,
+        duration_ms: start.elapsed().as_millis() as u64,
+    })
+}
+
+/// 下载过程中使用的临时文件：与目标位于同一目录，保证可以原子地 rename
+fn partial_path(path: &Path, transfer_id: &str) -> anyhow::Result<PathBuf> {
+    let name = local_file_name(path)?;
+    Ok(path.with_file_name(format!(".{}.{}.part", name, transfer_id)))
+}
+
+fn local_file_name(path: &Path) -> anyhow::Result<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| anyhow::anyhow!("无法从路径 {} 获取文件名", path.display()))
+}
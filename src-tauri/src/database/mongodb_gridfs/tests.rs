@@ -0,0 +1,80 @@
+use super::*;
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
+
+#[test]
+fn test_gridfs_file() {
+    let id = ObjectId::new();
+    let file = gridfs_file(doc! {
+        "_id": id,
+        "filename": "report.pdf",
+        "length": 1048576_i64,
+        "chunkSize": 261120,
+        "uploadDate": BsonDateTime::from_millis(1_700_000_000_000),
+        "metadata": { "contentType": "application/pdf", "owner": "alice" },
+    });
+    assert_eq!(file.id, serde_json::json!({ "$oid": id.to_hex() }));
+    assert_eq!(file.filename.as_deref(), Some("report.pdf"));
+    assert_eq!(file.length, 1048576);
+    assert_eq!(file.chunk_size, 261120);
+    assert_eq!(file.upload_date.unwrap().timestamp_millis(), 1_700_000_000_000);
+    assert_eq!(file.content_type.as_deref(), Some("application/pdf"));
+    assert_eq!(file.metadata.unwrap()["owner"], "alice");
+}
+
+#[test]
+fn test_gridfs_file_legacy_content_type() {
+    let file = gridfs_file(doc! { "_id": "a.txt", "length": 3, "contentType": "text/plain" });
+    assert_eq!(file.id, serde_json::json!("a.txt"));
+    assert_eq!(file.content_type.as_deref(), Some("text/plain"));
+    assert!(file.upload_date.is_none());
+    assert!(file.metadata.is_none());
+}
+
+#[test]
+fn test_file_filter() {
+    let query: GridFSFileQuery = serde_json::from_value(serde_json::json!({
+        "bucket": "fs",
+        "filename": "a.b",
+        "filter": { "metadata.owner": "alice" },
+    })).unwrap();
+    assert_eq!(query.limit, 50);
+    assert_eq!(
+        file_filter(&query).unwrap(),
+        doc! { "metadata.owner": "alice", "filename": { "$regex": "a\\.b", "$options": "i" } }
+    );
+
+    let invalid = GridFSFileQuery { filter: Some(serde_json::json!([1])), ..query };
+    assert!(file_filter(&invalid).is_err());
+}
+
+#[test]
+fn test_upload_metadata() {
+    let mut request: GridFSUploadRequest = serde_json::from_value(serde_json::json!({
+        "bucket": "fs",
+        "local_path": "/tmp/a.png",
+        "content_type": "image/png",
+        "metadata": { "tags": ["logo"] },
+    })).unwrap();
+    assert_eq!(
+        upload_metadata(&request).unwrap(),
+        Some(doc! { "tags": ["logo"], "contentType": "image/png" })
+    );
+
+    request.content_type = None;
+    request.metadata = None;
+    assert_eq!(upload_metadata(&request).unwrap(), None);
+}
+
+#[test]
+fn test_parse_file_id() {
+    let id = ObjectId::new();
+    assert_eq!(parse_file_id(serde_json::json!({ "$oid": id.to_hex() })).unwrap(), Bson::ObjectId(id));
+    assert_eq!(parse_file_id(serde_json::json!("name.txt")).unwrap(), Bson::String("name.txt".to_string()));
+}
+
+#[test]
+fn test_partial_path() {
+    let partial = partial_path(Path::new("/tmp/out/report.pdf"), "abc").unwrap();
+    assert_eq!(partial, PathBuf::from("/tmp/out/.report.pdf.abc.part"));
+    assert!(partial_path(Path::new("/"), "abc").is_err());
+}
@@ -0,0 +1,245 @@
+// MongoDB 索引管理：创建、删除、隐藏以及 $indexStats 使用统计
+//
+// 键模式支持升降序、text、2dsphere、2d、hashed 和通配符（$**），
+// 创建前先做与服务端一致的组合检查，避免大集合上建到一半才报错。
+
+use super::enhanced_types::MongoIndexInfo;
+use super::mongodb::MongoDBConnection;
+use super::mongodb_shell::as_i64;
+use super::mongodb_structure::{coll_stats, index_info};
+use futures::StreamExt;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    options::{Collation, IndexOptions},
+    Collection, IndexModel,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[cfg(test)]
+mod tests;
+
+/// 创建索引请求，键模式和过滤条件使用（扩展）JSON 表示
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateIndexRequest {
+    pub collection: String,
+    /// 例如 {"name": 1, "age": -1}、{"content": "text"}、{"location": "2dsphere"}
+    pub keys: serde_json::Value,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub unique: bool,
+    #[serde(default)]
+    pub sparse: bool,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub partial_filter: Option<serde_json::Value>,
+    /// TTL 索引的过期秒数
+    #[serde(default)]
+    pub expire_after_seconds: Option<u64>,
+    /// 例如 {"locale": "zh", "strength": 2}
+    #[serde(default)]
+    pub collation: Option<serde_json::Value>,
+    /// 文本索引的字段权重
+    #[serde(default)]
+    pub weights: Option<serde_json::Value>,
+    /// 文本索引的默认语言
+    #[serde(default)]
+    pub default_language: Option<String>,
+}
+
+/// 单个索引的使用统计，分片集群中每个分片各有一条
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexUsage {
+    pub name: String,
+    pub key: serde_json::Value,
+    /// 自统计开始以来的访问次数
+    pub accesses: u64,
+    /// 统计开始时间（服务端重启或索引重建后重置）
+    pub since: Option<String>,
+    pub host: Option<String>,
+    pub size: Option<u64>,
+    /// 统计期间从未被使用（_id 索引除外）
+    pub unused: bool,
+}
+
+impl MongoDBConnection {
+    /// 列出集合的索引，包含索引大小
+    pub async fn list_collection_indexes(&self, collection: &str) -> anyhow::Result<Vec<MongoIndexInfo>> {
+        let db = self.database()?;
+        let coll: Collection<Document> = db.collection(collection);
+        let stats = coll_stats(db, collection).await.ok();
+        let sizes = stats.as_ref().and_then(|stats| stats.get_document("indexSizes").ok());
+
+        let cursor = coll.list_indexes(None).await
+            .map_err(|e| anyhow::anyhow!("获取集合 {} 的索引失败: {}", collection, e))?;
+        let indexes = cursor.collect::<Vec<_>>().await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("读取索引信息失败: {}", e))?;
+        Ok(indexes.into_iter().map(|index| index_info(index, sizes)).collect())
+    }
+
+    /// 创建索引，返回索引名
+    pub async fn create_collection_index(&self, request: &CreateIndexRequest) -> anyhow::Result<String> {
+        let index = build_index(request)?;
+        let coll: Collection<Document> = self.database()?.collection(&request.collection);
+        let result = coll.create_index(index, None).await
+            .map_err(|e| anyhow::anyhow!("创建索引失败: {}", e))?;
+        Ok(result.index_name)
+    }
+
+    /// 删除索引
+    pub async fn drop_collection_index(&self, collection: &str, name: &str) -> anyhow::Result<()> {
+        if name == "_id_" {
+            return Err(anyhow::anyhow!("_id 索引不能删除"));
+        }
+        let coll: Collection<Document> = self.database()?.collection(collection);
+        coll.drop_index(name, None).await
+            .map_err(|e| anyhow::anyhow!("删除索引 {} 失败: {}", name, e))
+    }
+
+    /// 隐藏或取消隐藏索引，隐藏的索引仍会维护但不参与查询计划
+    pub async fn set_index_hidden(&self, collection: &str, name: &str, hidden: bool) -> anyhow::Result<()> {
+        if name == "_id_" {
+            return Err(anyhow::anyhow!("_id 索引不能隐藏"));
+        }
+        let command = doc! {
+            "collMod": collection,
+            "index": { "name": name, "hidden": hidden },
+        };
+        self.database()?.run_command(command, None).await
+            .map_err(|e| anyhow::anyhow!("修改索引 {} 失败: {}", name, e))?;
+        Ok(())
+    }
+
+    /// 通过 $indexStats 获取索引使用统计，按访问次数升序排列，未使用的索引在最前
+    pub async fn index_usage(&self, collection: &str) -> anyhow::Result<Vec<IndexUsage>> {
+        let db = self.database()?;
+        let coll: Collection<Document> = db.collection(collection);
+        let stats = coll_stats(db, collection).await.ok();
+        let sizes = stats.as_ref().and_then(|stats| stats.get_document("indexSizes").ok());
+
+        let cursor = coll.aggregate(vec![doc! { "$indexStats": {} }], None).await
+            .map_err(|e| anyhow::anyhow!("获取索引使用统计失败: {}", e))?;
+        let entries = cursor.collect::<Vec<_>>().await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("读取索引使用统计失败: {}", e))?;
+
+        let mut usages: Vec<IndexUsage> = entries.iter().map(|entry| parse_index_stats(entry, sizes)).collect();
+        usages.sort_by(|a, b| a.accesses.cmp(&b.accesses).then_with(|| a.name.cmp(&b.name)));
+        Ok(usages)
+    }
+}
+
+pub(crate) fn parse_index_stats(entry: &Document, sizes: Option<&Document>) -> IndexUsage {
+    let name = entry.get_str("name").unwrap_or_default().to_string();
+    let accesses = entry.get_document("accesses").ok();
+    let count = accesses
+        .and_then(|accesses| accesses.get("ops"))
+        .and_then(as_i64)
+        .map(|v| v.max(0) as u64)
+        .unwrap_or(0);
+
+    IndexUsage {
+        key: entry.get_document("key")
+            .map(|key| Bson::Document(key.clone()).into_relaxed_extjson())
+            .unwrap_or(serde_json::Value::Null),
+        accesses: count,
+        since: accesses
+            .and_then(|accesses| accesses.get_datetime("since").ok())
+            .and_then(|since| since.try_to_rfc3339_string().ok()),
+        host: entry.get_str("host").ok().map(str::to_string),
+        size: sizes.and_then(|sizes| sizes.get(&name)).and_then(as_i64).map(|v| v.max(0) as u64),
+        unused: count == 0 && name != "_id_",
+        name,
+    }
+}
+
+/// 索引键的类型
+fn key_kind(field: &str, value: &Bson) -> anyhow::Result<&'static str> {
+    match value {
+        Bson::String(kind) => match kind.as_str() {
+            "text" => Ok("text"),
+            "2dsphere" => Ok("2dsphere"),
+            "2d" => Ok("2d"),
+            "hashed" => Ok("hashed"),
+            _ => Err(anyhow::anyhow!("字段 {} 的索引类型 '{}' 不支持", field, kind)),
+        },
+        Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) => match as_i64(value) {
+            Some(1) => Ok("asc"),
+            Some(-1) => Ok("desc"),
+            _ => Err(anyhow::anyhow!("字段 {} 的排序方向只能是 1 或 -1", field)),
+        },
+        _ => Err(anyhow::anyhow!("字段 {} 的索引类型无效", field)),
+    }
+}
+
+fn optional_document(value: &Option<serde_json::Value>, what: &str) -> anyhow::Result<Option<Document>> {
+    match value {
+        None => Ok(None),
+        Some(value) => match Bson::try_from(value.clone()) {
+            Ok(Bson::Document(document)) => Ok(Some(document)),
+            Ok(_) => Err(anyhow::anyhow!("{}必须是文档", what)),
+            Err(e) => Err(anyhow::anyhow!("{}无效: {}", what, e)),
+        },
+    }
+}
+
+/// 校验请求并构造索引模型
+pub(crate) fn build_index(request: &CreateIndexRequest) -> anyhow::Result<IndexModel> {
+    let keys = optional_document(&Some(request.keys.clone()), "索引键")?.unwrap_or_default();
+    if keys.is_empty() {
+        return Err(anyhow::anyhow!("索引至少需要一个字段"));
+    }
+
+    let mut kinds = Vec::with_capacity(keys.len());
+    for (field, value) in &keys {
+        kinds.push(key_kind(field, value)?);
+    }
+    let is_text = kinds.contains(&"text");
+    let is_wildcard = keys.keys().any(|key| key == "$**" || key.ends_with(".$**"));
+
+    if kinds.iter().filter(|kind| **kind == "hashed").count() > 1 {
+        return Err(anyhow::anyhow!("一个索引只能包含一个 hashed 字段"));
+    }
+    if request.unique && (is_wildcard || kinds.contains(&"hashed")) {
+        return Err(anyhow::anyhow!("通配符索引和 hashed 索引不能是唯一索引"));
+    }
+    if request.sparse && request.partial_filter.is_some() {
+        return Err(anyhow::anyhow!("sparse 和 partialFilterExpression 不能同时使用"));
+    }
+    if request.expire_after_seconds.is_some() {
+        if keys.len() != 1 || !matches!(kinds[0], "asc" | "desc") || is_wildcard {
+            return Err(anyhow::anyhow!("TTL 索引只能建在单个日期字段上"));
+        }
+        if keys.contains_key("_id") {
+            return Err(anyhow::anyhow!("_id 字段不支持 TTL 索引"));
+        }
+    }
+    if !is_text && (request.weights.is_some() || request.default_language.is_some()) {
+        return Err(anyhow::anyhow!("weights 和 default_language 只适用于文本索引"));
+    }
+
+    let collation = match &request.collation {
+        Some(collation) => Some(serde_json::from_value::<Collation>(collation.clone())
+            .map_err(|e| anyhow::anyhow!("排序规则无效: {}", e))?),
+        None => None,
+    };
+
+    let options = IndexOptions::builder()
+        .name(request.name.clone().filter(|name| !name.is_empty()))
+        .unique(request.unique.then_some(true))
+        .sparse(request.sparse.then_some(true))
+        .hidden(request.hidden.then_some(true))
+        .partial_filter_expression(optional_document(&request.partial_filter, "部分索引过滤条件")?)
+        .expire_after(request.expire_after_seconds.map(Duration::from_secs))
+        .collation(collation)
+        .weights(optional_document(&request.weights, "文本索引权重")?)
+        .default_language(request.default_language.clone())
+        .build();
+
+    Ok(IndexModel::builder().keys(keys).options(options).build())
+}
@@ -0,0 +1,96 @@
+use super::*;
+use mongodb::bson::DateTime;
+
+fn request(keys: serde_json::Value) -> CreateIndexRequest {
+    serde_json::from_value(serde_json::json!({ "collection": "users", "keys": keys })).unwrap()
+}
+
+#[test]
+fn test_build_compound_unique_index() {
+    let mut req = request(serde_json::json!({ "email": 1, "created": -1 }));
+    req.unique = true;
+    req.name = Some("email_created".to_string());
+    req.collation = Some(serde_json::json!({ "locale": "zh", "strength": 2 }));
+
+    let index = build_index(&req).unwrap();
+    assert_eq!(index.keys, doc! { "email": 1, "created": -1 });
+    let options = index.options.unwrap();
+    assert_eq!(options.unique, Some(true));
+    assert_eq!(options.name.as_deref(), Some("email_created"));
+    assert_eq!(options.collation.unwrap().locale, "zh");
+    assert_eq!(options.sparse, None);
+}
+
+#[test]
+fn test_build_special_indexes() {
+    let mut text = request(serde_json::json!({ "title": "text", "body": "text" }));
+    text.weights = Some(serde_json::json!({ "title": 10 }));
+    text.default_language = Some("none".to_string());
+    let options = build_index(&text).unwrap().options.unwrap();
+    assert_eq!(options.weights, Some(doc! { "title": 10 }));
+
+    let geo = request(serde_json::json!({ "location": "2dsphere" }));
+    assert_eq!(build_index(&geo).unwrap().keys, doc! { "location": "2dsphere" });
+
+    let mut partial = request(serde_json::json!({ "status": 1 }));
+    partial.partial_filter = Some(serde_json::json!({ "status": { "$exists": true } }));
+    partial.hidden = true;
+    let options = build_index(&partial).unwrap().options.unwrap();
+    assert_eq!(options.partial_filter_expression, Some(doc! { "status": { "$exists": true } }));
+    assert_eq!(options.hidden, Some(true));
+}
+
+#[test]
+fn test_build_ttl_index() {
+    let mut ttl = request(serde_json::json!({ "expiresAt": 1 }));
+    ttl.expire_after_seconds = Some(3600);
+    let options = build_index(&ttl).unwrap().options.unwrap();
+    assert_eq!(options.expire_after, Some(Duration::from_secs(3600)));
+
+    let mut compound = request(serde_json::json!({ "a": 1, "b": 1 }));
+    compound.expire_after_seconds = Some(60);
+    assert!(build_index(&compound).is_err());
+}
+
+#[test]
+fn test_build_index_rejects_invalid_combinations() {
+    assert!(build_index(&request(serde_json::json!({}))).is_err());
+    assert!(build_index(&request(serde_json::json!({ "a": 2 }))).is_err());
+    assert!(build_index(&request(serde_json::json!({ "a": "btree" }))).is_err());
+    assert!(build_index(&request(serde_json::json!({ "a": "hashed", "b": "hashed" }))).is_err());
+
+    let mut sparse_partial = request(serde_json::json!({ "a": 1 }));
+    sparse_partial.sparse = true;
+    sparse_partial.partial_filter = Some(serde_json::json!({ "a": { "$gt": 1 } }));
+    assert!(build_index(&sparse_partial).is_err());
+
+    let mut unique_wildcard = request(serde_json::json!({ "$**": 1 }));
+    unique_wildcard.unique = true;
+    assert!(build_index(&unique_wildcard).is_err());
+
+    let mut weights_without_text = request(serde_json::json!({ "a": 1 }));
+    weights_without_text.weights = Some(serde_json::json!({ "a": 2 }));
+    assert!(build_index(&weights_without_text).is_err());
+}
+
+#[test]
+fn test_parse_index_stats() {
+    let sizes = doc! { "email_1": 4096 };
+    let entry = doc! {
+        "name": "email_1",
+        "key": { "email": 1 },
+        "host": "db1:27017",
+        "accesses": { "ops": 0_i64, "since": DateTime::from_millis(0) },
+    };
+    let usage = parse_index_stats(&entry, Some(&sizes));
+    assert_eq!(usage.name, "email_1");
+    assert_eq!(usage.key, serde_json::json!({ "email": 1 }));
+    assert_eq!(usage.accesses, 0);
+    assert_eq!(usage.since.as_deref(), Some("1970-01-01T00:00:00Z"));
+    assert_eq!(usage.host.as_deref(), Some("db1:27017"));
+    assert_eq!(usage.size, Some(4096));
+    assert!(usage.unused);
+
+    let id = doc! { "name": "_id_", "key": { "_id": 1 }, "accesses": { "ops": 0 } };
+    assert!(!parse_index_stats(&id, None).unused);
+}
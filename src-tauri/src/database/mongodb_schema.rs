@@ -0,0 +1,317 @@
+// MongoDB 结构推断
+//
+// 通过 $sample 抽样文档，统计每个字段路径（嵌套文档用 "." 连接）出现的次数、
+// 观察到的 BSON 类型及频率、数组元素类型，并映射为 TableSchema，
+// 供结构面板、MCP 上下文和 AI 提示使用。
+
+use super::mongodb::MongoDBConnection;
+use super::{ColumnInfo, TableSchema};
+use futures::StreamExt;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    Collection,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// 默认每个集合抽样的文档数
+pub const DEFAULT_SCHEMA_SAMPLE_SIZE: i64 = 100;
+
+/// get_schema 同时抽样的集合数上限
+const SCHEMA_SAMPLE_CONCURRENCY: usize = 4;
+
+/// get_schema 推断结果的缓存时间
+const SCHEMA_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// 按 (库名, 集合名) 缓存的表结构和缓存时间
+pub(crate) type SchemaCache = HashMap<(String, String), (Instant, TableSchema)>;
+
+/// 嵌套文档的最大展开深度
+const MAX_SCHEMA_DEPTH: usize = 16;
+
+/// 某种类型被观察到的次数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeFrequency {
+    pub bson_type: String,
+    pub count: u64,
+}
+
+/// 字段路径的统计信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldSchema {
+    /// 字段路径，例如 address.city
+    pub path: String,
+    /// 包含该字段的文档数
+    pub occurrences: u64,
+    /// 包含该字段的文档占比
+    pub probability: f64,
+    /// 所有抽样文档都包含该字段且从不为 null
+    pub required: bool,
+    /// 按出现次数降序排列
+    pub types: Vec<TypeFrequency>,
+    /// 字段为数组时元素的类型
+    pub array_types: Vec<TypeFrequency>,
+}
+
+/// 集合的推断结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionSchema {
+    pub collection: String,
+    pub sampled: u64,
+    pub fields: Vec<FieldSchema>,
+}
+
+impl MongoDBConnection {
+    /// 抽样推断集合结构
+    pub async fn infer_collection_schema(&self, collection: &str, sample_size: i64) -> anyhow::Result<CollectionSchema> {
+        let coll: Collection<Document> = self.database()?.collection(collection);
+        let pipeline = vec![doc! { "$sample": { "size": sample_size.max(1) } }];
+        let cursor = coll.aggregate(pipeline, None).await
+            .map_err(|e| anyhow::anyhow!("抽样集合 {} 失败: {}", collection, e))?;
+        let documents = cursor.collect::<Vec<_>>().await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("读取抽样文档失败: {}", e))?;
+        Ok(infer_schema(collection, &documents))
+    }
+
+    /// get_schema 使用：并发抽样各集合（数量有上限），结果缓存 SCHEMA_CACHE_TTL；
+    /// 视图无权限或集合为空时退回基本的"表"信息
+    pub(crate) async fn sampled_table_schemas(&self, collections: Vec<String>) -> Vec<TableSchema> {
+        let database = self.database().map(|db| db.name().to_string()).unwrap_or_default();
+        futures::stream::iter(collections)
+            .map(|collection| {
+                let cache_key = (database.clone(), collection);
+                async move {
+                    if let Some((cached_at, schema)) = self.schema_cache().get(&cache_key) {
+                        if cached_at.elapsed() < SCHEMA_CACHE_TTL {
+                            return schema.clone();
+                        }
+                    }
+                    let collection = &cache_key.1;
+                    let schema = match self.infer_collection_schema(collection, DEFAULT_SCHEMA_SAMPLE_SIZE).await {
+                        Ok(schema) if !schema.fields.is_empty() => schema.to_table_schema(),
+                        Ok(_) => basic_table_schema(collection),
+                        Err(e) => {
+                            tracing::warn!("推断集合 {} 的结构失败: {}", collection, e);
+                            return basic_table_schema(collection);
+                        }
+                    };
+                    self.schema_cache().insert(cache_key, (Instant::now(), schema.clone()));
+                    schema
+                }
+            })
+            .buffered(SCHEMA_SAMPLE_CONCURRENCY)
+            .collect()
+            .await
+    }
+}
+
+/// 无法推断字段时的基本结构
+fn basic_table_schema(collection: &str) -> TableSchema {
+    TableSchema::new(collection, vec![
+        ColumnInfo::new("_id", "ObjectId", false, true),
+        ColumnInfo::new("document", "Document", false, false),
+    ])
+}
+
+impl CollectionSchema {
+    /// 映射为通用的表结构，类型写为 "String | Null"、"Array<Int32>" 的形式，
+    /// 类型频率等信息放在 extra 中
+    pub fn to_table_schema(&self) -> TableSchema {
+        TableSchema {
+            name: self.collection.clone(),
+            columns: self.fields.iter().map(|field| {
+                let mut extra = HashMap::new();
+                extra.insert("occurrences".to_string(), field.occurrences.to_string());
+                extra.insert("probability".to_string(), format!("{:.4}", field.probability));
+                extra.insert("types".to_string(), frequencies(&field.types));
+                if !field.array_types.is_empty() {
+                    extra.insert("array_types".to_string(), frequencies(&field.array_types));
+                }
+                ColumnInfo {
+                    name: field.path.clone(),
+                    data_type: field.type_summary(),
+                    nullable: !field.required,
+                    primary_key: field.path == "_id",
+                    auto_increment: false,
+                    default_value: None,
+                    charset: None,
+                    comment: Some(format!("出现于 {:.0}% 的抽样文档（共 {} 个）", field.probability * 100.0, self.sampled)),
+                    extra,
+                }
+            }).collect(),
+            indexes: vec![],
+            foreign_keys: vec![],
+            table_comment: Some(format!("根据 {} 个抽样文档推断", self.sampled)),
+            engine: None,
+            charset: None,
+        }
+    }
+}
+
+impl FieldSchema {
+    fn type_summary(&self) -> String {
+        self.types.iter()
+            .map(|t| {
+                if t.bson_type == "Array" && !self.array_types.is_empty() {
+                    let elements: Vec<&str> = self.array_types.iter().map(|t| t.bson_type.as_str()).collect();
+                    format!("Array<{}>", elements.join(" | "))
+                } else {
+                    t.bson_type.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+fn frequencies(types: &[TypeFrequency]) -> String {
+    types.iter()
+        .map(|t| format!("{}:{}", t.bson_type, t.count))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// BSON 类型名
+pub(crate) fn bson_type_name(value: &Bson) -> &'static str {
+    match value {
+        Bson::Double(_) => "Double",
+        Bson::String(_) => "String",
+        Bson::Array(_) => "Array",
+        Bson::Document(_) => "Document",
+        Bson::Boolean(_) => "Boolean",
+        Bson::Null => "Null",
+        Bson::RegularExpression(_) => "Regex",
+        Bson::JavaScriptCode(_) => "JavaScript",
+        Bson::JavaScriptCodeWithScope(_) => "JavaScriptWithScope",
+        Bson::Int32(_) => "Int32",
+        Bson::Int64(_) => "Int64",
+        Bson::Timestamp(_) => "Timestamp",
+        Bson::Binary(_) => "Binary",
+        Bson::ObjectId(_) => "ObjectId",
+        Bson::DateTime(_) => "Date",
+        Bson::Symbol(_) => "Symbol",
+        Bson::Decimal128(_) => "Decimal128",
+        Bson::Undefined => "Undefined",
+        Bson::MaxKey => "MaxKey",
+        Bson::MinKey => "MinKey",
+        Bson::DbPointer(_) => "DBPointer",
+    }
+}
+
+#[derive(Default)]
+struct FieldStats {
+    path: String,
+    occurrences: u64,
+    types: Vec<(&'static str, u64)>,
+    array_types: Vec<(&'static str, u64)>,
+}
+
+#[derive(Default)]
+struct SchemaCollector {
+    fields: Vec<FieldStats>,
+    index: HashMap<String, usize>,
+}
+
+impl SchemaCollector {
+    fn field(&mut self, path: &str) -> &mut FieldStats {
+        let index = match self.index.get(path) {
+            Some(index) => *index,
+            None => {
+                self.fields.push(FieldStats { path: path.to_string(), ..Default::default() });
+                self.index.insert(path.to_string(), self.fields.len() - 1);
+                self.fields.len() - 1
+            }
+        };
+        &mut self.fields[index]
+    }
+
+    fn visit_document(&mut self, prefix: Option<&str>, document: &Document, seen: &mut HashSet<String>, depth: usize) {
+        if depth > MAX_SCHEMA_DEPTH {
+            return;
+        }
+        for (key, value) in document {
+            let path = match prefix {
+                Some(prefix) => format!("{}.{}", prefix, key),
+                None => key.clone(),
+            };
+            self.visit_value(&path, value, seen, depth);
+        }
+    }
+
+    fn visit_value(&mut self, path: &str, value: &Bson, seen: &mut HashSet<String>, depth: usize) {
+        let first_in_document = seen.insert(path.to_string());
+        let field = self.field(path);
+        if first_in_document {
+            field.occurrences += 1;
+        }
+        increment(&mut field.types, bson_type_name(value));
+
+        match value {
+            Bson::Document(document) => self.visit_document(Some(path), document, seen, depth + 1),
+            Bson::Array(items) => {
+                for item in items {
+                    increment(&mut self.field(path).array_types, bson_type_name(item));
+                }
+                // 数组中的子文档按 MongoDB 的点路径语义合并到同一路径下
+                for item in items {
+                    if let Bson::Document(document) = item {
+                        self.visit_document(Some(path), document, seen, depth + 1);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn increment(counts: &mut Vec<(&'static str, u64)>, name: &'static str) {
+    match counts.iter_mut().find(|(n, _)| *n == name) {
+        Some((_, count)) => *count += 1,
+        None => counts.push((name, 1)),
+    }
+}
+
+fn sorted(mut counts: Vec<(&'static str, u64)>) -> Vec<TypeFrequency> {
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts.into_iter()
+        .map(|(name, count)| TypeFrequency { bson_type: name.to_string(), count })
+        .collect()
+}
+
+/// 根据抽样文档推断结构，字段按首次出现的顺序排列，_id 总在最前
+pub(crate) fn infer_schema(collection: &str, documents: &[Document]) -> CollectionSchema {
+    let mut collector = SchemaCollector::default();
+    collector.field("_id");
+    for document in documents {
+        let mut seen = HashSet::new();
+        collector.visit_document(None, document, &mut seen, 0);
+    }
+
+    let sampled = documents.len() as u64;
+    let fields = collector.fields.into_iter()
+        .filter(|field| field.occurrences > 0)
+        .map(|field| {
+            let has_null = field.types.iter().any(|(name, _)| *name == "Null");
+            FieldSchema {
+                probability: field.occurrences as f64 / sampled as f64,
+                required: field.occurrences == sampled && !has_null,
+                path: field.path,
+                occurrences: field.occurrences,
+                types: sorted(field.types),
+                array_types: sorted(field.array_types),
+            }
+        })
+        .collect();
+
+    CollectionSchema {
+        collection: collection.to_string(),
+        sampled,
+        fields,
+    }
+}
@@ -0,0 +1,85 @@
+use super::*;
+use mongodb::bson::oid::ObjectId;
+
+fn sample() -> Vec<Document> {
+    vec![
+        doc! {
+            "_id": ObjectId::new(),
+            "name": "alice",
+            "age": 30,
+            "address": { "city": "Paris", "zip": "75001" },
+            "tags": ["a", "b"],
+            "orders": [{ "sku": "x", "qty": 1 }, { "sku": "y" }],
+        },
+        doc! {
+            "_id": ObjectId::new(),
+            "name": "bob",
+            "age": 31.5,
+            "address": { "city": "Lyon" },
+            "tags": ["c", 1],
+            "nickname": Bson::Null,
+        },
+    ]
+}
+
+fn field<'a>(schema: &'a CollectionSchema, path: &str) -> &'a FieldSchema {
+    schema.fields.iter().find(|f| f.path == path).unwrap_or_else(|| panic!("missing field {}", path))
+}
+
+#[test]
+fn test_infer_schema() {
+    let schema = infer_schema("users", &sample());
+    assert_eq!(schema.sampled, 2);
+    assert_eq!(schema.fields[0].path, "_id");
+    assert!(field(&schema, "_id").required);
+
+    let age = field(&schema, "age");
+    assert!(age.required);
+    assert_eq!(age.types, vec![
+        TypeFrequency { bson_type: "Int32".to_string(), count: 1 },
+        TypeFrequency { bson_type: "Double".to_string(), count: 1 },
+    ]);
+
+    let zip = field(&schema, "address.zip");
+    assert!(!zip.required);
+    assert!((zip.probability - 0.5).abs() < f64::EPSILON);
+    assert!(field(&schema, "address.city").required);
+
+    let tags = field(&schema, "tags");
+    assert_eq!(tags.array_types[0], TypeFrequency { bson_type: "String".to_string(), count: 3 });
+    assert_eq!(tags.array_types[1].bson_type, "Int32");
+
+    // 数组中的子文档合并到同一路径，每个文档只计一次出现
+    let sku = field(&schema, "orders.sku");
+    assert_eq!(sku.occurrences, 1);
+    assert_eq!(sku.types[0].count, 2);
+    assert_eq!(field(&schema, "orders.qty").occurrences, 1);
+
+    // 出现于全部文档但含 null 的字段不是必填
+    assert!(!field(&schema, "nickname").required);
+}
+
+#[test]
+fn test_schema_mapping() {
+    let schema = infer_schema("users", &sample());
+    let table = schema.to_table_schema();
+    assert_eq!(table.name, "users");
+    let id = table.columns.iter().find(|c| c.name == "_id").unwrap();
+    assert!(id.primary_key && !id.nullable);
+    let tags = table.columns.iter().find(|c| c.name == "tags").unwrap();
+    assert_eq!(tags.data_type, "Array<String | Int32>");
+    let age = table.columns.iter().find(|c| c.name == "age").unwrap();
+    assert_eq!(age.data_type, "Int32 | Double");
+
+    let zip = table.columns.iter().find(|c| c.name == "address.zip").unwrap();
+    assert!(zip.nullable);
+    assert_eq!(zip.extra.get("types").map(String::as_str), Some("String:1"));
+    assert_eq!(zip.extra.get("probability").map(String::as_str), Some("0.5000"));
+}
+
+#[test]
+fn test_infer_empty_collection() {
+    let schema = infer_schema("empty", &[]);
+    assert_eq!(schema.sampled, 0);
+    assert!(schema.fields.is_empty());
+}
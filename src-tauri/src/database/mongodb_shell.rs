@@ -0,0 +1,662 @@
+// mongosh 语法解析
+//
+// 支持 db.<集合>.<方法>(...)、db['集合']、db.getCollection('集合') 以及
+// .sort().skip().limit().project() 等链式调用。参数按 mongosh 的字面量语法解析：
+// 未加引号的键、单引号字符串、正则字面量、ObjectId()/ISODate()/NumberLong() 等构造函数，
+// 以及 // 和 /* */ 注释。
+
+use base64::Engine;
+use mongodb::bson::{oid::ObjectId, spec::BinarySubtype, Binary, Bson, DateTime, Decimal128, Document, Regex, Timestamp};
+
+#[cfg(test)]
+mod tests;
+
+/// 一次方法调用
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ShellCall {
+    pub name: String,
+    pub args: Vec<Bson>,
+}
+
+/// 一条 db.<集合>.<方法>(...) 语句，第一个调用是集合方法，其后是链式调用
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ShellStatement {
+    pub collection: String,
+    pub calls: Vec<ShellCall>,
+}
+
+/// 解析一条 mongosh 语句
+pub(crate) fn parse_statement(text: &str) -> anyhow::Result<ShellStatement> {
+    let mut parser = Parser::new(text);
+    let statement = parser.statement()?;
+    parser.skip_ws();
+    while parser.eat(';') {
+        parser.skip_ws();
+    }
+    if !parser.at_end() {
+        return Err(parser.error("语句末尾有多余内容"));
+    }
+    Ok(statement)
+}
+
+/// 解析单个字面量，例如 {name: 'a', _id: ObjectId('...')}
+pub(crate) fn parse_value(text: &str) -> anyhow::Result<Bson> {
+    let mut parser = Parser::new(text);
+    let value = parser.value()?;
+    parser.skip_ws();
+    if !parser.at_end() {
+        return Err(parser.error("字面量之后有多余内容"));
+    }
+    Ok(value)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn new(text: &str) -> Self {
+        Self { chars: text.chars().collect(), pos: 0, depth: 0 }
+    }
+
+    fn error(&self, message: &str) -> anyhow::Error {
+        anyhow::anyhow!("第 {} 个字符处{}", self.pos + 1, message)
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> anyhow::Result<()> {
+        self.skip_ws();
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("应为 '{}'", c)))
+        }
+    }
+
+    /// 跳过空白和注释
+    fn skip_ws(&mut self) {
+        loop {
+            match (self.peek(), self.peek_at(1)) {
+                (Some(c), _) if c.is_whitespace() => self.pos += 1,
+                (Some('/'), Some('/')) => {
+                    while !matches!(self.peek(), None | Some('\n')) {
+                        self.pos += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    self.pos += 2;
+                    while !self.at_end() && (self.peek(), self.peek_at(1)) != (Some('*'), Some('/')) {
+                        self.pos += 1;
+                    }
+                    self.pos = (self.pos + 2).min(self.chars.len());
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn is_ident_start(c: char) -> bool {
+        c.is_alphabetic() || c == '_' || c == '$'
+    }
+
+    fn is_ident_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_' || c == '$'
+    }
+
+    fn ident(&mut self) -> anyhow::Result<String> {
+        self.skip_ws();
+        match self.peek() {
+            Some(c) if Self::is_ident_start(c) => {}
+            _ => return Err(self.error("应为标识符")),
+        }
+        let start = self.pos;
+        while self.peek().is_some_and(Self::is_ident_char) {
+            self.pos += 1;
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn statement(&mut self) -> anyhow::Result<ShellStatement> {
+        if self.ident()? != "db" {
+            return Err(anyhow::anyhow!("语句必须以 db. 开头"));
+        }
+
+        // 集合名：db.a.b.find() 中的 "a.b"，或 db['a']、db.getCollection('a')
+        let mut path: Vec<String> = Vec::new();
+        let mut collection: Option<String> = None;
+        let method = loop {
+            self.skip_ws();
+            if collection.is_none() && path.is_empty() && self.eat('[') {
+                self.skip_ws();
+                collection = Some(self.string()?);
+                self.expect(']')?;
+                continue;
+            }
+            self.expect('.')?;
+            let name = self.ident()?;
+            self.skip_ws();
+            if self.peek() != Some('(') {
+                if collection.is_some() {
+                    return Err(self.error("应为方法调用"));
+                }
+                path.push(name);
+                continue;
+            }
+            if name == "getCollection" && collection.is_none() && path.is_empty() {
+                let args = self.arguments()?;
+                match args.as_slice() {
+                    [Bson::String(name)] => collection = Some(name.clone()),
+                    _ => return Err(anyhow::anyhow!("getCollection 需要一个字符串参数")),
+                }
+                continue;
+            }
+            break name;
+        };
+
+        let collection = match collection {
+            Some(name) => name,
+            None if !path.is_empty() => path.join("."),
+            None => return Err(anyhow::anyhow!("缺少集合名")),
+        };
+        if collection.is_empty() {
+            return Err(anyhow::anyhow!("集合名不能为空"));
+        }
+
+        let mut calls = vec![ShellCall { name: method, args: self.arguments()? }];
+        loop {
+            self.skip_ws();
+            if self.peek() != Some('.') {
+                break;
+            }
+            self.pos += 1;
+            let name = self.ident()?;
+            calls.push(ShellCall { name, args: self.arguments()? });
+        }
+        Ok(ShellStatement { collection, calls })
+    }
+
+    /// (值, 值, ...)，允许末尾逗号
+    fn arguments(&mut self) -> anyhow::Result<Vec<Bson>> {
+        self.expect('(')?;
+        self.list(')')
+    }
+
+    fn list(&mut self, end: char) -> anyhow::Result<Vec<Bson>> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_ws();
+            if self.eat(end) {
+                return Ok(items);
+            }
+            items.push(self.value()?);
+            self.skip_ws();
+            if !self.eat(',') {
+                self.expect(end)?;
+                return Ok(items);
+            }
+        }
+    }
+
+    fn value(&mut self) -> anyhow::Result<Bson> {
+        self.skip_ws();
+        self.depth += 1;
+        if self.depth > 128 {
+            return Err(self.error("嵌套层级过深"));
+        }
+        let value = match self.peek() {
+            None => Err(self.error("数据意外结束")),
+            Some('{') => {
+                self.pos += 1;
+                self.object().map(extended_json)
+            }
+            Some('[') => {
+                self.pos += 1;
+                self.list(']').map(Bson::Array)
+            }
+            Some('"') | Some('\'') => self.string().map(Bson::String),
+            Some('/') => self.regex(),
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => self.number(),
+            Some(c) if Self::is_ident_start(c) => self.word(),
+            Some(c) => Err(self.error(&format!("无法识别的字符 '{}'", c))),
+        };
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> anyhow::Result<Document> {
+        let mut document = Document::new();
+        loop {
+            self.skip_ws();
+            if self.eat('}') {
+                return Ok(document);
+            }
+            let key = match self.peek() {
+                Some('"') | Some('\'') => self.string()?,
+                Some(c) if c.is_ascii_digit() => {
+                    let start = self.pos;
+                    while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                        self.pos += 1;
+                    }
+                    self.chars[start..self.pos].iter().collect()
+                }
+                _ => self.ident()?,
+            };
+            self.expect(':')?;
+            let value = self.value()?;
+            document.insert(key, value);
+            self.skip_ws();
+            if !self.eat(',') {
+                self.expect('}')?;
+                return Ok(document);
+            }
+        }
+    }
+
+    fn string(&mut self) -> anyhow::Result<String> {
+        let quote = match self.peek() {
+            Some(c @ ('"' | '\'')) => c,
+            _ => return Err(self.error("应为字符串")),
+        };
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("字符串缺少结束引号"))?;
+            self.pos += 1;
+            match c {
+                c if c == quote => return Ok(value),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error("字符串缺少结束引号"))?;
+                    self.pos += 1;
+                    match escaped {
+                        'n' => value.push('\n'),
+                        'r' => value.push('\r'),
+                        't' => value.push('\t'),
+                        'b' => value.push('\u{8}'),
+                        'f' => value.push('\u{c}'),
+                        'v' => value.push('\u{b}'),
+                        '0' => value.push('\0'),
+                        'u' => value.push(self.unicode_escape()?),
+                        'x' => {
+                            let hex: String = (0..2).filter_map(|_| {
+                                let c = self.peek()?;
+                                self.pos += 1;
+                                Some(c)
+                            }).collect();
+                            let code = u32::from_str_radix(&hex, 16).map_err(|_| self.error("无效的 \\x 转义"))?;
+                            value.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                        }
+                        '\n' => {}
+                        other => value.push(other),
+                    }
+                }
+                c => value.push(c),
+            }
+        }
+    }
+
+    /// \uXXXX 或 \u{X...}，包括 UTF-16 代理对
+    fn unicode_escape(&mut self) -> anyhow::Result<char> {
+        let read_hex = |parser: &mut Self, len: usize| -> anyhow::Result<u32> {
+            let hex: String = parser.chars.get(parser.pos..parser.pos + len)
+                .ok_or_else(|| parser.error("无效的 \\u 转义"))?
+                .iter()
+                .collect();
+            parser.pos += len;
+            u32::from_str_radix(&hex, 16).map_err(|_| parser.error("无效的 \\u 转义"))
+        };
+
+        if self.eat('{') {
+            let start = self.pos;
+            while self.peek().is_some_and(|c| c != '}') {
+                self.pos += 1;
+            }
+            let len = self.pos - start;
+            self.pos = start;
+            let code = read_hex(self, len)?;
+            self.expect('}')?;
+            return char::from_u32(code).ok_or_else(|| self.error("无效的 Unicode 码点"));
+        }
+
+        let high = read_hex(self, 4)?;
+        if (0xD800..0xDC00).contains(&high) && self.peek() == Some('\\') && self.peek_at(1) == Some('u') {
+            self.pos += 2;
+            let low = read_hex(self, 4)?;
+            let code = 0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+            return Ok(char::from_u32(code).unwrap_or('\u{fffd}'));
+        }
+        Ok(char::from_u32(high).unwrap_or('\u{fffd}'))
+    }
+
+    /// /pattern/flags，字符类 [...] 中的 / 不结束正则
+    fn regex(&mut self) -> anyhow::Result<Bson> {
+        self.pos += 1;
+        let mut pattern = String::new();
+        let mut in_class = false;
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("正则表达式缺少结束的 /"))?;
+            self.pos += 1;
+            match c {
+                '\\' => {
+                    pattern.push(c);
+                    pattern.push(self.peek().ok_or_else(|| self.error("正则表达式缺少结束的 /"))?);
+                    self.pos += 1;
+                }
+                '[' => {
+                    in_class = true;
+                    pattern.push(c);
+                }
+                ']' => {
+                    in_class = false;
+                    pattern.push(c);
+                }
+                '/' if !in_class => break,
+                '\n' => return Err(self.error("正则表达式不能跨行")),
+                c => pattern.push(c),
+            }
+        }
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        let flags: String = self.chars[start..self.pos].iter().collect();
+        regex(pattern, &flags).map_err(|e| self.error(&e.to_string()))
+    }
+
+    fn number(&mut self) -> anyhow::Result<Bson> {
+        let negative = match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                true
+            }
+            Some('+') => {
+                self.pos += 1;
+                false
+            }
+            _ => false,
+        };
+        self.skip_ws();
+
+        if self.peek().is_some_and(Self::is_ident_start) {
+            return match self.ident()?.as_str() {
+                "Infinity" => Ok(Bson::Double(if negative { f64::NEG_INFINITY } else { f64::INFINITY })),
+                "NaN" => Ok(Bson::Double(f64::NAN)),
+                _ => Err(self.error("无效的数字")),
+            };
+        }
+
+        let start = self.pos;
+        if self.peek() == Some('0') && matches!(self.peek_at(1), Some('x' | 'X')) {
+            self.pos += 2;
+            let digits_start = self.pos;
+            while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                self.pos += 1;
+            }
+            let digits: String = self.chars[digits_start..self.pos].iter().collect();
+            let value = i64::from_str_radix(&digits, 16).map_err(|_| self.error("无效的十六进制数字"))?;
+            return Ok(integer(if negative { -value } else { value }));
+        }
+
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '_') {
+            self.pos += 1;
+        }
+        let mut is_float = false;
+        if self.peek() == Some('.') {
+            is_float = true;
+            self.pos += 1;
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.pos += 1;
+            }
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            is_float = true;
+            self.pos += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.pos += 1;
+            }
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.pos += 1;
+            }
+        }
+
+        let mut text: String = self.chars[start..self.pos].iter().filter(|c| **c != '_').collect();
+        if text.is_empty() || text == "." {
+            return Err(self.error("无效的数字"));
+        }
+        if negative {
+            text.insert(0, '-');
+        }
+        if !is_float {
+            if let Ok(value) = text.parse::<i64>() {
+                return Ok(integer(value));
+            }
+        }
+        text.parse::<f64>()
+            .map(Bson::Double)
+            .map_err(|_| self.error(&format!("无效的数字 '{}'", text)))
+    }
+
+    /// 关键字和构造函数
+    fn word(&mut self) -> anyhow::Result<Bson> {
+        let mut name = self.ident()?;
+        if name == "new" {
+            name = self.ident()?;
+        }
+        match name.as_str() {
+            "true" => return Ok(Bson::Boolean(true)),
+            "false" => return Ok(Bson::Boolean(false)),
+            "null" => return Ok(Bson::Null),
+            "undefined" => return Ok(Bson::Undefined),
+            "Infinity" => return Ok(Bson::Double(f64::INFINITY)),
+            "NaN" => return Ok(Bson::Double(f64::NAN)),
+            _ => {}
+        }
+
+        self.skip_ws();
+        let args = if self.peek() == Some('(') {
+            self.arguments()?
+        } else if matches!(name.as_str(), "MinKey" | "MaxKey") {
+            vec![]
+        } else {
+            return Err(self.error(&format!("未知的标识符 '{}'", name)));
+        };
+        constructor(&name, args).map_err(|e| self.error(&e.to_string()))
+    }
+}
+
+/// 扩展 JSON 中表示类型的键，查询操作符 $regex 不在其中
+const EXTENDED_JSON_KEYS: &[&str] = &[
+    "$oid", "$date", "$numberInt", "$numberLong", "$numberDouble", "$numberDecimal", "$binary", "$uuid",
+    "$timestamp", "$regularExpression", "$symbol", "$code", "$minKey", "$maxKey", "$undefined",
+];
+
+/// 将 {$oid: '...'}、{$date: '...'} 等扩展 JSON 写法还原为对应的 BSON 类型，
+/// 以便编辑从结果中复制出来的文档
+fn extended_json(document: Document) -> Bson {
+    let is_wrapper = document.len() <= 2
+        && document.keys().next().is_some_and(|key| EXTENDED_JSON_KEYS.contains(&key.as_str()));
+    if is_wrapper {
+        let json = Bson::Document(document.clone()).into_relaxed_extjson();
+        if let Ok(value) = Bson::try_from(json) {
+            if !matches!(value, Bson::Document(_)) {
+                return value;
+            }
+        }
+    }
+    Bson::Document(document)
+}
+
+/// 能放入 32 位的整数使用 Int32，否则使用 Int64
+fn integer(value: i64) -> Bson {
+    match i32::try_from(value) {
+        Ok(value) => Bson::Int32(value),
+        Err(_) => Bson::Int64(value),
+    }
+}
+
+fn regex(pattern: String, flags: &str) -> anyhow::Result<Bson> {
+    let mut options: Vec<char> = Vec::new();
+    for flag in flags.chars() {
+        match flag {
+            // g 和 y 只影响 JavaScript 端的匹配方式，服务端没有对应选项
+            'g' | 'y' | 'd' => {}
+            'i' | 'm' | 's' | 'u' | 'x' | 'l' => {
+                if !options.contains(&flag) {
+                    options.push(flag);
+                }
+            }
+            other => return Err(anyhow::anyhow!("不支持的正则选项 '{}'", other)),
+        }
+    }
+    options.sort_unstable();
+    Ok(Bson::RegularExpression(Regex { pattern, options: options.into_iter().collect() }))
+}
+
+fn constructor(name: &str, args: Vec<Bson>) -> anyhow::Result<Bson> {
+    let mut args = args.into_iter();
+    let first = args.next();
+    let second = args.next();
+    if args.next().is_some() {
+        return Err(anyhow::anyhow!("{}() 的参数过多", name));
+    }
+
+    match name {
+        "ObjectId" => match first {
+            None => Ok(Bson::ObjectId(ObjectId::new())),
+            Some(Bson::String(hex)) => ObjectId::parse_str(&hex)
+                .map(Bson::ObjectId)
+                .map_err(|_| anyhow::anyhow!("无效的 ObjectId '{}'", hex)),
+            Some(_) => Err(anyhow::anyhow!("ObjectId() 需要 24 位十六进制字符串")),
+        },
+        "ISODate" | "Date" => match first {
+            None => Ok(Bson::DateTime(DateTime::now())),
+            Some(Bson::String(text)) => parse_date(&text).map(Bson::DateTime),
+            Some(value) => as_i64(&value)
+                .map(|millis| Bson::DateTime(DateTime::from_millis(millis)))
+                .ok_or_else(|| anyhow::anyhow!("{}() 需要日期字符串或毫秒时间戳", name)),
+        },
+        "NumberInt" | "Int32" => {
+            let value = first.as_ref().and_then(as_i64)
+                .ok_or_else(|| anyhow::anyhow!("{}() 需要整数", name))?;
+            i32::try_from(value)
+                .map(Bson::Int32)
+                .map_err(|_| anyhow::anyhow!("{} 超出 32 位整数范围", value))
+        }
+        "NumberLong" | "Long" => first.as_ref().and_then(as_i64)
+            .map(Bson::Int64)
+            .ok_or_else(|| anyhow::anyhow!("{}() 需要整数", name)),
+        "Double" => match first {
+            Some(Bson::String(text)) => text.trim().parse().map(Bson::Double)
+                .map_err(|_| anyhow::anyhow!("无效的浮点数 '{}'", text)),
+            Some(Bson::Int32(v)) => Ok(Bson::Double(v as f64)),
+            Some(Bson::Int64(v)) => Ok(Bson::Double(v as f64)),
+            Some(Bson::Double(v)) => Ok(Bson::Double(v)),
+            _ => Err(anyhow::anyhow!("Double() 需要数字")),
+        },
+        "NumberDecimal" | "Decimal128" => {
+            let text = match first {
+                Some(Bson::String(text)) => text,
+                Some(Bson::Int32(v)) => v.to_string(),
+                Some(Bson::Int64(v)) => v.to_string(),
+                Some(Bson::Double(v)) => v.to_string(),
+                _ => return Err(anyhow::anyhow!("{}() 需要数字字符串", name)),
+            };
+            text.parse::<Decimal128>()
+                .map(Bson::Decimal128)
+                .map_err(|_| anyhow::anyhow!("无效的 Decimal128 '{}'", text))
+        }
+        "Timestamp" => {
+            // Timestamp(t, i) 或 Timestamp({t, i})
+            let (time, increment) = match (&first, &second) {
+                (Some(Bson::Document(doc)), None) => (doc.get("t").and_then(as_i64), doc.get("i").and_then(as_i64)),
+                (Some(t), Some(i)) => (as_i64(t), as_i64(i)),
+                (None, None) => (Some(0), Some(0)),
+                _ => (None, None),
+            };
+            match (time.and_then(|t| u32::try_from(t).ok()), increment.and_then(|i| u32::try_from(i).ok())) {
+                (Some(time), Some(increment)) => Ok(Bson::Timestamp(Timestamp { time, increment })),
+                _ => Err(anyhow::anyhow!("Timestamp() 需要两个非负整数")),
+            }
+        }
+        "UUID" => match first {
+            Some(Bson::String(text)) => {
+                let hex: String = text.chars().filter(|c| *c != '-').collect();
+                if hex.len() != 32 {
+                    return Err(anyhow::anyhow!("无效的 UUID '{}'", text));
+                }
+                let bytes = hex::decode(&hex).map_err(|_| anyhow::anyhow!("无效的 UUID '{}'", text))?;
+                Ok(Bson::Binary(Binary { subtype: BinarySubtype::Uuid, bytes }))
+            }
+            _ => Err(anyhow::anyhow!("UUID() 需要 UUID 字符串")),
+        },
+        "BinData" => match (first.as_ref().and_then(as_i64), second) {
+            (Some(subtype), Some(Bson::String(data))) => {
+                let subtype = u8::try_from(subtype).map_err(|_| anyhow::anyhow!("无效的二进制子类型 {}", subtype))?;
+                let bytes = base64::engine::general_purpose::STANDARD.decode(data.trim())
+                    .map_err(|e| anyhow::anyhow!("BinData 的 Base64 内容无效: {}", e))?;
+                Ok(Bson::Binary(Binary { subtype: BinarySubtype::from(subtype), bytes }))
+            }
+            _ => Err(anyhow::anyhow!("BinData() 需要子类型和 Base64 字符串")),
+        },
+        "RegExp" => match (first, second) {
+            (Some(Bson::String(pattern)), None) => regex(pattern, ""),
+            (Some(Bson::String(pattern)), Some(Bson::String(flags))) => regex(pattern, &flags),
+            (Some(Bson::RegularExpression(regex)), None) => Ok(Bson::RegularExpression(regex)),
+            _ => Err(anyhow::anyhow!("RegExp() 需要模式字符串")),
+        },
+        "MinKey" => Ok(Bson::MinKey),
+        "MaxKey" => Ok(Bson::MaxKey),
+        _ => Err(anyhow::anyhow!("不支持的构造函数 '{}'", name)),
+    }
+}
+
+/// 接受整数、整数值的浮点数以及数字字符串
+pub(crate) fn as_i64(value: &Bson) -> Option<i64> {
+    match value {
+        Bson::Int32(v) => Some(*v as i64),
+        Bson::Int64(v) => Some(*v),
+        Bson::Double(v) if v.fract() == 0.0 && v.is_finite() => Some(*v as i64),
+        Bson::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// ISO 8601 日期，缺少时区时按 UTC 处理，也接受仅有日期的形式
+fn parse_date(text: &str) -> anyhow::Result<DateTime> {
+    let text = text.trim();
+    if let Ok(date) = chrono::DateTime::parse_from_rfc3339(text) {
+        return Ok(DateTime::from_millis(date.timestamp_millis()));
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M"] {
+        if let Ok(date) = chrono::NaiveDateTime::parse_from_str(text, format) {
+            return Ok(DateTime::from_millis(date.and_utc().timestamp_millis()));
+        }
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        if let Some(date) = date.and_hms_opt(0, 0, 0) {
+            return Ok(DateTime::from_millis(date.and_utc().timestamp_millis()));
+        }
+    }
+    Err(anyhow::anyhow!("无效的日期 '{}'", text))
+}
@@ -0,0 +1,93 @@
+use super::*;
+use mongodb::bson::doc;
+
+#[test]
+fn test_parse_shell_literals() {
+    let value = parse_value(r#"{name: 'O\'Neil', "age": 30, big: 3000000000, ratio: -1.5e2, tags: ['a', "b",], ok: true, none: null}"#).unwrap();
+    assert_eq!(value, Bson::Document(doc! {
+        "name": "O'Neil",
+        "age": 30,
+        "big": 3000000000_i64,
+        "ratio": -150.0,
+        "tags": ["a", "b"],
+        "ok": true,
+        "none": Bson::Null,
+    }));
+}
+
+#[test]
+fn test_parse_constructors() {
+    let value = parse_value(r#"{
+        _id: ObjectId("507f1f77bcf86cd799439011"),
+        at: ISODate('2024-01-02T03:04:05Z'),
+        day: new Date("2024-01-02"),
+        count: NumberLong("9007199254740993"),
+        small: NumberInt(7),
+        price: NumberDecimal("19.99"),
+        ts: Timestamp(1700000000, 1),
+        id: UUID("123e4567-e89b-12d3-a456-426614174000"),
+        bin: BinData(0, "aGVsbG8="),
+        low: MinKey,
+    }"#).unwrap();
+    let doc = value.as_document().unwrap();
+
+    assert_eq!(doc.get_object_id("_id").unwrap().to_hex(), "507f1f77bcf86cd799439011");
+    assert_eq!(doc.get_datetime("at").unwrap().timestamp_millis(), 1704164645000);
+    assert_eq!(doc.get_datetime("day").unwrap().timestamp_millis(), 1704153600000);
+    assert_eq!(doc.get_i64("count").unwrap(), 9007199254740993);
+    assert_eq!(doc.get_i32("small").unwrap(), 7);
+    assert_eq!(doc.get("price").unwrap().to_string(), "19.99");
+    assert_eq!(doc.get_timestamp("ts").unwrap(), Timestamp { time: 1700000000, increment: 1 });
+    assert_eq!(doc.get_binary_generic("bin").unwrap(), b"hello");
+    assert!(matches!(doc.get("id"), Some(Bson::Binary(Binary { subtype: BinarySubtype::Uuid, .. }))));
+    assert_eq!(doc.get("low"), Some(&Bson::MinKey));
+
+    assert!(parse_value("ObjectId('zz')").is_err());
+    assert!(parse_value("NumberInt(3000000000)").is_err());
+    assert!(parse_value("Foo(1)").is_err());
+}
+
+#[test]
+fn test_parse_regex_and_comments() {
+    let value = parse_value(r#"{ /* 注释 */ name: /^a[/b]\/c/gi, // 行注释
+        other: RegExp("x", "m") }"#).unwrap();
+    let doc = value.as_document().unwrap();
+    assert_eq!(doc.get("name"), Some(&Bson::RegularExpression(Regex {
+        pattern: r"^a[/b]\/c".to_string(),
+        options: "i".to_string(),
+    })));
+    assert_eq!(doc.get("other"), Some(&Bson::RegularExpression(Regex {
+        pattern: "x".to_string(),
+        options: "m".to_string(),
+    })));
+}
+
+#[test]
+fn test_extended_json_wrappers() {
+    let value = parse_value(r#"{"_id": {"$oid": "507f1f77bcf86cd799439011"}, "n": {"$numberLong": "5"}, "q": {"$regex": "a"}}"#).unwrap();
+    let doc = value.as_document().unwrap();
+    assert!(matches!(doc.get("_id"), Some(Bson::ObjectId(_))));
+    assert_eq!(doc.get("n"), Some(&Bson::Int64(5)));
+    // 查询操作符保持原样
+    assert_eq!(doc.get_document("q").unwrap(), &doc! { "$regex": "a" });
+}
+
+#[test]
+fn test_parse_statement() {
+    let statement = parse_statement("db.system.profile.find({millis: {$gt: 5}}).sort({ts: -1}).limit(5);").unwrap();
+    assert_eq!(statement.collection, "system.profile");
+    let names: Vec<&str> = statement.calls.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["find", "sort", "limit"]);
+    assert_eq!(statement.calls[0].args, vec![Bson::Document(doc! { "millis": { "$gt": 5 } })]);
+
+    let statement = parse_statement("db.getCollection('my.coll').countDocuments()").unwrap();
+    assert_eq!(statement.collection, "my.coll");
+    assert_eq!(statement.calls[0].name, "countDocuments");
+
+    let statement = parse_statement(r#"db["orders"].distinct("status")"#).unwrap();
+    assert_eq!(statement.collection, "orders");
+
+    assert!(parse_statement("db.find()").is_err());
+    assert!(parse_statement("db.users.find({a: 1}) extra").is_err());
+    assert!(parse_statement("db.users.find({a: 1)").is_err());
+}
@@ -0,0 +1,1160 @@
+// SQL SELECT 到 MongoDB 查询的翻译
+//
+// 支持的子集：SELECT [DISTINCT] 列/聚合函数 FROM 集合 [[LEFT] JOIN 集合 ON a.x = b.y]
+// [WHERE ...] [GROUP BY ...] [HAVING ...] [ORDER BY ...] [LIMIT n [OFFSET m]]。
+// 单集合、无分组、无别名的查询翻译为 find，其余翻译为 aggregate 管道；
+// JOIN 翻译为 $lookup + $unwind，连接表的字段以表别名为前缀访问。
+// 翻译结果同时给出 mongosh 语句，执行时走与编辑器相同的解析和驱动路径。
+
+use super::enhanced_types::EnhancedQueryResult;
+use super::mongodb::{MongoDBConnection, DEFAULT_FIND_LIMIT};
+use super::mongodb_documents::{to_extended_json, ExtendedJsonMode};
+use mongodb::bson::{doc, Bson, Document};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// 不能作为隐式别名的关键字
+const RESERVED: [&str; 27] = [
+    "SELECT", "DISTINCT", "FROM", "WHERE", "GROUP", "BY", "HAVING", "ORDER", "LIMIT", "OFFSET", "JOIN", "INNER",
+    "LEFT", "OUTER", "ON", "AS", "AND", "OR", "NOT", "IN", "IS", "NULL", "LIKE", "ILIKE", "BETWEEN", "ASC", "DESC",
+];
+
+const AGGREGATE_FUNCTIONS: [&str; 5] = ["COUNT", "SUM", "AVG", "MIN", "MAX"];
+
+/// 翻译结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqlTranslation {
+    pub collection: String,
+    /// find | aggregate
+    pub operation: String,
+    pub filter: Option<serde_json::Value>,
+    pub projection: Option<serde_json::Value>,
+    pub sort: Option<serde_json::Value>,
+    pub skip: Option<u64>,
+    pub limit: Option<i64>,
+    pub pipeline: Option<Vec<serde_json::Value>>,
+    /// 可以直接在编辑器中执行的 mongosh 语句
+    pub shell: String,
+    pub warnings: Vec<String>,
+}
+
+impl MongoDBConnection {
+    /// 翻译并执行 SQL，结果与编辑器中执行生成的 mongosh 语句相同
+    pub async fn query_sql(&self, sql: &str, mode: ExtendedJsonMode) -> anyhow::Result<EnhancedQueryResult> {
+        let translation = translate_sql(sql)?;
+        let mut result = self.query_documents(&translation.shell, mode).await?;
+        result.metadata.warnings.extend(translation.warnings);
+        Ok(result)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 词法分析
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    /// "名称" 或 `名称`，总是作为标识符
+    Quoted(String),
+    Str(String),
+    Number(String),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: [&str; 15] = ["<=", ">=", "<>", "!=", "=", "<", ">", ",", "(", ")", ".", "*", ";", "-", "+"];
+
+fn tokenize(sql: &str) -> anyhow::Result<Vec<Token>> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let c = chars[pos];
+        if c.is_whitespace() {
+            pos += 1;
+        } else if c == '-' && chars.get(pos + 1) == Some(&'-') {
+            while pos < chars.len() && chars[pos] != '\n' {
+                pos += 1;
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_' || chars[pos] == '$') {
+                pos += 1;
+            }
+            tokens.push(Token::Ident(chars[start..pos].iter().collect()));
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(pos + 1).is_some_and(char::is_ascii_digit)) {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                pos += 1;
+            }
+            if pos < chars.len() && matches!(chars[pos], 'e' | 'E') {
+                pos += 1;
+                if pos < chars.len() && matches!(chars[pos], '+' | '-') {
+                    pos += 1;
+                }
+                while pos < chars.len() && chars[pos].is_ascii_digit() {
+                    pos += 1;
+                }
+            }
+            tokens.push(Token::Number(chars[start..pos].iter().collect()));
+        } else if c == '\'' || c == '"' || c == '`' {
+            let mut text = String::new();
+            pos += 1;
+            loop {
+                match chars.get(pos) {
+                    None => return Err(anyhow::anyhow!("字符串或标识符缺少结束的 {}", c)),
+                    // 连续两个引号表示引号本身
+                    Some(&q) if q == c && chars.get(pos + 1) == Some(&c) => {
+                        text.push(c);
+                        pos += 2;
+                    }
+                    Some(&q) if q == c => {
+                        pos += 1;
+                        break;
+                    }
+                    Some(&other) => {
+                        text.push(other);
+                        pos += 1;
+                    }
+                }
+            }
+            tokens.push(if c == '\'' { Token::Str(text) } else { Token::Quoted(text) });
+        } else {
+            let rest: String = chars[pos..chars.len().min(pos + 2)].iter().collect();
+            let symbol = SYMBOLS.iter()
+                .find(|symbol| rest.starts_with(**symbol))
+                .ok_or_else(|| anyhow::anyhow!("第 {} 个字符处无法识别的字符 '{}'", pos + 1, c))?;
+            tokens.push(Token::Symbol(symbol));
+            pos += symbol.len();
+        }
+    }
+    Ok(tokens)
+}
+
+// ---------------------------------------------------------------------------
+// 语法分析
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+impl CompareOp {
+    fn operator(self) -> &'static str {
+        match self {
+            CompareOp::Eq => "$eq",
+            CompareOp::Ne => "$ne",
+            CompareOp::Lt => "$lt",
+            CompareOp::Lte => "$lte",
+            CompareOp::Gt => "$gt",
+            CompareOp::Gte => "$gte",
+        }
+    }
+
+    /// 交换左右两边后的运算符
+    fn flip(self) -> Self {
+        match self {
+            CompareOp::Lt => CompareOp::Gt,
+            CompareOp::Lte => CompareOp::Gte,
+            CompareOp::Gt => CompareOp::Lt,
+            CompareOp::Gte => CompareOp::Lte,
+            other => other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Column(String),
+    Literal(Bson),
+    /// 聚合函数，arg 为 None 表示 COUNT(*)
+    Function { name: String, arg: Option<String> },
+    Compare { left: Box<Expr>, op: CompareOp, right: Box<Expr> },
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+    Not(Box<Expr>),
+    IsNull { expr: Box<Expr>, negated: bool },
+    In { expr: Box<Expr>, values: Vec<Bson>, negated: bool },
+    Like { expr: Box<Expr>, pattern: String, negated: bool, case_insensitive: bool },
+    /// 上下界装箱，避免整个 Expr 变大
+    Between { expr: Box<Expr>, bounds: Box<(Bson, Bson)>, negated: bool },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SelectItem {
+    Star,
+    /// t.*
+    TableStar(String),
+    Expr { expr: Expr, alias: Option<String> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct TableRef {
+    name: String,
+    alias: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Join {
+    table: TableRef,
+    left: bool,
+    on: (String, String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Select {
+    distinct: bool,
+    items: Vec<SelectItem>,
+    from: TableRef,
+    joins: Vec<Join>,
+    filter: Option<Expr>,
+    group_by: Vec<String>,
+    having: Option<Expr>,
+    order_by: Vec<(Expr, bool)>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+struct SqlParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl SqlParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+        matches!(token, Some(Token::Ident(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = Self::is_keyword(self.peek(), keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> anyhow::Result<()> {
+        if self.keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("应为 {}", keyword)))
+        }
+    }
+
+    fn symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> anyhow::Result<()> {
+        if self.symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("应为 '{}'", symbol)))
+        }
+    }
+
+    fn error(&self, message: &str) -> anyhow::Error {
+        let near = match self.peek() {
+            None => "语句末尾".to_string(),
+            Some(Token::Ident(word)) | Some(Token::Quoted(word)) | Some(Token::Number(word)) => format!("'{}' 附近", word),
+            Some(Token::Str(text)) => format!("'{}' 附近", text),
+            Some(Token::Symbol(symbol)) => format!("'{}' 附近", symbol),
+        };
+        anyhow::anyhow!("SQL 语法错误（{}）：{}", near, message)
+    }
+
+    fn is_reserved(word: &str) -> bool {
+        RESERVED.iter().any(|keyword| keyword.eq_ignore_ascii_case(word))
+    }
+
+    fn name(&mut self) -> anyhow::Result<String> {
+        match self.peek().cloned() {
+            Some(Token::Ident(word)) if !Self::is_reserved(&word) => {
+                self.pos += 1;
+                Ok(word)
+            }
+            Some(Token::Quoted(word)) if !word.is_empty() => {
+                self.pos += 1;
+                Ok(word)
+            }
+            _ => Err(self.error("应为名称")),
+        }
+    }
+
+    /// 列名，可以是 a.b.c 形式的路径
+    fn column(&mut self) -> anyhow::Result<String> {
+        let mut parts = vec![self.name()?];
+        while matches!(self.peek(), Some(Token::Symbol("."))) && !matches!(self.peek_at(1), Some(Token::Symbol("*"))) {
+            self.pos += 1;
+            parts.push(self.name()?);
+        }
+        Ok(parts.join("."))
+    }
+
+    /// AS 别名或隐式别名
+    fn alias(&mut self) -> anyhow::Result<Option<String>> {
+        if self.keyword("AS") {
+            return self.name().map(Some);
+        }
+        match self.peek() {
+            Some(Token::Ident(word)) if !Self::is_reserved(word) => self.name().map(Some),
+            Some(Token::Quoted(_)) => self.name().map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn select(&mut self) -> anyhow::Result<Select> {
+        if !self.keyword("SELECT") {
+            return Err(anyhow::anyhow!("只支持 SELECT 语句"));
+        }
+        let distinct = self.keyword("DISTINCT");
+        let mut items = Vec::new();
+        loop {
+            items.push(self.select_item()?);
+            if !self.symbol(",") {
+                break;
+            }
+        }
+
+        self.expect_keyword("FROM")?;
+        let from = self.table()?;
+        let mut joins = Vec::new();
+        loop {
+            let left = if self.keyword("LEFT") {
+                self.keyword("OUTER");
+                true
+            } else {
+                self.keyword("INNER");
+                false
+            };
+            if !self.keyword("JOIN") {
+                if left || Self::is_keyword(self.tokens.get(self.pos.wrapping_sub(1)), "INNER") {
+                    return Err(self.error("应为 JOIN"));
+                }
+                break;
+            }
+            let table = self.table()?;
+            self.expect_keyword("ON")?;
+            let first = self.column()?;
+            self.expect_symbol("=")?;
+            let second = self.column()?;
+            joins.push(Join { table, left, on: (first, second) });
+        }
+
+        let filter = if self.keyword("WHERE") { Some(self.or_expr()?) } else { None };
+        let mut group_by = Vec::new();
+        if self.keyword("GROUP") {
+            self.expect_keyword("BY")?;
+            loop {
+                group_by.push(self.column()?);
+                if !self.symbol(",") {
+                    break;
+                }
+            }
+        }
+        let having = if self.keyword("HAVING") { Some(self.or_expr()?) } else { None };
+        let mut order_by = Vec::new();
+        if self.keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let expr = self.operand()?;
+                if matches!(expr, Expr::Literal(_)) {
+                    return Err(self.error("ORDER BY 不支持按位置或常量排序"));
+                }
+                let ascending = !self.keyword("DESC");
+                if ascending {
+                    self.keyword("ASC");
+                }
+                order_by.push((expr, ascending));
+                if !self.symbol(",") {
+                    break;
+                }
+            }
+        }
+        let mut limit = None;
+        let mut offset = None;
+        if self.keyword("LIMIT") {
+            limit = Some(self.count()?);
+            if self.keyword("OFFSET") {
+                offset = Some(self.count()?);
+            } else if self.symbol(",") {
+                // MySQL 的 LIMIT 偏移, 条数
+                offset = limit;
+                limit = Some(self.count()?);
+            }
+        }
+        if offset.is_none() && self.keyword("OFFSET") {
+            offset = Some(self.count()?);
+        }
+        while self.symbol(";") {}
+        if self.peek().is_some() {
+            return Err(self.error("语句末尾有多余内容"));
+        }
+
+        Ok(Select { distinct, items, from, joins, filter, group_by, having, order_by, limit, offset })
+    }
+
+    fn count(&mut self) -> anyhow::Result<i64> {
+        match self.peek().cloned() {
+            Some(Token::Number(number)) => {
+                self.pos += 1;
+                number.parse::<i64>().map_err(|_| anyhow::anyhow!("LIMIT 和 OFFSET 必须是非负整数"))
+            }
+            _ => Err(self.error("应为非负整数")),
+        }
+    }
+
+    fn table(&mut self) -> anyhow::Result<TableRef> {
+        let name = self.column()?;
+        let alias = self.alias()?.unwrap_or_else(|| name.clone());
+        Ok(TableRef { name, alias })
+    }
+
+    fn select_item(&mut self) -> anyhow::Result<SelectItem> {
+        if self.symbol("*") {
+            return Ok(SelectItem::Star);
+        }
+        if matches!(self.peek_at(1), Some(Token::Symbol("."))) && matches!(self.peek_at(2), Some(Token::Symbol("*"))) {
+            let table = self.name()?;
+            self.pos += 2;
+            return Ok(SelectItem::TableStar(table));
+        }
+        let expr = self.operand()?;
+        if matches!(expr, Expr::Literal(_)) {
+            return Err(self.error("SELECT 列表不支持常量"));
+        }
+        Ok(SelectItem::Expr { expr, alias: self.alias()? })
+    }
+
+    fn or_expr(&mut self) -> anyhow::Result<Expr> {
+        let mut parts = vec![self.and_expr()?];
+        while self.keyword("OR") {
+            parts.push(self.and_expr()?);
+        }
+        Ok(if parts.len() == 1 { parts.remove(0) } else { Expr::Or(parts) })
+    }
+
+    fn and_expr(&mut self) -> anyhow::Result<Expr> {
+        let mut parts = vec![self.not_expr()?];
+        while self.keyword("AND") {
+            parts.push(self.not_expr()?);
+        }
+        Ok(if parts.len() == 1 { parts.remove(0) } else { Expr::And(parts) })
+    }
+
+    fn not_expr(&mut self) -> anyhow::Result<Expr> {
+        if self.keyword("NOT") {
+            return Ok(Expr::Not(Box::new(self.not_expr()?)));
+        }
+        self.predicate()
+    }
+
+    fn predicate(&mut self) -> anyhow::Result<Expr> {
+        if self.symbol("(") {
+            let expr = self.or_expr()?;
+            self.expect_symbol(")")?;
+            return Ok(expr);
+        }
+
+        let left = Box::new(self.operand()?);
+        let op = match self.peek() {
+            Some(Token::Symbol("=")) => Some(CompareOp::Eq),
+            Some(Token::Symbol("!=")) | Some(Token::Symbol("<>")) => Some(CompareOp::Ne),
+            Some(Token::Symbol("<")) => Some(CompareOp::Lt),
+            Some(Token::Symbol("<=")) => Some(CompareOp::Lte),
+            Some(Token::Symbol(">")) => Some(CompareOp::Gt),
+            Some(Token::Symbol(">=")) => Some(CompareOp::Gte),
+            _ => None,
+        };
+        if let Some(op) = op {
+            self.pos += 1;
+            return Ok(Expr::Compare { left, op, right: Box::new(self.operand()?) });
+        }
+        if self.keyword("IS") {
+            let negated = self.keyword("NOT");
+            self.expect_keyword("NULL")?;
+            return Ok(Expr::IsNull { expr: left, negated });
+        }
+
+        let negated = self.keyword("NOT");
+        if self.keyword("IN") {
+            self.expect_symbol("(")?;
+            let mut values = Vec::new();
+            loop {
+                values.push(self.literal()?);
+                if !self.symbol(",") {
+                    break;
+                }
+            }
+            self.expect_symbol(")")?;
+            return Ok(Expr::In { expr: left, values, negated });
+        }
+        let like = self.keyword("LIKE");
+        if like || self.keyword("ILIKE") {
+            let pattern = match self.literal()? {
+                Bson::String(pattern) => pattern,
+                _ => return Err(self.error("LIKE 的模式必须是字符串")),
+            };
+            return Ok(Expr::Like { expr: left, pattern, negated, case_insensitive: !like });
+        }
+        if self.keyword("BETWEEN") {
+            let low = self.literal()?;
+            self.expect_keyword("AND")?;
+            let high = self.literal()?;
+            return Ok(Expr::Between { expr: left, bounds: Box::new((low, high)), negated });
+        }
+        Err(self.error("应为比较运算符、IS、IN、LIKE 或 BETWEEN"))
+    }
+
+    fn operand(&mut self) -> anyhow::Result<Expr> {
+        if let Some(Token::Ident(word)) = self.peek() {
+            let upper = word.to_ascii_uppercase();
+            if AGGREGATE_FUNCTIONS.contains(&upper.as_str()) && matches!(self.peek_at(1), Some(Token::Symbol("("))) {
+                self.pos += 2;
+                if self.keyword("DISTINCT") {
+                    return Err(anyhow::anyhow!("暂不支持 {}(DISTINCT ...)", upper));
+                }
+                let arg = if self.symbol("*") {
+                    if upper != "COUNT" {
+                        return Err(anyhow::anyhow!("只有 COUNT 可以使用 *"));
+                    }
+                    None
+                } else {
+                    Some(self.column()?)
+                };
+                self.expect_symbol(")")?;
+                return Ok(Expr::Function { name: upper, arg });
+            }
+        }
+        match self.peek() {
+            Some(Token::Ident(word)) if ["TRUE", "FALSE", "NULL"].iter().any(|k| k.eq_ignore_ascii_case(word)) => {
+                self.literal().map(Expr::Literal)
+            }
+            Some(Token::Ident(_)) | Some(Token::Quoted(_)) => self.column().map(Expr::Column),
+            _ => self.literal().map(Expr::Literal),
+        }
+    }
+
+    fn literal(&mut self) -> anyhow::Result<Bson> {
+        let negative = self.symbol("-");
+        if !negative {
+            self.symbol("+");
+        }
+        let value = match self.peek().cloned() {
+            Some(Token::Number(number)) => {
+                let text = if negative { format!("-{}", number) } else { number };
+                if text.contains(['.', 'e', 'E']) {
+                    Bson::Double(text.parse().map_err(|_| anyhow::anyhow!("无效的数字: {}", text))?)
+                } else {
+                    let value: i64 = text.parse().map_err(|_| anyhow::anyhow!("无效的整数: {}", text))?;
+                    match i32::try_from(value) {
+                        Ok(value) => Bson::Int32(value),
+                        Err(_) => Bson::Int64(value),
+                    }
+                }
+            }
+            _ if negative => return Err(self.error("负号之后应为数字")),
+            Some(Token::Str(text)) => Bson::String(text),
+            Some(Token::Ident(word)) if word.eq_ignore_ascii_case("TRUE") => Bson::Boolean(true),
+            Some(Token::Ident(word)) if word.eq_ignore_ascii_case("FALSE") => Bson::Boolean(false),
+            Some(Token::Ident(word)) if word.eq_ignore_ascii_case("NULL") => Bson::Null,
+            _ => return Err(self.error("应为常量")),
+        };
+        self.pos += 1;
+        Ok(value)
+    }
+}
+
+fn parse_select(sql: &str) -> anyhow::Result<Select> {
+    let tokens = tokenize(sql)?;
+    if tokens.is_empty() {
+        return Err(anyhow::anyhow!("SQL 语句为空"));
+    }
+    SqlParser { tokens, pos: 0 }.select()
+}
+
+// ---------------------------------------------------------------------------
+// 翻译
+
+/// 分组键：字段路径和 $group 中的键名
+struct GroupKey {
+    path: String,
+    name: String,
+}
+
+/// 累加器：输出名、函数和参数字段
+struct Accumulator {
+    name: String,
+    function: String,
+    path: Option<String>,
+}
+
+struct Translator<'a> {
+    select: &'a Select,
+    join_aliases: Vec<String>,
+    keys: Vec<GroupKey>,
+    accumulators: Vec<Accumulator>,
+    warnings: Vec<String>,
+}
+
+impl<'a> Translator<'a> {
+    fn new(select: &'a Select) -> Self {
+        Self {
+            select,
+            join_aliases: select.joins.iter().map(|join| join.table.alias.clone()).collect(),
+            keys: Vec::new(),
+            accumulators: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// 列名对应的字段路径：去掉主表前缀，连接表的字段保留别名前缀（$lookup 的 as）
+    fn path(&self, column: &str) -> String {
+        match column.split_once('.') {
+            Some((head, rest)) if head == self.select.from.alias || head == self.select.from.name => rest.to_string(),
+            _ => column.to_string(),
+        }
+    }
+
+    fn is_join_column(&self, column: &str) -> bool {
+        column.split_once('.').is_some_and(|(head, _)| self.join_aliases.iter().any(|alias| alias == head))
+    }
+
+    fn references_join(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Column(column) => self.is_join_column(column),
+            Expr::Compare { left, right, .. } => self.references_join(left) || self.references_join(right),
+            Expr::And(parts) | Expr::Or(parts) => parts.iter().any(|part| self.references_join(part)),
+            Expr::Not(inner) => self.references_join(inner),
+            Expr::IsNull { expr, .. } | Expr::In { expr, .. } | Expr::Like { expr, .. } | Expr::Between { expr, .. } => {
+                self.references_join(expr)
+            }
+            Expr::Literal(_) | Expr::Function { .. } => false,
+        }
+    }
+
+    fn has_aggregates(&self) -> bool {
+        self.select.items.iter().any(|item| matches!(item, SelectItem::Expr { expr: Expr::Function { .. }, .. }))
+            || self.select.having.is_some()
+    }
+
+    fn grouped(&self) -> bool {
+        !self.select.group_by.is_empty() || self.has_aggregates() || self.select.distinct
+    }
+
+    /// SELECT 中的别名对应的表达式
+    fn aliased(&self, name: &str) -> Option<&'a Expr> {
+        self.select.items.iter().find_map(|item| match item {
+            SelectItem::Expr { expr, alias: Some(alias) } if alias == name => Some(expr),
+            _ => None,
+        })
+    }
+
+    /// WHERE 和未分组时 ORDER BY 中的字段
+    fn source_field(&self, expr: &Expr) -> anyhow::Result<Option<String>> {
+        match expr {
+            Expr::Column(column) => Ok(Some(self.path(column))),
+            Expr::Literal(_) => Ok(None),
+            Expr::Function { .. } => Err(anyhow::anyhow!("WHERE 中不能使用聚合函数，请使用 HAVING")),
+            _ => Err(anyhow::anyhow!("不支持的表达式")),
+        }
+    }
+
+    /// 分组后（HAVING、ORDER BY）的字段：分组键在 _id 中，聚合函数为累加器
+    fn grouped_field(&mut self, expr: &Expr) -> anyhow::Result<Option<String>> {
+        match expr {
+            Expr::Literal(_) => Ok(None),
+            Expr::Function { name, arg } => {
+                let path = arg.as_deref().map(|arg| self.path(arg));
+                Ok(Some(self.accumulator(name, path, None)))
+            }
+            Expr::Column(column) => {
+                if let Some(expr) = self.aliased(column) {
+                    if expr != &Expr::Column(column.clone()) {
+                        return self.grouped_field(expr);
+                    }
+                }
+                let path = self.path(column);
+                match self.keys.iter().position(|key| key.path == path) {
+                    Some(index) => Ok(Some(self.key_reference(index).trim_start_matches('$').to_string())),
+                    None => Err(anyhow::anyhow!("列 {} 必须出现在 GROUP BY 中或使用聚合函数", column)),
+                }
+            }
+            _ => Err(anyhow::anyhow!("不支持的表达式")),
+        }
+    }
+
+    fn key_reference(&self, index: usize) -> String {
+        if self.keys.len() == 1 {
+            "$_id".to_string()
+        } else {
+            format!("$_id.{}", self.keys[index].name)
+        }
+    }
+
+    /// 找到或添加累加器，返回其输出名
+    fn accumulator(&mut self, function: &str, path: Option<String>, alias: Option<&str>) -> String {
+        if alias.is_none() {
+            if let Some(existing) = self.accumulators.iter().find(|acc| acc.function == function && acc.path == path) {
+                return existing.name.clone();
+            }
+        }
+        let base = alias.map(str::to_string).unwrap_or_else(|| match &path {
+            Some(path) => format!("{}_{}", function.to_ascii_lowercase(), path.replace('.', "_")),
+            None => function.to_ascii_lowercase(),
+        });
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while self.accumulators.iter().any(|acc| acc.name == name) || self.keys.iter().any(|key| key.name == name) {
+            name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        self.accumulators.push(Accumulator { name: name.clone(), function: function.to_string(), path });
+        name
+    }
+
+    fn condition(&mut self, expr: &Expr, grouped: bool) -> anyhow::Result<Document> {
+        let field = |this: &mut Self, expr: &Expr| -> anyhow::Result<String> {
+            let field = if grouped { this.grouped_field(expr)? } else { this.source_field(expr)? };
+            field.ok_or_else(|| anyhow::anyhow!("条件的左边必须是列"))
+        };
+        Ok(match expr {
+            Expr::And(parts) => {
+                let parts = parts.iter()
+                    .map(|part| self.condition(part, grouped))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                and(parts)
+            }
+            Expr::Or(parts) => {
+                let parts = parts.iter()
+                    .map(|part| self.condition(part, grouped).map(Bson::Document))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                doc! { "$or": parts }
+            }
+            Expr::Not(inner) => doc! { "$nor": [self.condition(inner, grouped)?] },
+            Expr::Compare { left, op, right } => {
+                let (left_field, right_field) = if grouped {
+                    (self.grouped_field(left)?, self.grouped_field(right)?)
+                } else {
+                    (self.source_field(left)?, self.source_field(right)?)
+                };
+                match (left_field, right_field) {
+                    (Some(field), None) => comparison(field, *op, literal(right)),
+                    (None, Some(field)) => comparison(field, op.flip(), literal(left)),
+                    (Some(left), Some(right)) => doc! {
+                        "$expr": { op.operator(): [format!("${}", left), format!("${}", right)] },
+                    },
+                    (None, None) => return Err(anyhow::anyhow!("比较的两边不能都是常量")),
+                }
+            }
+            Expr::IsNull { expr, negated } => {
+                let field = field(self, expr)?;
+                if *negated {
+                    doc! { field: { "$ne": Bson::Null } }
+                } else {
+                    doc! { field: Bson::Null }
+                }
+            }
+            Expr::In { expr, values, negated } => {
+                let field = field(self, expr)?;
+                doc! { field: { if *negated { "$nin" } else { "$in" }: values.clone() } }
+            }
+            Expr::Like { expr, pattern, negated, case_insensitive } => {
+                let field = field(self, expr)?;
+                let mut regex = doc! { "$regex": like_regex(pattern) };
+                if *case_insensitive {
+                    regex.insert("$options", "i");
+                }
+                let matched = doc! { field: regex };
+                if *negated { doc! { "$nor": [matched] } } else { matched }
+            }
+            Expr::Between { expr, bounds, negated } => {
+                let field = field(self, expr)?;
+                let (low, high) = bounds.as_ref().clone();
+                let range = doc! { field: { "$gte": low, "$lte": high } };
+                if *negated { doc! { "$nor": [range] } } else { range }
+            }
+            Expr::Column(_) | Expr::Literal(_) | Expr::Function { .. } => {
+                return Err(anyhow::anyhow!("条件必须是比较表达式"));
+            }
+        })
+    }
+
+    fn lookup_stages(&self) -> anyhow::Result<Vec<Document>> {
+        let mut stages = Vec::new();
+        for join in &self.select.joins {
+            let alias = &join.table.alias;
+            let foreign = |column: &str| column.strip_prefix(alias.as_str()).and_then(|rest| rest.strip_prefix('.'))
+                .map(str::to_string);
+            let (local, foreign) = match (foreign(&join.on.0), foreign(&join.on.1)) {
+                (None, Some(field)) => (self.path(&join.on.0), field),
+                (Some(field), None) => (self.path(&join.on.1), field),
+                _ => return Err(anyhow::anyhow!(
+                    "JOIN {} 的 ON 条件必须一边是 {}.字段，另一边是之前的表的字段", join.table.name, alias
+                )),
+            };
+            stages.push(doc! {
+                "$lookup": { "from": join.table.name.as_str(), "localField": local, "foreignField": foreign, "as": alias.as_str() },
+            });
+            stages.push(doc! {
+                "$unwind": { "path": format!("${}", alias), "preserveNullAndEmptyArrays": join.left },
+            });
+        }
+        Ok(stages)
+    }
+
+    /// 未分组查询的投影，选择了 * 时返回 None
+    fn projection(&mut self) -> anyhow::Result<Option<Document>> {
+        let mut projection = Document::new();
+        let mut star = false;
+        for item in &self.select.items {
+            match item {
+                SelectItem::Star => star = true,
+                SelectItem::TableStar(table) if *table == self.select.from.alias || *table == self.select.from.name => {
+                    star = true;
+                }
+                SelectItem::TableStar(table) if self.join_aliases.contains(table) => {
+                    projection.insert(table.as_str(), 1);
+                }
+                SelectItem::TableStar(table) => return Err(anyhow::anyhow!("未知的表 {}", table)),
+                SelectItem::Expr { expr: Expr::Column(column), alias } => {
+                    let path = self.path(column);
+                    match alias {
+                        Some(alias) if *alias != path => projection.insert(alias.as_str(), format!("${}", path)),
+                        _ => projection.insert(path, 1),
+                    };
+                }
+                SelectItem::Expr { .. } => return Err(anyhow::anyhow!("SELECT 列表只支持列和聚合函数")),
+            }
+        }
+        if star {
+            if !projection.is_empty() {
+                self.warnings.push("选择了 * 时返回完整文档，其余列不会单独投影".to_string());
+            }
+            return Ok(None);
+        }
+        if !projection.contains_key("_id") {
+            projection.insert("_id", 0);
+        }
+        Ok(Some(projection))
+    }
+
+    /// 未分组时的排序，别名还原为原字段
+    fn source_sort(&self) -> anyhow::Result<Option<Document>> {
+        if self.select.order_by.is_empty() {
+            return Ok(None);
+        }
+        let mut sort = Document::new();
+        for (expr, ascending) in &self.select.order_by {
+            let expr = match expr {
+                Expr::Column(column) => self.aliased(column).unwrap_or(expr),
+                other => other,
+            };
+            let field = self.source_field(expr)?
+                .ok_or_else(|| anyhow::anyhow!("ORDER BY 必须是列"))?;
+            sort.insert(field, if *ascending { 1 } else { -1 });
+        }
+        Ok(Some(sort))
+    }
+
+    fn group_stages(&mut self) -> anyhow::Result<Vec<Document>> {
+        let select = self.select;
+        if select.distinct && self.has_aggregates() {
+            return Err(anyhow::anyhow!("DISTINCT 不能与聚合函数同时使用"));
+        }
+
+        let key_columns: Vec<String> = if select.distinct && select.group_by.is_empty() {
+            select.items.iter()
+                .map(|item| match item {
+                    SelectItem::Expr { expr: Expr::Column(column), .. } => Ok(column.clone()),
+                    _ => Err(anyhow::anyhow!("DISTINCT 只支持列")),
+                })
+                .collect::<anyhow::Result<_>>()?
+        } else {
+            select.group_by.clone()
+        };
+        for column in key_columns {
+            let path = self.path(&column);
+            if self.keys.iter().any(|key| key.path == path) {
+                continue;
+            }
+            let name = path.replace('.', "_");
+            self.keys.push(GroupKey { path, name });
+        }
+
+        // 先按 SELECT 顺序登记输出，HAVING 和 ORDER BY 可能追加隐藏的累加器
+        let mut outputs: Vec<(String, String)> = Vec::new();
+        for item in &select.items {
+            match item {
+                SelectItem::Expr { expr: Expr::Function { name, arg }, alias } => {
+                    let path = arg.as_deref().map(|arg| self.path(arg));
+                    let output = self.accumulator(name, path, alias.as_deref());
+                    outputs.push((output.clone(), format!("${}", output)));
+                }
+                SelectItem::Expr { expr: Expr::Column(column), alias } => {
+                    let path = self.path(column);
+                    let index = self.keys.iter().position(|key| key.path == path)
+                        .ok_or_else(|| anyhow::anyhow!("列 {} 必须出现在 GROUP BY 中或使用聚合函数", column))?;
+                    outputs.push((alias.clone().unwrap_or(path), self.key_reference(index)));
+                }
+                _ => return Err(anyhow::anyhow!("分组查询不能选择 *")),
+            }
+        }
+
+        let having = match &select.having {
+            Some(having) => Some(self.condition(having, true)?),
+            None => None,
+        };
+        let mut sort = Document::new();
+        for (expr, ascending) in &select.order_by {
+            // 排序在投影之前，先把输出别名还原为分组字段
+            let field = match expr {
+                Expr::Column(column) => match outputs.iter().find(|(name, _)| name == column) {
+                    Some((_, reference)) => reference.trim_start_matches('$').to_string(),
+                    None => self.grouped_field(expr)?.unwrap_or_default(),
+                },
+                _ => self.grouped_field(expr)?.unwrap_or_default(),
+            };
+            sort.insert(field, if *ascending { 1 } else { -1 });
+        }
+
+        let id = match self.keys.as_slice() {
+            [] => Bson::Null,
+            [key] => Bson::String(format!("${}", key.path)),
+            keys => Bson::Document(keys.iter().map(|key| (key.name.clone(), Bson::String(format!("${}", key.path)))).collect()),
+        };
+        let mut group = doc! { "_id": id };
+        for acc in &self.accumulators {
+            group.insert(acc.name.as_str(), accumulator_expression(&acc.function, acc.path.as_deref()));
+        }
+
+        let mut stages = vec![doc! { "$group": group }];
+        if let Some(having) = having {
+            stages.push(doc! { "$match": having });
+        }
+        if !sort.is_empty() {
+            stages.push(doc! { "$sort": sort });
+        }
+        stages.extend(self.page_stages());
+
+        let mut projection = Document::new();
+        if !outputs.iter().any(|(name, _)| name == "_id") {
+            projection.insert("_id", 0);
+        }
+        for (name, reference) in outputs {
+            projection.insert(name, reference);
+        }
+        stages.push(doc! { "$project": projection });
+        Ok(stages)
+    }
+
+    fn page_stages(&self) -> Vec<Document> {
+        let mut stages = Vec::new();
+        if let Some(offset) = self.select.offset.filter(|offset| *offset > 0) {
+            stages.push(doc! { "$skip": offset });
+        }
+        // 与 find 一样限制默认条数，避免 JOIN 或分组结果整个载入内存
+        stages.push(doc! { "$limit": self.select.limit.unwrap_or(DEFAULT_FIND_LIMIT) });
+        stages
+    }
+
+    fn uses_aliases(&self) -> bool {
+        self.select.items.iter().any(|item| match item {
+            SelectItem::Expr { expr: Expr::Column(column), alias: Some(alias) } => *alias != self.path(column),
+            _ => false,
+        })
+    }
+
+    fn translate(mut self) -> anyhow::Result<SqlTranslation> {
+        let select = self.select;
+        if select.limit == Some(0) {
+            return Err(anyhow::anyhow!("LIMIT 必须大于 0"));
+        }
+        let collection = select.from.name.clone();
+        if select.limit.is_none() {
+            self.warnings.push(format!("未指定 LIMIT，默认只返回前 {} 条", DEFAULT_FIND_LIMIT));
+        }
+
+        if select.joins.is_empty() && !self.grouped() && !self.uses_aliases() {
+            let filter = match &select.filter {
+                Some(filter) => self.condition(filter, false)?,
+                None => Document::new(),
+            };
+            let projection = self.projection()?;
+            let sort = self.source_sort()?;
+            let skip = select.offset.filter(|offset| *offset > 0).map(|offset| offset as u64);
+
+            let mut shell = format!("{}.find({}", collection_reference(&collection), shell_json(&filter));
+            if let Some(projection) = &projection {
+                shell.push_str(&format!(", {}", shell_json(projection)));
+            }
+            shell.push(')');
+            if let Some(sort) = &sort {
+                shell.push_str(&format!(".sort({})", shell_json(sort)));
+            }
+            if let Some(skip) = skip {
+                shell.push_str(&format!(".skip({})", skip));
+            }
+            if let Some(limit) = select.limit {
+                shell.push_str(&format!(".limit({})", limit));
+            }
+
+            return Ok(SqlTranslation {
+                collection,
+                operation: "find".to_string(),
+                filter: Some(json(filter)),
+                projection: projection.map(json),
+                sort: sort.map(json),
+                skip,
+                limit: select.limit,
+                pipeline: None,
+                shell,
+                warnings: self.warnings,
+            });
+        }
+
+        let mut pipeline = Vec::new();
+        let filter = match &select.filter {
+            Some(filter) => Some((self.references_join(filter), self.condition(filter, false)?)),
+            None => None,
+        };
+        // 只涉及主表的条件放在 $lookup 之前，可以使用索引
+        if let Some((false, filter)) = &filter {
+            pipeline.push(doc! { "$match": filter.clone() });
+        }
+        pipeline.extend(self.lookup_stages()?);
+        if let Some((true, filter)) = &filter {
+            pipeline.push(doc! { "$match": filter.clone() });
+        }
+
+        if self.grouped() {
+            pipeline.extend(self.group_stages()?);
+        } else {
+            if let Some(sort) = self.source_sort()? {
+                pipeline.push(doc! { "$sort": sort });
+            }
+            pipeline.extend(self.page_stages());
+            if let Some(projection) = self.projection()? {
+                pipeline.push(doc! { "$project": projection });
+            }
+        }
+
+        let shell = format!(
+            "{}.aggregate({})",
+            collection_reference(&collection),
+            serde_json::to_string(&pipeline.iter().cloned().map(json).collect::<Vec<_>>())?,
+        );
+        Ok(SqlTranslation {
+            collection,
+            operation: "aggregate".to_string(),
+            filter: None,
+            projection: None,
+            sort: None,
+            skip: None,
+            limit: None,
+            pipeline: Some(pipeline.into_iter().map(json).collect()),
+            shell,
+            warnings: self.warnings,
+        })
+    }
+}
+
+/// 把 SQL SELECT 翻译为 find 或 aggregate
+pub fn translate_sql(sql: &str) -> anyhow::Result<SqlTranslation> {
+    let select = parse_select(sql)?;
+    Translator::new(&select).translate()
+}
+
+fn literal(expr: &Expr) -> Bson {
+    match expr {
+        Expr::Literal(value) => value.clone(),
+        _ => Bson::Null,
+    }
+}
+
+fn comparison(field: String, op: CompareOp, value: Bson) -> Document {
+    match op {
+        CompareOp::Eq => doc! { field: value },
+        op => doc! { field: { op.operator(): value } },
+    }
+}
+
+/// 多个条件的键互不相同时合并为一个文档，否则使用 $and
+fn and(parts: Vec<Document>) -> Document {
+    let total: usize = parts.iter().map(Document::len).sum();
+    let mut merged = Document::new();
+    for part in &parts {
+        merged.extend(part.clone());
+    }
+    if merged.len() == total {
+        merged
+    } else {
+        doc! { "$and": parts }
+    }
+}
+
+/// LIKE 模式转换为锚定的正则：% 匹配任意串，_ 匹配单个字符
+fn like_regex(pattern: &str) -> String {
+    let mut regex = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+fn accumulator_expression(function: &str, path: Option<&str>) -> Document {
+    let field = path.map(|path| format!("${}", path));
+    match (function, field) {
+        ("COUNT", None) => doc! { "$sum": 1 },
+        // COUNT(列) 只统计非空值
+        ("COUNT", Some(field)) => doc! {
+            "$sum": { "$cond": [{ "$eq": [{ "$ifNull": [field, Bson::Null] }, Bson::Null] }, 0, 1] },
+        },
+        ("SUM", Some(field)) => doc! { "$sum": field },
+        ("AVG", Some(field)) => doc! { "$avg": field },
+        ("MIN", Some(field)) => doc! { "$min": field },
+        (_, field) => doc! { "$max": field.map_or(Bson::Null, Bson::String) },
+    }
+}
+
+fn collection_reference(collection: &str) -> String {
+    let mut chars = collection.chars();
+    let simple = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if simple {
+        format!("db.{}", collection)
+    } else {
+        format!("db.getCollection({})", serde_json::Value::String(collection.to_string()))
+    }
+}
+
+fn json(document: Document) -> serde_json::Value {
+    to_extended_json(document, ExtendedJsonMode::Relaxed)
+}
+
+fn shell_json(document: &Document) -> String {
+    json(document.clone()).to_string()
+}
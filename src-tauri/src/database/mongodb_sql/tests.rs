@@ -0,0 +1,182 @@
+use super::*;
+use serde_json::json;
+
+#[test]
+fn test_translate_simple_find() {
+    let translation = translate_sql(
+        "SELECT name, age FROM users WHERE age >= 18 AND status = 'active' ORDER BY age DESC, name LIMIT 20 OFFSET 40",
+    ).unwrap();
+
+    assert_eq!(translation.collection, "users");
+    assert_eq!(translation.operation, "find");
+    assert_eq!(translation.filter, Some(json!({ "age": { "$gte": 18 }, "status": "active" })));
+    assert_eq!(translation.projection, Some(json!({ "name": 1, "age": 1, "_id": 0 })));
+    assert_eq!(translation.sort, Some(json!({ "age": -1, "name": 1 })));
+    assert_eq!(translation.skip, Some(40));
+    assert_eq!(translation.limit, Some(20));
+    assert!(translation.pipeline.is_none());
+    assert_eq!(
+        translation.shell,
+        r#"db.users.find({"age":{"$gte":18},"status":"active"}, {"name":1,"age":1,"_id":0}).sort({"age":-1,"name":1}).skip(40).limit(20)"#,
+    );
+    assert!(translation.warnings.is_empty());
+}
+
+#[test]
+fn test_translate_star_without_limit() {
+    let translation = translate_sql("select * from `order-items`;").unwrap();
+    assert_eq!(translation.filter, Some(json!({})));
+    assert_eq!(translation.projection, None);
+    assert_eq!(translation.shell, r#"db.getCollection("order-items").find({})"#);
+    assert_eq!(translation.warnings.len(), 1);
+
+    // MySQL 的 LIMIT 偏移, 条数
+    let translation = translate_sql("SELECT * FROM logs LIMIT 10, 5").unwrap();
+    assert_eq!(translation.skip, Some(10));
+    assert_eq!(translation.limit, Some(5));
+}
+
+#[test]
+fn test_translate_where_operators() {
+    let filter = |sql: &str| translate_sql(sql).unwrap().filter.unwrap();
+
+    assert_eq!(
+        filter("SELECT * FROM t WHERE a IN (1, 'x') AND b NOT IN (2) AND c IS NULL AND d IS NOT NULL"),
+        json!({ "a": { "$in": [1, "x"] }, "b": { "$nin": [2] }, "c": null, "d": { "$ne": null } }),
+    );
+    assert_eq!(
+        filter("SELECT * FROM t WHERE name LIKE 'J_n%' OR email ILIKE '%@Example.com'"),
+        json!({ "$or": [
+            { "name": { "$regex": "^J.n.*$" } },
+            { "email": { "$regex": "^.*@Example\\.com$", "$options": "i" } },
+        ] }),
+    );
+    assert_eq!(
+        filter("SELECT * FROM t WHERE price BETWEEN 1.5 AND 10 AND NOT (qty < 0)"),
+        json!({ "price": { "$gte": 1.5, "$lte": 10 }, "$nor": [{ "qty": { "$lt": 0 } }] }),
+    );
+    // 常量在左边时翻转运算符，两边都是列时使用 $expr
+    assert_eq!(filter("SELECT * FROM t WHERE 10 > score"), json!({ "score": { "$lt": 10 } }));
+    assert_eq!(
+        filter("SELECT * FROM t WHERE t.sent <> t.received"),
+        json!({ "$expr": { "$ne": ["$sent", "$received"] } }),
+    );
+    // 同一字段的多个条件使用 $and
+    assert_eq!(
+        filter("SELECT * FROM t WHERE age > 1 AND age < -5"),
+        json!({ "$and": [{ "age": { "$gt": 1 } }, { "age": { "$lt": -5 } }] }),
+    );
+    assert_eq!(
+        filter("SELECT * FROM t WHERE address.city = 'Paris' AND big = 3000000000"),
+        json!({ "address.city": "Paris", "big": 3000000000_i64 }),
+    );
+}
+
+#[test]
+fn test_translate_group_by() {
+    let translation = translate_sql(
+        "SELECT status, COUNT(*) AS total, AVG(amount) FROM orders WHERE year = 2024 \
+         GROUP BY status HAVING COUNT(*) > 5 ORDER BY total DESC LIMIT 3",
+    ).unwrap();
+
+    assert_eq!(translation.operation, "aggregate");
+    assert_eq!(translation.pipeline, Some(vec![
+        json!({ "$match": { "year": 2024 } }),
+        json!({ "$group": { "_id": "$status", "total": { "$sum": 1 }, "avg_amount": { "$avg": "$amount" } } }),
+        json!({ "$match": { "total": { "$gt": 5 } } }),
+        json!({ "$sort": { "total": -1 } }),
+        json!({ "$limit": 3 }),
+        json!({ "$project": { "_id": 0, "status": "$_id", "total": "$total", "avg_amount": "$avg_amount" } }),
+    ]));
+    assert!(translation.shell.starts_with(r#"db.orders.aggregate([{"$match":{"year":2024}},"#));
+}
+
+#[test]
+fn test_translate_multiple_group_keys() {
+    let translation = translate_sql(
+        "SELECT region, city, SUM(sales) FROM shops GROUP BY region, city HAVING MAX(sales) >= 100 ORDER BY city",
+    ).unwrap();
+    assert_eq!(translation.pipeline, Some(vec![
+        json!({ "$group": {
+            "_id": { "region": "$region", "city": "$city" },
+            "sum_sales": { "$sum": "$sales" },
+            "max_sales": { "$max": "$sales" },
+        } }),
+        json!({ "$match": { "max_sales": { "$gte": 100 } } }),
+        json!({ "$sort": { "_id.city": 1 } }),
+        json!({ "$limit": 100 }),
+        json!({ "$project": { "_id": 0, "region": "$_id.region", "city": "$_id.city", "sum_sales": "$sum_sales" } }),
+    ]));
+
+    // 没有 GROUP BY 的聚合作用于整个集合
+    let translation = translate_sql("SELECT COUNT(email) FROM users").unwrap();
+    let pipeline = translation.pipeline.unwrap();
+    assert_eq!(pipeline[0]["$group"]["_id"], serde_json::Value::Null);
+    assert!(pipeline[0]["$group"]["count_email"]["$sum"]["$cond"].is_array());
+}
+
+#[test]
+fn test_translate_distinct_and_aliases() {
+    let translation = translate_sql("SELECT DISTINCT country FROM users ORDER BY country").unwrap();
+    assert_eq!(translation.pipeline, Some(vec![
+        json!({ "$group": { "_id": "$country" } }),
+        json!({ "$sort": { "_id": 1 } }),
+        json!({ "$limit": 100 }),
+        json!({ "$project": { "_id": 0, "country": "$_id" } }),
+    ]));
+    assert_eq!(translation.warnings.len(), 1);
+
+    // 列别名需要 $project 重命名，翻译为 aggregate
+    let translation = translate_sql("SELECT u.name AS userName FROM users u ORDER BY userName LIMIT 5").unwrap();
+    assert_eq!(translation.pipeline, Some(vec![
+        json!({ "$sort": { "name": 1 } }),
+        json!({ "$limit": 5 }),
+        json!({ "$project": { "userName": "$name", "_id": 0 } }),
+    ]));
+}
+
+#[test]
+fn test_translate_join() {
+    let translation = translate_sql(
+        "SELECT o.total, c.name FROM orders o LEFT JOIN customers c ON o.customer_id = c._id \
+         WHERE o.total > 100 AND c.country = 'FR' LIMIT 10",
+    ).unwrap();
+    assert_eq!(translation.pipeline, Some(vec![
+        json!({ "$lookup": { "from": "customers", "localField": "customer_id", "foreignField": "_id", "as": "c" } }),
+        json!({ "$unwind": { "path": "$c", "preserveNullAndEmptyArrays": true } }),
+        json!({ "$match": { "total": { "$gt": 100 }, "c.country": "FR" } }),
+        json!({ "$limit": 10 }),
+        json!({ "$project": { "total": 1, "c.name": 1, "_id": 0 } }),
+    ]));
+
+    // 只涉及主表的条件放在 $lookup 之前
+    let translation = translate_sql(
+        "SELECT * FROM orders JOIN items i ON i.order_id = orders.id WHERE orders.status = 'paid'",
+    ).unwrap();
+    let pipeline = translation.pipeline.unwrap();
+    assert_eq!(pipeline[0], json!({ "$match": { "status": "paid" } }));
+    assert_eq!(pipeline[1]["$lookup"]["localField"], "id");
+    assert_eq!(pipeline[1]["$lookup"]["foreignField"], "order_id");
+    assert_eq!(pipeline[2]["$unwind"]["preserveNullAndEmptyArrays"], false);
+    // 没有 LIMIT 的 JOIN 同样限制默认条数
+    assert_eq!(pipeline[3], json!({ "$limit": 100 }));
+    assert_eq!(translation.warnings.len(), 1);
+}
+
+#[test]
+fn test_translate_errors() {
+    let error = |sql: &str| translate_sql(sql).unwrap_err().to_string();
+
+    assert!(error("UPDATE users SET a = 1").contains("只支持 SELECT"));
+    assert!(error("").contains("为空"));
+    assert!(error("SELECT * FROM users WHERE name = 'abc").contains("缺少结束"));
+    assert!(error("SELECT * FROM users WHERE").contains("语句末尾"));
+    assert!(error("SELECT * FROM users LIMIT 5 extra").contains("'extra' 附近"));
+    assert!(error("SELECT * FROM users WHERE COUNT(*) > 1").contains("HAVING"));
+    assert!(error("SELECT name, COUNT(*) FROM users").contains("GROUP BY"));
+    assert!(error("SELECT * FROM users GROUP BY name").contains("*"));
+    assert!(error("SELECT * FROM a JOIN b ON a.x = c.y").contains("ON 条件"));
+    assert!(error("SELECT * FROM users LIMIT 0").contains("大于 0"));
+    assert!(error("SELECT SUM(*) FROM users").contains("COUNT"));
+    assert!(error("SELECT * FROM users WHERE 1 = 2").contains("常量"));
+}
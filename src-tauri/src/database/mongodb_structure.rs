@@ -0,0 +1,213 @@
+// MongoDB 数据库结构：集合统计、索引和 GridFS 桶
+//
+// 集合统计来自 collStats，索引来自 listIndexes，GridFS 桶通过成对出现的
+// <桶>.files / <桶>.chunks 集合识别，这两个集合不再作为普通集合列出。
+
+use super::enhanced_types::{CollectionStats, GridFSBucketNode, MongoCollectionInfo, MongoCollectionNode, MongoIndexInfo};
+use super::mongodb::MongoDBConnection;
+use super::mongodb_shell::as_i64;
+use futures::StreamExt;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    results::CollectionType,
+    Collection, Database, IndexModel,
+};
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod tests;
+
+impl MongoDBConnection {
+    /// 获取数据库的集合、索引和 GridFS 桶，database_name 为空时使用当前数据库
+    pub async fn collection_structure(&self, database_name: Option<&str>) -> anyhow::Result<MongoCollectionInfo> {
+        let db = match database_name.filter(|name| !name.is_empty()) {
+            Some(name) => self.client()?.database(name),
+            None => self.database()?.clone(),
+        };
+
+        let mut specs = Vec::new();
+        let mut cursor = db.list_collections(None, None).await
+            .map_err(|e| anyhow::anyhow!("获取集合列表失败: {}", e))?;
+        while let Some(spec) = cursor.next().await {
+            specs.push(spec.map_err(|e| anyhow::anyhow!("读取集合信息失败: {}", e))?);
+        }
+        specs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let names: Vec<String> = specs.iter().map(|spec| spec.name.clone()).collect();
+        let buckets = gridfs_buckets(&names);
+        let bucket_collections: HashSet<String> = buckets.iter()
+            .flat_map(|bucket| [format!("{}.files", bucket), format!("{}.chunks", bucket)])
+            .collect();
+
+        let mut collections = Vec::new();
+        for spec in specs {
+            if bucket_collections.contains(&spec.name) {
+                continue;
+            }
+            let collection_type = match spec.collection_type {
+                CollectionType::View => "view",
+                CollectionType::Timeseries => "timeseries",
+                _ => "collection",
+            };
+
+            if collection_type == "view" {
+                collections.push(MongoCollectionNode {
+                    name: spec.name,
+                    collection_type: collection_type.to_string(),
+                    document_count: None,
+                    size: None,
+                    stats: None,
+                    indexes: vec![],
+                });
+                continue;
+            }
+
+            // 权限不足时仍然列出集合，只是没有统计信息
+            let raw_stats = match coll_stats(&db, &spec.name).await {
+                Ok(stats) => Some(stats),
+                Err(e) => {
+                    tracing::warn!("获取集合 {} 的统计信息失败: {}", spec.name, e);
+                    None
+                }
+            };
+            let stats = raw_stats.as_ref().map(parse_coll_stats);
+            let index_sizes = raw_stats.as_ref().and_then(|stats| stats.get_document("indexSizes").ok());
+
+            let coll: Collection<Document> = db.collection(&spec.name);
+            let indexes = match coll.list_indexes(None).await {
+                Ok(cursor) => cursor.collect::<Vec<_>>().await
+                    .into_iter()
+                    .filter_map(|index| index.ok())
+                    .map(|index| index_info(index, index_sizes))
+                    .collect(),
+                Err(e) => {
+                    tracing::warn!("获取集合 {} 的索引失败: {}", spec.name, e);
+                    vec![]
+                }
+            };
+
+            collections.push(MongoCollectionNode {
+                name: spec.name,
+                collection_type: collection_type.to_string(),
+                document_count: stats.as_ref().map(|s| s.document_count),
+                size: stats.as_ref().map(|s| s.size),
+                stats,
+                indexes,
+            });
+        }
+
+        let mut gridfs_buckets = Vec::new();
+        for bucket in buckets {
+            gridfs_buckets.push(bucket_info(&db, bucket).await);
+        }
+
+        Ok(MongoCollectionInfo { collections, gridfs_buckets })
+    }
+}
+
+pub(crate) async fn coll_stats(db: &Database, collection: &str) -> anyhow::Result<Document> {
+    db.run_command(doc! { "collStats": collection }, None).await
+        .map_err(|e| anyhow::anyhow!("collStats 失败: {}", e))
+}
+
+/// 文件数和总大小来自 files 集合，块数来自 chunks 集合的估计值
+async fn bucket_info(db: &Database, bucket: String) -> GridFSBucketNode {
+    let files: Collection<Document> = db.collection(&format!("{}.files", bucket));
+    let chunks: Collection<Document> = db.collection(&format!("{}.chunks", bucket));
+
+    let pipeline = vec![doc! { "$group": { "_id": Bson::Null, "count": { "$sum": 1 }, "total": { "$sum": "$length" } } }];
+    let summary = match files.aggregate(pipeline, None).await {
+        Ok(mut cursor) => match cursor.next().await {
+            Some(Ok(summary)) => Some(summary),
+            Some(Err(e)) => {
+                tracing::warn!("统计 GridFS 桶 {} 失败: {}", bucket, e);
+                None
+            }
+            // 没有文件时 $group 不输出
+            None => Some(doc! { "count": 0, "total": 0 }),
+        },
+        Err(e) => {
+            tracing::warn!("统计 GridFS 桶 {} 失败: {}", bucket, e);
+            None
+        }
+    };
+    let chunk_count = chunks.estimated_document_count(None).await.ok();
+
+    GridFSBucketNode {
+        file_count: summary.as_ref().and_then(|s| s.get("count")).and_then(as_i64).map(|v| v.max(0) as u64),
+        total_size: summary.as_ref().and_then(|s| s.get("total")).and_then(as_i64).map(|v| v.max(0) as u64),
+        chunk_count,
+        name: bucket,
+    }
+}
+
+/// 同时存在 <桶>.files 和 <桶>.chunks 时视为 GridFS 桶
+pub(crate) fn gridfs_buckets(names: &[String]) -> Vec<String> {
+    let names: HashSet<&str> = names.iter().map(String::as_str).collect();
+    let mut buckets: Vec<String> = names.iter()
+        .filter_map(|name| name.strip_suffix(".files"))
+        .filter(|bucket| !bucket.is_empty() && names.contains(format!("{}.chunks", bucket).as_str()))
+        .map(str::to_string)
+        .collect();
+    buckets.sort();
+    buckets
+}
+
+fn stat(stats: &Document, name: &str) -> u64 {
+    stats.get(name).and_then(as_i64).map(|v| v.max(0) as u64).unwrap_or(0)
+}
+
+pub(crate) fn parse_coll_stats(stats: &Document) -> CollectionStats {
+    CollectionStats {
+        document_count: stat(stats, "count"),
+        size: stat(stats, "size"),
+        storage_size: stat(stats, "storageSize"),
+        average_document_size: match stats.get("avgObjSize") {
+            Some(Bson::Double(v)) => *v,
+            Some(value) => as_i64(value).unwrap_or(0) as f64,
+            None => 0.0,
+        },
+        indexes_size: stat(stats, "totalIndexSize"),
+    }
+}
+
+/// 从键模式推断索引类型
+fn index_type(keys: &Document) -> String {
+    if keys.keys().any(|key| key == "$**" || key.ends_with(".$**")) {
+        return "wildcard".to_string();
+    }
+    keys.values()
+        .find_map(|value| match value {
+            Bson::String(kind) => Some(kind.clone()),
+            _ => None,
+        })
+        .unwrap_or_else(|| "btree".to_string())
+}
+
+pub(crate) fn index_info(index: IndexModel, sizes: Option<&Document>) -> MongoIndexInfo {
+    let options = index.options.unwrap_or_default();
+    let name = options.name.clone().unwrap_or_else(|| {
+        // 没有名称时按服务端规则拼出默认名
+        index.keys.iter()
+            .map(|(key, value)| match value {
+                Bson::String(kind) => format!("{}_{}", key, kind),
+                value => format!("{}_{}", key, value),
+            })
+            .collect::<Vec<_>>()
+            .join("_")
+    });
+
+    MongoIndexInfo {
+        columns: index.keys.keys().cloned().collect(),
+        unique: options.unique.unwrap_or(false) || name == "_id_",
+        index_type: index_type(&index.keys),
+        key: Bson::Document(index.keys).into_relaxed_extjson(),
+        sparse: options.sparse.unwrap_or(false),
+        hidden: options.hidden.unwrap_or(false),
+        ttl_seconds: options.expire_after.map(|ttl| ttl.as_secs()),
+        partial_filter: options.partial_filter_expression
+            .map(|filter| Bson::Document(filter).into_relaxed_extjson()),
+        size: sizes.and_then(|sizes| sizes.get(&name)).and_then(as_i64).map(|v| v.max(0) as u64),
+        name,
+    }
+}
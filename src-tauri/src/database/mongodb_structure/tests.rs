@@ -0,0 +1,63 @@
+use super::*;
+use mongodb::options::IndexOptions;
+use std::time::Duration;
+
+#[test]
+fn test_gridfs_buckets() {
+    let names: Vec<String> = ["fs.files", "fs.chunks", "uploads.files", "users", "orphan.chunks", "media.files"]
+        .iter().map(|s| s.to_string()).collect();
+    assert_eq!(gridfs_buckets(&names), vec!["fs"]);
+}
+
+#[test]
+fn test_parse_coll_stats() {
+    let stats = doc! {
+        "count": 1500,
+        "size": 524288_i64,
+        "storageSize": 1048576,
+        "avgObjSize": 349,
+        "totalIndexSize": 65536.0,
+        "indexSizes": { "_id_": 32768 },
+    };
+    let stats = parse_coll_stats(&stats);
+    assert_eq!(stats.document_count, 1500);
+    assert_eq!(stats.size, 524288);
+    assert_eq!(stats.storage_size, 1048576);
+    assert_eq!(stats.average_document_size, 349.0);
+    assert_eq!(stats.indexes_size, 65536);
+}
+
+#[test]
+fn test_index_info() {
+    let sizes = doc! { "_id_": 4096, "created_at_1": 8192 };
+
+    let index = IndexModel::builder().keys(doc! { "_id": 1 }).options(
+        IndexOptions::builder().name("_id_".to_string()).build()
+    ).build();
+    let info = index_info(index, Some(&sizes));
+    assert!(info.unique);
+    assert_eq!(info.index_type, "btree");
+    assert_eq!(info.size, Some(4096));
+
+    let index = IndexModel::builder().keys(doc! { "created_at": 1 }).options(
+        IndexOptions::builder()
+            .name("created_at_1".to_string())
+            .expire_after(Duration::from_secs(3600))
+            .partial_filter_expression(doc! { "archived": false })
+            .build()
+    ).build();
+    let info = index_info(index, Some(&sizes));
+    assert_eq!(info.ttl_seconds, Some(3600));
+    assert_eq!(info.partial_filter, Some(serde_json::json!({ "archived": false })));
+    assert_eq!(info.columns, vec!["created_at"]);
+    assert!(!info.unique);
+
+    let index = IndexModel::builder().keys(doc! { "title": "text", "lang": 1 }).build();
+    let info = index_info(index, None);
+    assert_eq!(info.index_type, "text");
+    assert_eq!(info.name, "title_text_lang_1");
+    assert_eq!(info.key, serde_json::json!({ "title": "text", "lang": 1 }));
+
+    let index = IndexModel::builder().keys(doc! { "attrs.$**": 1 }).build();
+    assert_eq!(index_info(index, None).index_type, "wildcard");
+}
@@ -0,0 +1,250 @@
+// MongoDB 集合校验规则：读取/修改 validator，从抽样生成 $jsonSchema，试运行校验
+//
+// 试运行使用 {$nor: [validator]} 查询现有数据，统计不满足规则的文档，
+// 与服务端在 validationLevel 为 strict 时的判定一致。
+
+use super::mongodb::MongoDBConnection;
+use super::mongodb_documents::{to_extended_json, ExtendedJsonMode};
+use super::mongodb_schema::CollectionSchema;
+use futures::StreamExt;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    options::FindOptions,
+    Collection,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// 试运行默认返回的不合规文档样例数
+const DEFAULT_FAILURE_SAMPLES: i64 = 10;
+
+/// 集合当前的校验设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CollectionValidation {
+    pub validator: Option<serde_json::Value>,
+    /// strict | moderate | off
+    pub validation_level: Option<String>,
+    /// error | warn
+    pub validation_action: Option<String>,
+}
+
+/// 试运行结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationDryRun {
+    pub total: u64,
+    pub failing: u64,
+    /// 部分不合规文档，宽松模式扩展 JSON
+    pub samples: Vec<serde_json::Value>,
+    pub execution_time: u64,
+}
+
+impl MongoDBConnection {
+    /// 读取集合的 validator、validationLevel 和 validationAction
+    pub async fn collection_validation(&self, collection: &str) -> anyhow::Result<CollectionValidation> {
+        let db = self.database()?;
+        let reply = db.run_command(doc! { "listCollections": 1, "filter": { "name": collection } }, None).await
+            .map_err(|e| anyhow::anyhow!("读取集合 {} 的信息失败: {}", collection, e))?;
+        let spec = reply.get_document("cursor")
+            .and_then(|cursor| cursor.get_array("firstBatch"))
+            .ok()
+            .and_then(|batch| batch.first())
+            .and_then(Bson::as_document)
+            .ok_or_else(|| anyhow::anyhow!("集合 {} 不存在", collection))?;
+        Ok(parse_validation(spec))
+    }
+
+    /// 通过 collMod 修改校验设置，未提供的项保持不变，validator 为空文档时移除校验
+    pub async fn update_validation(&self, collection: &str, validation: &CollectionValidation) -> anyhow::Result<()> {
+        let command = coll_mod_command(collection, validation)?;
+        self.database()?.run_command(command, None).await
+            .map_err(|e| anyhow::anyhow!("修改集合 {} 的校验规则失败: {}", collection, e))?;
+        Ok(())
+    }
+
+    /// 根据抽样文档生成初始的 $jsonSchema 校验规则
+    pub async fn generate_json_schema(&self, collection: &str, sample_size: i64) -> anyhow::Result<serde_json::Value> {
+        let schema = self.infer_collection_schema(collection, sample_size).await?;
+        Ok(to_extended_json(doc! { "$jsonSchema": json_schema(&schema) }, ExtendedJsonMode::Relaxed))
+    }
+
+    /// 用候选 validator 检查现有数据，统计会校验失败的文档
+    pub async fn dry_run_validator(
+        &self,
+        collection: &str,
+        validator: serde_json::Value,
+        sample_size: Option<i64>,
+    ) -> anyhow::Result<ValidationDryRun> {
+        let start = std::time::Instant::now();
+        let validator = validator_document(validator)?;
+        let coll: Collection<Document> = self.database()?.collection(collection);
+        let filter = doc! { "$nor": [validator] };
+
+        let total = coll.estimated_document_count(None).await
+            .map_err(|e| anyhow::anyhow!("统计集合 {} 的文档数失败: {}", collection, e))?;
+        let failing = coll.count_documents(filter.clone(), None).await
+            .map_err(|e| anyhow::anyhow!("校验规则无效: {}", e))?;
+
+        let options = FindOptions::builder()
+            .limit(sample_size.filter(|n| *n > 0).unwrap_or(DEFAULT_FAILURE_SAMPLES))
+            .build();
+        let cursor = coll.find(filter, options).await
+            .map_err(|e| anyhow::anyhow!("查询不合规文档失败: {}", e))?;
+        let samples = cursor.collect::<Vec<_>>().await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("读取不合规文档失败: {}", e))?;
+
+        Ok(ValidationDryRun {
+            total,
+            failing,
+            samples: samples.into_iter()
+                .map(|document| to_extended_json(document, ExtendedJsonMode::Relaxed))
+                .collect(),
+            execution_time: start.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+pub(crate) fn parse_validation(spec: &Document) -> CollectionValidation {
+    let options = spec.get_document("options").ok();
+    let option = |name: &str| options.and_then(|options| options.get_str(name).ok()).map(str::to_string);
+    CollectionValidation {
+        validator: options
+            .and_then(|options| options.get_document("validator").ok())
+            .map(|validator| to_extended_json(validator.clone(), ExtendedJsonMode::Relaxed)),
+        validation_level: option("validationLevel"),
+        validation_action: option("validationAction"),
+    }
+}
+
+fn validator_document(validator: serde_json::Value) -> anyhow::Result<Document> {
+    match Bson::try_from(validator) {
+        Ok(Bson::Document(document)) => Ok(document),
+        Ok(_) => Err(anyhow::anyhow!("validator 必须是文档")),
+        Err(e) => Err(anyhow::anyhow!("validator 无效: {}", e)),
+    }
+}
+
+pub(crate) fn coll_mod_command(collection: &str, validation: &CollectionValidation) -> anyhow::Result<Document> {
+    let mut command = doc! { "collMod": collection };
+    if let Some(validator) = &validation.validator {
+        command.insert("validator", validator_document(validator.clone())?);
+    }
+    if let Some(level) = &validation.validation_level {
+        if !matches!(level.as_str(), "strict" | "moderate" | "off") {
+            return Err(anyhow::anyhow!("validationLevel 只能是 strict、moderate 或 off"));
+        }
+        command.insert("validationLevel", level.as_str());
+    }
+    if let Some(action) = &validation.validation_action {
+        if !matches!(action.as_str(), "error" | "warn") {
+            return Err(anyhow::anyhow!("validationAction 只能是 error 或 warn"));
+        }
+        command.insert("validationAction", action.as_str());
+    }
+    if command.len() == 1 {
+        return Err(anyhow::anyhow!("没有需要修改的校验设置"));
+    }
+    Ok(command)
+}
+
+/// 结构推断中的类型名对应的 $jsonSchema bsonType
+fn json_schema_type(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "Double" => "double",
+        "String" => "string",
+        "Array" => "array",
+        "Document" => "object",
+        "Boolean" => "bool",
+        "Null" => "null",
+        "Regex" => "regex",
+        "JavaScript" => "javascript",
+        "JavaScriptWithScope" => "javascriptWithScope",
+        "Int32" => "int",
+        "Int64" => "long",
+        "Timestamp" => "timestamp",
+        "Binary" => "binData",
+        "ObjectId" => "objectId",
+        "Date" => "date",
+        "Symbol" => "symbol",
+        "Decimal128" => "decimal",
+        "Undefined" => "undefined",
+        "MaxKey" => "maxKey",
+        "MinKey" => "minKey",
+        "DBPointer" => "dbPointer",
+        _ => return None,
+    })
+}
+
+fn bson_type<'a>(names: impl Iterator<Item = &'a str>) -> Bson {
+    let types: Vec<&str> = names.filter_map(json_schema_type).collect();
+    match types.as_slice() {
+        [single] => Bson::String(single.to_string()),
+        _ => Bson::Array(types.into_iter().map(Bson::from).collect()),
+    }
+}
+
+/// 由推断结果生成 $jsonSchema，嵌套文档生成 properties，文档数组生成 items
+pub(crate) fn json_schema(schema: &CollectionSchema) -> Document {
+    object_schema(schema, None, schema.sampled, false)
+}
+
+fn object_schema(
+    schema: &CollectionSchema,
+    parent: Option<&str>,
+    parent_occurrences: u64,
+    in_array: bool,
+) -> Document {
+    let mut properties = Document::new();
+    let mut required = Vec::new();
+
+    for field in &schema.fields {
+        let name = match parent {
+            Some(parent) => match field.path.strip_prefix(parent).and_then(|rest| rest.strip_prefix('.')) {
+                Some(name) if !name.contains('.') => name,
+                _ => continue,
+            },
+            None if !field.path.contains('.') => field.path.as_str(),
+            None => continue,
+        };
+        let has_children = schema.fields.iter()
+            .any(|other| other.path.len() > field.path.len() + 1 && other.path.starts_with(&format!("{}.", field.path)));
+
+        let mut property = doc! { "bsonType": bson_type(field.types.iter().map(|t| t.bson_type.as_str())) };
+        let is_array = field.types.iter().any(|t| t.bson_type == "Array");
+        let element_documents = field.array_types.iter().any(|t| t.bson_type == "Document");
+
+        if has_children && field.types.iter().any(|t| t.bson_type == "Document") {
+            for (key, value) in object_schema(schema, Some(&field.path), field.occurrences, in_array) {
+                property.insert(key, value);
+            }
+        }
+        if is_array && !field.array_types.is_empty() {
+            let mut items = doc! { "bsonType": bson_type(field.array_types.iter().map(|t| t.bson_type.as_str())) };
+            if has_children && element_documents {
+                // 数组元素的出现次数按文档统计，无法判断元素中哪些字段必填
+                for (key, value) in object_schema(schema, Some(&field.path), field.occurrences, true) {
+                    if key != "required" {
+                        items.insert(key, value);
+                    }
+                }
+            }
+            property.insert("items", items);
+        }
+
+        let nullable = field.types.iter().any(|t| t.bson_type == "Null");
+        if !in_array && field.occurrences == parent_occurrences && !nullable {
+            required.push(Bson::String(name.to_string()));
+        }
+        properties.insert(name, property);
+    }
+
+    let mut object = doc! { "bsonType": "object" };
+    if !required.is_empty() {
+        object.insert("required", required);
+    }
+    object.insert("properties", properties);
+    object
+}
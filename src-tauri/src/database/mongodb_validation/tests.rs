@@ -0,0 +1,93 @@
+use super::*;
+use crate::database::mongodb_schema::infer_schema;
+use mongodb::bson::oid::ObjectId;
+
+#[test]
+fn test_json_schema_from_samples() {
+    let documents = vec![
+        doc! {
+            "_id": ObjectId::new(),
+            "name": "Alice",
+            "age": 30,
+            "address": { "city": "Beijing", "zip": "100000" },
+            "orders": [{ "sku": "A1", "qty": 2 }],
+        },
+        doc! {
+            "_id": ObjectId::new(),
+            "name": "Bob",
+            "age": 31.5,
+            "address": { "city": "Shanghai" },
+            "orders": [],
+            "nickname": null,
+        },
+    ];
+    let schema = json_schema(&infer_schema("users", &documents));
+
+    assert_eq!(schema.get_str("bsonType").unwrap(), "object");
+    let required: Vec<&str> = schema.get_array("required").unwrap().iter().filter_map(Bson::as_str).collect();
+    assert_eq!(required, vec!["_id", "name", "age", "address", "orders"]);
+
+    let properties = schema.get_document("properties").unwrap();
+    assert_eq!(properties.get_document("_id").unwrap().get_str("bsonType").unwrap(), "objectId");
+    assert_eq!(
+        properties.get_document("age").unwrap().get_array("bsonType").unwrap(),
+        &vec![Bson::from("int"), Bson::from("double")]
+    );
+    assert_eq!(properties.get_document("nickname").unwrap().get_str("bsonType").unwrap(), "null");
+
+    let address = properties.get_document("address").unwrap();
+    assert_eq!(address.get_array("required").unwrap(), &vec![Bson::from("city")]);
+    assert!(address.get_document("properties").unwrap().contains_key("zip"));
+
+    let items = properties.get_document("orders").unwrap().get_document("items").unwrap();
+    assert_eq!(items.get_str("bsonType").unwrap(), "object");
+    assert!(!items.contains_key("required"));
+    assert!(items.get_document("properties").unwrap().contains_key("qty"));
+}
+
+#[test]
+fn test_coll_mod_command() {
+    let validation = CollectionValidation {
+        validator: Some(serde_json::json!({ "$jsonSchema": { "bsonType": "object", "required": ["name"] } })),
+        validation_level: Some("moderate".to_string()),
+        validation_action: Some("warn".to_string()),
+    };
+    let command = coll_mod_command("users", &validation).unwrap();
+    assert_eq!(command.get_str("collMod").unwrap(), "users");
+    assert_eq!(command.get_str("validationLevel").unwrap(), "moderate");
+    assert_eq!(command.get_str("validationAction").unwrap(), "warn");
+    assert!(command.get_document("validator").unwrap().contains_key("$jsonSchema"));
+}
+
+#[test]
+fn test_coll_mod_command_rejects_invalid() {
+    assert!(coll_mod_command("users", &CollectionValidation::default()).is_err());
+
+    let level = CollectionValidation { validation_level: Some("loose".to_string()), ..Default::default() };
+    assert!(coll_mod_command("users", &level).is_err());
+
+    let action = CollectionValidation { validation_action: Some("ignore".to_string()), ..Default::default() };
+    assert!(coll_mod_command("users", &action).is_err());
+
+    let validator = CollectionValidation { validator: Some(serde_json::json!([1])), ..Default::default() };
+    assert!(coll_mod_command("users", &validator).is_err());
+}
+
+#[test]
+fn test_parse_validation() {
+    let spec = doc! {
+        "name": "users",
+        "options": {
+            "validator": { "age": { "$gte": 0 } },
+            "validationLevel": "strict",
+            "validationAction": "error",
+        },
+    };
+    let validation = parse_validation(&spec);
+    assert_eq!(validation.validator, Some(serde_json::json!({ "age": { "$gte": 0 } })));
+    assert_eq!(validation.validation_level.as_deref(), Some("strict"));
+    assert_eq!(validation.validation_action.as_deref(), Some("error"));
+
+    let plain = parse_validation(&doc! { "name": "logs", "options": {} });
+    assert!(plain.validator.is_none());
+}
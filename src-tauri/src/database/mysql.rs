@@ -1,4 +1,4 @@
-use super::{DatabaseConnection, ConnectionConfig, QueryResult, TableInfo, ColumnInfo};
+use super::{ColumnInfo, ConnectionConfig, DatabaseAdapter, DatabaseSchema, DatabaseType, QueryResult, TableSchema};
 use mysql_async::{Pool, Conn, prelude::*, Row};
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -21,7 +21,11 @@ impl MySQLConnection {
 }
 
 #[async_trait]
-impl DatabaseConnection for MySQLConnection {
+impl DatabaseAdapter for MySQLConnection {
+    fn db_type(&self) -> DatabaseType {
+        DatabaseType::MySQL
+    }
+
     async fn connect(&mut self, config: &ConnectionConfig) -> anyhow::Result<()> {
         use tracing::{info, error, debug};
         
@@ -84,7 +88,7 @@ impl DatabaseConnection for MySQLConnection {
         Ok(())
     }
 
-    async fn execute(&self, query: &str) -> anyhow::Result<QueryResult> {
+    async fn execute_raw(&self, query: &str) -> anyhow::Result<QueryResult> {
         let pool = self.pool.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Not connected"))?;
         
@@ -142,7 +146,7 @@ impl DatabaseConnection for MySQLConnection {
         }
     }
 
-    async fn get_schema(&self) -> anyhow::Result<Vec<TableInfo>> {
+    async fn get_schema(&self) -> anyhow::Result<DatabaseSchema> {
         let pool = self.pool.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Not connected"))?;
             
@@ -160,20 +164,13 @@ impl DatabaseConnection for MySQLConnection {
                     continue;
                 }
                 
-                table_infos.push(TableInfo {
-                    name: format!("{}", db_name), // 使用文件夹图标表示数据库
-                    columns: vec![
-                        ColumnInfo {
-                            name: "database".to_string(),
-                            data_type: "DATABASE".to_string(),
-                            nullable: false,
-                            primary_key: false,
-                        }
-                    ],
-                });
+                table_infos.push(TableSchema::new(
+                    db_name,
+                    vec![ColumnInfo::new("database", "DATABASE", false, false)],
+                ));
             }
             
-            return Ok(table_infos);
+            return Ok(DatabaseSchema::new("", table_infos));
         }
         
         // 如果有当前数据库，确保选择了正确的数据库
@@ -199,22 +196,14 @@ impl DatabaseConnection for MySQLConnection {
                     let nullable: String = row.get("Null").unwrap();
                     let key: String = row.get("Key").unwrap();
                     
-                    ColumnInfo {
-                        name,
-                        data_type,
-                        nullable: nullable == "YES",
-                        primary_key: key == "PRI",
-                    }
+                    ColumnInfo::new(name, data_type, nullable == "YES", key == "PRI")
                 })
                 .collect();
             
-            table_infos.push(TableInfo {
-                name: table_name,
-                columns: column_infos,
-            });
+            table_infos.push(TableSchema::new(table_name, column_infos));
         }
         
-        Ok(table_infos)
+        Ok(DatabaseSchema::new(self.current_database.clone().unwrap_or_default(), table_infos))
     }
 
     async fn get_databases(&self) -> anyhow::Result<Vec<String>> {
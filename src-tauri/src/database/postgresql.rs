@@ -1,4 +1,4 @@
-use super::{DatabaseConnection, ConnectionConfig, QueryResult, TableInfo, ColumnInfo};
+use super::{ColumnInfo, ConnectionConfig, DatabaseAdapter, DatabaseSchema, DatabaseType, QueryResult, TableSchema};
 use tokio_postgres::{NoTls, Row, Column};
 use deadpool_postgres::{Pool, Config, ManagerConfig, RecyclingMethod, Runtime};
 use async_trait::async_trait;
@@ -52,7 +52,11 @@ impl PostgreSQLConnection {
 }
 
 #[async_trait]
-impl DatabaseConnection for PostgreSQLConnection {
+impl DatabaseAdapter for PostgreSQLConnection {
+    fn db_type(&self) -> DatabaseType {
+        DatabaseType::PostgreSQL
+    }
+
     async fn connect(&mut self, config: &ConnectionConfig) -> anyhow::Result<()> {
         use tracing::{info, error, debug};
         
@@ -116,7 +120,7 @@ impl DatabaseConnection for PostgreSQLConnection {
         Ok(())
     }
 
-    async fn execute(&self, query: &str) -> anyhow::Result<QueryResult> {
+    async fn execute_raw(&self, query: &str) -> anyhow::Result<QueryResult> {
         let pool = self.pool.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Not connected"))?;
         
@@ -169,7 +173,7 @@ impl DatabaseConnection for PostgreSQLConnection {
         }
     }
 
-    async fn get_schema(&self) -> anyhow::Result<Vec<TableInfo>> {
+    async fn get_schema(&self) -> anyhow::Result<DatabaseSchema> {
         let pool = self.pool.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Not connected"))?;
             
@@ -186,20 +190,13 @@ impl DatabaseConnection for PostgreSQLConnection {
                     continue;
                 }
                 
-                table_infos.push(TableInfo {
-                    name: format!("{}", db_name),
-                    columns: vec![
-                        ColumnInfo {
-                            name: "database".to_string(),
-                            data_type: "DATABASE".to_string(),
-                            nullable: false,
-                            primary_key: false,
-                        }
-                    ],
-                });
+                table_infos.push(TableSchema::new(
+                    db_name,
+                    vec![ColumnInfo::new("database", "DATABASE", false, false)],
+                ));
             }
             
-            return Ok(table_infos);
+            return Ok(DatabaseSchema::new("", table_infos));
         }
         
         // 获取表信息的SQL查询
@@ -234,12 +231,7 @@ impl DatabaseConnection for PostgreSQLConnection {
             let is_nullable: String = row.get("is_nullable");
             let is_primary_key: bool = row.get("is_primary_key");
             
-            let column_info = ColumnInfo {
-                name: column_name,
-                data_type,
-                nullable: is_nullable == "YES",
-                primary_key: is_primary_key,
-            };
+            let column_info = ColumnInfo::new(column_name, data_type, is_nullable == "YES", is_primary_key);
             
             table_map.entry(table_name)
                 .or_insert_with(Vec::new)
@@ -247,10 +239,10 @@ impl DatabaseConnection for PostgreSQLConnection {
         }
         
         let table_infos = table_map.into_iter()
-            .map(|(name, columns)| TableInfo { name, columns })
+            .map(|(name, columns)| TableSchema::new(name, columns))
             .collect();
         
-        Ok(table_infos)
+        Ok(DatabaseSchema::new(self.current_database.clone().unwrap_or_default(), table_infos))
     }
 
     async fn get_databases(&self) -> anyhow::Result<Vec<String>> {
@@ -1,6 +1,9 @@
-use super::{DatabaseConnection, ConnectionConfig, QueryResult, TableInfo, ColumnInfo};
+use super::{ColumnInfo, ConnectionConfig, DatabaseAdapter, DatabaseSchema, DatabaseType, QueryResult, TableSchema};
+use super::enhanced_types::DatabaseStats;
+use super::redis_admin::info_stats;
 use redis::{Client, Connection, Commands, RedisResult};
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -8,6 +11,7 @@ pub struct RedisConnection {
     client: Option<Client>,
     connection: Option<Arc<Mutex<Connection>>>,
     current_database: i64, // 添加当前数据库索引
+    script_cache: std::sync::Mutex<HashMap<String, String>>, // 加载过的Lua脚本，按SHA索引
 }
 
 impl RedisConnection {
@@ -16,12 +20,39 @@ impl RedisConnection {
             client: None,
             connection: None,
             current_database: 0, // Redis默认使用0号数据库
+            script_cache: std::sync::Mutex::new(HashMap::new()),
         }
     }
+
+    /// 获取共享连接，供扩展命令模块执行原始命令
+    pub(crate) fn connection(&self) -> anyhow::Result<Arc<Mutex<Connection>>> {
+        self.connection.clone()
+            .ok_or_else(|| anyhow::anyhow!("Not connected"))
+    }
+
+    /// 本连接加载过的Lua脚本源码，按SHA索引
+    pub(crate) fn script_cache(&self) -> std::sync::MutexGuard<'_, HashMap<String, String>> {
+        self.script_cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 当前选择的数据库索引
+    pub fn current_database(&self) -> i64 {
+        self.current_database
+    }
+
+    /// 获取客户端，用于订阅、MONITOR等需要独占连接的操作
+    pub fn client(&self) -> anyhow::Result<Client> {
+        self.client.clone()
+            .ok_or_else(|| anyhow::anyhow!("Not connected"))
+    }
 }
 
 #[async_trait]
-impl DatabaseConnection for RedisConnection {
+impl DatabaseAdapter for RedisConnection {
+    fn db_type(&self) -> DatabaseType {
+        DatabaseType::Redis
+    }
+
     async fn connect(&mut self, config: &ConnectionConfig) -> anyhow::Result<()> {
         let url = if let Some(password) = &config.password {
             format!("redis://:{}@{}:{}", password, config.host, config.port)
@@ -55,7 +86,7 @@ impl DatabaseConnection for RedisConnection {
         Ok(())
     }
 
-    async fn execute(&self, command: &str) -> anyhow::Result<QueryResult> {
+    async fn execute_raw(&self, command: &str) -> anyhow::Result<QueryResult> {
         let conn_arc = self.connection.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Not connected"))?;
         
@@ -380,6 +411,17 @@ impl DatabaseConnection for RedisConnection {
                     Err(e) => return Err(anyhow::anyhow!("DECRBY failed: {}", e)),
                 }
             }
+            "PUBLISH" => {
+                if parts.len() < 3 {
+                    return Err(anyhow::anyhow!("PUBLISH command requires channel and message"));
+                }
+                let message = parts[2..].join(" ");
+                let receivers: RedisResult<i64> = conn.publish(parts[1], message);
+                match receivers {
+                    Ok(r) => vec![vec![r.to_string()]],
+                    Err(e) => return Err(anyhow::anyhow!("PUBLISH failed: {}", e)),
+                }
+            }
             "FLUSHDB" => {
                 let _: RedisResult<()> = redis::cmd("FLUSHDB").query(&mut *conn);
                 vec![vec!["OK".to_string()]]
@@ -419,7 +461,7 @@ impl DatabaseConnection for RedisConnection {
             "HGETALL" => vec!["Field".to_string(), "Value".to_string()],
             "KEYS" | "LRANGE" | "SMEMBERS" | "ZRANGE" => vec!["Key".to_string()],
             "INFO" => vec!["Info".to_string()],
-            "LPUSH" | "RPUSH" | "SADD" | "ZADD" | "EXISTS" | "SREM" => vec!["Count".to_string()],
+            "LPUSH" | "RPUSH" | "SADD" | "ZADD" | "EXISTS" | "SREM" | "PUBLISH" => vec!["Count".to_string()],
             "TTL" | "EXPIRE" | "INCR" | "DECR" | "INCRBY" | "DECRBY" => vec!["Value".to_string()],
             "TYPE" | "PING" => vec!["Result".to_string()],
             _ => vec!["Value".to_string()],
@@ -433,7 +475,7 @@ impl DatabaseConnection for RedisConnection {
         })
     }
 
-    async fn get_schema(&self) -> anyhow::Result<Vec<TableInfo>> {
+    async fn get_schema(&self) -> anyhow::Result<DatabaseSchema> {
         let conn_arc = self.connection.as_ref()
             .ok_or_else(|| anyhow::anyhow!("Not connected"))?;
         
@@ -446,29 +488,14 @@ impl DatabaseConnection for RedisConnection {
             Err(_) => "No info available".to_string(),
         };
         
-        Ok(vec![TableInfo {
-            name: "Redis Database".to_string(),
-            columns: vec![
-                ColumnInfo {
-                    name: "Key".to_string(),
-                    data_type: "String".to_string(),
-                    nullable: false,
-                    primary_key: true,
-                },
-                ColumnInfo {
-                    name: "Type".to_string(),
-                    data_type: "String".to_string(),
-                    nullable: false,
-                    primary_key: false,
-                },
-                ColumnInfo {
-                    name: "Value".to_string(),
-                    data_type: "Any".to_string(),
-                    nullable: true,
-                    primary_key: false,
-                },
-            ],
-        }])
+        Ok(DatabaseSchema::new(
+            self.current_database.to_string(),
+            vec![TableSchema::new("Redis Database", vec![
+                ColumnInfo::new("Key", "String", false, true),
+                ColumnInfo::new("Type", "String", false, false),
+                ColumnInfo::new("Value", "Any", true, false),
+            ])],
+        ))
     }
 
     async fn get_databases(&self) -> anyhow::Result<Vec<String>> { 
@@ -497,4 +524,13 @@ impl DatabaseConnection for RedisConnection {
     fn is_connected(&self) -> bool {
         self.connection.is_some()
     }
+
+    async fn get_real_time_stats(&self) -> anyhow::Result<Option<DatabaseStats>> {
+        let info = self.server_info(None).await?;
+        Ok(Some(info_stats(&info)))
+    }
+
+    fn as_redis(&self) -> Option<&RedisConnection> {
+        Some(self)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,447 @@
+// Redis 服务器管理：INFO、CONFIG、SLOWLOG、CLIENT
+
+use super::enhanced_types::DatabaseStats;
+use super::redis::RedisConnection;
+use super::redis_value::{value_items, value_to_i64, value_to_opt_string, value_to_string};
+use redis::{RedisResult, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+mod tests;
+
+// ===== INFO =====
+
+/// INFO 字段值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum InfoValue {
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    /// 形如 `keys=1,expires=0` 的复合值（keyspace、commandstats 等）
+    Map(BTreeMap<String, InfoValue>),
+}
+
+impl std::fmt::Display for InfoValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InfoValue::Integer(i) => write!(f, "{}", i),
+            InfoValue::Float(v) => write!(f, "{}", v),
+            InfoValue::Text(s) => f.write_str(s),
+            InfoValue::Map(map) => {
+                let pairs: Vec<String> = map.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                f.write_str(&pairs.join(","))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InfoField {
+    pub name: String,
+    pub value: InfoValue,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InfoSection {
+    /// 分节名称（小写），如 server、memory、keyspace
+    pub name: String,
+    pub fields: Vec<InfoField>,
+}
+
+// ===== CONFIG =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigEntry {
+    pub name: String,
+    pub value: String,
+}
+
+/// 配置差异比较结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDiff {
+    /// 服务端版本（INFO server 中的 redis_version）
+    pub server_version: String,
+    /// 所用默认值表对应的主版本，没有对应的默认值表时为空
+    pub defaults_version: Option<u32>,
+    /// 与默认值不同的配置项
+    pub entries: Vec<ConfigDiffEntry>,
+    /// 默认值表中没有的配置项，无法判断是否被修改过
+    pub unknown: Vec<ConfigEntry>,
+}
+
+/// 与默认值不同的配置项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDiffEntry {
+    pub name: String,
+    pub value: String,
+    pub default: String,
+}
+
+/// Redis 6 和 7 相同的常用配置默认值
+const CONFIG_DEFAULTS_COMMON: &[(&str, &str)] = &[
+    ("activedefrag", "no"),
+    ("active-expire-effort", "1"),
+    ("appendfsync", "everysec"),
+    ("appendonly", "no"),
+    ("client-output-buffer-limit", "normal 0 0 0 slave 268435456 67108864 60 pubsub 33554432 8388608 60"),
+    ("databases", "16"),
+    ("hz", "10"),
+    ("io-threads", "1"),
+    ("latency-monitor-threshold", "0"),
+    ("lazyfree-lazy-eviction", "no"),
+    ("lazyfree-lazy-expire", "no"),
+    ("lazyfree-lazy-server-del", "no"),
+    ("lazyfree-lazy-user-del", "no"),
+    ("list-compress-depth", "0"),
+    ("loglevel", "notice"),
+    ("maxclients", "10000"),
+    ("maxmemory", "0"),
+    ("maxmemory-policy", "noeviction"),
+    ("maxmemory-samples", "5"),
+    ("min-replicas-to-write", "0"),
+    ("notify-keyspace-events", ""),
+    ("proto-max-bulk-len", "536870912"),
+    ("protected-mode", "yes"),
+    ("rdbchecksum", "yes"),
+    ("rdbcompression", "yes"),
+    ("repl-backlog-size", "1048576"),
+    ("repl-timeout", "60"),
+    ("set-max-intset-entries", "512"),
+    ("slowlog-log-slower-than", "10000"),
+    ("slowlog-max-len", "128"),
+    ("stop-writes-on-bgsave-error", "yes"),
+    ("stream-node-max-bytes", "4096"),
+    ("stream-node-max-entries", "100"),
+    ("tcp-backlog", "511"),
+    ("tcp-keepalive", "300"),
+    ("timeout", "0"),
+];
+
+/// Redis 6 特有的默认值：编码配置仍以 ziplist 命名
+const CONFIG_DEFAULTS_6: &[(&str, &str)] = &[
+    ("hash-max-ziplist-entries", "128"),
+    ("hash-max-ziplist-value", "64"),
+    ("list-max-ziplist-size", "-2"),
+    ("lua-time-limit", "5000"),
+    ("save", "900 1 300 10 60 10000"),
+    ("zset-max-ziplist-entries", "128"),
+    ("zset-max-ziplist-value", "64"),
+];
+
+/// Redis 7 特有的默认值，ziplist 和 lua-time-limit 为兼容保留的别名
+const CONFIG_DEFAULTS_7: &[(&str, &str)] = &[
+    ("busy-reply-threshold", "5000"),
+    ("hash-max-listpack-entries", "128"),
+    ("hash-max-listpack-value", "64"),
+    ("hash-max-ziplist-entries", "128"),
+    ("hash-max-ziplist-value", "64"),
+    ("list-max-listpack-size", "-2"),
+    ("list-max-ziplist-size", "-2"),
+    ("lua-time-limit", "5000"),
+    ("maxmemory-clients", "0"),
+    ("save", "3600 1 300 100 60 10000"),
+    ("zset-max-listpack-entries", "128"),
+    ("zset-max-listpack-value", "64"),
+    ("zset-max-ziplist-entries", "128"),
+    ("zset-max-ziplist-value", "64"),
+];
+
+// ===== SLOWLOG =====
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlowlogEntry {
+    pub id: i64,
+    /// 记录时间（Unix 秒）
+    pub timestamp: i64,
+    /// 执行耗时（微秒）
+    pub duration_us: i64,
+    /// 命令及参数，服务端可能截断过长的参数
+    pub command: Vec<String>,
+    /// Redis 4.0 起提供
+    pub client_addr: Option<String>,
+    pub client_name: Option<String>,
+}
+
+// ===== CLIENT =====
+
+/// CLIENT LIST 中的一个客户端
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientInfo {
+    pub id: u64,
+    pub addr: String,
+    pub name: String,
+    /// 连接时长（秒）
+    pub age: u64,
+    /// 空闲时长（秒）
+    pub idle: u64,
+    pub flags: String,
+    pub db: i64,
+    /// 最近执行的命令
+    pub cmd: String,
+    pub user: Option<String>,
+    /// 全部原始字段
+    pub fields: BTreeMap<String, String>,
+}
+
+/// CLIENT KILL 的过滤条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "by", content = "value")]
+pub enum ClientKillFilter {
+    Id(u64),
+    Addr(String),
+    User(String),
+}
+
+impl RedisConnection {
+    /// INFO，可指定分节（如 "memory"、"all"）
+    pub async fn server_info(&self, section: Option<&str>) -> anyhow::Result<Vec<InfoSection>> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let mut cmd = redis::cmd("INFO");
+        if let Some(section) = section.filter(|s| !s.is_empty()) {
+            cmd.arg(section);
+        }
+        let info: RedisResult<String> = cmd.query(&mut *conn);
+        let info = info.map_err(|e| anyhow::anyhow!("INFO 失败: {}", e))?;
+        Ok(parse_info(&info))
+    }
+
+    /// CONFIG GET，pattern 支持通配符
+    pub async fn config_get(&self, pattern: &str) -> anyhow::Result<Vec<ConfigEntry>> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd("CONFIG").arg("GET").arg(pattern).query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("CONFIG GET 失败: {}", e))?;
+        Ok(parse_config(&reply))
+    }
+
+    /// CONFIG SET
+    pub async fn config_set(&self, name: &str, value: &str) -> anyhow::Result<()> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let result: RedisResult<()> = redis::cmd("CONFIG").arg("SET").arg(name).arg(value).query(&mut *conn);
+        result.map_err(|e| anyhow::anyhow!("CONFIG SET {} 失败: {}", name, e))
+    }
+
+    /// 与所连服务端版本的默认值不同，或默认值未知的配置项
+    pub async fn config_diff(&self) -> anyhow::Result<ConfigDiff> {
+        let server = self.server_info(Some("server")).await?;
+        let version = server.iter()
+            .flat_map(|section| section.fields.iter())
+            .find(|field| field.name == "redis_version")
+            .map(|field| field.value.to_string())
+            .unwrap_or_default();
+        let entries = self.config_get("*").await?;
+        Ok(diff_config(&entries, &version))
+    }
+
+    /// SLOWLOG GET，count 为空时使用服务端默认值（10 条）
+    pub async fn slowlog_get(&self, count: Option<i64>) -> anyhow::Result<Vec<SlowlogEntry>> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let mut cmd = redis::cmd("SLOWLOG");
+        cmd.arg("GET");
+        if let Some(count) = count {
+            cmd.arg(count);
+        }
+        let reply: RedisResult<Value> = cmd.query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("SLOWLOG GET 失败: {}", e))?;
+        Ok(value_items(&reply).iter().filter_map(parse_slowlog_entry).collect())
+    }
+
+    /// SLOWLOG RESET
+    pub async fn slowlog_reset(&self) -> anyhow::Result<()> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let result: RedisResult<()> = redis::cmd("SLOWLOG").arg("RESET").query(&mut *conn);
+        result.map_err(|e| anyhow::anyhow!("SLOWLOG RESET 失败: {}", e))
+    }
+
+    /// CLIENT LIST
+    pub async fn client_list(&self) -> anyhow::Result<Vec<ClientInfo>> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let list: RedisResult<String> = redis::cmd("CLIENT").arg("LIST").query(&mut *conn);
+        let list = list.map_err(|e| anyhow::anyhow!("CLIENT LIST 失败: {}", e))?;
+        Ok(list.lines().filter(|line| !line.trim().is_empty()).map(parse_client_line).collect())
+    }
+
+    /// CLIENT KILL，返回断开的客户端数量
+    pub async fn client_kill(&self, filter: &ClientKillFilter) -> anyhow::Result<u64> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let mut cmd = redis::cmd("CLIENT");
+        cmd.arg("KILL");
+        match filter {
+            ClientKillFilter::Id(id) => cmd.arg("ID").arg(*id),
+            ClientKillFilter::Addr(addr) => cmd.arg("ADDR").arg(addr),
+            ClientKillFilter::User(user) => cmd.arg("USER").arg(user),
+        };
+        let killed: RedisResult<u64> = cmd.query(&mut *conn);
+        killed.map_err(|e| anyhow::anyhow!("CLIENT KILL 失败: {}", e))
+    }
+}
+
+/// 将 INFO 输出按分节解析
+pub(crate) fn parse_info(info: &str) -> Vec<InfoSection> {
+    let mut sections: Vec<InfoSection> = Vec::new();
+    for line in info.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if let Some(name) = line.strip_prefix('#') {
+            sections.push(InfoSection { name: name.trim().to_lowercase(), fields: vec![] });
+            continue;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        if sections.is_empty() {
+            sections.push(InfoSection { name: String::new(), fields: vec![] });
+        }
+        if let Some(section) = sections.last_mut() {
+            section.fields.push(InfoField { name: name.to_string(), value: parse_info_value(value) });
+        }
+    }
+    sections
+}
+
+/// 从 INFO 分节中提取适配器层通用的实时统计
+pub(crate) fn info_stats(sections: &[InfoSection]) -> DatabaseStats {
+    let field = |name: &str| sections.iter()
+        .flat_map(|section| section.fields.iter())
+        .find(|field| field.name == name)
+        .map(|field| &field.value);
+    let integer = |name: &str| match field(name) {
+        Some(InfoValue::Integer(i)) => (*i).max(0) as u64,
+        _ => 0,
+    };
+
+    let hits = integer("keyspace_hits");
+    let misses = integer("keyspace_misses");
+    DatabaseStats {
+        connection_count: integer("connected_clients") as u32,
+        query_count: integer("total_commands_processed"),
+        cache_hit_ratio: if hits + misses == 0 { 0.0 } else { hits as f64 / (hits + misses) as f64 },
+        uptime: integer("uptime_in_seconds"),
+        version: match field("redis_version") {
+            Some(InfoValue::Text(version)) => version.clone(),
+            _ => String::new(),
+        },
+    }
+}
+
+fn parse_info_value(value: &str) -> InfoValue {
+    if let Ok(i) = value.parse::<i64>() {
+        return InfoValue::Integer(i);
+    }
+    if let Ok(f) = value.parse::<f64>() {
+        if f.is_finite() {
+            return InfoValue::Float(f);
+        }
+    }
+    // 复合值的每一段都必须是 name=value，避免把普通文本误判
+    if value.contains('=') {
+        let pairs: Option<BTreeMap<String, InfoValue>> = value.split(',')
+            .map(|pair| pair.split_once('=').map(|(k, v)| (k.to_string(), parse_info_value(v))))
+            .collect();
+        if let Some(map) = pairs {
+            return InfoValue::Map(map);
+        }
+    }
+    InfoValue::Text(value.to_string())
+}
+
+/// 解析 CONFIG GET 回复，按名称排序
+pub(crate) fn parse_config(reply: &Value) -> Vec<ConfigEntry> {
+    let mut entries: Vec<ConfigEntry> = value_items(reply)
+        .chunks(2)
+        .filter(|pair| pair.len() == 2)
+        .map(|pair| ConfigEntry { name: value_to_string(&pair[0]), value: value_to_string(&pair[1]) })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+}
+
+/// 按服务端主版本选择默认值表，目前只有 Redis 6 和 7
+fn config_defaults(server_version: &str) -> Option<(u32, &'static [(&'static str, &'static str)])> {
+    let major = server_version.split('.').next()?.parse::<u32>().ok()?;
+    match major {
+        6 => Some((6, CONFIG_DEFAULTS_6)),
+        7 => Some((7, CONFIG_DEFAULTS_7)),
+        _ => None,
+    }
+}
+
+pub(crate) fn diff_config(entries: &[ConfigEntry], server_version: &str) -> ConfigDiff {
+    let defaults = config_defaults(server_version);
+    let default_of = |name: &str| {
+        let (_, specific) = defaults?;
+        CONFIG_DEFAULTS_COMMON.iter().chain(specific)
+            .find(|(default_name, _)| *default_name == name)
+            .map(|(_, value)| *value)
+    };
+
+    let mut diff = ConfigDiff {
+        server_version: server_version.to_string(),
+        defaults_version: defaults.map(|(major, _)| major),
+        entries: Vec::new(),
+        unknown: Vec::new(),
+    };
+    for entry in entries {
+        match default_of(&entry.name) {
+            Some(default) if default == entry.value => {}
+            Some(default) => diff.entries.push(ConfigDiffEntry {
+                name: entry.name.clone(),
+                value: entry.value.clone(),
+                default: default.to_string(),
+            }),
+            None => diff.unknown.push(entry.clone()),
+        }
+    }
+    diff
+}
+
+pub(crate) fn parse_slowlog_entry(value: &Value) -> Option<SlowlogEntry> {
+    let items = value_items(value);
+    if items.len() < 4 {
+        return None;
+    }
+    Some(SlowlogEntry {
+        id: value_to_i64(&items[0])?,
+        timestamp: value_to_i64(&items[1]).unwrap_or(0),
+        duration_us: value_to_i64(&items[2]).unwrap_or(0),
+        command: value_items(&items[3]).iter().map(value_to_string).collect(),
+        client_addr: items.get(4).and_then(value_to_opt_string),
+        client_name: items.get(5).and_then(value_to_opt_string).filter(|name| !name.is_empty()),
+    })
+}
+
+/// 解析 CLIENT LIST 的一行：空格分隔的 name=value
+pub(crate) fn parse_client_line(line: &str) -> ClientInfo {
+    let fields: BTreeMap<String, String> = line.split_whitespace()
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    let text = |name: &str| fields.get(name).cloned().unwrap_or_default();
+    let number = |name: &str| fields.get(name).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+
+    ClientInfo {
+        id: number("id"),
+        addr: text("addr"),
+        name: text("name"),
+        age: number("age"),
+        idle: number("idle"),
+        flags: text("flags"),
+        db: fields.get("db").and_then(|v| v.parse().ok()).unwrap_or(0),
+        cmd: text("cmd"),
+        user: fields.get("user").cloned(),
+        fields: fields.clone(),
+    }
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+fn data(s: &str) -> Value {
+    Value::Data(s.as_bytes().to_vec())
+}
+
+#[test]
+fn test_parse_info() {
+    let info = "# Server\r\nredis_version:7.2.4\r\nuptime_in_seconds:3600\r\n\r\n# Memory\r\nmem_fragmentation_ratio:1.25\r\n\r\n# Keyspace\r\ndb0:keys=12,expires=3,avg_ttl=0\r\n";
+    let sections = parse_info(info);
+
+    assert_eq!(sections.len(), 3);
+    assert_eq!(sections[0].name, "server");
+    assert_eq!(sections[0].fields[0].value, InfoValue::Text("7.2.4".to_string()));
+    assert_eq!(sections[0].fields[1].value, InfoValue::Integer(3600));
+    assert_eq!(sections[1].fields[0].value, InfoValue::Float(1.25));
+
+    match &sections[2].fields[0].value {
+        InfoValue::Map(map) => {
+            assert_eq!(map.get("keys"), Some(&InfoValue::Integer(12)));
+            assert_eq!(map.get("expires"), Some(&InfoValue::Integer(3)));
+        }
+        other => panic!("expected map, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_info_stats() {
+    let info = "# Server\r\nredis_version:7.2.4\r\nuptime_in_seconds:3600\r\n# Clients\r\nconnected_clients:5\r\n# Stats\r\ntotal_commands_processed:1200\r\nkeyspace_hits:75\r\nkeyspace_misses:25\r\n";
+    let stats = info_stats(&parse_info(info));
+
+    assert_eq!(stats.version, "7.2.4");
+    assert_eq!(stats.uptime, 3600);
+    assert_eq!(stats.connection_count, 5);
+    assert_eq!(stats.query_count, 1200);
+    assert_eq!(stats.cache_hit_ratio, 0.75);
+
+    // 没有命中统计时命中率为 0
+    assert_eq!(info_stats(&parse_info("# Stats\r\nkeyspace_hits:0\r\n")).cache_hit_ratio, 0.0);
+}
+
+#[test]
+fn test_config_diff() {
+    let reply = Value::Bulk(vec![
+        data("maxmemory-policy"), data("allkeys-lru"),
+        data("hz"), data("10"),
+        data("dir"), data("/data"),
+        data("maxmemory"), data("1073741824"),
+        data("save"), data("900 1 300 10 60 10000"),
+    ]);
+    let entries = parse_config(&reply);
+    assert_eq!(entries[0].name, "dir");
+
+    // 默认值表中没有的配置项单独列出
+    let diff = diff_config(&entries, "7.2.4");
+    assert_eq!(diff.defaults_version, Some(7));
+    let names: Vec<&str> = diff.entries.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, vec!["maxmemory", "maxmemory-policy", "save"]);
+    assert_eq!(diff.entries[1].default, "noeviction");
+    assert_eq!(diff.entries[2].default, "3600 1 300 100 60 10000");
+    let unknown: Vec<&str> = diff.unknown.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(unknown, vec!["dir"]);
+
+    // Redis 6 的 save 默认值不同
+    let diff = diff_config(&entries, "6.2.14");
+    assert_eq!(diff.defaults_version, Some(6));
+    assert!(diff.entries.iter().all(|d| d.name != "save"));
+
+    // 没有默认值表的版本全部列为默认值未知
+    let diff = diff_config(&entries, "5.0.14");
+    assert_eq!(diff.server_version, "5.0.14");
+    assert_eq!(diff.defaults_version, None);
+    assert!(diff.entries.is_empty());
+    assert_eq!(diff.unknown.len(), entries.len());
+}
+
+#[test]
+fn test_parse_slowlog_entry() {
+    let entry = Value::Bulk(vec![
+        Value::Int(14),
+        Value::Int(1700000000),
+        Value::Int(25000),
+        Value::Bulk(vec![data("KEYS"), data("*")]),
+        data("10.0.0.5:51234"),
+        data(""),
+    ]);
+    let entry = parse_slowlog_entry(&entry).unwrap();
+    assert_eq!(entry.id, 14);
+    assert_eq!(entry.duration_us, 25000);
+    assert_eq!(entry.command, vec!["KEYS", "*"]);
+    assert_eq!(entry.client_addr.as_deref(), Some("10.0.0.5:51234"));
+    assert!(entry.client_name.is_none());
+
+    // Redis 4.0 之前只有四个字段
+    let old = Value::Bulk(vec![Value::Int(1), Value::Int(0), Value::Int(10), Value::Bulk(vec![data("GET")])]);
+    assert!(parse_slowlog_entry(&old).unwrap().client_addr.is_none());
+}
+
+#[test]
+fn test_parse_client_line() {
+    let line = "id=7 addr=10.0.0.5:51234 laddr=10.0.0.1:6379 fd=8 name=worker age=120 idle=3 flags=N db=2 sub=0 psub=0 cmd=get user=default";
+    let client = parse_client_line(line);
+    assert_eq!(client.id, 7);
+    assert_eq!(client.addr, "10.0.0.5:51234");
+    assert_eq!(client.name, "worker");
+    assert_eq!(client.db, 2);
+    assert_eq!(client.cmd, "get");
+    assert_eq!(client.user.as_deref(), Some("default"));
+    assert_eq!(client.fields.get("laddr").map(String::as_str), Some("10.0.0.1:6379"));
+}
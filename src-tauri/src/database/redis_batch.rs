@@ -0,0 +1,267 @@
+// Redis 批量操作：管道（pipeline）与 MULTI/EXEC 事务
+//
+// 命令一次性写入连接后逐条读取回复，这样单条命令失败只会体现在它自己的
+// 回复上，而不会像 redis::Pipeline::query 那样让整批结果变成一个错误。
+//
+// 事务在独占连接上执行，不会把 MULTI 状态留在共享连接上。需要乐观锁时使用
+// 事务会话：开启时在会话连接上 WATCH，调用方在同一连接上读取被监视的键，
+// 最后在同一连接上 MULTI/EXEC；期间键被其他客户端修改时 EXEC 返回空回复。
+//
+// redis-rs 无法解析包含错误的 EXEC 数组，执行阶段有命令失败时只能得知
+// 事务已执行，拿不到各命令的回复，此时会话连接随之丢弃。
+
+use super::redis::RedisConnection;
+use super::redis_value::RedisReply;
+use chrono::{DateTime, Utc};
+use redis::{Client, Connection, ErrorKind, RedisResult, Value};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+#[cfg(test)]
+mod tests;
+
+/// 事务连接的建立和读写超时
+const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 批量执行方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RedisBatchMode {
+    /// 管道：一次往返发送全部命令，不保证原子性
+    Pipeline,
+    /// MULTI/EXEC 事务；需要 WATCH 时使用事务会话
+    Transaction,
+}
+
+/// 批量执行请求，每条命令为已拆分好的参数列表（首个元素为命令名）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisBatchRequest {
+    pub commands: Vec<Vec<String>>,
+    pub mode: RedisBatchMode,
+}
+
+/// 批量执行结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisBatchResult {
+    pub mode: RedisBatchMode,
+    /// 与请求中的命令一一对应；事务未执行或执行结果无法解析时为入队阶段的回复（QUEUED 或错误）
+    pub replies: Vec<RedisReply>,
+    /// 事务是否因 WATCH 的键被修改而放弃执行
+    pub aborted: bool,
+    /// 事务级错误，如入队失败导致的 EXECABORT
+    pub error: Option<String>,
+    pub execution_time: u64,
+}
+
+/// 事务会话信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisTransactionInfo {
+    pub transaction_id: String,
+    pub connection_id: String,
+    pub watch_keys: Vec<String>,
+    pub started_at: DateTime<Utc>,
+}
+
+/// 不允许出现在批量命令中的命令：会改变连接状态或破坏事务边界
+const FORBIDDEN_COMMANDS: &[&str] = &[
+    "MULTI", "EXEC", "DISCARD", "WATCH", "UNWATCH", "SELECT", "MONITOR",
+    "SUBSCRIBE", "PSUBSCRIBE", "SSUBSCRIBE", "UNSUBSCRIBE", "PUNSUBSCRIBE", "SUNSUBSCRIBE",
+    "QUIT", "RESET",
+];
+
+impl RedisConnection {
+    /// 批量执行和事务会话所需的连接，取出后即可释放全局连接锁
+    pub fn batch_target(&self) -> anyhow::Result<BatchTarget> {
+        Ok(BatchTarget {
+            shared: self.connection()?,
+            client: self.client()?,
+            database: self.current_database(),
+        })
+    }
+}
+
+/// 批量执行目标：管道使用共享连接，事务使用新建的独占连接
+pub struct BatchTarget {
+    shared: Arc<Mutex<Connection>>,
+    client: Client,
+    database: i64,
+}
+
+impl BatchTarget {
+    /// 以管道或事务方式执行一批命令
+    pub async fn execute(&self, request: &RedisBatchRequest) -> anyhow::Result<RedisBatchResult> {
+        validate_commands(&request.commands)?;
+
+        let start = std::time::Instant::now();
+        let mut result = match request.mode {
+            RedisBatchMode::Pipeline => {
+                let mut conn = self.shared.lock().await;
+                run_pipeline(&mut conn, &request.commands)?
+            }
+            // 独占连接在事务结束后直接关闭
+            RedisBatchMode::Transaction => {
+                let mut conn = self.open_connection()?;
+                run_transaction(&mut conn, &request.commands)?
+            }
+        };
+        result.execution_time = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    /// 开启事务会话：在独占连接上 WATCH 指定的键
+    pub async fn begin_transaction(&self, info: RedisTransactionInfo) -> anyhow::Result<RedisTransactionSession> {
+        if info.watch_keys.is_empty() {
+            return Err(anyhow::anyhow!("事务会话至少需要监视一个键"));
+        }
+        let mut conn = self.open_connection()?;
+        redis::cmd("WATCH").arg(&info.watch_keys).query::<()>(&mut conn)
+            .map_err(|e| anyhow::anyhow!("WATCH 失败: {}", e))?;
+
+        Ok(RedisTransactionSession {
+            info,
+            connection: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// 建立设置了超时的独占连接，并切换到当前数据库
+    fn open_connection(&self) -> anyhow::Result<Connection> {
+        let mut conn = self.client.get_connection_with_timeout(TRANSACTION_TIMEOUT)
+            .map_err(|e| anyhow::anyhow!("建立事务连接失败: {}", e))?;
+        conn.set_read_timeout(Some(TRANSACTION_TIMEOUT))?;
+        conn.set_write_timeout(Some(TRANSACTION_TIMEOUT))?;
+        if self.database != 0 {
+            redis::cmd("SELECT").arg(self.database).query::<()>(&mut conn)
+                .map_err(|e| anyhow::anyhow!("SELECT 失败: {}", e))?;
+        }
+        Ok(conn)
+    }
+}
+
+/// 已 WATCH 的事务会话，drop 后连接关闭，监视随之取消
+#[derive(Clone)]
+pub struct RedisTransactionSession {
+    pub info: RedisTransactionInfo,
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl RedisTransactionSession {
+    /// 在会话连接上执行一条命令，通常用于读取被监视的键
+    pub async fn query(&self, command: &[String]) -> anyhow::Result<RedisReply> {
+        validate_commands(&[command.to_vec()])?;
+        let mut conn = self.connection.lock().await;
+        reply_or_error(build_command(command).query::<Value>(&mut *conn))
+    }
+
+    /// 在会话连接上执行 MULTI/EXEC，会话随之结束
+    pub async fn exec(self, commands: &[Vec<String>]) -> anyhow::Result<RedisBatchResult> {
+        validate_commands(commands)?;
+
+        let start = std::time::Instant::now();
+        let mut conn = self.connection.lock().await;
+        let mut result = run_transaction(&mut conn, commands)?;
+        result.execution_time = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+}
+
+/// 校验批量命令
+pub(crate) fn validate_commands(commands: &[Vec<String>]) -> anyhow::Result<()> {
+    if commands.is_empty() {
+        return Err(anyhow::anyhow!("批量命令不能为空"));
+    }
+
+    for (index, command) in commands.iter().enumerate() {
+        let name = command.first()
+            .map(|name| name.trim().to_uppercase())
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("第 {} 条命令为空", index + 1))?;
+        if FORBIDDEN_COMMANDS.contains(&name.as_str()) {
+            return Err(anyhow::anyhow!("第 {} 条命令 {} 不能在批量操作中使用", index + 1, name));
+        }
+    }
+    Ok(())
+}
+
+/// 将多条命令打包为一次写入的字节流
+fn pack_commands<'a>(commands: impl IntoIterator<Item = &'a redis::Cmd>) -> Vec<u8> {
+    commands.into_iter().flat_map(|cmd| cmd.get_packed_command()).collect()
+}
+
+fn build_command(args: &[String]) -> redis::Cmd {
+    let mut cmd = redis::cmd(&args[0]);
+    cmd.arg(&args[1..]);
+    cmd
+}
+
+/// 服务端返回的命令错误转为回复，连接级错误（I/O 等）继续向上抛出
+fn reply_or_error(response: RedisResult<Value>) -> anyhow::Result<RedisReply> {
+    match response {
+        Ok(value) => Ok(RedisReply::from(value)),
+        Err(e) if is_connection_error(&e) => Err(anyhow::anyhow!("读取批量命令回复失败: {}", e)),
+        Err(e) => Ok(RedisReply::Error(e.to_string())),
+    }
+}
+
+pub(crate) fn is_connection_error(e: &redis::RedisError) -> bool {
+    e.is_io_error() || e.is_connection_dropped() || e.is_timeout()
+}
+
+fn run_pipeline(conn: &mut Connection, commands: &[Vec<String>]) -> anyhow::Result<RedisBatchResult> {
+    let cmds: Vec<redis::Cmd> = commands.iter().map(|args| build_command(args)).collect();
+    conn.send_packed_command(&pack_commands(&cmds))
+        .map_err(|e| anyhow::anyhow!("发送管道命令失败: {}", e))?;
+
+    let replies = (0..cmds.len())
+        .map(|_| reply_or_error(conn.recv_response()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(RedisBatchResult {
+        mode: RedisBatchMode::Pipeline,
+        replies,
+        aborted: false,
+        error: None,
+        execution_time: 0,
+    })
+}
+
+fn run_transaction(conn: &mut Connection, commands: &[Vec<String>]) -> anyhow::Result<RedisBatchResult> {
+    let mut cmds = vec![redis::cmd("MULTI")];
+    cmds.extend(commands.iter().map(|args| build_command(args)));
+    cmds.push(redis::cmd("EXEC"));
+    conn.send_packed_command(&pack_commands(&cmds))
+        .map_err(|e| anyhow::anyhow!("发送事务命令失败: {}", e))?;
+
+    if let RedisReply::Error(e) = reply_or_error(conn.recv_response())? {
+        return Err(anyhow::anyhow!("MULTI 失败: {}", e));
+    }
+    let queued = (0..commands.len())
+        .map(|_| reply_or_error(conn.recv_response()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    transaction_result(queued, conn.recv_response())
+}
+
+/// 根据入队回复和 EXEC 回复组装事务结果
+pub(crate) fn transaction_result(queued: Vec<RedisReply>, exec: RedisResult<Value>) -> anyhow::Result<RedisBatchResult> {
+    let (replies, aborted, error) = match exec {
+        Ok(Value::Bulk(items)) => (items.into_iter().map(RedisReply::from).collect(), false, None),
+        // WATCH 的键被其他客户端修改，EXEC 返回空回复
+        Ok(Value::Nil) => (queued, true, None),
+        Ok(other) => (queued, false, Some(format!("无法识别的 EXEC 回复: {:?}", other))),
+        Err(e) if is_connection_error(&e) => return Err(anyhow::anyhow!("读取 EXEC 回复失败: {}", e)),
+        // 有命令入队失败，整个事务被服务端丢弃
+        Err(e) if e.kind() == ErrorKind::ExecAbortError => (queued, false, Some(e.to_string())),
+        // EXEC 数组中含有执行失败的命令，redis-rs 无法解析整个回复
+        Err(e) => (queued, false, Some(format!("事务已执行，但有命令执行失败，无法读取各命令的回复: {}", e))),
+    };
+
+    Ok(RedisBatchResult {
+        mode: RedisBatchMode::Transaction,
+        replies,
+        aborted,
+        error,
+        execution_time: 0,
+    })
+}
@@ -0,0 +1,65 @@
+use super::*;
+
+fn commands(commands: &[&[&str]]) -> Vec<Vec<String>> {
+    commands.iter()
+        .map(|args| args.iter().map(|a| a.to_string()).collect())
+        .collect()
+}
+
+#[test]
+fn test_validate_commands() {
+    assert!(validate_commands(&commands(&[&["SET", "a", "1"], &["INCR", "b"]])).is_ok());
+
+    assert!(validate_commands(&commands(&[])).is_err());
+    assert!(validate_commands(&commands(&[&[]])).is_err());
+    assert!(validate_commands(&commands(&[&["set", "a", "1"], &["exec"]])).is_err());
+    assert!(validate_commands(&commands(&[&["watch", "a"]])).is_err());
+    assert!(validate_commands(&commands(&[&["select", "2"]])).is_err());
+}
+
+#[test]
+fn test_transaction_result_executed() {
+    let queued = vec![RedisReply::Status("QUEUED".to_string()); 2];
+    let exec = Ok(Value::Bulk(vec![Value::Okay, Value::Int(2)]));
+
+    let result = transaction_result(queued, exec).unwrap();
+    assert!(!result.aborted);
+    assert!(result.error.is_none());
+    assert_eq!(result.replies, vec![RedisReply::Ok, RedisReply::Integer(2)]);
+}
+
+#[test]
+fn test_transaction_result_command_error() {
+    // redis-rs 无法解析含有错误的 EXEC 数组，只能报告事务已执行
+    let queued = vec![RedisReply::Status("QUEUED".to_string()); 3];
+    let exec = redis::parse_redis_value(b"*3\r\n+OK\r\n-WRONGTYPE Operation against a key holding the wrong kind of value\r\n:5\r\n");
+
+    let result = transaction_result(queued.clone(), exec).unwrap();
+    assert!(!result.aborted);
+    assert!(result.error.unwrap().contains("事务已执行"));
+    assert_eq!(result.replies, queued);
+}
+
+#[test]
+fn test_transaction_result_watch_conflict() {
+    let queued = vec![RedisReply::Status("QUEUED".to_string())];
+
+    let result = transaction_result(queued.clone(), redis::parse_redis_value(b"*-1\r\n")).unwrap();
+    assert!(result.aborted);
+    assert!(result.error.is_none());
+    assert_eq!(result.replies, queued);
+}
+
+#[test]
+fn test_transaction_result_exec_abort() {
+    let queued = vec![
+        RedisReply::Status("QUEUED".to_string()),
+        RedisReply::Error("ERR wrong number of arguments for 'incr' command".to_string()),
+    ];
+    let exec = redis::parse_redis_value(b"-EXECABORT Transaction discarded because of previous errors.\r\n");
+
+    let result = transaction_result(queued.clone(), exec).unwrap();
+    assert!(!result.aborted);
+    assert!(result.error.unwrap().contains("Transaction discarded"));
+    assert_eq!(result.replies, queued);
+}
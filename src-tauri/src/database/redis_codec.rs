@@ -0,0 +1,355 @@
+// Redis 字符串值的编码识别与解码
+//
+// 值可能被多层包装，例如 base64(zstd(MessagePack))。解码时从外到内逐层
+// 识别：压缩（gzip/zlib/zstd）和文本传输编码（hex/base64）作为外层，
+// 最内层为具体格式（JSON、MessagePack、PHP/Java 序列化、文本或二进制）。
+// 写回时按相同的层次反向编码，保证服务端读到的格式不变。
+
+mod java;
+mod msgpack;
+mod php;
+
+use super::redis::RedisConnection;
+use base64::Engine;
+use redis::RedisResult;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+#[cfg(test)]
+mod tests;
+
+/// 最多识别的外层数量，避免恶意数据导致无限解码
+const MAX_LAYERS: usize = 4;
+
+/// 单层解压后的最大字节数，超过时不再解压，直接展示原始值
+const MAX_DECODED_BYTES: usize = 16 * 1024 * 1024;
+
+/// 外层编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueLayer {
+    Gzip,
+    Zlib,
+    Zstd,
+    Base64,
+    Hex,
+}
+
+/// 最内层的数据格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueFormat {
+    Text,
+    Json,
+    MessagePack,
+    PhpSerialized,
+    JavaSerialized,
+    Binary,
+}
+
+/// 结构化内容的树节点
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueTreeNode {
+    /// 字段名或数组下标，根节点为 None
+    pub key: Option<String>,
+    /// 节点类型，如 object、array、string、int；PHP/Java 对象为类名
+    pub kind: String,
+    /// 标量的值
+    pub value: Option<String>,
+    pub children: Vec<ValueTreeNode>,
+}
+
+impl ValueTreeNode {
+    pub(crate) fn leaf(kind: &str, value: impl Into<String>) -> Self {
+        Self {
+            key: None,
+            kind: kind.to_string(),
+            value: Some(value.into()),
+            children: vec![],
+        }
+    }
+
+    pub(crate) fn branch(kind: &str, children: Vec<ValueTreeNode>) -> Self {
+        Self {
+            key: None,
+            kind: kind.to_string(),
+            value: None,
+            children,
+        }
+    }
+
+    pub(crate) fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+}
+
+/// 解码后的值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedValue {
+    /// 外层编码，从外到内
+    pub layers: Vec<ValueLayer>,
+    pub format: ValueFormat,
+    /// 可编辑的文本形式：JSON 和 PHP 为原文，MessagePack 为格式化的 JSON，
+    /// 二进制为十六进制
+    pub text: String,
+    pub tree: Option<ValueTreeNode>,
+    /// Java 序列化数据和无法原样写回的 MessagePack 只读
+    pub editable: bool,
+    pub raw_size: usize,
+    pub decoded_size: usize,
+}
+
+impl RedisConnection {
+    /// 读取字符串键并识别其编码
+    pub async fn get_decoded_value(&self, key: &str) -> anyhow::Result<DecodedValue> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let raw: RedisResult<Option<Vec<u8>>> = redis::cmd("GET").arg(key).query(&mut *conn);
+        let raw = raw.map_err(|e| anyhow::anyhow!("读取键 {} 失败: {}", key, e))?
+            .ok_or_else(|| anyhow::anyhow!("键 {} 不存在", key))?;
+        Ok(decode_value(&raw))
+    }
+
+    /// 按给定的编码层次写回字符串键，保留原有的过期时间
+    pub async fn set_encoded_value(
+        &self,
+        key: &str,
+        text: &str,
+        layers: &[ValueLayer],
+        format: ValueFormat,
+    ) -> anyhow::Result<()> {
+        let bytes = encode_value(text, layers, format)?;
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let current: RedisResult<Option<Vec<u8>>> = redis::cmd("GET").arg(key).query(&mut *conn);
+        let current = current.map_err(|e| anyhow::anyhow!("读取键 {} 失败: {}", key, e))?;
+        if current.is_some_and(|raw| !decode_value(&raw).editable) {
+            return Err(anyhow::anyhow!("键 {} 的值无法按原编码写回，只能查看", key));
+        }
+
+        // KEEPTTL（Redis 6.0+）在同一条命令中保留过期时间，XX 避免重新创建已过期或被删除的键
+        let written: RedisResult<Option<String>> = redis::cmd("SET").arg(key).arg(&bytes)
+            .arg("XX").arg("KEEPTTL")
+            .query(&mut *conn);
+        let written = match written {
+            // 旧版本不认识 KEEPTTL，改用脚本原子地读取过期时间并写入
+            Err(e) if e.to_string().contains("syntax error") => redis::Script::new(SET_KEEP_TTL_SCRIPT)
+                .key(key)
+                .arg(&bytes)
+                .invoke(&mut *conn),
+            other => other,
+        };
+        match written.map_err(|e| anyhow::anyhow!("写入键 {} 失败: {}", key, e))? {
+            Some(_) => Ok(()),
+            None => Err(anyhow::anyhow!("键 {} 不存在", key)),
+        }
+    }
+}
+
+/// 不支持 KEEPTTL 时保留过期时间写入，键不存在时返回 nil
+const SET_KEEP_TTL_SCRIPT: &str = r"
+local ttl = redis.call('PTTL', KEYS[1])
+if ttl == -2 then
+    return false
+end
+if ttl > 0 then
+    return redis.call('SET', KEYS[1], ARGV[1], 'PX', ttl)
+end
+return redis.call('SET', KEYS[1], ARGV[1])
+";
+
+/// 识别并解码原始字节
+pub fn decode_value(raw: &[u8]) -> DecodedValue {
+    let mut layers = Vec::new();
+    let mut payload = raw.to_vec();
+
+    while layers.len() < MAX_LAYERS {
+        match unwrap_layer(&payload) {
+            Some((layer, inner)) => {
+                layers.push(layer);
+                payload = inner;
+            }
+            None => break,
+        }
+    }
+
+    let (format, text, tree) = decode_payload(&payload);
+    DecodedValue {
+        layers,
+        editable: is_editable(format, &payload),
+        format,
+        text,
+        tree,
+        raw_size: raw.len(),
+        decoded_size: payload.len(),
+    }
+}
+
+/// 按编码层次和格式把编辑后的文本编码为原始字节
+pub fn encode_value(text: &str, layers: &[ValueLayer], format: ValueFormat) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = match format {
+        ValueFormat::Text => text.as_bytes().to_vec(),
+        // 按编辑后的原文写回，保留键顺序、数字精度和排版
+        ValueFormat::Json => {
+            serde_json::from_str::<serde::de::IgnoredAny>(text)
+                .map_err(|e| anyhow::anyhow!("JSON 格式错误: {}", e))?;
+            text.as_bytes().to_vec()
+        }
+        ValueFormat::MessagePack => {
+            let value: serde_json::Value = serde_json::from_str(text)
+                .map_err(|e| anyhow::anyhow!("JSON 格式错误: {}", e))?;
+            msgpack::encode(&value)?
+        }
+        ValueFormat::PhpSerialized => {
+            php::parse(text.as_bytes()).map_err(|e| anyhow::anyhow!("PHP 序列化格式错误: {}", e))?;
+            text.as_bytes().to_vec()
+        }
+        ValueFormat::Binary => hex::decode(text.trim())
+            .map_err(|e| anyhow::anyhow!("十六进制格式错误: {}", e))?,
+        ValueFormat::JavaSerialized => return Err(anyhow::anyhow!("Java 序列化数据不支持写回")),
+    };
+
+    for layer in layers.iter().rev() {
+        bytes = wrap_layer(*layer, &bytes)?;
+    }
+    Ok(bytes)
+}
+
+/// 识别一层外层编码，返回解开后的内容
+fn unwrap_layer(data: &[u8]) -> Option<(ValueLayer, Vec<u8>)> {
+    if data.starts_with(&[0x1f, 0x8b]) {
+        return decompress(flate2::read::GzDecoder::new(data)).map(|out| (ValueLayer::Gzip, out));
+    }
+    if data.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        let decoder = zstd::stream::read::Decoder::new(data).ok()?;
+        return decompress(decoder).map(|out| (ValueLayer::Zstd, out));
+    }
+    if is_zlib_header(data) {
+        if let Some(out) = decompress(flate2::read::ZlibDecoder::new(data)) {
+            return Some((ValueLayer::Zlib, out));
+        }
+    }
+
+    // 文本传输编码只有在内容能进一步识别时才采用，避免把普通文本误判
+    let text = std::str::from_utf8(data).ok()?.trim();
+    if text.len() >= 8 && text.len() % 2 == 0 && text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        if let Ok(inner) = hex::decode(text) {
+            if is_meaningful(&inner) {
+                return Some((ValueLayer::Hex, inner));
+            }
+        }
+    }
+    if text.len() >= 8 && text.len() % 4 == 0 {
+        if let Ok(inner) = base64::engine::general_purpose::STANDARD.decode(text) {
+            if is_meaningful(&inner) {
+                return Some((ValueLayer::Base64, inner));
+            }
+        }
+    }
+    None
+}
+
+/// MessagePack 经 JSON 编辑后可能丢失非字符串键、float32 宽度和键顺序，
+/// 只有重新编码后与原始字节一致的值才允许编辑
+fn is_editable(format: ValueFormat, payload: &[u8]) -> bool {
+    match format {
+        ValueFormat::JavaSerialized => false,
+        ValueFormat::MessagePack => msgpack::decode_document(payload)
+            .and_then(|value| msgpack::encode(&value).ok())
+            .is_some_and(|encoded| encoded == payload),
+        _ => true,
+    }
+}
+
+/// 读取解压结果，失败或超过 MAX_DECODED_BYTES 时返回 None
+fn decompress(decoder: impl Read) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    decoder.take(MAX_DECODED_BYTES as u64 + 1).read_to_end(&mut out).ok()?;
+    (out.len() <= MAX_DECODED_BYTES).then_some(out)
+}
+
+fn wrap_layer(layer: ValueLayer, data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    Ok(match layer {
+        ValueLayer::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        ValueLayer::Zlib => {
+            let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        ValueLayer::Zstd => zstd::stream::encode_all(data, 0)?,
+        ValueLayer::Base64 => base64::engine::general_purpose::STANDARD.encode(data).into_bytes(),
+        ValueLayer::Hex => hex::encode(data).into_bytes(),
+    })
+}
+
+/// zlib 头：CMF 为 deflate（0x78），且 CMF/FLG 组合能被 31 整除
+fn is_zlib_header(data: &[u8]) -> bool {
+    data.len() >= 2 && data[0] == 0x78 && (u16::from(data[0]) << 8 | u16::from(data[1])) % 31 == 0
+}
+
+/// hex/base64 解开后的内容是否为可识别的压缩或结构化数据
+fn is_meaningful(data: &[u8]) -> bool {
+    if unwrap_layer(data).is_some() {
+        return true;
+    }
+    !matches!(decode_payload(data).0, ValueFormat::Text | ValueFormat::Binary)
+}
+
+/// 识别最内层格式
+fn decode_payload(data: &[u8]) -> (ValueFormat, String, Option<ValueTreeNode>) {
+    if data.starts_with(&java::STREAM_MAGIC) {
+        if let Ok(tree) = java::parse(data) {
+            let text = serde_json::to_string_pretty(&tree).unwrap_or_default();
+            return (ValueFormat::JavaSerialized, text, Some(tree));
+        }
+    }
+
+    match std::str::from_utf8(data) {
+        Ok(text) => {
+            let trimmed = text.trim_start();
+            if trimmed.starts_with('{') || trimmed.starts_with('[') {
+                if let Ok(value) = serde_json::from_str::<serde_json::Value>(text) {
+                    return (ValueFormat::Json, text.to_string(), Some(json_tree(&value)));
+                }
+            }
+            if let Ok(tree) = php::parse(data) {
+                return (ValueFormat::PhpSerialized, text.to_string(), Some(tree));
+            }
+            (ValueFormat::Text, text.to_string(), None)
+        }
+        Err(_) => {
+            if let Some(value) = msgpack::decode_document(data) {
+                let pretty = serde_json::to_string_pretty(&value).unwrap_or_default();
+                return (ValueFormat::MessagePack, pretty, Some(json_tree(&value)));
+            }
+            (ValueFormat::Binary, hex::encode(data), None)
+        }
+    }
+}
+
+/// 将 JSON 值转换为树
+pub(crate) fn json_tree(value: &serde_json::Value) -> ValueTreeNode {
+    use serde_json::Value;
+    match value {
+        Value::Null => ValueTreeNode::leaf("null", "null"),
+        Value::Bool(b) => ValueTreeNode::leaf("bool", b.to_string()),
+        Value::Number(n) if n.is_f64() => ValueTreeNode::leaf("float", n.to_string()),
+        Value::Number(n) => ValueTreeNode::leaf("int", n.to_string()),
+        Value::String(s) => ValueTreeNode::leaf("string", s.clone()),
+        Value::Array(items) => ValueTreeNode::branch(
+            "array",
+            items.iter().enumerate().map(|(i, item)| json_tree(item).with_key(i.to_string())).collect(),
+        ),
+        Value::Object(map) => ValueTreeNode::branch(
+            "object",
+            map.iter().map(|(k, v)| json_tree(v).with_key(k.clone())).collect(),
+        ),
+    }
+}
@@ -0,0 +1,333 @@
+// Java 对象序列化流（java.io.ObjectOutputStream）解析
+//
+// 只用于展示：把对象、数组、枚举和字符串还原为树，类描述中的字段按声明
+// 顺序读取。writeObject 写入的自定义数据保留为块数据的十六进制内容。
+// 已解析的对象再次出现时（TC_REFERENCE）以引用节点表示，避免循环。
+
+use super::ValueTreeNode;
+use std::rc::Rc;
+
+pub(crate) const STREAM_MAGIC: [u8; 4] = [0xac, 0xed, 0x00, 0x05];
+
+const TC_NULL: u8 = 0x70;
+const TC_REFERENCE: u8 = 0x71;
+const TC_CLASSDESC: u8 = 0x72;
+const TC_OBJECT: u8 = 0x73;
+const TC_STRING: u8 = 0x74;
+const TC_ARRAY: u8 = 0x75;
+const TC_CLASS: u8 = 0x76;
+const TC_BLOCKDATA: u8 = 0x77;
+const TC_ENDBLOCKDATA: u8 = 0x78;
+const TC_RESET: u8 = 0x79;
+const TC_BLOCKDATALONG: u8 = 0x7a;
+const TC_EXCEPTION: u8 = 0x7b;
+const TC_LONGSTRING: u8 = 0x7c;
+const TC_PROXYCLASSDESC: u8 = 0x7d;
+const TC_ENUM: u8 = 0x7e;
+
+const BASE_WIRE_HANDLE: u32 = 0x7e0000;
+
+/// 对象和类描述（含父类链）的最大嵌套层级
+const MAX_DEPTH: usize = 128;
+
+const SC_WRITE_METHOD: u8 = 0x01;
+const SC_SERIALIZABLE: u8 = 0x02;
+const SC_EXTERNALIZABLE: u8 = 0x04;
+const SC_BLOCK_DATA: u8 = 0x08;
+
+struct ClassDesc {
+    name: String,
+    flags: u8,
+    fields: Vec<(u8, String)>,
+    super_desc: Option<Rc<ClassDesc>>,
+}
+
+enum Handle {
+    ClassDesc(Rc<ClassDesc>),
+    Value(ValueTreeNode),
+    /// 已分配但尚未解析完成
+    Pending,
+}
+
+/// 解析完整的序列化流
+pub(crate) fn parse(data: &[u8]) -> anyhow::Result<ValueTreeNode> {
+    if !data.starts_with(&STREAM_MAGIC) {
+        return Err(anyhow::anyhow!("不是 Java 序列化数据"));
+    }
+    let mut parser = Parser { data, pos: STREAM_MAGIC.len(), depth: 0, handles: vec![] };
+
+    let mut contents = Vec::new();
+    while parser.pos < data.len() {
+        contents.push(parser.read_content()?);
+    }
+    Ok(match contents.len() {
+        1 => contents.remove(0),
+        _ => ValueTreeNode::branch("stream", contents),
+    })
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    depth: usize,
+    handles: Vec<Handle>,
+}
+
+impl Parser<'_> {
+    fn take(&mut self, len: usize) -> anyhow::Result<&[u8]> {
+        let end = self.pos.checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| anyhow::anyhow!("Java 序列化数据被截断"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_be<const N: usize>(&mut self) -> anyhow::Result<[u8; N]> {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(self.take(N)?);
+        Ok(bytes)
+    }
+
+    fn peek(&self) -> anyhow::Result<u8> {
+        self.data.get(self.pos).copied().ok_or_else(|| anyhow::anyhow!("Java 序列化数据被截断"))
+    }
+
+    fn read_utf(&mut self) -> anyhow::Result<String> {
+        let len = u16::from_be_bytes(self.read_be()?) as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+
+    fn read_long_utf(&mut self) -> anyhow::Result<String> {
+        let len = u64::from_be_bytes(self.read_be()?) as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+
+    fn new_handle(&mut self, handle: Handle) -> usize {
+        self.handles.push(handle);
+        self.handles.len() - 1
+    }
+
+    fn lookup(&mut self) -> anyhow::Result<&Handle> {
+        let handle = u32::from_be_bytes(self.read_be()?);
+        handle.checked_sub(BASE_WIRE_HANDLE)
+            .and_then(|index| self.handles.get(index as usize))
+            .ok_or_else(|| anyhow::anyhow!("无效的对象引用 0x{:x}", handle))
+    }
+
+    /// 读取流中的一个内容：对象或块数据
+    fn read_content(&mut self) -> anyhow::Result<ValueTreeNode> {
+        match self.peek()? {
+            TC_BLOCKDATA => {
+                self.pos += 1;
+                let len = self.read_u8()? as usize;
+                Ok(ValueTreeNode::leaf("blockdata", hex::encode(self.take(len)?)))
+            }
+            TC_BLOCKDATALONG => {
+                self.pos += 1;
+                let len = u32::from_be_bytes(self.read_be()?) as usize;
+                Ok(ValueTreeNode::leaf("blockdata", hex::encode(self.take(len)?)))
+            }
+            _ => self.read_object(),
+        }
+    }
+
+    fn read_object(&mut self) -> anyhow::Result<ValueTreeNode> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(anyhow::anyhow!("Java 对象嵌套层级过深"));
+        }
+        let node = self.read_object_inner();
+        self.depth -= 1;
+        node
+    }
+
+    fn read_object_inner(&mut self) -> anyhow::Result<ValueTreeNode> {
+        let tc = self.read_u8()?;
+        match tc {
+            TC_NULL => Ok(ValueTreeNode::leaf("null", "null")),
+            TC_REFERENCE => Ok(match self.lookup()? {
+                Handle::Value(node) if node.kind == "string" => node.clone(),
+                Handle::Value(node) => ValueTreeNode::leaf("reference", node.kind.clone()),
+                Handle::ClassDesc(desc) => ValueTreeNode::leaf("class", desc.name.clone()),
+                Handle::Pending => ValueTreeNode::leaf("reference", "(cycle)"),
+            }),
+            TC_STRING | TC_LONGSTRING => {
+                let value = if tc == TC_STRING { self.read_utf()? } else { self.read_long_utf()? };
+                let node = ValueTreeNode::leaf("string", value);
+                self.new_handle(Handle::Value(node.clone()));
+                Ok(node)
+            }
+            TC_CLASS => {
+                let desc = self.require_class_desc()?;
+                let node = ValueTreeNode::leaf("class", desc.name.clone());
+                self.new_handle(Handle::Value(node.clone()));
+                Ok(node)
+            }
+            TC_CLASSDESC | TC_PROXYCLASSDESC => {
+                self.pos -= 1;
+                let desc = self.require_class_desc()?;
+                Ok(ValueTreeNode::leaf("class", desc.name.clone()))
+            }
+            TC_ENUM => {
+                let desc = self.require_class_desc()?;
+                let handle = self.new_handle(Handle::Pending);
+                let constant = self.read_object()?;
+                let node = ValueTreeNode::leaf(&desc.name, constant.value.unwrap_or_default());
+                self.handles[handle] = Handle::Value(node.clone());
+                Ok(node)
+            }
+            TC_ARRAY => {
+                let desc = self.require_class_desc()?;
+                self.new_handle(Handle::Value(ValueTreeNode::leaf(&desc.name, "")));
+                let size = i32::from_be_bytes(self.read_be()?);
+                let size = usize::try_from(size).map_err(|_| anyhow::anyhow!("无效的数组长度 {}", size))?;
+                let typecode = desc.name.as_bytes().get(1).copied().unwrap_or(b'L');
+
+                let mut items = Vec::with_capacity(size.min(1024));
+                for index in 0..size {
+                    items.push(self.read_field(typecode)?.with_key(index.to_string()));
+                }
+                Ok(ValueTreeNode::branch(&desc.name, items))
+            }
+            TC_OBJECT => self.read_new_object(),
+            TC_RESET => {
+                self.handles.clear();
+                self.read_object()
+            }
+            TC_EXCEPTION => Err(anyhow::anyhow!("序列化流中包含写入时的异常")),
+            other => Err(anyhow::anyhow!("未知的类型标记 0x{:02x}", other)),
+        }
+    }
+
+    fn read_new_object(&mut self) -> anyhow::Result<ValueTreeNode> {
+        let desc = self.require_class_desc()?;
+        self.new_handle(Handle::Value(ValueTreeNode::leaf(&desc.name, "")));
+
+        // 字段数据按继承层次从最顶层的父类开始
+        let mut hierarchy = vec![desc.clone()];
+        while let Some(parent) = hierarchy.last().and_then(|d| d.super_desc.clone()) {
+            hierarchy.push(parent);
+        }
+
+        let mut children = Vec::new();
+        for class in hierarchy.iter().rev() {
+            if class.flags & SC_EXTERNALIZABLE != 0 {
+                if class.flags & SC_BLOCK_DATA == 0 {
+                    return Err(anyhow::anyhow!("不支持旧版协议的 Externalizable 对象 {}", class.name));
+                }
+                children.push(ValueTreeNode::branch("annotation", self.read_annotation()?).with_key("writeExternal"));
+            } else if class.flags & SC_SERIALIZABLE != 0 {
+                for (typecode, name) in &class.fields {
+                    children.push(self.read_field(*typecode)?.with_key(name.clone()));
+                }
+                if class.flags & SC_WRITE_METHOD != 0 {
+                    let annotation = self.read_annotation()?;
+                    if !annotation.is_empty() {
+                        children.push(ValueTreeNode::branch("annotation", annotation).with_key("writeObject"));
+                    }
+                }
+            }
+        }
+
+        Ok(ValueTreeNode::branch(&desc.name, children))
+    }
+
+    fn read_field(&mut self, typecode: u8) -> anyhow::Result<ValueTreeNode> {
+        Ok(match typecode {
+            b'B' => ValueTreeNode::leaf("byte", (self.read_u8()? as i8).to_string()),
+            b'C' => {
+                let code = u16::from_be_bytes(self.read_be()?);
+                let c = char::from_u32(u32::from(code)).unwrap_or(char::REPLACEMENT_CHARACTER);
+                ValueTreeNode::leaf("char", c.to_string())
+            }
+            b'D' => ValueTreeNode::leaf("double", f64::from_be_bytes(self.read_be()?).to_string()),
+            b'F' => ValueTreeNode::leaf("float", f32::from_be_bytes(self.read_be()?).to_string()),
+            b'I' => ValueTreeNode::leaf("int", i32::from_be_bytes(self.read_be()?).to_string()),
+            b'J' => ValueTreeNode::leaf("long", i64::from_be_bytes(self.read_be()?).to_string()),
+            b'S' => ValueTreeNode::leaf("short", i16::from_be_bytes(self.read_be()?).to_string()),
+            b'Z' => ValueTreeNode::leaf("boolean", (self.read_u8()? != 0).to_string()),
+            b'L' | b'[' => self.read_object()?,
+            other => return Err(anyhow::anyhow!("未知的字段类型 '{}'", other as char)),
+        })
+    }
+
+    /// 读取类或对象的附加数据，直到 TC_ENDBLOCKDATA
+    fn read_annotation(&mut self) -> anyhow::Result<Vec<ValueTreeNode>> {
+        let mut contents = Vec::new();
+        while self.peek()? != TC_ENDBLOCKDATA {
+            contents.push(self.read_content()?);
+        }
+        self.pos += 1;
+        Ok(contents)
+    }
+
+    fn require_class_desc(&mut self) -> anyhow::Result<Rc<ClassDesc>> {
+        self.read_class_desc()?.ok_or_else(|| anyhow::anyhow!("缺少类描述"))
+    }
+
+    fn read_class_desc(&mut self) -> anyhow::Result<Option<Rc<ClassDesc>>> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(anyhow::anyhow!("Java 类描述嵌套层级过深"));
+        }
+        let desc = self.read_class_desc_inner();
+        self.depth -= 1;
+        desc
+    }
+
+    fn read_class_desc_inner(&mut self) -> anyhow::Result<Option<Rc<ClassDesc>>> {
+        match self.read_u8()? {
+            TC_NULL => Ok(None),
+            TC_REFERENCE => match self.lookup()? {
+                Handle::ClassDesc(desc) => Ok(Some(desc.clone())),
+                _ => Err(anyhow::anyhow!("引用的对象不是类描述")),
+            },
+            TC_CLASSDESC => {
+                let name = self.read_utf()?;
+                self.take(8)?; // serialVersionUID
+                let handle = self.new_handle(Handle::Pending);
+                let flags = self.read_u8()?;
+                let field_count = u16::from_be_bytes(self.read_be()?);
+
+                let mut fields = Vec::with_capacity(field_count as usize);
+                for _ in 0..field_count {
+                    let typecode = self.read_u8()?;
+                    let field_name = self.read_utf()?;
+                    if matches!(typecode, b'L' | b'[') {
+                        // 字段的类型签名，展示时不需要
+                        self.read_object()?;
+                    }
+                    fields.push((typecode, field_name));
+                }
+                self.read_annotation()?;
+                let super_desc = self.read_class_desc()?;
+
+                let desc = Rc::new(ClassDesc { name, flags, fields, super_desc });
+                self.handles[handle] = Handle::ClassDesc(desc.clone());
+                Ok(Some(desc))
+            }
+            TC_PROXYCLASSDESC => {
+                let handle = self.new_handle(Handle::Pending);
+                let count = i32::from_be_bytes(self.read_be()?).max(0);
+                let interfaces = (0..count).map(|_| self.read_utf()).collect::<anyhow::Result<Vec<_>>>()?;
+                self.read_annotation()?;
+                let super_desc = self.read_class_desc()?;
+
+                let desc = Rc::new(ClassDesc {
+                    name: format!("Proxy[{}]", interfaces.join(", ")),
+                    flags: SC_SERIALIZABLE,
+                    fields: vec![],
+                    super_desc,
+                });
+                self.handles[handle] = Handle::ClassDesc(desc.clone());
+                Ok(Some(desc))
+            }
+            other => Err(anyhow::anyhow!("无效的类描述标记 0x{:02x}", other)),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+// MessagePack 与 JSON 之间的转换
+//
+// 二进制和扩展类型在 JSON 中分别表示为 {"$bin": base64} 和
+// {"$ext": 类型, "data": base64}，写回时还原。非字符串的 map 键会转为字符串，
+// float32 读作 f64，serde_json 未启用 preserve_order 时键按字典序排列；这些信息
+// 在写回时无法还原，调用方需先比较重新编码的结果。
+
+use base64::Engine;
+use serde_json::{Map, Number, Value};
+
+/// 作为完整文档解码：必须恰好消费全部字节，且顶层为 map 或数组
+pub(crate) fn decode_document(data: &[u8]) -> Option<Value> {
+    let first = *data.first()?;
+    let is_container = matches!(first, 0x80..=0x9f | 0xdc..=0xdf);
+    if !is_container {
+        return None;
+    }
+    let mut reader = Reader { data, pos: 0, depth: 0 };
+    let value = reader.read_value().ok()?;
+    (reader.pos == data.len()).then_some(value)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> anyhow::Result<&[u8]> {
+        let end = self.pos.checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| anyhow::anyhow!("MessagePack 数据被截断"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_uint(&mut self, len: usize) -> anyhow::Result<u64> {
+        Ok(self.take(len)?.iter().fold(0u64, |acc, b| acc << 8 | u64::from(*b)))
+    }
+
+    fn read_int(&mut self, len: usize) -> anyhow::Result<i64> {
+        let value = self.read_uint(len)?;
+        let shift = 64 - len * 8;
+        Ok(((value << shift) as i64) >> shift)
+    }
+
+    fn read_value(&mut self) -> anyhow::Result<Value> {
+        let marker = self.take(1)?[0];
+        Ok(match marker {
+            0x00..=0x7f => Value::from(marker),
+            0x80..=0x8f => self.read_map(usize::from(marker & 0x0f))?,
+            0x90..=0x9f => self.read_array(usize::from(marker & 0x0f))?,
+            0xa0..=0xbf => self.read_str(usize::from(marker & 0x1f))?,
+            0xc0 => Value::Null,
+            0xc2 => Value::Bool(false),
+            0xc3 => Value::Bool(true),
+            0xc4..=0xc6 => {
+                let len = self.read_uint(1 << (marker - 0xc4))? as usize;
+                let bytes = self.take(len)?;
+                serde_json::json!({ "$bin": base64::engine::general_purpose::STANDARD.encode(bytes) })
+            }
+            0xc7..=0xc9 => {
+                let len = self.read_uint(1 << (marker - 0xc7))? as usize;
+                self.read_ext(len)?
+            }
+            0xca => float(f64::from(f32::from_bits(self.read_uint(4)? as u32))),
+            0xcb => float(f64::from_bits(self.read_uint(8)?)),
+            0xcc..=0xcf => Value::from(self.read_uint(1 << (marker - 0xcc))?),
+            0xd0..=0xd3 => Value::from(self.read_int(1 << (marker - 0xd0))?),
+            0xd4..=0xd8 => self.read_ext(1 << (marker - 0xd4))?,
+            0xd9..=0xdb => {
+                let len = self.read_uint(1 << (marker - 0xd9))? as usize;
+                self.read_str(len)?
+            }
+            0xdc | 0xdd => {
+                let len = self.read_uint(if marker == 0xdc { 2 } else { 4 })? as usize;
+                self.read_array(len)?
+            }
+            0xde | 0xdf => {
+                let len = self.read_uint(if marker == 0xde { 2 } else { 4 })? as usize;
+                self.read_map(len)?
+            }
+            0xe0..=0xff => Value::from(i64::from(marker as i8)),
+            0xc1 => return Err(anyhow::anyhow!("无效的 MessagePack 标记 0xc1")),
+        })
+    }
+
+    fn read_str(&mut self, len: usize) -> anyhow::Result<Value> {
+        let bytes = self.take(len)?;
+        Ok(Value::String(std::str::from_utf8(bytes)?.to_string()))
+    }
+
+    fn read_ext(&mut self, len: usize) -> anyhow::Result<Value> {
+        let ext_type = self.take(1)?[0] as i8;
+        let bytes = self.take(len)?;
+        Ok(serde_json::json!({
+            "$ext": ext_type,
+            "data": base64::engine::general_purpose::STANDARD.encode(bytes),
+        }))
+    }
+
+    fn read_array(&mut self, len: usize) -> anyhow::Result<Value> {
+        self.enter()?;
+        // 长度来自数据本身，不能直接用于预分配
+        let mut items = Vec::with_capacity(len.min(1024));
+        for _ in 0..len {
+            items.push(self.read_value()?);
+        }
+        self.depth -= 1;
+        Ok(Value::Array(items))
+    }
+
+    fn read_map(&mut self, len: usize) -> anyhow::Result<Value> {
+        self.enter()?;
+        let mut map = Map::new();
+        for _ in 0..len {
+            let key = match self.read_value()? {
+                Value::String(s) => s,
+                other => other.to_string(),
+            };
+            map.insert(key, self.read_value()?);
+        }
+        self.depth -= 1;
+        Ok(Value::Object(map))
+    }
+
+    fn enter(&mut self) -> anyhow::Result<()> {
+        self.depth += 1;
+        if self.depth > 128 {
+            return Err(anyhow::anyhow!("MessagePack 嵌套层级过深"));
+        }
+        Ok(())
+    }
+}
+
+fn float(value: f64) -> Value {
+    Number::from_f64(value).map(Value::Number).unwrap_or(Value::Null)
+}
+
+/// 将 JSON 值编码为 MessagePack
+pub(crate) fn encode(value: &Value) -> anyhow::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    write_value(&mut out, value)?;
+    Ok(out)
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) -> anyhow::Result<()> {
+    match value {
+        Value::Null => out.push(0xc0),
+        Value::Bool(b) => out.push(if *b { 0xc3 } else { 0xc2 }),
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                write_uint(out, u);
+            } else if let Some(i) = n.as_i64() {
+                write_int(out, i);
+            } else {
+                out.push(0xcb);
+                out.extend_from_slice(&n.as_f64().unwrap_or_default().to_bits().to_be_bytes());
+            }
+        }
+        Value::String(s) => {
+            write_len(out, s.len(), Some(0xa0), 0x1f, [0xd9, 0xda, 0xdb]);
+            out.extend_from_slice(s.as_bytes());
+        }
+        Value::Array(items) => {
+            write_len(out, items.len(), Some(0x90), 0x0f, [0xdc, 0xdc, 0xdd]);
+            for item in items {
+                write_value(out, item)?;
+            }
+        }
+        Value::Object(map) => {
+            if let Some(bytes) = special(map, "$bin")? {
+                write_len(out, bytes.len(), None, 0, [0xc4, 0xc5, 0xc6]);
+                out.extend_from_slice(&bytes);
+                return Ok(());
+            }
+            if let Some(ext_type) = map.get("$ext").and_then(Value::as_i64) {
+                if let Some(bytes) = special(map, "data")? {
+                    write_ext(out, ext_type as i8, &bytes);
+                    return Ok(());
+                }
+            }
+            write_len(out, map.len(), Some(0x80), 0x0f, [0xde, 0xde, 0xdf]);
+            for (key, item) in map {
+                write_value(out, &Value::String(key.clone()))?;
+                write_value(out, item)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 读取 {"$bin": base64} 这类特殊对象中的 base64 内容
+fn special(map: &Map<String, Value>, field: &str) -> anyhow::Result<Option<Vec<u8>>> {
+    let expected_len = if field == "$bin" { 1 } else { 2 };
+    match map.get(field).and_then(Value::as_str) {
+        Some(encoded) if map.len() == expected_len => Ok(Some(
+            base64::engine::general_purpose::STANDARD.decode(encoded)
+                .map_err(|e| anyhow::anyhow!("{} 不是有效的 base64: {}", field, e))?,
+        )),
+        _ => Ok(None),
+    }
+}
+
+fn write_uint(out: &mut Vec<u8>, value: u64) {
+    match value {
+        0..=0x7f => out.push(value as u8),
+        0x80..=0xff => out.extend_from_slice(&[0xcc, value as u8]),
+        0x100..=0xffff => {
+            out.push(0xcd);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(0xce);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(0xcf);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+fn write_int(out: &mut Vec<u8>, value: i64) {
+    if value >= 0 {
+        write_uint(out, value as u64);
+    } else if value >= -32 {
+        out.push(value as i8 as u8);
+    } else if value >= i64::from(i8::MIN) {
+        out.extend_from_slice(&[0xd0, value as i8 as u8]);
+    } else if value >= i64::from(i16::MIN) {
+        out.push(0xd1);
+        out.extend_from_slice(&(value as i16).to_be_bytes());
+    } else if value >= i64::from(i32::MIN) {
+        out.push(0xd2);
+        out.extend_from_slice(&(value as i32).to_be_bytes());
+    } else {
+        out.push(0xd3);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+/// 写入长度前缀：可选的 fix 形式，其后为 8/16/32 位长度的标记
+fn write_len(out: &mut Vec<u8>, len: usize, fix: Option<u8>, fix_max: usize, markers: [u8; 3]) {
+    match fix {
+        Some(fix) if len <= fix_max => out.push(fix | len as u8),
+        // 数组和 map 没有 8 位长度形式，markers[0] 与 markers[1] 相同时跳过
+        _ if len <= 0xff && markers[0] != markers[1] => out.extend_from_slice(&[markers[0], len as u8]),
+        _ if len <= 0xffff => {
+            out.push(markers[1]);
+            out.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        _ => {
+            out.push(markers[2]);
+            out.extend_from_slice(&(len as u32).to_be_bytes());
+        }
+    }
+}
+
+fn write_ext(out: &mut Vec<u8>, ext_type: i8, data: &[u8]) {
+    match data.len() {
+        1 => out.push(0xd4),
+        2 => out.push(0xd5),
+        4 => out.push(0xd6),
+        8 => out.push(0xd7),
+        16 => out.push(0xd8),
+        len => write_len(out, len, None, 0, [0xc7, 0xc8, 0xc9]),
+    }
+    out.push(ext_type as u8);
+    out.extend_from_slice(data);
+}
@@ -0,0 +1,170 @@
+// PHP serialize() 格式解析
+//
+// 支持 N、b、i、d、s、a、O、C、E 以及 r/R 引用。写回时直接使用编辑后的
+// 序列化文本，这里只负责校验和生成展示用的树。
+
+use super::ValueTreeNode;
+
+/// 解析完整的序列化文本，必须恰好消费全部字节
+pub(crate) fn parse(data: &[u8]) -> anyhow::Result<ValueTreeNode> {
+    let mut parser = Parser { data, pos: 0, depth: 0 };
+    let node = parser.read_value()?;
+    if parser.pos != data.len() {
+        return Err(anyhow::anyhow!("第 {} 字节之后有多余内容", parser.pos));
+    }
+    Ok(node)
+}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> anyhow::Result<()> {
+        if self.peek() != Some(byte) {
+            return Err(anyhow::anyhow!("第 {} 字节处应为 '{}'", self.pos, byte as char));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// 读取到分隔符为止的内容（不含分隔符）
+    fn read_until(&mut self, end: u8) -> anyhow::Result<&str> {
+        let start = self.pos;
+        let len = self.data[start..].iter()
+            .position(|b| *b == end)
+            .ok_or_else(|| anyhow::anyhow!("第 {} 字节之后缺少 '{}'", start, end as char))?;
+        self.pos = start + len + 1;
+        Ok(std::str::from_utf8(&self.data[start..start + len])?)
+    }
+
+    fn read_len(&mut self, end: u8) -> anyhow::Result<usize> {
+        let text = self.read_until(end)?;
+        text.parse().map_err(|_| anyhow::anyhow!("无效的长度 '{}'", text))
+    }
+
+    /// 读取 "<len>:\"...\"" 形式的字符串，长度按字节计算
+    fn read_quoted(&mut self) -> anyhow::Result<String> {
+        let len = self.read_len(b':')?;
+        self.expect(b'"')?;
+        let end = self.pos.checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| anyhow::anyhow!("字符串长度 {} 超出数据范围", len))?;
+        let value = String::from_utf8_lossy(&self.data[self.pos..end]).into_owned();
+        self.pos = end;
+        self.expect(b'"')?;
+        Ok(value)
+    }
+
+    fn read_value(&mut self) -> anyhow::Result<ValueTreeNode> {
+        let tag = self.peek().ok_or_else(|| anyhow::anyhow!("数据意外结束"))?;
+        self.pos += 1;
+        if tag == b'N' {
+            self.expect(b';')?;
+            return Ok(ValueTreeNode::leaf("null", "null"));
+        }
+        self.expect(b':')?;
+
+        Ok(match tag {
+            b'b' => match self.read_until(b';')? {
+                "0" => ValueTreeNode::leaf("bool", "false"),
+                "1" => ValueTreeNode::leaf("bool", "true"),
+                other => return Err(anyhow::anyhow!("无效的布尔值 '{}'", other)),
+            },
+            b'i' => {
+                let text = self.read_until(b';')?;
+                text.parse::<i64>().map_err(|_| anyhow::anyhow!("无效的整数 '{}'", text))?;
+                ValueTreeNode::leaf("int", text)
+            }
+            b'd' => {
+                let text = self.read_until(b';')?;
+                if !matches!(text, "INF" | "-INF" | "NAN") {
+                    text.parse::<f64>().map_err(|_| anyhow::anyhow!("无效的浮点数 '{}'", text))?;
+                }
+                ValueTreeNode::leaf("float", text)
+            }
+            b's' => {
+                let value = self.read_quoted()?;
+                self.expect(b';')?;
+                ValueTreeNode::leaf("string", value)
+            }
+            b'E' => {
+                let value = self.read_quoted()?;
+                self.expect(b';')?;
+                ValueTreeNode::leaf("enum", value)
+            }
+            b'r' | b'R' => {
+                let index = self.read_until(b';')?;
+                ValueTreeNode::leaf("reference", format!("#{}", index))
+            }
+            b'a' => {
+                let count = self.read_len(b':')?;
+                ValueTreeNode::branch("array", self.read_entries(count, None)?)
+            }
+            b'O' => {
+                let class = self.read_quoted()?;
+                self.expect(b':')?;
+                let count = self.read_len(b':')?;
+                ValueTreeNode::branch(&class, self.read_entries(count, Some(&class))?)
+            }
+            b'C' => {
+                // 实现了 Serializable 接口的对象，内容格式由类自行决定
+                let class = self.read_quoted()?;
+                self.expect(b':')?;
+                let len = self.read_len(b':')?;
+                self.expect(b'{')?;
+                let end = self.pos.checked_add(len)
+                    .filter(|end| *end <= self.data.len())
+                    .ok_or_else(|| anyhow::anyhow!("对象数据长度 {} 超出数据范围", len))?;
+                let payload = String::from_utf8_lossy(&self.data[self.pos..end]).into_owned();
+                self.pos = end;
+                self.expect(b'}')?;
+                ValueTreeNode::branch(&class, vec![ValueTreeNode::leaf("string", payload).with_key("data")])
+            }
+            other => return Err(anyhow::anyhow!("未知的类型标记 '{}'", other as char)),
+        })
+    }
+
+    /// 读取数组或对象属性的 {键;值...}
+    fn read_entries(&mut self, count: usize, class: Option<&str>) -> anyhow::Result<Vec<ValueTreeNode>> {
+        self.depth += 1;
+        if self.depth > 128 {
+            return Err(anyhow::anyhow!("嵌套层级过深"));
+        }
+        self.expect(b'{')?;
+        let mut entries = Vec::with_capacity(count.min(1024));
+        for _ in 0..count {
+            let key = self.read_value()?;
+            if !matches!(key.kind.as_str(), "int" | "string") {
+                return Err(anyhow::anyhow!("数组键只能是整数或字符串"));
+            }
+            let key = key.value.unwrap_or_default();
+            let key = match class {
+                Some(class) => property_name(&key, class),
+                None => key,
+            };
+            entries.push(self.read_value()?.with_key(key));
+        }
+        self.expect(b'}')?;
+        self.depth -= 1;
+        Ok(entries)
+    }
+}
+
+/// 去掉 protected（\0*\0）和 private（\0类名\0）属性名的前缀
+fn property_name(name: &str, class: &str) -> String {
+    if let Some(rest) = name.strip_prefix("\0*\0") {
+        return rest.to_string();
+    }
+    let private = format!("\0{}\0", class);
+    name.strip_prefix(&private)
+        .or_else(|| name.strip_prefix('\0').and_then(|rest| rest.split_once('\0')).map(|(_, n)| n))
+        .unwrap_or(name)
+        .to_string()
+}
@@ -0,0 +1,171 @@
+use super::*;
+
+fn child<'a>(node: &'a ValueTreeNode, key: &str) -> &'a ValueTreeNode {
+    node.children.iter().find(|c| c.key.as_deref() == Some(key)).unwrap()
+}
+
+#[test]
+fn test_decode_json_and_text() {
+    let raw = r#"{"user":{"id":1}}"#;
+    let decoded = decode_value(raw.as_bytes());
+    assert!(decoded.layers.is_empty());
+    assert_eq!(decoded.format, ValueFormat::Json);
+    assert_eq!(decoded.text, raw);
+    assert_eq!(child(child(decoded.tree.as_ref().unwrap(), "user"), "id").value.as_deref(), Some("1"));
+
+    let decoded = decode_value(b"hello world");
+    assert_eq!(decoded.format, ValueFormat::Text);
+    assert!(decoded.tree.is_none());
+
+    // 看起来像 hex/base64 的普通文本不会被解码
+    assert_eq!(decode_value(b"deadbeef").format, ValueFormat::Text);
+    assert_eq!(decode_value(b"password").format, ValueFormat::Text);
+}
+
+#[test]
+fn test_compressed_msgpack_round_trip() {
+    let text = r#"{"id": 42, "name": "alice", "scores": [1, -7, 300, 2.5], "active": true, "raw": {"$bin": "AAEC"}}"#;
+    let bytes = encode_value(text, &[ValueLayer::Zstd], ValueFormat::MessagePack).unwrap();
+    assert!(bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]));
+
+    let decoded = decode_value(&bytes);
+    assert_eq!(decoded.layers, vec![ValueLayer::Zstd]);
+    assert_eq!(decoded.format, ValueFormat::MessagePack);
+    assert!(decoded.editable);
+
+    let value: serde_json::Value = serde_json::from_str(&decoded.text).unwrap();
+    assert_eq!(value, serde_json::from_str::<serde_json::Value>(text).unwrap());
+
+    let rewritten = encode_value(&decoded.text, &decoded.layers, decoded.format).unwrap();
+    assert_eq!(decode_value(&rewritten).text, decoded.text);
+}
+
+#[test]
+fn test_msgpack_wire_format() {
+    // {"a": 1, "b": [true, nil]}
+    let bytes = [0x82, 0xa1, b'a', 0x01, 0xa1, b'b', 0x92, 0xc3, 0xc0];
+    let value = msgpack::decode_document(&bytes).unwrap();
+    assert_eq!(value, serde_json::json!({"a": 1, "b": [true, null]}));
+    assert_eq!(msgpack::encode(&value).unwrap(), bytes);
+
+    // 末尾多余字节不是完整文档
+    assert!(msgpack::decode_document(&[0x81, 0xa1, b'a', 0x01, 0x00]).is_none());
+}
+
+#[test]
+fn test_msgpack_lossy_values_read_only() {
+    // 整数键和 float32 无法原样写回
+    let int_key = [0x81, 0x01, 0xa1, b'a'];
+    let float32 = [0x81, 0xa1, b'f', 0xca, 0x3f, 0xc0, 0x00, 0x00];
+    for bytes in [&int_key[..], &float32[..]] {
+        let decoded = decode_value(bytes);
+        assert_eq!(decoded.format, ValueFormat::MessagePack, "{:?}", bytes);
+        assert!(!decoded.editable, "{:?}", bytes);
+    }
+    assert!(decode_value(&[0x82, 0xa1, b'a', 0x01, 0xa1, b'b', 0x02]).editable);
+}
+
+#[test]
+fn test_json_written_verbatim() {
+    // 键顺序、大整数精度和排版都按原文保留
+    let text = "{\n  \"b\": 1,\n  \"a\": 123456789012345678901234567890\n}";
+    assert_eq!(encode_value(text, &[], ValueFormat::Json).unwrap(), text.as_bytes());
+    assert!(encode_value("{\"a\":", &[], ValueFormat::Json).is_err());
+}
+
+#[test]
+fn test_nested_layers() {
+    let bytes = encode_value(r#"{"a":1}"#, &[ValueLayer::Base64, ValueLayer::Gzip], ValueFormat::Json).unwrap();
+    let decoded = decode_value(&bytes);
+    assert_eq!(decoded.layers, vec![ValueLayer::Base64, ValueLayer::Gzip]);
+    assert_eq!(decoded.format, ValueFormat::Json);
+
+    let bytes = encode_value("[1,2,3]", &[ValueLayer::Hex, ValueLayer::Zlib], ValueFormat::Json).unwrap();
+    let decoded = decode_value(&bytes);
+    assert_eq!(decoded.layers, vec![ValueLayer::Hex, ValueLayer::Zlib]);
+    assert_eq!(decoded.format, ValueFormat::Json);
+}
+
+#[test]
+fn test_php_serialized() {
+    let text = "a:2:{s:4:\"name\";s:5:\"alice\";i:0;O:4:\"User\":2:{s:5:\"\0*\0id\";i:7;s:10:\"\0User\0role\";N;}}";
+    let decoded = decode_value(text.as_bytes());
+    assert_eq!(decoded.format, ValueFormat::PhpSerialized);
+    assert_eq!(decoded.text, text);
+
+    let tree = decoded.tree.unwrap();
+    assert_eq!(tree.kind, "array");
+    assert_eq!(child(&tree, "name").value.as_deref(), Some("alice"));
+    let user = child(&tree, "0");
+    assert_eq!(user.kind, "User");
+    assert_eq!(child(user, "id").value.as_deref(), Some("7"));
+    assert_eq!(child(user, "role").kind, "null");
+
+    assert!(encode_value("s:10:\"short\";", &[], ValueFormat::PhpSerialized).is_err());
+}
+
+#[test]
+fn test_java_serialized() {
+    let mut bytes = vec![0xac, 0xed, 0x00, 0x05, 0x73, 0x72, 0x00, 0x04];
+    bytes.extend_from_slice(b"Demo");
+    bytes.extend_from_slice(&[0; 8]);
+    bytes.extend_from_slice(&[0x02, 0x00, 0x02]);
+    bytes.extend_from_slice(&[b'I', 0x00, 0x02]);
+    bytes.extend_from_slice(b"id");
+    bytes.extend_from_slice(&[b'L', 0x00, 0x04]);
+    bytes.extend_from_slice(b"name");
+    bytes.extend_from_slice(&[0x74, 0x00, 0x12]);
+    bytes.extend_from_slice(b"Ljava/lang/String;");
+    bytes.extend_from_slice(&[0x78, 0x70]);
+    bytes.extend_from_slice(&[0x00, 0x00, 0x00, 0x2a, 0x74, 0x00, 0x05]);
+    bytes.extend_from_slice(b"alice");
+
+    let decoded = decode_value(&bytes);
+    assert_eq!(decoded.format, ValueFormat::JavaSerialized);
+    assert!(!decoded.editable);
+
+    let tree = decoded.tree.unwrap();
+    assert_eq!(tree.kind, "Demo");
+    assert_eq!(child(&tree, "id").value.as_deref(), Some("42"));
+    assert_eq!(child(&tree, "name").value.as_deref(), Some("alice"));
+
+    assert!(encode_value(&decoded.text, &[], ValueFormat::JavaSerialized).is_err());
+}
+
+#[test]
+fn test_java_class_desc_depth_limit() {
+    // 每层父类描述：TC_CLASSDESC、类名、serialVersionUID、flags、字段数、TC_ENDBLOCKDATA
+    let mut bytes = vec![0xac, 0xed, 0x00, 0x05, 0x73];
+    for _ in 0..100_000 {
+        bytes.extend_from_slice(&[0x72, 0x00, 0x01, b'A']);
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.extend_from_slice(&[0x02, 0x00, 0x00, 0x78]);
+    }
+    bytes.push(0x70);
+
+    let decoded = decode_value(&bytes);
+    assert!(decoded.tree.is_none());
+    assert_eq!(decoded.format, ValueFormat::Binary);
+}
+
+#[test]
+fn test_binary_round_trip() {
+    let raw = [0xff, 0x00, 0xfe, 0x10];
+    let decoded = decode_value(&raw);
+    assert_eq!(decoded.format, ValueFormat::Binary);
+    assert_eq!(decoded.text, "ff00fe10");
+    assert_eq!(encode_value(&decoded.text, &[], ValueFormat::Binary).unwrap(), raw);
+}
+
+#[test]
+fn test_decompression_limit() {
+    // 解压后超过上限的值不解码，按原始二进制展示
+    let oversized = vec![0u8; MAX_DECODED_BYTES + 1];
+    for layer in [ValueLayer::Gzip, ValueLayer::Zlib, ValueLayer::Zstd] {
+        let compressed = wrap_layer(layer, &oversized).unwrap();
+        let decoded = decode_value(&compressed);
+        assert!(decoded.layers.is_empty(), "{:?}", layer);
+        assert_eq!(decoded.format, ValueFormat::Binary);
+        assert_eq!(decoded.decoded_size, compressed.len());
+    }
+}
@@ -0,0 +1,397 @@
+// Redis 键空间命名空间树
+//
+// 按分隔符把键名拆成层级，展开某个节点时只 SCAN 该前缀下的键并按下一级
+// 分组，因此整棵树是按需加载的。子树的批量操作（删除、设置 TTL、导出）
+// 同样基于 SCAN MATCH <前缀>*，不会使用阻塞的 KEYS 命令。
+
+use super::enhanced_types::{RedisDataType, RedisValue};
+use super::redis::RedisConnection;
+use super::redis_modules::parse_ts_samples;
+use super::redis_streams::parse_stream_entries;
+use redis::{Connection, RedisResult, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+#[cfg(test)]
+mod tests;
+
+/// 命名空间树查询
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyspaceQuery {
+    /// 要展开的节点路径，根节点为空字符串；命名空间节点以分隔符结尾
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default = "default_delimiter")]
+    pub delimiter: String,
+    /// SCAN COUNT 提示值
+    #[serde(default = "default_scan_count")]
+    pub scan_count: usize,
+    /// 单次展开最多扫描的键数
+    #[serde(default = "default_max_keys")]
+    pub max_keys: u64,
+}
+
+fn default_delimiter() -> String {
+    ":".to_string()
+}
+
+fn default_scan_count() -> usize {
+    1000
+}
+
+fn default_max_keys() -> u64 {
+    100_000
+}
+
+impl Default for KeyspaceQuery {
+    fn default() -> Self {
+        Self {
+            prefix: String::new(),
+            delimiter: default_delimiter(),
+            scan_count: default_scan_count(),
+            max_keys: default_max_keys(),
+        }
+    }
+}
+
+/// 命名空间树节点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyspaceNode {
+    /// 当前层级的名称
+    pub name: String,
+    /// 完整路径：命名空间为带分隔符的前缀，键为完整键名
+    pub path: String,
+    /// 是否为键（叶子节点）
+    pub is_key: bool,
+    /// 子树中的键数量，键节点为 1
+    pub key_count: u64,
+    /// 仅键节点有值
+    pub data_type: Option<RedisDataType>,
+    /// 剩余生存时间（秒），None 表示永不过期
+    pub ttl: Option<i64>,
+}
+
+/// 展开一个节点的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyspaceLevel {
+    pub prefix: String,
+    /// 命名空间在前，键在后，各自按名称排序
+    pub nodes: Vec<KeyspaceNode>,
+    pub scanned_keys: u64,
+    /// 达到 max_keys 上限，计数可能不完整
+    pub truncated: bool,
+}
+
+/// 导出的键
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedKey {
+    pub key: String,
+    /// TYPE 返回的类型名
+    pub key_type: String,
+    pub ttl: Option<i64>,
+    /// 不支持导出的模块类型为 None
+    pub value: Option<RedisValue>,
+}
+
+/// 子树导出结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyspaceExport {
+    pub prefix: String,
+    pub keys: Vec<ExportedKey>,
+    pub truncated: bool,
+}
+
+impl RedisConnection {
+    /// 子树操作的目标，只持有共享连接，调用方可先释放连接表的锁
+    pub fn keyspace_target(&self) -> anyhow::Result<KeyspaceTarget> {
+        Ok(KeyspaceTarget { connection: self.connection()? })
+    }
+}
+
+/// 命名空间树操作目标
+pub struct KeyspaceTarget {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl KeyspaceTarget {
+    /// 展开命名空间树的一个节点
+    pub async fn children(&self, query: &KeyspaceQuery) -> anyhow::Result<KeyspaceLevel> {
+        if query.delimiter.is_empty() {
+            return Err(anyhow::anyhow!("分隔符不能为空"));
+        }
+        let mut conn = self.connection.lock().await;
+
+        let (keys, truncated) = scan_prefix(&mut conn, &query.prefix, query.scan_count, query.max_keys)?;
+        let keys: Vec<String> = keys.iter().map(|k| String::from_utf8_lossy(k).into_owned()).collect();
+        let grouped = group_level(&query.prefix, &query.delimiter, &keys);
+
+        let mut nodes: Vec<KeyspaceNode> = grouped.namespaces.into_iter()
+            .map(|(name, key_count)| KeyspaceNode {
+                path: format!("{}{}{}", query.prefix, name, query.delimiter),
+                name,
+                is_key: false,
+                key_count,
+                data_type: None,
+                ttl: None,
+            })
+            .collect();
+
+        if !grouped.keys.is_empty() {
+            let mut pipe = redis::pipe();
+            for key in &grouped.keys {
+                pipe.cmd("TYPE").arg(key).cmd("PTTL").arg(key);
+            }
+            let details: Vec<(String, i64)> = pipe.query(&mut *conn)
+                .map_err(|e| anyhow::anyhow!("获取键类型失败: {}", e))?;
+
+            nodes.extend(grouped.keys.into_iter().zip(details).map(|(key, (key_type, pttl))| KeyspaceNode {
+                name: key[query.prefix.len()..].to_string(),
+                path: key,
+                is_key: true,
+                key_count: 1,
+                data_type: RedisDataType::from_type_name(&key_type),
+                ttl: if pttl >= 0 { Some(pttl / 1000) } else { None },
+            }));
+        }
+
+        Ok(KeyspaceLevel {
+            prefix: query.prefix.clone(),
+            nodes,
+            scanned_keys: keys.len() as u64,
+            truncated,
+        })
+    }
+
+    /// 删除前缀下的所有键，返回删除数量
+    ///
+    /// 每批 SCAN 结果立即 UNLINK，批次之间释放连接锁；重复返回的键
+    /// 再次 UNLINK 时计数为 0，因此不需要去重。
+    pub async fn delete(&self, prefix: &str) -> anyhow::Result<u64> {
+        require_prefix(prefix)?;
+        let mut scan = PrefixScan::new(prefix, default_scan_count(), false);
+        let mut deleted = 0;
+        loop {
+            let mut conn = self.connection.lock().await;
+            let Some(batch) = scan.next_batch(&mut conn)? else {
+                return Ok(deleted);
+            };
+            if batch.is_empty() {
+                continue;
+            }
+            let count: RedisResult<u64> = redis::cmd("UNLINK").arg(&batch).query(&mut *conn);
+            deleted += count.map_err(|e| anyhow::anyhow!("删除键失败: {}", e))?;
+        }
+    }
+
+    /// 为前缀下的所有键设置过期时间，ttl 为 None 时移除过期时间；返回生效的键数量
+    ///
+    /// 每批 SCAN 结果立即处理，批次之间释放连接锁；只记录已处理的键名用于
+    /// 去重，避免 SCAN 重复返回的键被重复计数。
+    pub async fn expire(&self, prefix: &str, ttl: Option<i64>) -> anyhow::Result<u64> {
+        require_prefix(prefix)?;
+        if ttl.is_some_and(|ttl| ttl <= 0) {
+            return Err(anyhow::anyhow!("过期时间必须大于0"));
+        }
+        let mut scan = PrefixScan::new(prefix, default_scan_count(), true);
+        let mut updated = 0;
+        loop {
+            let mut conn = self.connection.lock().await;
+            let Some(batch) = scan.next_batch(&mut conn)? else {
+                return Ok(updated);
+            };
+            if batch.is_empty() {
+                continue;
+            }
+            let mut pipe = redis::pipe();
+            for key in &batch {
+                match ttl {
+                    Some(ttl) => pipe.cmd("EXPIRE").arg(key).arg(ttl),
+                    None => pipe.cmd("PERSIST").arg(key),
+                };
+            }
+            let results: Vec<u64> = pipe.query(&mut *conn)
+                .map_err(|e| anyhow::anyhow!("设置过期时间失败: {}", e))?;
+            updated += results.into_iter().sum::<u64>();
+        }
+    }
+
+    /// 导出前缀下的键（类型、TTL 和值）
+    pub async fn export(&self, prefix: &str, max_keys: u64) -> anyhow::Result<KeyspaceExport> {
+        require_prefix(prefix)?;
+        let mut conn = self.connection.lock().await;
+
+        let (keys, truncated) = scan_prefix(&mut conn, prefix, default_scan_count(), max_keys)?;
+        let mut exported = Vec::with_capacity(keys.len());
+        for key in &keys {
+            let (key_type, pttl): (String, i64) = redis::pipe()
+                .cmd("TYPE").arg(key)
+                .cmd("PTTL").arg(key)
+                .query(&mut *conn)
+                .map_err(|e| anyhow::anyhow!("获取键类型失败: {}", e))?;
+            // 扫描期间被删除的键
+            if key_type == "none" {
+                continue;
+            }
+            let value = read_value(&mut conn, key, &key_type)?;
+            exported.push(ExportedKey {
+                key: String::from_utf8_lossy(key).into_owned(),
+                key_type,
+                ttl: if pttl >= 0 { Some(pttl / 1000) } else { None },
+                value,
+            });
+        }
+
+        Ok(KeyspaceExport {
+            prefix: prefix.to_string(),
+            keys: exported,
+            truncated,
+        })
+    }
+}
+
+/// 子树批量操作必须指定前缀，整库操作请使用 FLUSHDB
+fn require_prefix(prefix: &str) -> anyhow::Result<()> {
+    if prefix.is_empty() {
+        return Err(anyhow::anyhow!("批量操作需要指定命名空间前缀"));
+    }
+    Ok(())
+}
+
+/// 按批次 SCAN 前缀下的键
+///
+/// SCAN 在 rehash 期间可能重复返回同一个键，开启去重时会过滤已返回过的键。
+struct PrefixScan {
+    pattern: String,
+    scan_count: usize,
+    cursor: u64,
+    finished: bool,
+    seen: Option<HashSet<Vec<u8>>>,
+}
+
+impl PrefixScan {
+    fn new(prefix: &str, scan_count: usize, dedupe: bool) -> Self {
+        Self {
+            pattern: format!("{}*", escape_glob(prefix)),
+            scan_count: scan_count.max(1),
+            cursor: 0,
+            finished: false,
+            seen: dedupe.then(HashSet::new),
+        }
+    }
+
+    /// 取下一批键，扫描结束后返回 None；单批可能为空
+    fn next_batch(&mut self, conn: &mut Connection) -> anyhow::Result<Option<Vec<Vec<u8>>>> {
+        if self.finished {
+            return Ok(None);
+        }
+        let (next_cursor, batch): (u64, Vec<Vec<u8>>) = redis::cmd("SCAN")
+            .arg(self.cursor)
+            .arg("MATCH")
+            .arg(&self.pattern)
+            .arg("COUNT")
+            .arg(self.scan_count)
+            .query(conn)
+            .map_err(|e| anyhow::anyhow!("SCAN 失败: {}", e))?;
+        self.cursor = next_cursor;
+        self.finished = next_cursor == 0;
+        Ok(Some(dedupe_keys(self.seen.as_mut(), batch)))
+    }
+}
+
+/// 过滤已出现过的键，seen 为 None 时原样返回
+fn dedupe_keys(seen: Option<&mut HashSet<Vec<u8>>>, batch: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+    match seen {
+        Some(seen) => batch.into_iter().filter(|key| seen.insert(key.clone())).collect(),
+        None => batch,
+    }
+}
+
+/// SCAN 前缀下不重复的键，最多 max_keys 个；返回键列表和是否因达到上限而截断
+fn scan_prefix(
+    conn: &mut Connection,
+    prefix: &str,
+    scan_count: usize,
+    max_keys: u64,
+) -> anyhow::Result<(Vec<Vec<u8>>, bool)> {
+    let mut scan = PrefixScan::new(prefix, scan_count, true);
+    let mut keys = Vec::new();
+
+    while let Some(batch) = scan.next_batch(conn)? {
+        keys.extend(batch);
+        if keys.len() as u64 >= max_keys {
+            let truncated = !scan.finished || keys.len() as u64 > max_keys;
+            keys.truncate(max_keys as usize);
+            return Ok((keys, truncated));
+        }
+    }
+    Ok((keys, false))
+}
+
+/// 转义 glob 特殊字符，使前缀按字面匹配
+pub(crate) fn escape_glob(prefix: &str) -> String {
+    let mut escaped = String::with_capacity(prefix.len());
+    for c in prefix.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 一个层级的分组结果
+#[derive(Debug, Default)]
+pub(crate) struct GroupedLevel {
+    /// 下一级命名空间及其子树键数量
+    pub namespaces: BTreeMap<String, u64>,
+    /// 直接位于该层级的键（完整键名，已排序）
+    pub keys: Vec<String>,
+}
+
+/// 将前缀下的键按下一级命名空间分组
+pub(crate) fn group_level(prefix: &str, delimiter: &str, keys: &[String]) -> GroupedLevel {
+    let mut level = GroupedLevel::default();
+    for key in keys {
+        let Some(rest) = key.strip_prefix(prefix) else {
+            continue;
+        };
+        match rest.find(delimiter) {
+            Some(pos) => *level.namespaces.entry(rest[..pos].to_string()).or_insert(0) += 1,
+            None => level.keys.push(key.clone()),
+        }
+    }
+    level.keys.sort();
+    level
+}
+
+/// 按类型读取完整的值
+fn read_value(conn: &mut Connection, key: &[u8], key_type: &str) -> anyhow::Result<Option<RedisValue>> {
+    let value = match key_type {
+        "string" => {
+            let value: Vec<u8> = redis::cmd("GET").arg(key).query(conn)?;
+            RedisValue::String { value: String::from_utf8_lossy(&value).into_owned() }
+        }
+        "hash" => RedisValue::Hash { fields: redis::cmd("HGETALL").arg(key).query(conn)? },
+        "list" => RedisValue::List { items: redis::cmd("LRANGE").arg(key).arg(0).arg(-1).query(conn)? },
+        "set" => RedisValue::Set { members: redis::cmd("SMEMBERS").arg(key).query(conn)? },
+        "zset" => RedisValue::ZSet {
+            members: redis::cmd("ZRANGE").arg(key).arg(0).arg(-1).arg("WITHSCORES").query(conn)?,
+        },
+        "stream" => {
+            let entries: Value = redis::cmd("XRANGE").arg(key).arg("-").arg("+").query(conn)?;
+            RedisValue::Stream { entries: parse_stream_entries(&entries) }
+        }
+        "ReJSON-RL" => {
+            let document: String = redis::cmd("JSON.GET").arg(key).query(conn)?;
+            RedisValue::Json { document: serde_json::from_str(&document)? }
+        }
+        "TSDB-TYPE" => {
+            let samples: Value = redis::cmd("TS.RANGE").arg(key).arg("-").arg("+").query(conn)?;
+            RedisValue::TimeSeries { samples: parse_ts_samples(&samples) }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
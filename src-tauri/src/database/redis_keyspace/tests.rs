@@ -0,0 +1,62 @@
+use super::*;
+
+fn keys(names: &[&str]) -> Vec<String> {
+    names.iter().map(|k| k.to_string()).collect()
+}
+
+#[test]
+fn test_group_level_root() {
+    let all = keys(&[
+        "svc:tenant:user:123:session",
+        "svc:tenant:user:456:session",
+        "svc:other:config",
+        "cache:a",
+        "counter",
+    ]);
+    let level = group_level("", ":", &all);
+
+    assert_eq!(level.namespaces.get("svc"), Some(&3));
+    assert_eq!(level.namespaces.get("cache"), Some(&1));
+    assert_eq!(level.keys, vec!["counter".to_string()]);
+}
+
+#[test]
+fn test_group_level_nested() {
+    let all = keys(&[
+        "svc:tenant:user:123:session",
+        "svc:tenant:user:123:profile",
+        "svc:tenant:user:456:session",
+        "svc:tenant:user:789",
+    ]);
+    let level = group_level("svc:tenant:user:", ":", &all);
+
+    assert_eq!(level.namespaces.len(), 2);
+    assert_eq!(level.namespaces.get("123"), Some(&2));
+    assert_eq!(level.namespaces.get("456"), Some(&1));
+    assert_eq!(level.keys, vec!["svc:tenant:user:789".to_string()]);
+}
+
+#[test]
+fn test_group_level_custom_delimiter() {
+    let all = keys(&["app/v1/users", "app/v1/orders", "app/v2"]);
+    let level = group_level("app/", "/", &all);
+
+    assert_eq!(level.namespaces.get("v1"), Some(&2));
+    assert_eq!(level.keys, vec!["app/v2".to_string()]);
+}
+
+#[test]
+fn test_escape_glob() {
+    assert_eq!(escape_glob("svc:tenant:"), "svc:tenant:");
+    assert_eq!(escape_glob("a*b?[c]\\"), "a\\*b\\?\\[c\\]\\\\");
+}
+
+#[test]
+fn test_dedupe_keys() {
+    let batch = |names: &[&str]| names.iter().map(|k| k.as_bytes().to_vec()).collect::<Vec<_>>();
+    let mut seen = HashSet::new();
+
+    assert_eq!(dedupe_keys(Some(&mut seen), batch(&["a", "b", "a"])), batch(&["a", "b"]));
+    assert_eq!(dedupe_keys(Some(&mut seen), batch(&["b", "c"])), batch(&["c"]));
+    assert_eq!(dedupe_keys(None, batch(&["a", "a"])), batch(&["a", "a"]));
+}
@@ -0,0 +1,576 @@
+// Redis 大键与内存分析
+//
+// 后台任务使用独立的异步连接 SCAN 整个键空间，对每个键采样 MEMORY USAGE、
+// 元素数量和 TTL，汇总出最大的 N 个键、按前缀/类型的内存分布以及 TTL 分布。
+// 每批 SCAN 结束后推送进度，取消标志在批次之间检查。
+
+use super::enhanced_types::RedisMemoryStats;
+use super::redis::RedisConnection;
+use super::redis_batch::is_connection_error;
+use chrono::{DateTime, Utc};
+use redis::{Client, RedisResult};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[cfg(test)]
+mod tests;
+
+// ===== 分析配置 =====
+
+/// 内存分析选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryAnalysisOptions {
+    /// 要分析的数据库，默认使用连接当前的数据库
+    pub database: Option<i64>,
+    /// SCAN MATCH 模式
+    pub pattern: Option<String>,
+    /// SCAN COUNT 提示值，同时也是每批采样的键数
+    #[serde(default = "default_scan_count")]
+    pub scan_count: usize,
+    /// 最多分析的键数，None 表示扫描全部
+    pub max_keys: Option<u64>,
+    /// 返回最大的键的数量
+    #[serde(default = "default_top_n")]
+    pub top_n: usize,
+    /// 前缀分隔符
+    #[serde(default = "default_delimiter")]
+    pub delimiter: String,
+    /// 前缀统计使用的层级数
+    #[serde(default = "default_prefix_depth")]
+    pub prefix_depth: usize,
+    /// 返回的前缀统计条数上限（按内存排序）
+    #[serde(default = "default_max_prefixes")]
+    pub max_prefixes: usize,
+    /// MEMORY USAGE 对嵌套类型的采样数，0 表示全部采样
+    #[serde(default = "default_memory_samples")]
+    pub memory_samples: usize,
+}
+
+fn default_scan_count() -> usize {
+    500
+}
+
+fn default_top_n() -> usize {
+    50
+}
+
+fn default_delimiter() -> String {
+    ":".to_string()
+}
+
+fn default_prefix_depth() -> usize {
+    2
+}
+
+fn default_max_prefixes() -> usize {
+    200
+}
+
+fn default_memory_samples() -> usize {
+    5
+}
+
+impl Default for MemoryAnalysisOptions {
+    fn default() -> Self {
+        Self {
+            database: None,
+            pattern: None,
+            scan_count: default_scan_count(),
+            max_keys: None,
+            top_n: default_top_n(),
+            delimiter: default_delimiter(),
+            prefix_depth: default_prefix_depth(),
+            max_prefixes: default_max_prefixes(),
+            memory_samples: default_memory_samples(),
+        }
+    }
+}
+
+// ===== 分析结果 =====
+
+/// 大键信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BigKeyInfo {
+    pub key: String,
+    /// TYPE 返回的类型名
+    pub key_type: String,
+    pub memory_usage: u64,
+    /// 元素数量（字符串为字节长度）
+    pub element_count: Option<u64>,
+    /// 剩余生存时间（秒），None 表示永不过期
+    pub ttl: Option<i64>,
+}
+
+/// 按前缀/类型汇总的内存
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MemoryGroupStats {
+    pub name: String,
+    pub key_count: u64,
+    pub memory: u64,
+}
+
+/// TTL 分布区间
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtlBucket {
+    pub label: String,
+    /// 区间下限（秒，包含），None 表示永不过期
+    pub min_seconds: Option<i64>,
+    /// 区间上限（秒，不包含），None 表示无上限
+    pub max_seconds: Option<i64>,
+    pub key_count: u64,
+    pub memory: u64,
+}
+
+/// 内存分析报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryAnalysisReport {
+    pub job_id: String,
+    pub database: i64,
+    pub scanned_keys: u64,
+    /// 所有已分析键的 MEMORY USAGE 之和
+    pub total_memory: u64,
+    pub top_keys: Vec<BigKeyInfo>,
+    pub by_prefix: Vec<MemoryGroupStats>,
+    pub by_type: Vec<MemoryGroupStats>,
+    pub ttl_distribution: Vec<TtlBucket>,
+    pub memory_stats: Option<RedisMemoryStats>,
+    /// 任务被取消时报告只包含已扫描的部分
+    pub cancelled: bool,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+}
+
+/// 分析进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryAnalysisProgress {
+    pub job_id: String,
+    pub scanned_keys: u64,
+    /// 开始时的 DBSIZE，用于估算进度；指定了匹配模式时无法预知总数，
+    /// 与 scanned_keys 相同
+    pub total_keys: u64,
+    pub total_memory: u64,
+}
+
+/// 分析结果接收端，由命令层实现（转发为 Tauri 事件）
+pub trait MemoryAnalysisSink: Send + Sync + 'static {
+    fn on_progress(&self, progress: MemoryAnalysisProgress);
+    fn on_complete(&self, job_id: &str, result: Result<MemoryAnalysisReport, String>);
+}
+
+/// 运行中的分析任务
+pub struct MemoryAnalysisHandle {
+    pub connection_id: String,
+    cancelled: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+}
+
+impl MemoryAnalysisHandle {
+    /// 请求取消，任务在当前批次结束后停止并推送部分结果
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+}
+
+impl RedisConnection {
+    /// 从 INFO memory 读取内存统计
+    pub async fn memory_stats(&self) -> anyhow::Result<RedisMemoryStats> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let info: RedisResult<String> = redis::cmd("INFO").arg("memory").query(&mut *conn);
+        let info = info.map_err(|e| anyhow::anyhow!("INFO memory 失败: {}", e))?;
+
+        Ok(parse_memory_stats(&info))
+    }
+}
+
+/// 启动后台分析任务
+pub fn start_analysis(
+    client: Client,
+    job_id: String,
+    connection_id: String,
+    database: i64,
+    options: MemoryAnalysisOptions,
+    sink: Arc<dyn MemoryAnalysisSink>,
+) -> MemoryAnalysisHandle {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let finished = Arc::new(AtomicBool::new(false));
+
+    let task_cancelled = cancelled.clone();
+    let task_finished = finished.clone();
+    tokio::spawn(async move {
+        let result = run_analysis(&client, &job_id, database, &options, &task_cancelled, sink.as_ref())
+            .await
+            .map_err(|e| e.to_string());
+        task_finished.store(true, Ordering::Relaxed);
+        sink.on_complete(&job_id, result);
+    });
+
+    MemoryAnalysisHandle {
+        connection_id,
+        cancelled,
+        finished,
+    }
+}
+
+async fn run_analysis(
+    client: &Client,
+    job_id: &str,
+    database: i64,
+    options: &MemoryAnalysisOptions,
+    cancelled: &AtomicBool,
+    sink: &dyn MemoryAnalysisSink,
+) -> anyhow::Result<MemoryAnalysisReport> {
+    let started_at = Utc::now();
+    let start = std::time::Instant::now();
+
+    let mut conn = client.get_async_connection().await
+        .map_err(|e| anyhow::anyhow!("创建分析连接失败: {}", e))?;
+    redis::cmd("SELECT").arg(database).query_async::<_, ()>(&mut conn).await
+        .map_err(|e| anyhow::anyhow!("切换到数据库 {} 失败: {}", database, e))?;
+
+    let pattern = options.pattern.as_deref().filter(|p| !p.is_empty());
+    // 带匹配模式时 DBSIZE 不代表需要扫描的键数，改为报告已扫描数
+    let db_size: Option<u64> = match pattern {
+        Some(_) => None,
+        None => Some(redis::cmd("DBSIZE").query_async(&mut conn).await.unwrap_or(0)),
+    };
+    let mut aggregator = MemoryAggregator::new(options);
+    let mut cursor: u64 = 0;
+
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let mut scan = redis::cmd("SCAN");
+        scan.arg(cursor);
+        if let Some(pattern) = pattern {
+            scan.arg("MATCH").arg(pattern);
+        }
+        scan.arg("COUNT").arg(options.scan_count.max(1));
+        let (next_cursor, mut keys): (u64, Vec<Vec<u8>>) = scan.query_async(&mut conn).await
+            .map_err(|e| anyhow::anyhow!("SCAN 失败: {}", e))?;
+
+        if let Some(max_keys) = options.max_keys {
+            let remaining = max_keys.saturating_sub(aggregator.scanned_keys) as usize;
+            keys.truncate(remaining);
+        }
+
+        if !keys.is_empty() {
+            sample_keys(&mut conn, &keys, options, &mut aggregator).await?;
+            sink.on_progress(MemoryAnalysisProgress {
+                job_id: job_id.to_string(),
+                scanned_keys: aggregator.scanned_keys,
+                total_keys: db_size.unwrap_or(aggregator.scanned_keys),
+                total_memory: aggregator.total_memory,
+            });
+        }
+
+        cursor = next_cursor;
+        let reached_limit = options.max_keys.is_some_and(|max| aggregator.scanned_keys >= max);
+        if cursor == 0 || reached_limit {
+            break;
+        }
+    }
+
+    let info: Option<String> = redis::cmd("INFO").arg("memory").query_async(&mut conn).await.ok();
+
+    Ok(aggregator.into_report(
+        job_id.to_string(),
+        database,
+        info.as_deref().map(parse_memory_stats),
+        cancelled.load(Ordering::Relaxed),
+        started_at,
+        start.elapsed().as_millis() as u64,
+    ))
+}
+
+/// 对一批键采样类型、内存、TTL 和元素数量
+async fn sample_keys(
+    conn: &mut redis::aio::Connection,
+    keys: &[Vec<u8>],
+    options: &MemoryAnalysisOptions,
+    aggregator: &mut MemoryAggregator,
+) -> anyhow::Result<()> {
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.cmd("TYPE").arg(key);
+        pipe.cmd("MEMORY").arg("USAGE").arg(key).arg("SAMPLES").arg(options.memory_samples);
+        pipe.cmd("PTTL").arg(key);
+    }
+    let replies: Vec<(String, Option<u64>, i64)> = pipe.query_async(conn).await
+        .map_err(|e| anyhow::anyhow!("采样键信息失败: {}", e))?;
+
+    // 元素数量只对已知类型查询，记录每条命令对应的键
+    let mut count_pipe = redis::pipe();
+    let mut count_targets = Vec::new();
+    for (index, (key_type, _, _)) in replies.iter().enumerate() {
+        if let Some(command) = element_count_command(key_type) {
+            count_pipe.cmd(command).arg(&keys[index]);
+            count_targets.push((index, command));
+        }
+    }
+    let mut element_counts = vec![None; keys.len()];
+    if !count_targets.is_empty() {
+        match count_pipe.query_async::<_, Vec<u64>>(conn).await {
+            Ok(counts) => {
+                for ((index, _), count) in count_targets.into_iter().zip(counts) {
+                    element_counts[index] = Some(count);
+                }
+            }
+            Err(e) if is_connection_error(&e) => {
+                return Err(anyhow::anyhow!("统计元素数量失败: {}", e));
+            }
+            // 任一键出错（例如扫描期间被改成其他类型返回 WRONGTYPE）都会让整个管道失败，
+            // 逐个重新统计，出错的键元素数量记为未知
+            Err(_) => {
+                for (index, command) in count_targets {
+                    let count: RedisResult<u64> = redis::cmd(command).arg(&keys[index]).query_async(conn).await;
+                    element_counts[index] = match count {
+                        Ok(count) => Some(count),
+                        Err(e) if is_connection_error(&e) => {
+                            return Err(anyhow::anyhow!("统计元素数量失败: {}", e));
+                        }
+                        Err(_) => None,
+                    };
+                }
+            }
+        }
+    }
+
+    for (index, (key_type, memory, pttl)) in replies.into_iter().enumerate() {
+        // 扫描期间被删除的键
+        if key_type == "none" {
+            continue;
+        }
+        aggregator.add(BigKeyInfo {
+            key: String::from_utf8_lossy(&keys[index]).into_owned(),
+            key_type,
+            memory_usage: memory.unwrap_or(0),
+            element_count: element_counts[index],
+            ttl: if pttl >= 0 { Some(pttl / 1000) } else { None },
+        });
+    }
+
+    Ok(())
+}
+
+/// 各类型对应的元素数量命令
+fn element_count_command(key_type: &str) -> Option<&'static str> {
+    match key_type {
+        "string" => Some("STRLEN"),
+        "hash" => Some("HLEN"),
+        "list" => Some("LLEN"),
+        "set" => Some("SCARD"),
+        "zset" => Some("ZCARD"),
+        "stream" => Some("XLEN"),
+        _ => None,
+    }
+}
+
+// ===== 汇总 =====
+
+/// TTL 分布区间定义：(标签, 下限, 上限)
+const TTL_BUCKETS: &[(&str, i64, Option<i64>)] = &[
+    ("< 1m", 0, Some(60)),
+    ("1m - 1h", 60, Some(3600)),
+    ("1h - 1d", 3600, Some(86400)),
+    ("1d - 7d", 86400, Some(604800)),
+    ("> 7d", 604800, None),
+];
+
+/// 增量汇总采样结果
+pub(crate) struct MemoryAggregator {
+    top_n: usize,
+    delimiter: String,
+    prefix_depth: usize,
+    max_prefixes: usize,
+    scanned_keys: u64,
+    total_memory: u64,
+    // SCAN 可能多次返回同一个键，只记录键名的哈希以控制内存占用
+    seen_keys: HashSet<u64>,
+    // 小顶堆，只保留最大的 top_n 个键
+    top_keys: BinaryHeap<Reverse<(u64, String)>>,
+    top_key_details: HashMap<String, BigKeyInfo>,
+    by_prefix: HashMap<String, MemoryGroupStats>,
+    by_type: HashMap<String, MemoryGroupStats>,
+    no_expiry: MemoryGroupStats,
+    ttl_buckets: Vec<MemoryGroupStats>,
+}
+
+impl MemoryAggregator {
+    pub(crate) fn new(options: &MemoryAnalysisOptions) -> Self {
+        Self {
+            top_n: options.top_n,
+            delimiter: options.delimiter.clone(),
+            prefix_depth: options.prefix_depth,
+            max_prefixes: options.max_prefixes,
+            scanned_keys: 0,
+            total_memory: 0,
+            seen_keys: HashSet::new(),
+            top_keys: BinaryHeap::new(),
+            top_key_details: HashMap::new(),
+            by_prefix: HashMap::new(),
+            by_type: HashMap::new(),
+            no_expiry: MemoryGroupStats::default(),
+            ttl_buckets: vec![MemoryGroupStats::default(); TTL_BUCKETS.len()],
+        }
+    }
+
+    pub(crate) fn add(&mut self, key: BigKeyInfo) {
+        let mut hasher = DefaultHasher::new();
+        key.key.hash(&mut hasher);
+        if !self.seen_keys.insert(hasher.finish()) {
+            return;
+        }
+
+        self.scanned_keys += 1;
+        self.total_memory += key.memory_usage;
+
+        let prefix = key_prefix(&key.key, &self.delimiter, self.prefix_depth);
+        add_to_group(self.by_prefix.entry(prefix.clone()).or_default(), &prefix, key.memory_usage);
+        add_to_group(self.by_type.entry(key.key_type.clone()).or_default(), &key.key_type, key.memory_usage);
+
+        let ttl_group = match key.ttl {
+            None => &mut self.no_expiry,
+            Some(ttl) => {
+                let index = TTL_BUCKETS.iter()
+                    .position(|(_, _, max)| max.is_none_or(|max| ttl < max))
+                    .unwrap_or(TTL_BUCKETS.len() - 1);
+                &mut self.ttl_buckets[index]
+            }
+        };
+        ttl_group.key_count += 1;
+        ttl_group.memory += key.memory_usage;
+
+        if self.top_n == 0 {
+            return;
+        }
+        let is_bigger = self.top_keys.peek()
+            .is_none_or(|Reverse((smallest, _))| key.memory_usage > *smallest);
+        if self.top_keys.len() < self.top_n || is_bigger {
+            self.top_keys.push(Reverse((key.memory_usage, key.key.clone())));
+            self.top_key_details.insert(key.key.clone(), key);
+            if self.top_keys.len() > self.top_n {
+                if let Some(Reverse((_, evicted))) = self.top_keys.pop() {
+                    self.top_key_details.remove(&evicted);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn into_report(
+        mut self,
+        job_id: String,
+        database: i64,
+        memory_stats: Option<RedisMemoryStats>,
+        cancelled: bool,
+        started_at: DateTime<Utc>,
+        duration_ms: u64,
+    ) -> MemoryAnalysisReport {
+        let mut top_keys: Vec<BigKeyInfo> = self.top_keys.into_sorted_vec()
+            .into_iter()
+            .filter_map(|Reverse((_, key))| self.top_key_details.remove(&key))
+            .collect();
+        top_keys.sort_by_key(|key| Reverse(key.memory_usage));
+
+        let sorted_groups = |groups: HashMap<String, MemoryGroupStats>| {
+            let mut groups: Vec<MemoryGroupStats> = groups.into_values().collect();
+            groups.sort_by(|a, b| b.memory.cmp(&a.memory).then_with(|| a.name.cmp(&b.name)));
+            groups
+        };
+        let mut by_prefix = sorted_groups(self.by_prefix);
+        by_prefix.truncate(self.max_prefixes);
+
+        let mut ttl_distribution = vec![TtlBucket {
+            label: "no expiry".to_string(),
+            min_seconds: None,
+            max_seconds: None,
+            key_count: self.no_expiry.key_count,
+            memory: self.no_expiry.memory,
+        }];
+        ttl_distribution.extend(TTL_BUCKETS.iter().zip(self.ttl_buckets).map(|((label, min, max), stats)| TtlBucket {
+            label: label.to_string(),
+            min_seconds: Some(*min),
+            max_seconds: *max,
+            key_count: stats.key_count,
+            memory: stats.memory,
+        }));
+
+        MemoryAnalysisReport {
+            job_id,
+            database,
+            scanned_keys: self.scanned_keys,
+            total_memory: self.total_memory,
+            top_keys,
+            by_prefix,
+            by_type: sorted_groups(self.by_type),
+            ttl_distribution,
+            memory_stats,
+            cancelled,
+            started_at,
+            duration_ms,
+        }
+    }
+}
+
+fn add_to_group(group: &mut MemoryGroupStats, name: &str, memory: u64) {
+    if group.name.is_empty() {
+        group.name = name.to_string();
+    }
+    group.key_count += 1;
+    group.memory += memory;
+}
+
+/// 取键的前 depth 层命名空间，例如 `svc:tenant:user:1` 在 depth=2 时为 `svc:tenant:`；
+/// 最后一段视为键名本身，不计入前缀，没有命名空间的键返回空字符串
+pub(crate) fn key_prefix(key: &str, delimiter: &str, depth: usize) -> String {
+    if delimiter.is_empty() || depth == 0 {
+        return String::new();
+    }
+    let segments: Vec<&str> = key.split(delimiter).collect();
+    let depth = depth.min(segments.len() - 1);
+    if depth == 0 {
+        return String::new();
+    }
+    let mut prefix = segments[..depth].join(delimiter);
+    prefix.push_str(delimiter);
+    prefix
+}
+
+// ===== INFO 解析 =====
+
+/// 将 INFO 输出解析为字段映射，忽略分节标题和空行
+pub(crate) fn parse_info_fields(info: &str) -> HashMap<String, String> {
+    info.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+/// 从 INFO memory 输出构建内存统计
+pub(crate) fn parse_memory_stats(info: &str) -> RedisMemoryStats {
+    let fields = parse_info_fields(info);
+    let number = |name: &str| fields.get(name).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+
+    RedisMemoryStats {
+        used_memory: number("used_memory"),
+        used_memory_human: fields.get("used_memory_human").cloned().unwrap_or_default(),
+        used_memory_rss: number("used_memory_rss"),
+        used_memory_peak: number("used_memory_peak"),
+        fragmentation_ratio: fields.get("mem_fragmentation_ratio")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0.0),
+    }
+}
@@ -0,0 +1,70 @@
+use super::*;
+
+fn key(name: &str, key_type: &str, memory: u64, ttl: Option<i64>) -> BigKeyInfo {
+    BigKeyInfo {
+        key: name.to_string(),
+        key_type: key_type.to_string(),
+        memory_usage: memory,
+        element_count: None,
+        ttl,
+    }
+}
+
+#[test]
+fn test_key_prefix() {
+    assert_eq!(key_prefix("svc:tenant:user:123:session", ":", 2), "svc:tenant:");
+    assert_eq!(key_prefix("svc:tenant", ":", 2), "svc:");
+    assert_eq!(key_prefix("counter", ":", 2), "");
+    assert_eq!(key_prefix("a/b/c", "/", 1), "a/");
+    assert_eq!(key_prefix("a:b:c", ":", 0), "");
+}
+
+#[test]
+fn test_aggregator_top_keys_and_groups() {
+    let options = MemoryAnalysisOptions {
+        top_n: 2,
+        prefix_depth: 1,
+        ..Default::default()
+    };
+    let mut aggregator = MemoryAggregator::new(&options);
+    aggregator.add(key("user:1", "hash", 100, None));
+    aggregator.add(key("user:2", "hash", 300, Some(30)));
+    aggregator.add(key("cache:a", "string", 200, Some(7200)));
+    aggregator.add(key("lonely", "string", 50, Some(999999)));
+    // SCAN 重复返回的键不重复计数，也不会挤掉前 N 个键的详情
+    aggregator.add(key("user:2", "hash", 300, Some(30)));
+    aggregator.add(key("cache:a", "string", 200, Some(7200)));
+
+    let report = aggregator.into_report("job".to_string(), 0, None, false, Utc::now(), 1);
+
+    assert_eq!(report.scanned_keys, 4);
+    assert_eq!(report.total_memory, 650);
+
+    let top: Vec<&str> = report.top_keys.iter().map(|k| k.key.as_str()).collect();
+    assert_eq!(top, vec!["user:2", "cache:a"]);
+
+    assert_eq!(report.by_prefix[0].name, "user:");
+    assert_eq!(report.by_prefix[0].key_count, 2);
+    assert_eq!(report.by_prefix[0].memory, 400);
+
+    assert_eq!(report.by_type[0].name, "hash");
+    assert_eq!(report.by_type[0].memory, 400);
+
+    let bucket = |label: &str| report.ttl_distribution.iter().find(|b| b.label == label).unwrap();
+    assert_eq!(bucket("no expiry").key_count, 1);
+    assert_eq!(bucket("< 1m").key_count, 1);
+    assert_eq!(bucket("1h - 1d").memory, 200);
+    assert_eq!(bucket("> 7d").key_count, 1);
+}
+
+#[test]
+fn test_parse_memory_stats() {
+    let info = "# Memory\r\nused_memory:1048576\r\nused_memory_human:1.00M\r\nused_memory_rss:2097152\r\nused_memory_peak:3145728\r\nmem_fragmentation_ratio:2.00\r\n";
+    let stats = parse_memory_stats(info);
+
+    assert_eq!(stats.used_memory, 1048576);
+    assert_eq!(stats.used_memory_human, "1.00M");
+    assert_eq!(stats.used_memory_rss, 2097152);
+    assert_eq!(stats.used_memory_peak, 3145728);
+    assert!((stats.fragmentation_ratio - 2.0).abs() < f64::EPSILON);
+}
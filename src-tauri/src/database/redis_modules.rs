@@ -0,0 +1,422 @@
+// Redis Stack 模块：RedisJSON、RediSearch、RedisTimeSeries
+//
+// 通过 MODULE LIST 检测服务端加载的模块，界面据此决定展示哪些查看器。
+// 搜索结果和时间序列转换为表格形式的 EnhancedQueryResult，时间序列使用
+// DisplayMode::TimeSeries 展示为图表。
+
+use super::enhanced_types::{
+    CellValue, ColumnInfo, DatabaseType, DatabaseUIConfig, DisplayMode, EnhancedQueryResult, QueryData,
+    QueryMetadata, TimeSeriesSample,
+};
+use super::redis::RedisConnection;
+use super::redis_value::{value_items, value_to_i64, value_to_map, value_to_opt_string, value_to_string};
+use redis::{RedisResult, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests;
+
+/// MODULE LIST 中的模块
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisModuleInfo {
+    pub name: String,
+    pub version: i64,
+    pub path: Option<String>,
+    pub args: Vec<String>,
+}
+
+/// 服务端的模块支持情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisModuleSupport {
+    pub modules: Vec<RedisModuleInfo>,
+    pub json: bool,
+    pub search: bool,
+    pub timeseries: bool,
+    pub bloom: bool,
+}
+
+/// 搜索索引的字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchFieldInfo {
+    /// 哈希字段名或 JSON 路径
+    pub identifier: String,
+    /// 查询中使用的名称
+    pub attribute: String,
+    /// TEXT、TAG、NUMERIC、GEO、VECTOR 等
+    pub field_type: String,
+    /// SORTABLE、NOSTEM 等其余选项
+    pub options: Vec<String>,
+}
+
+/// FT.INFO 的主要信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchIndexInfo {
+    pub name: String,
+    /// HASH 或 JSON
+    pub key_type: String,
+    pub prefixes: Vec<String>,
+    pub fields: Vec<SearchFieldInfo>,
+    pub num_docs: u64,
+    pub indexing: bool,
+    pub percent_indexed: f64,
+    pub hash_indexing_failures: u64,
+}
+
+/// TS.RANGE 的聚合参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeSeriesAggregation {
+    /// avg、sum、min、max、count 等
+    pub aggregator: String,
+    pub bucket_duration_ms: u64,
+}
+
+/// TS.INFO 的主要信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeSeriesInfo {
+    pub total_samples: u64,
+    pub memory_usage: u64,
+    pub first_timestamp: i64,
+    pub last_timestamp: i64,
+    pub retention_time: u64,
+    pub labels: Vec<(String, String)>,
+}
+
+impl RedisConnection {
+    /// 检测已加载的模块
+    pub async fn detect_modules(&self) -> anyhow::Result<RedisModuleSupport> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd("MODULE").arg("LIST").query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("MODULE LIST 失败: {}", e))?;
+        Ok(module_support(parse_module_list(&reply)))
+    }
+
+    /// JSON.GET，未指定路径时返回整个文档
+    pub async fn json_get(&self, key: &str, paths: &[String]) -> anyhow::Result<serde_json::Value> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Option<String>> = redis::cmd("JSON.GET").arg(key).arg(paths).query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("JSON.GET 失败: {}", e))?
+            .ok_or_else(|| anyhow::anyhow!("键 {} 不存在", key))?;
+        serde_json::from_str(&reply).map_err(|e| anyhow::anyhow!("JSON.GET 返回的内容无法解析: {}", e))
+    }
+
+    /// JSON.SET
+    pub async fn json_set(&self, key: &str, path: &str, json: &str) -> anyhow::Result<()> {
+        serde_json::from_str::<serde_json::Value>(json)
+            .map_err(|e| anyhow::anyhow!("JSON 格式错误: {}", e))?;
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let result: RedisResult<()> = redis::cmd("JSON.SET").arg(key).arg(path).arg(json).query(&mut *conn);
+        result.map_err(|e| anyhow::anyhow!("JSON.SET 失败: {}", e))
+    }
+
+    /// JSON.DEL，返回删除的路径数量
+    pub async fn json_del(&self, key: &str, path: &str) -> anyhow::Result<u64> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let deleted: RedisResult<u64> = redis::cmd("JSON.DEL").arg(key).arg(path).query(&mut *conn);
+        deleted.map_err(|e| anyhow::anyhow!("JSON.DEL 失败: {}", e))
+    }
+
+    /// FT._LIST
+    pub async fn search_indexes(&self) -> anyhow::Result<Vec<String>> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let mut indexes: Vec<String> = redis::cmd("FT._LIST").query(&mut *conn)
+            .map_err(|e| anyhow::anyhow!("FT._LIST 失败: {}", e))?;
+        indexes.sort();
+        Ok(indexes)
+    }
+
+    /// FT.INFO
+    pub async fn search_index_info(&self, index: &str) -> anyhow::Result<SearchIndexInfo> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd("FT.INFO").arg(index).query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("FT.INFO 失败: {}", e))?;
+        Ok(parse_search_index_info(&reply))
+    }
+
+    /// FT.SEARCH，结果以键为第一列展开为表格
+    pub async fn search(
+        &self,
+        index: &str,
+        query: &str,
+        offset: u64,
+        limit: u64,
+    ) -> anyhow::Result<EnhancedQueryResult> {
+        let start = std::time::Instant::now();
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd("FT.SEARCH")
+            .arg(index)
+            .arg(query)
+            .arg("LIMIT")
+            .arg(offset)
+            .arg(limit)
+            .query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("FT.SEARCH 失败: {}", e))?;
+
+        let (total, documents) = parse_search_reply(&reply);
+        let (columns, rows) = search_rows(documents);
+        Ok(table_result(
+            format!("FT.SEARCH {} {} LIMIT {} {}", index, query, offset, limit),
+            columns,
+            rows,
+            Some(total),
+            DisplayMode::Table,
+            start.elapsed().as_millis() as u64,
+        ))
+    }
+
+    /// TS.RANGE，from/to 为毫秒时间戳，None 表示最早/最新
+    pub async fn ts_range(
+        &self,
+        key: &str,
+        from: Option<i64>,
+        to: Option<i64>,
+        count: Option<u64>,
+        aggregation: Option<&TimeSeriesAggregation>,
+    ) -> anyhow::Result<EnhancedQueryResult> {
+        let start = std::time::Instant::now();
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let from = from.map(|ts| ts.to_string()).unwrap_or_else(|| "-".to_string());
+        let to = to.map(|ts| ts.to_string()).unwrap_or_else(|| "+".to_string());
+        let mut cmd = redis::cmd("TS.RANGE");
+        cmd.arg(key).arg(&from).arg(&to);
+        if let Some(count) = count {
+            cmd.arg("COUNT").arg(count);
+        }
+        if let Some(aggregation) = aggregation {
+            cmd.arg("AGGREGATION").arg(&aggregation.aggregator).arg(aggregation.bucket_duration_ms);
+        }
+        let reply: RedisResult<Value> = cmd.query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("TS.RANGE 失败: {}", e))?;
+
+        let rows: Vec<Vec<CellValue>> = parse_ts_samples(&reply).into_iter()
+            .map(|sample| vec![
+                chrono::DateTime::from_timestamp_millis(sample.timestamp)
+                    .map(CellValue::DateTime)
+                    .unwrap_or(CellValue::Integer(sample.timestamp)),
+                CellValue::Float(sample.value),
+            ])
+            .collect();
+        Ok(table_result(
+            format!("TS.RANGE {} {} {}", key, from, to),
+            vec![ColumnInfo::new("timestamp", "TIMESTAMP", true, false), ColumnInfo::new("value", "DOUBLE", true, false)],
+            rows,
+            None,
+            DisplayMode::TimeSeries,
+            start.elapsed().as_millis() as u64,
+        ))
+    }
+
+    /// TS.INFO
+    pub async fn ts_info(&self, key: &str) -> anyhow::Result<TimeSeriesInfo> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd("TS.INFO").arg(key).query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("TS.INFO 失败: {}", e))?;
+        Ok(parse_ts_info(&reply))
+    }
+}
+
+fn table_result(
+    query: String,
+    columns: Vec<ColumnInfo>,
+    rows: Vec<Vec<CellValue>>,
+    total_rows: Option<u64>,
+    display_mode: DisplayMode,
+    execution_time: u64,
+) -> EnhancedQueryResult {
+    let mut ui_config = DatabaseUIConfig::for_db_type(&DatabaseType::Redis);
+    ui_config.display_mode = display_mode;
+
+    EnhancedQueryResult {
+        db_type: DatabaseType::Redis,
+        data: QueryData::Relational {
+            columns,
+            rows,
+            total_rows,
+            affected_rows: 0,
+            schema_info: None,
+        },
+        metadata: QueryMetadata {
+            query,
+            timestamp: chrono::Utc::now(),
+            execution_plan: None,
+            warnings: vec![],
+        },
+        execution_time,
+        ui_config,
+    }
+}
+
+pub(crate) fn parse_module_list(reply: &Value) -> Vec<RedisModuleInfo> {
+    value_items(reply).iter().map(|module| {
+        let module = value_to_map(module);
+        RedisModuleInfo {
+            name: module.get("name").map(|v| value_to_string(v)).unwrap_or_default(),
+            version: module.get("ver").and_then(|v| value_to_i64(v)).unwrap_or(0),
+            path: module.get("path").and_then(|v| value_to_opt_string(v)),
+            args: module.get("args")
+                .map(|args| value_items(args).iter().map(value_to_string).collect())
+                .unwrap_or_default(),
+        }
+    }).collect()
+}
+
+pub(crate) fn module_support(modules: Vec<RedisModuleInfo>) -> RedisModuleSupport {
+    let has = |names: &[&str]| modules.iter().any(|m| names.iter().any(|n| m.name.eq_ignore_ascii_case(n)));
+    RedisModuleSupport {
+        json: has(&["ReJSON"]),
+        search: has(&["search", "searchlight", "ft"]),
+        timeseries: has(&["timeseries"]),
+        bloom: has(&["bf"]),
+        modules,
+    }
+}
+
+pub(crate) fn parse_search_index_info(reply: &Value) -> SearchIndexInfo {
+    let info = value_to_map(reply);
+    let definition = info.get("index_definition").map(|v| value_to_map(v)).unwrap_or_default();
+    let number = |name: &str| info.get(name).and_then(|v| value_to_i64(v)).and_then(|v| u64::try_from(v).ok()).unwrap_or(0);
+
+    SearchIndexInfo {
+        name: info.get("index_name").map(|v| value_to_string(v)).unwrap_or_default(),
+        key_type: definition.get("key_type").map(|v| value_to_string(v)).unwrap_or_default(),
+        prefixes: definition.get("prefixes")
+            .map(|prefixes| value_items(prefixes).iter().map(value_to_string).collect())
+            .unwrap_or_default(),
+        fields: info.get("attributes")
+            .map(|attributes| value_items(attributes).iter().map(parse_search_field).collect())
+            .unwrap_or_default(),
+        num_docs: number("num_docs"),
+        indexing: number("indexing") != 0,
+        percent_indexed: info.get("percent_indexed")
+            .and_then(|v| value_to_string(v).parse().ok())
+            .unwrap_or(0.0),
+        hash_indexing_failures: number("hash_indexing_failures"),
+    }
+}
+
+/// 字段描述中既有 name value 对，也有单独的标志（如 SORTABLE）
+fn parse_search_field(value: &Value) -> SearchFieldInfo {
+    let items: Vec<String> = value_items(value).iter().map(value_to_string).collect();
+    let mut field = SearchFieldInfo {
+        identifier: String::new(),
+        attribute: String::new(),
+        field_type: String::new(),
+        options: vec![],
+    };
+
+    let mut iter = items.into_iter();
+    while let Some(item) = iter.next() {
+        match item.as_str() {
+            "identifier" => field.identifier = iter.next().unwrap_or_default(),
+            "attribute" => field.attribute = iter.next().unwrap_or_default(),
+            "type" => field.field_type = iter.next().unwrap_or_default(),
+            "WEIGHT" | "SEPARATOR" | "PHONETIC" => {
+                let value = iter.next().unwrap_or_default();
+                field.options.push(format!("{} {}", item, value));
+            }
+            _ => field.options.push(item),
+        }
+    }
+    field
+}
+
+/// 搜索结果中的一个文档：键和字段
+pub(crate) type SearchDocument = (String, Vec<(String, String)>);
+
+/// 解析 FT.SEARCH 回复：总数以及每个文档的键和字段
+pub(crate) fn parse_search_reply(reply: &Value) -> (u64, Vec<SearchDocument>) {
+    let items = value_items(reply);
+    let total = items.first().and_then(value_to_i64).and_then(|v| u64::try_from(v).ok()).unwrap_or(0);
+
+    let mut documents = Vec::new();
+    let mut rest = items.iter().skip(1).peekable();
+    while let Some(id) = rest.next() {
+        // NOCONTENT 时没有字段数组
+        let fields = match rest.peek() {
+            Some(Value::Bulk(_)) => rest.next()
+                .map(|fields| value_items(fields)
+                    .chunks(2)
+                    .filter(|pair| pair.len() == 2)
+                    .map(|pair| (value_to_string(&pair[0]), value_to_string(&pair[1])))
+                    .collect())
+                .unwrap_or_default(),
+            _ => vec![],
+        };
+        documents.push((value_to_string(id), fields));
+    }
+    (total, documents)
+}
+
+/// 以首次出现的顺序合并各文档的字段作为列
+pub(crate) fn search_rows(documents: Vec<SearchDocument>) -> (Vec<ColumnInfo>, Vec<Vec<CellValue>>) {
+    let mut names: Vec<String> = Vec::new();
+    for (_, fields) in &documents {
+        for (name, _) in fields {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+    }
+
+    let rows = documents.into_iter().map(|(id, fields)| {
+        let fields: HashMap<String, String> = fields.into_iter().collect();
+        let mut row = vec![CellValue::String(id)];
+        row.extend(names.iter().map(|name| match fields.get(name) {
+            Some(value) => CellValue::String(value.clone()),
+            None => CellValue::Null,
+        }));
+        row
+    }).collect();
+
+    let mut columns = vec![ColumnInfo::new("__key", "KEY", true, false)];
+    columns.extend(names.iter().map(|name| ColumnInfo::new(name.as_str(), "VARCHAR", true, false)));
+    (columns, rows)
+}
+
+/// 解析 TS.RANGE 的 [时间戳, 值] 列表
+pub(crate) fn parse_ts_samples(reply: &Value) -> Vec<TimeSeriesSample> {
+    value_items(reply).iter().filter_map(|sample| {
+        let sample = value_items(sample);
+        Some(TimeSeriesSample {
+            timestamp: value_to_i64(sample.first()?)?,
+            value: value_to_string(sample.get(1)?).parse().ok()?,
+        })
+    }).collect()
+}
+
+pub(crate) fn parse_ts_info(reply: &Value) -> TimeSeriesInfo {
+    let info = value_to_map(reply);
+    let number = |name: &str| info.get(name).and_then(|v| value_to_i64(v)).unwrap_or(0);
+
+    TimeSeriesInfo {
+        total_samples: number("totalSamples").max(0) as u64,
+        memory_usage: number("memoryUsage").max(0) as u64,
+        first_timestamp: number("firstTimestamp"),
+        last_timestamp: number("lastTimestamp"),
+        retention_time: number("retentionTime").max(0) as u64,
+        labels: info.get("labels")
+            .map(|labels| value_items(labels).iter().filter_map(|label| {
+                let pair = value_items(label);
+                Some((value_to_string(pair.first()?), value_to_string(pair.get(1)?)))
+            }).collect())
+            .unwrap_or_default(),
+    }
+}
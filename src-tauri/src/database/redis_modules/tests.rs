@@ -0,0 +1,101 @@
+use super::*;
+
+fn data(s: &str) -> Value {
+    Value::Data(s.as_bytes().to_vec())
+}
+
+#[test]
+fn test_module_detection() {
+    let reply = Value::Bulk(vec![
+        Value::Bulk(vec![data("name"), data("ReJSON"), data("ver"), Value::Int(20607), data("path"), data("/opt/rejson.so"), data("args"), Value::Bulk(vec![])]),
+        Value::Bulk(vec![data("name"), data("search"), data("ver"), Value::Int(20813)]),
+        Value::Bulk(vec![data("name"), data("timeseries"), data("ver"), Value::Int(11011)]),
+    ]);
+    let support = module_support(parse_module_list(&reply));
+
+    assert_eq!(support.modules.len(), 3);
+    assert_eq!(support.modules[0].version, 20607);
+    assert_eq!(support.modules[0].path.as_deref(), Some("/opt/rejson.so"));
+    assert!(support.json && support.search && support.timeseries);
+    assert!(!support.bloom);
+}
+
+#[test]
+fn test_parse_search_index_info() {
+    let reply = Value::Bulk(vec![
+        data("index_name"), data("idx:users"),
+        data("index_definition"), Value::Bulk(vec![
+            data("key_type"), data("JSON"),
+            data("prefixes"), Value::Bulk(vec![data("user:")]),
+            data("default_score"), data("1"),
+        ]),
+        data("attributes"), Value::Bulk(vec![
+            Value::Bulk(vec![
+                data("identifier"), data("$.name"), data("attribute"), data("name"),
+                data("type"), data("TEXT"), data("WEIGHT"), data("1"), data("SORTABLE"),
+            ]),
+        ]),
+        data("num_docs"), data("42"),
+        data("indexing"), Value::Int(0),
+        data("percent_indexed"), data("1"),
+        data("hash_indexing_failures"), Value::Int(0),
+    ]);
+    let info = parse_search_index_info(&reply);
+
+    assert_eq!(info.name, "idx:users");
+    assert_eq!(info.key_type, "JSON");
+    assert_eq!(info.prefixes, vec!["user:"]);
+    assert_eq!(info.num_docs, 42);
+    assert!(!info.indexing);
+    assert!((info.percent_indexed - 1.0).abs() < f64::EPSILON);
+    assert_eq!(info.fields[0].identifier, "$.name");
+    assert_eq!(info.fields[0].field_type, "TEXT");
+    assert_eq!(info.fields[0].options, vec!["WEIGHT 1", "SORTABLE"]);
+}
+
+#[test]
+fn test_search_rows() {
+    let reply = Value::Bulk(vec![
+        Value::Int(5),
+        data("user:1"), Value::Bulk(vec![data("name"), data("alice"), data("age"), data("30")]),
+        data("user:2"), Value::Bulk(vec![data("name"), data("bob"), data("city"), data("Paris")]),
+    ]);
+    let (total, documents) = parse_search_reply(&reply);
+    assert_eq!(total, 5);
+
+    let (columns, rows) = search_rows(documents);
+    let names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["__key", "name", "age", "city"]);
+    assert_eq!(rows[1][0].to_display_string(), "user:2");
+    assert!(rows[1][2].is_null());
+    assert_eq!(rows[1][3].to_display_string(), "Paris");
+
+    // NOCONTENT
+    let reply = Value::Bulk(vec![Value::Int(2), data("user:1"), data("user:2")]);
+    let (_, documents) = parse_search_reply(&reply);
+    assert_eq!(documents.len(), 2);
+    assert!(documents[1].1.is_empty());
+}
+
+#[test]
+fn test_parse_ts() {
+    let reply = Value::Bulk(vec![
+        Value::Bulk(vec![Value::Int(1700000000000), data("1.5")]),
+        Value::Bulk(vec![Value::Int(1700000001000), Value::Status("2".to_string())]),
+    ]);
+    let samples = parse_ts_samples(&reply);
+    assert_eq!(samples, vec![
+        TimeSeriesSample { timestamp: 1700000000000, value: 1.5 },
+        TimeSeriesSample { timestamp: 1700000001000, value: 2.0 },
+    ]);
+
+    let info = Value::Bulk(vec![
+        data("totalSamples"), Value::Int(2),
+        data("retentionTime"), Value::Int(86400000),
+        data("labels"), Value::Bulk(vec![Value::Bulk(vec![data("sensor"), data("temp")])]),
+    ]);
+    let info = parse_ts_info(&info);
+    assert_eq!(info.total_samples, 2);
+    assert_eq!(info.retention_time, 86400000);
+    assert_eq!(info.labels, vec![("sensor".to_string(), "temp".to_string())]);
+}
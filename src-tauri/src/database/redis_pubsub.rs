@@ -0,0 +1,441 @@
+// Redis 发布/订阅与 MONITOR 流式推送
+//
+// 每个订阅使用独立的 Redis 连接：读取任务把消息写入有界缓冲区，
+// 推送任务按批次取出并交给 RedisStreamSink（由命令层转发为 Tauri 事件）。
+// 缓冲区写满时丢弃新消息并计数，避免前端处理不过来时拖垮 Redis 连接。
+
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use redis::Client;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+#[cfg(test)]
+mod tests;
+
+// ===== 订阅配置 =====
+
+/// 订阅类型
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RedisStreamKind {
+    /// SUBSCRIBE / PSUBSCRIBE
+    PubSub,
+    /// MONITOR
+    Monitor,
+}
+
+/// 流式推送选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisStreamOptions {
+    /// 有界缓冲区大小，写满后丢弃新消息
+    #[serde(default = "default_buffer_size")]
+    pub buffer_size: usize,
+    /// 单个事件最多携带的消息数
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+    /// 批量推送的最长等待时间（毫秒）
+    #[serde(default = "default_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+    /// 消息过滤条件
+    #[serde(default)]
+    pub filter: RedisMessageFilter,
+}
+
+fn default_buffer_size() -> usize {
+    1000
+}
+
+fn default_max_batch_size() -> usize {
+    100
+}
+
+fn default_flush_interval_ms() -> u64 {
+    100
+}
+
+impl Default for RedisStreamOptions {
+    fn default() -> Self {
+        Self {
+            buffer_size: default_buffer_size(),
+            max_batch_size: default_max_batch_size(),
+            flush_interval_ms: default_flush_interval_ms(),
+            filter: RedisMessageFilter::default(),
+        }
+    }
+}
+
+/// 消息过滤条件（正则表达式），所有条件同时满足才推送
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RedisMessageFilter {
+    /// 匹配频道名（MONITOR 下匹配命令的第一个参数，通常是键名）
+    pub channel_pattern: Option<String>,
+    /// 匹配消息内容（MONITOR 下匹配完整的命令行）
+    pub payload_pattern: Option<String>,
+    /// 仅 MONITOR：只保留这些命令，为空表示不限制
+    #[serde(default)]
+    pub commands: Vec<String>,
+}
+
+impl RedisMessageFilter {
+    /// 编译过滤条件，正则无效时返回错误
+    pub fn compile(&self) -> anyhow::Result<CompiledMessageFilter> {
+        let compile = |pattern: &Option<String>| -> anyhow::Result<Option<Regex>> {
+            match pattern.as_deref().filter(|p| !p.is_empty()) {
+                Some(p) => Regex::new(p)
+                    .map(Some)
+                    .map_err(|e| anyhow::anyhow!("无效的过滤表达式 {}: {}", p, e)),
+                None => Ok(None),
+            }
+        };
+
+        Ok(CompiledMessageFilter {
+            channel: compile(&self.channel_pattern)?,
+            payload: compile(&self.payload_pattern)?,
+            commands: self.commands.iter().map(|c| c.to_uppercase()).collect(),
+        })
+    }
+}
+
+/// 编译后的过滤条件
+#[derive(Debug, Clone)]
+pub struct CompiledMessageFilter {
+    channel: Option<Regex>,
+    payload: Option<Regex>,
+    commands: HashSet<String>,
+}
+
+impl CompiledMessageFilter {
+    /// 判断消息是否应该推送
+    pub fn matches(&self, message: &RedisStreamMessage) -> bool {
+        match message {
+            RedisStreamMessage::Message { channel, payload, .. } => {
+                self.channel.as_ref().is_none_or(|re| re.is_match(channel))
+                    && self.payload.as_ref().is_none_or(|re| re.is_match(payload))
+            }
+            RedisStreamMessage::Monitor { command, args, raw, .. } => {
+                let first_arg = args.first().map(String::as_str).unwrap_or("");
+                (self.commands.is_empty() || self.commands.contains(command))
+                    && self.channel.as_ref().is_none_or(|re| re.is_match(first_arg))
+                    && self.payload.as_ref().is_none_or(|re| re.is_match(raw))
+            }
+        }
+    }
+}
+
+// ===== 推送数据结构 =====
+
+/// 推送到前端的单条消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum RedisStreamMessage {
+    /// 发布/订阅消息
+    Message {
+        channel: String,
+        /// 通过 PSUBSCRIBE 收到时的匹配模式
+        pattern: Option<String>,
+        payload: String,
+        received_at: DateTime<Utc>,
+    },
+    /// MONITOR 输出的一条命令
+    Monitor {
+        /// 服务端时间戳（秒，含微秒）
+        timestamp: f64,
+        database: i64,
+        client: String,
+        command: String,
+        args: Vec<String>,
+        raw: String,
+    },
+}
+
+/// 一次推送的消息批次
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisStreamBatch {
+    pub subscription_id: String,
+    pub messages: Vec<RedisStreamMessage>,
+    /// 自上一批次以来因缓冲区已满而丢弃的消息数
+    pub dropped: u64,
+}
+
+/// 订阅信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisSubscriptionInfo {
+    pub subscription_id: String,
+    pub connection_id: String,
+    pub kind: RedisStreamKind,
+    pub channels: Vec<String>,
+    pub patterns: Vec<String>,
+    pub started_at: DateTime<Utc>,
+}
+
+/// 消息接收端，由命令层实现（转发为 Tauri 事件）
+pub trait RedisStreamSink: Send + Sync + 'static {
+    /// 推送一批消息
+    fn on_batch(&self, batch: RedisStreamBatch);
+    /// 订阅结束，在最后一批消息推送之后调用；error 为 None 表示正常停止，
+    /// dropped 为整个订阅期间因缓冲区已满而丢弃的消息总数
+    fn on_closed(&self, subscription_id: &str, error: Option<String>, dropped: u64);
+}
+
+// ===== 订阅句柄 =====
+
+/// 运行中的订阅，drop 或调用 stop 后后台任务退出
+pub struct RedisStreamHandle {
+    pub info: RedisSubscriptionInfo,
+    stop_tx: Option<oneshot::Sender<()>>,
+    finished: Arc<AtomicBool>,
+}
+
+impl RedisStreamHandle {
+    /// 连接断开后后台任务已退出
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    /// 停止订阅
+    pub fn stop(mut self) {
+        if let Some(tx) = self.stop_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+impl Drop for RedisStreamHandle {
+    fn drop(&mut self) {
+        if let Some(tx) = self.stop_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+/// 订阅频道和模式，消息通过 sink 推送
+pub async fn start_pubsub(
+    client: Client,
+    info: RedisSubscriptionInfo,
+    options: RedisStreamOptions,
+    sink: Arc<dyn RedisStreamSink>,
+) -> anyhow::Result<RedisStreamHandle> {
+    if info.channels.is_empty() && info.patterns.is_empty() {
+        return Err(anyhow::anyhow!("至少需要指定一个频道或模式"));
+    }
+    let filter = options.filter.compile()?;
+
+    let mut pubsub = client.get_async_connection().await
+        .map_err(|e| anyhow::anyhow!("创建订阅连接失败: {}", e))?
+        .into_pubsub();
+    for channel in &info.channels {
+        pubsub.subscribe(channel).await
+            .map_err(|e| anyhow::anyhow!("订阅频道 {} 失败: {}", channel, e))?;
+    }
+    for pattern in &info.patterns {
+        pubsub.psubscribe(pattern).await
+            .map_err(|e| anyhow::anyhow!("订阅模式 {} 失败: {}", pattern, e))?;
+    }
+
+    let messages = pubsub.into_on_message().map(|msg| RedisStreamMessage::Message {
+        channel: msg.get_channel_name().to_string(),
+        pattern: if msg.from_pattern() { msg.get_pattern().ok() } else { None },
+        payload: String::from_utf8_lossy(msg.get_payload_bytes()).into_owned(),
+        received_at: Utc::now(),
+    });
+
+    Ok(spawn_stream(messages, info, options, filter, sink))
+}
+
+/// 开启 MONITOR，服务端执行的每条命令通过 sink 推送
+pub async fn start_monitor(
+    client: Client,
+    info: RedisSubscriptionInfo,
+    options: RedisStreamOptions,
+    sink: Arc<dyn RedisStreamSink>,
+) -> anyhow::Result<RedisStreamHandle> {
+    let filter = options.filter.compile()?;
+
+    let mut monitor = client.get_async_connection().await
+        .map_err(|e| anyhow::anyhow!("创建监控连接失败: {}", e))?
+        .into_monitor();
+    monitor.monitor().await
+        .map_err(|e| anyhow::anyhow!("执行 MONITOR 失败: {}", e))?;
+
+    let messages = monitor.into_on_message::<String>()
+        .filter_map(|line| async move { parse_monitor_line(&line) });
+
+    Ok(spawn_stream(messages, info, options, filter, sink))
+}
+
+/// 启动读取与推送两个后台任务
+fn spawn_stream<S>(
+    messages: S,
+    info: RedisSubscriptionInfo,
+    options: RedisStreamOptions,
+    filter: CompiledMessageFilter,
+    sink: Arc<dyn RedisStreamSink>,
+) -> RedisStreamHandle
+where
+    S: futures::Stream<Item = RedisStreamMessage> + Send + 'static,
+{
+    let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+    let (tx, mut rx) = mpsc::channel::<RedisStreamMessage>(options.buffer_size.max(1));
+    let (closed_tx, closed_rx) = oneshot::channel::<Option<String>>();
+    let dropped = Arc::new(AtomicU64::new(0));
+    let finished = Arc::new(AtomicBool::new(false));
+    let handle_finished = finished.clone();
+    let subscription_id = info.subscription_id.clone();
+
+    // 读取任务：从 Redis 读取消息并写入有界缓冲区
+    let reader_dropped = dropped.clone();
+    tokio::spawn(async move {
+        let mut messages = Box::pin(messages);
+        let error = loop {
+            tokio::select! {
+                _ = &mut stop_rx => break None,
+                next = messages.next() => match next {
+                    Some(message) => {
+                        if !filter.matches(&message) {
+                            continue;
+                        }
+                        match tx.try_send(message) {
+                            Ok(()) => {}
+                            Err(mpsc::error::TrySendError::Full(_)) => {
+                                reader_dropped.fetch_add(1, Ordering::Relaxed);
+                            }
+                            Err(mpsc::error::TrySendError::Closed(_)) => break None,
+                        }
+                    }
+                    None => break Some("Redis 连接已断开".to_string()),
+                },
+            }
+        };
+        // 关闭缓冲区，推送任务发送剩余消息后通知订阅结束
+        let _ = closed_tx.send(error);
+        drop(tx);
+    });
+
+    // 推送任务：按批次或定时把缓冲区中的消息交给 sink，缓冲区关闭后推送
+    // 剩余消息并通知订阅结束
+    let max_batch_size = options.max_batch_size.max(1);
+    let flush_interval = Duration::from_millis(options.flush_interval_ms.max(10));
+    tokio::spawn(async move {
+        let mut batch = Vec::with_capacity(max_batch_size);
+        let mut ticker = tokio::time::interval(flush_interval);
+        let mut total_dropped = 0;
+        loop {
+            let closed = tokio::select! {
+                received = rx.recv() => match received {
+                    Some(message) => {
+                        batch.push(message);
+                        if batch.len() < max_batch_size {
+                            continue;
+                        }
+                        false
+                    }
+                    None => true,
+                },
+                _ = ticker.tick() => false,
+            };
+
+            let dropped_count = dropped.swap(0, Ordering::Relaxed);
+            total_dropped += dropped_count;
+            if !batch.is_empty() || dropped_count > 0 {
+                sink.on_batch(RedisStreamBatch {
+                    subscription_id: subscription_id.clone(),
+                    messages: std::mem::replace(&mut batch, Vec::with_capacity(max_batch_size)),
+                    dropped: dropped_count,
+                });
+            }
+            if closed {
+                break;
+            }
+        }
+
+        let error = closed_rx.await.unwrap_or(None);
+        finished.store(true, Ordering::Relaxed);
+        sink.on_closed(&subscription_id, error, total_dropped);
+    });
+
+    RedisStreamHandle {
+        info,
+        stop_tx: Some(stop_tx),
+        finished: handle_finished,
+    }
+}
+
+// ===== MONITOR 输出解析 =====
+
+/// 解析 MONITOR 输出行，例如：
+/// `1339518083.107412 [0 127.0.0.1:60866] "keys" "*"`
+pub fn parse_monitor_line(line: &str) -> Option<RedisStreamMessage> {
+    let (timestamp, rest) = line.split_once(' ')?;
+    let timestamp: f64 = timestamp.parse().ok()?;
+
+    let rest = rest.strip_prefix('[')?;
+    let (source, rest) = rest.split_once("] ")?;
+    let (database, client) = source.split_once(' ')?;
+    let database: i64 = database.parse().ok()?;
+
+    let mut tokens = parse_quoted_args(rest).into_iter();
+    let command = tokens.next()?.to_uppercase();
+
+    Some(RedisStreamMessage::Monitor {
+        timestamp,
+        database,
+        client: client.to_string(),
+        command,
+        args: tokens.collect(),
+        raw: rest.to_string(),
+    })
+}
+
+/// 拆分 MONITOR 输出中带引号和转义的参数
+///
+/// `\x` 转义的是原始字节，多字节 UTF-8 字符会被拆成多个转义，因此先收集
+/// 字节，整个参数读完后再按 UTF-8 解码。
+fn parse_quoted_args(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c != '"' {
+            chars.next();
+            continue;
+        }
+        chars.next();
+
+        let mut arg = Vec::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => arg.push(b'\n'),
+                    Some('r') => arg.push(b'\r'),
+                    Some('t') => arg.push(b'\t'),
+                    Some('x') => {
+                        let hex: String = chars.by_ref().take(2).collect();
+                        match u8::from_str_radix(&hex, 16) {
+                            Ok(byte) => arg.push(byte),
+                            Err(_) => {
+                                arg.extend_from_slice(b"\\x");
+                                arg.extend_from_slice(hex.as_bytes());
+                            }
+                        }
+                    }
+                    Some(other) => push_char(&mut arg, other),
+                    None => break,
+                },
+                other => push_char(&mut arg, other),
+            }
+        }
+        args.push(String::from_utf8_lossy(&arg).into_owned());
+    }
+
+    args
+}
+
+fn push_char(bytes: &mut Vec<u8>, c: char) {
+    bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+}
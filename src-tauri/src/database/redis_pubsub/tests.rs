@@ -0,0 +1,94 @@
+use super::*;
+
+#[test]
+fn test_parse_monitor_line() {
+    let line = r#"1339518083.107412 [0 127.0.0.1:60866] "SET" "user:1" "hello world""#;
+    match parse_monitor_line(line) {
+        Some(RedisStreamMessage::Monitor { timestamp, database, client, command, args, .. }) => {
+            assert!((timestamp - 1339518083.107412).abs() < 1e-6);
+            assert_eq!(database, 0);
+            assert_eq!(client, "127.0.0.1:60866");
+            assert_eq!(command, "SET");
+            assert_eq!(args, vec!["user:1".to_string(), "hello world".to_string()]);
+        }
+        other => panic!("unexpected parse result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_monitor_line_escapes() {
+    let line = r#"1.5 [3 lua] "publish" "events" "{\"id\":1}\n""#;
+    match parse_monitor_line(line) {
+        Some(RedisStreamMessage::Monitor { database, client, command, args, .. }) => {
+            assert_eq!(database, 3);
+            assert_eq!(client, "lua");
+            assert_eq!(command, "PUBLISH");
+            assert_eq!(args[1], "{\"id\":1}\n");
+        }
+        other => panic!("unexpected parse result: {:?}", other),
+    }
+
+    assert!(parse_monitor_line("OK").is_none());
+}
+
+#[test]
+fn test_parse_monitor_line_utf8_escapes() {
+    // "键" 的 UTF-8 编码为 e9 94 ae
+    let line = r#"1.5 [0 127.0.0.1:6000] "get" "\xe9\x94\xae:1" "\xffok" "\xzz""#;
+    match parse_monitor_line(line) {
+        Some(RedisStreamMessage::Monitor { args, .. }) => {
+            assert_eq!(args, vec!["键:1", "\u{fffd}ok", "\\xzz"]);
+        }
+        other => panic!("unexpected parse result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_filter_pubsub_message() {
+    let filter = RedisMessageFilter {
+        channel_pattern: Some("^orders\\.".to_string()),
+        payload_pattern: Some("failed".to_string()),
+        commands: vec![],
+    }
+    .compile()
+    .unwrap();
+
+    let message = |channel: &str, payload: &str| RedisStreamMessage::Message {
+        channel: channel.to_string(),
+        pattern: None,
+        payload: payload.to_string(),
+        received_at: Utc::now(),
+    };
+
+    assert!(filter.matches(&message("orders.created", "payment failed")));
+    assert!(!filter.matches(&message("users.created", "payment failed")));
+    assert!(!filter.matches(&message("orders.created", "ok")));
+}
+
+#[test]
+fn test_filter_monitor_commands() {
+    let filter = RedisMessageFilter {
+        channel_pattern: Some("^session:".to_string()),
+        payload_pattern: None,
+        commands: vec!["get".to_string(), "del".to_string()],
+    }
+    .compile()
+    .unwrap();
+
+    let get = parse_monitor_line(r#"1.0 [0 127.0.0.1:1] "get" "session:42""#).unwrap();
+    let set = parse_monitor_line(r#"1.0 [0 127.0.0.1:1] "set" "session:42" "x""#).unwrap();
+    let other_key = parse_monitor_line(r#"1.0 [0 127.0.0.1:1] "get" "user:42""#).unwrap();
+
+    assert!(filter.matches(&get));
+    assert!(!filter.matches(&set));
+    assert!(!filter.matches(&other_key));
+}
+
+#[test]
+fn test_invalid_filter_pattern() {
+    let filter = RedisMessageFilter {
+        channel_pattern: Some("(".to_string()),
+        ..Default::default()
+    };
+    assert!(filter.compile().is_err());
+}
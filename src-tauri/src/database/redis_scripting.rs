@@ -0,0 +1,276 @@
+// Redis Lua 脚本与 Redis 7 Functions 工作台
+//
+// 脚本先经 SCRIPT LOAD 缓存到服务端，再以 EVALSHA 执行；源码按 SHA 保存在
+// 连接上，服务端缓存被清空（NOSCRIPT）时自动重新加载后重试。
+
+use super::redis::RedisConnection;
+use super::redis_value::{value_items, value_to_map, value_to_opt_string, value_to_string, RedisReply};
+use redis::{Connection, ErrorKind, RedisResult, Value};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// 脚本或函数的执行结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptResult {
+    pub reply: RedisReply,
+    /// 脚本的 SHA1（FCALL 时为 None）
+    pub sha: Option<String>,
+    pub execution_time: u64,
+}
+
+/// 连接上缓存的脚本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedScript {
+    pub sha: String,
+    pub source: String,
+    /// 服务端 SCRIPT EXISTS 的结果
+    pub loaded: bool,
+}
+
+/// FUNCTION LIST 中的函数库
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisFunctionLibrary {
+    pub name: String,
+    pub engine: String,
+    pub functions: Vec<RedisFunctionInfo>,
+    /// 仅在 WITHCODE 时返回
+    pub code: Option<String>,
+}
+
+/// 函数库中的单个函数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisFunctionInfo {
+    pub name: String,
+    pub description: Option<String>,
+    pub flags: Vec<String>,
+}
+
+impl RedisConnection {
+    /// 执行 Lua 脚本，KEYS 和 ARGV 分开传入
+    pub async fn eval_script(
+        &self,
+        script: &str,
+        keys: &[String],
+        args: &[String],
+    ) -> anyhow::Result<ScriptResult> {
+        let start = std::time::Instant::now();
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let sha = load_script(&mut conn, script)?;
+        self.cache_script(&sha, script);
+        let reply = evalsha(&mut conn, &sha, keys, args)
+            .map_err(|e| anyhow::anyhow!("脚本执行失败: {}", e))?;
+
+        Ok(ScriptResult {
+            reply: RedisReply::from(reply),
+            sha: Some(sha),
+            execution_time: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// 按 SHA 执行已缓存的脚本，服务端缺失时使用本地缓存的源码重新加载
+    pub async fn eval_sha(
+        &self,
+        sha: &str,
+        keys: &[String],
+        args: &[String],
+    ) -> anyhow::Result<ScriptResult> {
+        let start = std::time::Instant::now();
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply = match evalsha(&mut conn, sha, keys, args) {
+            Err(e) if e.kind() == ErrorKind::NoScriptError => {
+                let source = self.cached_script(sha)
+                    .ok_or_else(|| anyhow::anyhow!("服务端和本地都没有SHA为 {} 的脚本", sha))?;
+                load_script(&mut conn, &source)?;
+                evalsha(&mut conn, sha, keys, args)
+            }
+            other => other,
+        };
+        let reply = reply.map_err(|e| anyhow::anyhow!("脚本执行失败: {}", e))?;
+
+        Ok(ScriptResult {
+            reply: RedisReply::from(reply),
+            sha: Some(sha.to_string()),
+            execution_time: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// SCRIPT LOAD，返回 SHA1
+    pub async fn script_load(&self, script: &str) -> anyhow::Result<String> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let sha = load_script(&mut conn, script)?;
+        self.cache_script(&sha, script);
+        Ok(sha)
+    }
+
+    /// SCRIPT EXISTS
+    pub async fn script_exists(&self, shas: &[String]) -> anyhow::Result<Vec<bool>> {
+        if shas.is_empty() {
+            return Ok(vec![]);
+        }
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let exists: RedisResult<Vec<bool>> = redis::cmd("SCRIPT").arg("EXISTS").arg(shas).query(&mut *conn);
+        exists.map_err(|e| anyhow::anyhow!("SCRIPT EXISTS 失败: {}", e))
+    }
+
+    /// SCRIPT FLUSH，同时清空本地缓存
+    pub async fn script_flush(&self) -> anyhow::Result<()> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let flushed: RedisResult<()> = redis::cmd("SCRIPT").arg("FLUSH").query(&mut *conn);
+        flushed.map_err(|e| anyhow::anyhow!("SCRIPT FLUSH 失败: {}", e))?;
+        self.script_cache().clear();
+        Ok(())
+    }
+
+    /// 列出本连接缓存过的脚本及其在服务端是否仍存在
+    pub async fn list_cached_scripts(&self) -> anyhow::Result<Vec<CachedScript>> {
+        let mut scripts: Vec<(String, String)> = self.script_cache()
+            .iter()
+            .map(|(sha, source)| (sha.clone(), source.clone()))
+            .collect();
+        scripts.sort();
+
+        let shas: Vec<String> = scripts.iter().map(|(sha, _)| sha.clone()).collect();
+        let loaded = self.script_exists(&shas).await?;
+
+        Ok(scripts.into_iter()
+            .zip(loaded)
+            .map(|((sha, source), loaded)| CachedScript { sha, source, loaded })
+            .collect())
+    }
+
+    /// FUNCTION LIST
+    pub async fn function_list(
+        &self,
+        library_pattern: Option<&str>,
+        with_code: bool,
+    ) -> anyhow::Result<Vec<RedisFunctionLibrary>> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let mut cmd = redis::cmd("FUNCTION");
+        cmd.arg("LIST");
+        if let Some(pattern) = library_pattern.filter(|p| !p.is_empty()) {
+            cmd.arg("LIBRARYNAME").arg(pattern);
+        }
+        if with_code {
+            cmd.arg("WITHCODE");
+        }
+
+        let reply: RedisResult<Value> = cmd.query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("FUNCTION LIST 失败: {}", e))?;
+
+        Ok(parse_function_list(&reply))
+    }
+
+    /// FUNCTION LOAD，返回函数库名称
+    pub async fn function_load(&self, code: &str, replace: bool) -> anyhow::Result<String> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let mut cmd = redis::cmd("FUNCTION");
+        cmd.arg("LOAD");
+        if replace {
+            cmd.arg("REPLACE");
+        }
+        cmd.arg(code);
+
+        let library: RedisResult<String> = cmd.query(&mut *conn);
+        library.map_err(|e| anyhow::anyhow!("FUNCTION LOAD 失败: {}", e))
+    }
+
+    /// FUNCTION DELETE
+    pub async fn function_delete(&self, library: &str) -> anyhow::Result<()> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let deleted: RedisResult<()> = redis::cmd("FUNCTION").arg("DELETE").arg(library).query(&mut *conn);
+        deleted.map_err(|e| anyhow::anyhow!("FUNCTION DELETE 失败: {}", e))
+    }
+
+    /// FCALL / FCALL_RO
+    pub async fn function_call(
+        &self,
+        function: &str,
+        keys: &[String],
+        args: &[String],
+        read_only: bool,
+    ) -> anyhow::Result<ScriptResult> {
+        let start = std::time::Instant::now();
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd(if read_only { "FCALL_RO" } else { "FCALL" })
+            .arg(function)
+            .arg(keys.len())
+            .arg(keys)
+            .arg(args)
+            .query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("函数执行失败: {}", e))?;
+
+        Ok(ScriptResult {
+            reply: RedisReply::from(reply),
+            sha: None,
+            execution_time: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    fn cache_script(&self, sha: &str, source: &str) {
+        self.script_cache().insert(sha.to_string(), source.to_string());
+    }
+
+    fn cached_script(&self, sha: &str) -> Option<String> {
+        self.script_cache().get(sha).cloned()
+    }
+}
+
+fn load_script(conn: &mut Connection, script: &str) -> anyhow::Result<String> {
+    let sha: RedisResult<String> = redis::cmd("SCRIPT").arg("LOAD").arg(script).query(conn);
+    sha.map_err(|e| anyhow::anyhow!("脚本加载失败: {}", e))
+}
+
+fn evalsha(conn: &mut Connection, sha: &str, keys: &[String], args: &[String]) -> RedisResult<Value> {
+    redis::cmd("EVALSHA")
+        .arg(sha)
+        .arg(keys.len())
+        .arg(keys)
+        .arg(args)
+        .query(conn)
+}
+
+/// 解析 FUNCTION LIST 回复
+pub(crate) fn parse_function_list(value: &Value) -> Vec<RedisFunctionLibrary> {
+    value_items(value).iter().map(|library| {
+        let library = value_to_map(library);
+        let functions = library.get("functions")
+            .map(|functions| value_items(functions).iter().map(|function| {
+                let function = value_to_map(function);
+                RedisFunctionInfo {
+                    name: function.get("name").map(|v| value_to_string(v)).unwrap_or_default(),
+                    description: function.get("description").and_then(|v| value_to_opt_string(v)),
+                    flags: function.get("flags")
+                        .map(|flags| value_items(flags).iter().map(value_to_string).collect())
+                        .unwrap_or_default(),
+                }
+            }).collect())
+            .unwrap_or_default();
+
+        RedisFunctionLibrary {
+            name: library.get("library_name").map(|v| value_to_string(v)).unwrap_or_default(),
+            engine: library.get("engine").map(|v| value_to_string(v)).unwrap_or_default(),
+            functions,
+            code: library.get("library_code").and_then(|v| value_to_opt_string(v)),
+        }
+    }).collect()
+}
@@ -0,0 +1,52 @@
+use super::*;
+
+fn data(s: &str) -> Value {
+    Value::Data(s.as_bytes().to_vec())
+}
+
+#[test]
+fn test_reply_conversion() {
+    let value = Value::Bulk(vec![
+        Value::Int(1),
+        data("ok"),
+        Value::Data(vec![0xff, 0xfe]),
+        Value::Nil,
+        Value::Okay,
+        Value::Status("QUEUED".to_string()),
+    ]);
+
+    assert_eq!(
+        RedisReply::from(&value),
+        RedisReply::Array(vec![
+            RedisReply::Integer(1),
+            RedisReply::String("ok".to_string()),
+            RedisReply::Binary(vec![0xff, 0xfe]),
+            RedisReply::Nil,
+            RedisReply::Ok,
+            RedisReply::Status("QUEUED".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn test_parse_function_list() {
+    let reply = Value::Bulk(vec![Value::Bulk(vec![
+        data("library_name"), data("ratelimit"),
+        data("engine"), data("LUA"),
+        data("functions"), Value::Bulk(vec![Value::Bulk(vec![
+            data("name"), data("rl_check"),
+            data("description"), Value::Nil,
+            data("flags"), Value::Bulk(vec![data("no-writes")]),
+        ])]),
+        data("library_code"), data("#!lua name=ratelimit"),
+    ])]);
+
+    let libraries = parse_function_list(&reply);
+    assert_eq!(libraries.len(), 1);
+    assert_eq!(libraries[0].name, "ratelimit");
+    assert_eq!(libraries[0].engine, "LUA");
+    assert_eq!(libraries[0].code.as_deref(), Some("#!lua name=ratelimit"));
+    assert_eq!(libraries[0].functions[0].name, "rl_check");
+    assert_eq!(libraries[0].functions[0].description, None);
+    assert_eq!(libraries[0].functions[0].flags, vec!["no-writes".to_string()]);
+}
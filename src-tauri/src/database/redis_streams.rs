@@ -0,0 +1,398 @@
+// Redis Streams 与消费者组检查器
+//
+// 基于 XRANGE/XREVRANGE 的分页浏览、XINFO 统计、XPENDING 待处理条目，
+// 以及 XACK/XCLAIM/XDEL/XTRIM 等维护操作。
+
+use super::enhanced_types::StreamEntry;
+use super::redis::RedisConnection;
+use super::redis_value::{value_items, value_to_i64, value_to_map, value_to_opt_string, value_to_string};
+use redis::{RedisResult, Value};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// 分页浏览结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamPage {
+    pub entries: Vec<StreamEntry>,
+    /// 下一页的起始ID，None 表示已经到底
+    pub next_id: Option<String>,
+}
+
+/// XINFO STREAM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamInfo {
+    pub key: String,
+    pub length: u64,
+    pub radix_tree_keys: u64,
+    pub radix_tree_nodes: u64,
+    pub groups: u64,
+    pub last_generated_id: String,
+    /// Redis 7.0+
+    pub entries_added: Option<u64>,
+    pub first_entry: Option<StreamEntry>,
+    pub last_entry: Option<StreamEntry>,
+}
+
+/// XINFO GROUPS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamGroupInfo {
+    pub name: String,
+    pub consumers: u64,
+    pub pending: u64,
+    pub last_delivered_id: String,
+    /// Redis 7.0+
+    pub entries_read: Option<u64>,
+    /// Redis 7.0+，尚未投递给该组的条目数
+    pub lag: Option<u64>,
+}
+
+/// XINFO CONSUMERS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamConsumerInfo {
+    pub name: String,
+    pub pending: u64,
+    /// 距最后一次读取/认领的毫秒数
+    pub idle_ms: u64,
+    /// Redis 7.2+，距最后一次成功交互的毫秒数
+    pub inactive_ms: Option<i64>,
+}
+
+/// XPENDING 汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingSummary {
+    pub count: u64,
+    pub min_id: Option<String>,
+    pub max_id: Option<String>,
+    /// 每个消费者的待处理条目数
+    pub consumers: Vec<(String, u64)>,
+}
+
+/// XPENDING 明细条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingEntry {
+    pub id: String,
+    pub consumer: String,
+    pub idle_ms: u64,
+    pub delivery_count: u64,
+}
+
+/// XPENDING 明细查询条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingQuery {
+    #[serde(default = "default_range_start")]
+    pub start: String,
+    #[serde(default = "default_range_end")]
+    pub end: String,
+    #[serde(default = "default_pending_count")]
+    pub count: usize,
+    pub consumer: Option<String>,
+    /// 只返回空闲时间不少于该值的条目（Redis 6.2+）
+    pub min_idle_ms: Option<u64>,
+}
+
+fn default_range_start() -> String {
+    "-".to_string()
+}
+
+fn default_range_end() -> String {
+    "+".to_string()
+}
+
+fn default_pending_count() -> usize {
+    100
+}
+
+/// XTRIM 裁剪策略
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "strategy", content = "threshold")]
+pub enum StreamTrimStrategy {
+    /// 保留最新的 N 条
+    MaxLen(u64),
+    /// 删除ID小于该值的条目（Redis 6.2+）
+    MinId(String),
+}
+
+impl RedisConnection {
+    /// 分页读取 Stream 条目，reverse 为 true 时按ID从新到旧
+    ///
+    /// `start` 为本页起点（包含），不指定时从头（或从尾）开始。
+    pub async fn stream_range(
+        &self,
+        key: &str,
+        start: Option<&str>,
+        count: usize,
+        reverse: bool,
+    ) -> anyhow::Result<StreamPage> {
+        let count = count.max(1);
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        // 多取一条用于判断是否还有下一页
+        let mut cmd = if reverse {
+            let mut cmd = redis::cmd("XREVRANGE");
+            cmd.arg(key).arg(start.unwrap_or("+")).arg("-");
+            cmd
+        } else {
+            let mut cmd = redis::cmd("XRANGE");
+            cmd.arg(key).arg(start.unwrap_or("-")).arg("+");
+            cmd
+        };
+        cmd.arg("COUNT").arg(count + 1);
+
+        let reply: RedisResult<Value> = cmd.query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("读取Stream失败: {}", e))?;
+
+        let mut entries = parse_stream_entries(&reply);
+        let next_id = if entries.len() > count {
+            entries.pop().map(|entry| entry.id)
+        } else {
+            None
+        };
+
+        Ok(StreamPage { entries, next_id })
+    }
+
+    /// XINFO STREAM
+    pub async fn stream_info(&self, key: &str) -> anyhow::Result<StreamInfo> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd("XINFO").arg("STREAM").arg(key).query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("XINFO STREAM 失败: {}", e))?;
+
+        Ok(parse_stream_info(key, &reply))
+    }
+
+    /// XINFO GROUPS
+    pub async fn stream_groups(&self, key: &str) -> anyhow::Result<Vec<StreamGroupInfo>> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd("XINFO").arg("GROUPS").arg(key).query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("XINFO GROUPS 失败: {}", e))?;
+
+        Ok(value_items(&reply).iter().map(parse_group_info).collect())
+    }
+
+    /// XINFO CONSUMERS
+    pub async fn stream_consumers(&self, key: &str, group: &str) -> anyhow::Result<Vec<StreamConsumerInfo>> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd("XINFO").arg("CONSUMERS").arg(key).arg(group).query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("XINFO CONSUMERS 失败: {}", e))?;
+
+        Ok(value_items(&reply).iter().map(parse_consumer_info).collect())
+    }
+
+    /// XPENDING 汇总
+    pub async fn stream_pending_summary(&self, key: &str, group: &str) -> anyhow::Result<PendingSummary> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd("XPENDING").arg(key).arg(group).query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("XPENDING 失败: {}", e))?;
+
+        Ok(parse_pending_summary(&reply))
+    }
+
+    /// XPENDING 明细
+    pub async fn stream_pending(
+        &self,
+        key: &str,
+        group: &str,
+        query: &PendingQuery,
+    ) -> anyhow::Result<Vec<PendingEntry>> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let mut cmd = redis::cmd("XPENDING");
+        cmd.arg(key).arg(group);
+        if let Some(min_idle) = query.min_idle_ms {
+            cmd.arg("IDLE").arg(min_idle);
+        }
+        cmd.arg(&query.start).arg(&query.end).arg(query.count.max(1));
+        if let Some(consumer) = &query.consumer {
+            cmd.arg(consumer);
+        }
+
+        let reply: RedisResult<Value> = cmd.query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("XPENDING 失败: {}", e))?;
+
+        Ok(value_items(&reply).iter().filter_map(parse_pending_entry).collect())
+    }
+
+    /// XACK，返回确认成功的条目数
+    pub async fn stream_ack(&self, key: &str, group: &str, ids: &[String]) -> anyhow::Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let acked: RedisResult<u64> = redis::cmd("XACK").arg(key).arg(group).arg(ids).query(&mut *conn);
+        acked.map_err(|e| anyhow::anyhow!("XACK 失败: {}", e))
+    }
+
+    /// XCLAIM，把空闲时间不少于 min_idle_ms 的待处理条目转给指定消费者
+    pub async fn stream_claim(
+        &self,
+        key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle_ms: u64,
+        ids: &[String],
+    ) -> anyhow::Result<Vec<StreamEntry>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let reply: RedisResult<Value> = redis::cmd("XCLAIM")
+            .arg(key)
+            .arg(group)
+            .arg(consumer)
+            .arg(min_idle_ms)
+            .arg(ids)
+            .query(&mut *conn);
+        let reply = reply.map_err(|e| anyhow::anyhow!("XCLAIM 失败: {}", e))?;
+
+        Ok(parse_stream_entries(&reply))
+    }
+
+    /// XDEL，返回删除的条目数
+    pub async fn stream_delete(&self, key: &str, ids: &[String]) -> anyhow::Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let deleted: RedisResult<u64> = redis::cmd("XDEL").arg(key).arg(ids).query(&mut *conn);
+        deleted.map_err(|e| anyhow::anyhow!("XDEL 失败: {}", e))
+    }
+
+    /// XTRIM，approximate 为 true 时使用 `~` 以提升性能，返回删除的条目数
+    pub async fn stream_trim(
+        &self,
+        key: &str,
+        strategy: &StreamTrimStrategy,
+        approximate: bool,
+    ) -> anyhow::Result<u64> {
+        let conn_arc = self.connection()?;
+        let mut conn = conn_arc.lock().await;
+
+        let mut cmd = redis::cmd("XTRIM");
+        cmd.arg(key);
+        match strategy {
+            StreamTrimStrategy::MaxLen(len) => {
+                cmd.arg("MAXLEN").arg(if approximate { "~" } else { "=" }).arg(*len);
+            }
+            StreamTrimStrategy::MinId(id) => {
+                cmd.arg("MINID").arg(if approximate { "~" } else { "=" }).arg(id);
+            }
+        }
+
+        let trimmed: RedisResult<u64> = cmd.query(&mut *conn);
+        trimmed.map_err(|e| anyhow::anyhow!("XTRIM 失败: {}", e))
+    }
+}
+
+// ===== 回复解析 =====
+
+/// 解析 `[id, [field, value, ...]]`，已删除的条目（字段为 Nil）返回 None
+pub(crate) fn parse_stream_entry(value: &Value) -> Option<StreamEntry> {
+    let items = value_items(value);
+    let id = value_to_opt_string(items.first()?)?;
+    let fields = value_items(items.get(1)?)
+        .chunks(2)
+        .filter(|pair| pair.len() == 2)
+        .map(|pair| (value_to_string(&pair[0]), value_to_string(&pair[1])))
+        .collect();
+
+    Some(StreamEntry { id, fields })
+}
+
+pub(crate) fn parse_stream_entries(value: &Value) -> Vec<StreamEntry> {
+    value_items(value).iter().filter_map(parse_stream_entry).collect()
+}
+
+fn map_u64(map: &std::collections::HashMap<String, &Value>, key: &str) -> Option<u64> {
+    map.get(key).and_then(|v| value_to_i64(v)).and_then(|v| u64::try_from(v).ok())
+}
+
+fn map_string(map: &std::collections::HashMap<String, &Value>, key: &str) -> String {
+    map.get(key).map(|v| value_to_string(v)).unwrap_or_default()
+}
+
+fn parse_stream_info(key: &str, value: &Value) -> StreamInfo {
+    let map = value_to_map(value);
+
+    StreamInfo {
+        key: key.to_string(),
+        length: map_u64(&map, "length").unwrap_or(0),
+        radix_tree_keys: map_u64(&map, "radix-tree-keys").unwrap_or(0),
+        radix_tree_nodes: map_u64(&map, "radix-tree-nodes").unwrap_or(0),
+        groups: map_u64(&map, "groups").unwrap_or(0),
+        last_generated_id: map_string(&map, "last-generated-id"),
+        entries_added: map_u64(&map, "entries-added"),
+        first_entry: map.get("first-entry").and_then(|v| parse_stream_entry(v)),
+        last_entry: map.get("last-entry").and_then(|v| parse_stream_entry(v)),
+    }
+}
+
+fn parse_group_info(value: &Value) -> StreamGroupInfo {
+    let map = value_to_map(value);
+
+    StreamGroupInfo {
+        name: map_string(&map, "name"),
+        consumers: map_u64(&map, "consumers").unwrap_or(0),
+        pending: map_u64(&map, "pending").unwrap_or(0),
+        last_delivered_id: map_string(&map, "last-delivered-id"),
+        entries_read: map_u64(&map, "entries-read"),
+        lag: map_u64(&map, "lag"),
+    }
+}
+
+fn parse_consumer_info(value: &Value) -> StreamConsumerInfo {
+    let map = value_to_map(value);
+
+    StreamConsumerInfo {
+        name: map_string(&map, "name"),
+        pending: map_u64(&map, "pending").unwrap_or(0),
+        idle_ms: map_u64(&map, "idle").unwrap_or(0),
+        inactive_ms: map.get("inactive").and_then(|v| value_to_i64(v)),
+    }
+}
+
+fn parse_pending_summary(value: &Value) -> PendingSummary {
+    let items = value_items(value);
+
+    PendingSummary {
+        count: items.first().and_then(value_to_i64).unwrap_or(0).max(0) as u64,
+        min_id: items.get(1).and_then(value_to_opt_string),
+        max_id: items.get(2).and_then(value_to_opt_string),
+        consumers: items.get(3)
+            .map(|v| value_items(v).iter().filter_map(|consumer| {
+                let pair = value_items(consumer);
+                let name = value_to_string(pair.first()?);
+                let count = value_to_i64(pair.get(1)?)?.max(0) as u64;
+                Some((name, count))
+            }).collect())
+            .unwrap_or_default(),
+    }
+}
+
+fn parse_pending_entry(value: &Value) -> Option<PendingEntry> {
+    let items = value_items(value);
+
+    Some(PendingEntry {
+        id: value_to_string(items.first()?),
+        consumer: value_to_string(items.get(1)?),
+        idle_ms: value_to_i64(items.get(2)?)?.max(0) as u64,
+        delivery_count: value_to_i64(items.get(3)?)?.max(0) as u64,
+    })
+}
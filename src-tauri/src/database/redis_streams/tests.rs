@@ -0,0 +1,103 @@
+use super::*;
+
+fn data(s: &str) -> Value {
+    Value::Data(s.as_bytes().to_vec())
+}
+
+fn entry(id: &str, fields: &[(&str, &str)]) -> Value {
+    Value::Bulk(vec![
+        data(id),
+        Value::Bulk(fields.iter().flat_map(|(f, v)| vec![data(f), data(v)]).collect()),
+    ])
+}
+
+#[test]
+fn test_parse_stream_entries_skips_deleted() {
+    let reply = Value::Bulk(vec![
+        entry("1700000000000-0", &[("job", "email"), ("to", "a@example.com")]),
+        Value::Bulk(vec![data("1700000000000-1"), Value::Nil]),
+        Value::Nil,
+    ]);
+
+    let entries = parse_stream_entries(&reply);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].id, "1700000000000-0");
+    assert_eq!(entries[0].fields.get("job"), Some(&"email".to_string()));
+    assert!(entries[1].fields.is_empty());
+}
+
+#[test]
+fn test_parse_stream_info() {
+    let reply = Value::Bulk(vec![
+        data("length"), Value::Int(2),
+        data("radix-tree-keys"), Value::Int(1),
+        data("radix-tree-nodes"), Value::Int(2),
+        data("last-generated-id"), data("1700000000000-1"),
+        data("groups"), Value::Int(1),
+        data("first-entry"), entry("1700000000000-0", &[("job", "a")]),
+        data("last-entry"), entry("1700000000000-1", &[("job", "b")]),
+    ]);
+
+    let info = parse_stream_info("jobs", &reply);
+    assert_eq!(info.length, 2);
+    assert_eq!(info.groups, 1);
+    assert_eq!(info.entries_added, None);
+    assert_eq!(info.last_generated_id, "1700000000000-1");
+    assert_eq!(info.last_entry.unwrap().fields.get("job"), Some(&"b".to_string()));
+}
+
+#[test]
+fn test_parse_group_and_consumer_info() {
+    let group = Value::Bulk(vec![
+        data("name"), data("workers"),
+        data("consumers"), Value::Int(2),
+        data("pending"), Value::Int(5),
+        data("last-delivered-id"), data("1-0"),
+        data("entries-read"), Value::Int(10),
+        data("lag"), Value::Nil,
+    ]);
+    let info = parse_group_info(&group);
+    assert_eq!(info.name, "workers");
+    assert_eq!(info.pending, 5);
+    assert_eq!(info.entries_read, Some(10));
+    assert_eq!(info.lag, None);
+
+    let consumer = Value::Bulk(vec![
+        data("name"), data("worker-1"),
+        data("pending"), Value::Int(3),
+        data("idle"), Value::Int(60000),
+    ]);
+    let info = parse_consumer_info(&consumer);
+    assert_eq!(info.name, "worker-1");
+    assert_eq!(info.idle_ms, 60000);
+    assert_eq!(info.inactive_ms, None);
+}
+
+#[test]
+fn test_parse_pending() {
+    let summary = Value::Bulk(vec![
+        Value::Int(3),
+        data("1-0"),
+        data("3-0"),
+        Value::Bulk(vec![
+            Value::Bulk(vec![data("worker-1"), data("2")]),
+            Value::Bulk(vec![data("worker-2"), data("1")]),
+        ]),
+    ]);
+    let summary = parse_pending_summary(&summary);
+    assert_eq!(summary.count, 3);
+    assert_eq!(summary.min_id.as_deref(), Some("1-0"));
+    assert_eq!(summary.consumers, vec![("worker-1".to_string(), 2), ("worker-2".to_string(), 1)]);
+
+    let empty = Value::Bulk(vec![Value::Int(0), Value::Nil, Value::Nil, Value::Nil]);
+    let empty = parse_pending_summary(&empty);
+    assert_eq!(empty.count, 0);
+    assert!(empty.min_id.is_none());
+    assert!(empty.consumers.is_empty());
+
+    let detail = Value::Bulk(vec![data("1-0"), data("worker-1"), Value::Int(120000), Value::Int(4)]);
+    let detail = parse_pending_entry(&detail).unwrap();
+    assert_eq!(detail.consumer, "worker-1");
+    assert_eq!(detail.idle_ms, 120000);
+    assert_eq!(detail.delivery_count, 4);
+}
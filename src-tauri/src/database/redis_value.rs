@@ -0,0 +1,94 @@
+// Redis 原始回复（redis::Value）的解析辅助函数
+
+use redis::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 转换为字符串，二进制内容做有损UTF-8转换
+pub(crate) fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Nil => String::new(),
+        Value::Int(i) => i.to_string(),
+        Value::Data(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Value::Status(s) => s.clone(),
+        Value::Okay => "OK".to_string(),
+        Value::Bulk(items) => items.iter()
+            .map(value_to_string)
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// 转换为可选字符串，Nil 返回 None
+pub(crate) fn value_to_opt_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Nil => None,
+        other => Some(value_to_string(other)),
+    }
+}
+
+/// 转换为整数，兼容以字符串形式返回的数字
+pub(crate) fn value_to_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Int(i) => Some(*i),
+        Value::Data(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+        Value::Status(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// 数组回复的元素，非数组返回空切片
+pub(crate) fn value_items(value: &Value) -> &[Value] {
+    match value {
+        Value::Bulk(items) => items,
+        _ => &[],
+    }
+}
+
+/// 将扁平的键值数组（如 XINFO、CONFIG GET 的回复）转换为映射
+pub(crate) fn value_to_map(value: &Value) -> HashMap<String, &Value> {
+    value_items(value)
+        .chunks(2)
+        .filter(|pair| pair.len() == 2)
+        .map(|pair| (value_to_string(&pair[0]), &pair[1]))
+        .collect()
+}
+
+/// 带类型的 Redis 回复，用于脚本、事务等返回任意结构的命令
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum RedisReply {
+    Nil,
+    Integer(i64),
+    /// 可按UTF-8解码的批量字符串
+    String(String),
+    /// 无法按UTF-8解码的批量字符串
+    Binary(Vec<u8>),
+    Status(String),
+    Ok,
+    Array(Vec<RedisReply>),
+    /// 命令级错误（如事务中单条命令执行失败）
+    Error(String),
+}
+
+impl From<&Value> for RedisReply {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Nil => RedisReply::Nil,
+            Value::Int(i) => RedisReply::Integer(*i),
+            Value::Data(bytes) => match std::str::from_utf8(bytes) {
+                Ok(s) => RedisReply::String(s.to_string()),
+                Err(_) => RedisReply::Binary(bytes.clone()),
+            },
+            Value::Bulk(items) => RedisReply::Array(items.iter().map(RedisReply::from).collect()),
+            Value::Status(s) => RedisReply::Status(s.clone()),
+            Value::Okay => RedisReply::Ok,
+        }
+    }
+}
+
+impl From<Value> for RedisReply {
+    fn from(value: Value) -> Self {
+        RedisReply::from(&value)
+    }
+}
@@ -2,17 +2,13 @@ pub mod database;
 pub mod ai;
 pub mod commands;
 pub mod mcp;
+pub mod redis_commands;
+pub mod mongodb_commands;
 
 // 重新导出常用类型
 pub use database::{
     enhanced_types::*,
-    test_adapter::{AdapterConverter, TestDatabaseAdapter},
-    DatabaseConnection,
-    ConnectionConfig,
-    QueryResult,
-    TableInfo,
-    ColumnInfo,
-    DatabaseType,
+    adapter::DatabaseAdapterFactory,
 };
 
 pub use commands::AppState;
\ No newline at end of file
@@ -5,8 +5,12 @@ mod database;
 mod ai;
 mod commands;
 mod mcp;
+mod redis_commands;
+mod mongodb_commands;
 
 use commands::*;
+use redis_commands::*;
+use mongodb_commands::*;
 use tauri::Manager;
 use tracing::info;
 use tracing_subscriber;
@@ -26,6 +30,7 @@ async fn main() {
             connect_database,
             execute_query,
             get_database_schema,
+            get_database_schema_enhanced,
             test_database_connection,
             disconnect_database,
             configure_ai_service,
@@ -50,10 +55,114 @@ async fn main() {
             execute_query_enhanced,
             get_ui_config,
             get_query_suggestions,
+            get_real_time_stats,
             // 新增的数据库结构导航API
             get_database_structure,
             get_redis_structure,
-            get_mongodb_structure
+            get_mongodb_structure,
+            // Redis 特定命令
+            redis_subscribe,
+            redis_start_monitor,
+            redis_stop_stream,
+            redis_list_streams,
+            redis_stream_range,
+            redis_stream_info,
+            redis_stream_groups,
+            redis_stream_consumers,
+            redis_stream_pending_summary,
+            redis_stream_pending,
+            redis_stream_ack,
+            redis_stream_claim,
+            redis_stream_delete,
+            redis_stream_trim,
+            redis_get_memory_stats,
+            redis_start_memory_analysis,
+            redis_cancel_memory_analysis,
+            redis_eval_script,
+            redis_eval_sha,
+            redis_script_load,
+            redis_script_exists,
+            redis_script_flush,
+            redis_list_scripts,
+            redis_function_list,
+            redis_function_load,
+            redis_function_delete,
+            redis_function_call,
+            redis_execute_batch,
+            redis_begin_transaction,
+            redis_transaction_query,
+            redis_exec_transaction,
+            redis_discard_transaction,
+            redis_list_transactions,
+            redis_keyspace_children,
+            redis_keyspace_delete,
+            redis_keyspace_expire,
+            redis_keyspace_export,
+            redis_get_decoded_value,
+            redis_set_encoded_value,
+            redis_server_info,
+            redis_config_get,
+            redis_config_set,
+            redis_config_diff,
+            redis_slowlog_get,
+            redis_slowlog_reset,
+            redis_client_list,
+            redis_client_kill,
+            redis_detect_modules,
+            redis_json_get,
+            redis_json_set,
+            redis_json_del,
+            redis_search_indexes,
+            redis_search_index_info,
+            redis_search,
+            redis_ts_range,
+            redis_ts_info,
+            // MongoDB 特定命令
+            mongodb_aggregate,
+            mongodb_preview_pipeline,
+            mongodb_infer_schema,
+            mongodb_table_schema,
+            mongodb_replace_document,
+            mongodb_list_indexes,
+            mongodb_create_index,
+            mongodb_drop_index,
+            mongodb_set_index_hidden,
+            mongodb_index_stats,
+            mongodb_get_validation,
+            mongodb_update_validation,
+            mongodb_generate_json_schema,
+            mongodb_dry_run_validator,
+            mongodb_watch,
+            mongodb_unwatch,
+            mongodb_list_watches,
+            mongodb_gridfs_list,
+            mongodb_gridfs_upload,
+            mongodb_gridfs_download,
+            mongodb_gridfs_cancel_transfer,
+            mongodb_gridfs_delete,
+            mongodb_explain,
+            mongodb_get_profiler,
+            mongodb_set_profiler,
+            mongodb_profile_entries,
+            mongodb_insert_many,
+            mongodb_bulk_write,
+            mongodb_list_databases,
+            mongodb_create_database,
+            mongodb_drop_database,
+            mongodb_create_collection,
+            mongodb_drop_collection,
+            mongodb_rename_collection,
+            mongodb_list_users,
+            mongodb_create_user,
+            mongodb_update_user,
+            mongodb_drop_user,
+            mongodb_list_roles,
+            mongodb_create_role,
+            mongodb_update_role,
+            mongodb_drop_role,
+            mongodb_topology,
+            mongodb_translate_sql,
+            mongodb_query_sql
         ])
         .setup(|app| {
             info!("Application setup completed");
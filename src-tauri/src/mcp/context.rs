@@ -1,4 +1,4 @@
-use crate::database::TableInfo;
+use crate::database::TableSchema;
 use crate::mcp::types::*;
 use lru::LruCache;
 use std::sync::Arc;
@@ -20,7 +20,7 @@ impl ContextBuilder {
     pub async fn build_enhanced_context(
         &self,
         tables: &[String],
-        schema: &[TableInfo],
+        schema: &[TableSchema],
     ) -> anyhow::Result<DatabaseContext> {
         // 创建缓存键
         let cache_key = format!("{:?}", tables);
@@ -65,8 +65,8 @@ impl ContextBuilder {
     /// 构建单个表的上下文
     async fn build_table_context(
         &self,
-        table_info: &TableInfo,
-        schema: &[TableInfo],
+        table_info: &TableSchema,
+        schema: &[TableSchema],
     ) -> anyhow::Result<TableContext> {
         let analysis = self.analyze_table_structure(table_info);
         let business_type = self.infer_business_type(&table_info.name);
@@ -83,7 +83,7 @@ impl ContextBuilder {
     }
 
     /// 分析表结构
-    fn analyze_table_structure(&self, table_info: &TableInfo) -> TableAnalysis {
+    fn analyze_table_structure(&self, table_info: &TableSchema) -> TableAnalysis {
         let mut analysis = TableAnalysis::default();
         
         analysis.column_count = table_info.columns.len() as u32;
@@ -125,7 +125,7 @@ impl ContextBuilder {
     }
 
     /// 找到表关系
-    fn find_table_relationships(&self, table_info: &TableInfo, schema: &[TableInfo]) -> Vec<String> {
+    fn find_table_relationships(&self, table_info: &TableSchema, schema: &[TableSchema]) -> Vec<String> {
         let mut relationships = Vec::new();
         let table_name = table_info.name.to_lowercase();
 
@@ -149,7 +149,7 @@ impl ContextBuilder {
     }
 
     /// 生成示例查询
-    fn generate_sample_queries(&self, table_info: &TableInfo) -> Vec<String> {
+    fn generate_sample_queries(&self, table_info: &TableSchema) -> Vec<String> {
         let mut queries = Vec::new();
         let table_name = &table_info.name;
 
@@ -173,7 +173,7 @@ impl ContextBuilder {
     }
 
     /// 检查是否有时间列
-    fn has_time_columns(&self, table_info: &TableInfo) -> bool {
+    fn has_time_columns(&self, table_info: &TableSchema) -> bool {
         table_info.columns.iter().any(|col| {
             let name = col.name.to_lowercase();
             name.contains("time") || name.contains("date") || 
@@ -185,7 +185,7 @@ impl ContextBuilder {
     async fn infer_relationships(
         &self,
         tables: &[String],
-        schema: &[TableInfo],
+        schema: &[TableSchema],
     ) -> anyhow::Result<Vec<TableRelationship>> {
         let mut relationships = Vec::new();
 
@@ -55,7 +55,8 @@ impl MCPTool for EnhancedContextTool {
 
         // 获取数据库结构
         let schema = connection.get_schema().await
-            .map_err(|e| anyhow::anyhow!("获取数据库结构失败: {}", e))?;
+            .map_err(|e| anyhow::anyhow!("获取数据库结构失败: {}", e))?
+            .tables;
 
         // 验证请求的表是否存在
         let mut missing_tables = Vec::new();
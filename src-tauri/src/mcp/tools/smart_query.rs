@@ -197,7 +197,7 @@ impl MCPTool for SmartQueryTool {
 
         // 执行查询并计时
         let start_time = Instant::now();
-        let query_result = connection.execute(sql).await
+        let query_result = connection.execute_raw(sql).await
             .map_err(|e| anyhow::anyhow!("查询执行失败: {}", e))?;
         let execution_time = start_time.elapsed();
 
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::database::TableInfo;
+use crate::database::TableSchema;
 
 /// MCP工具的通用响应类型
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,7 +58,7 @@ impl DatabaseContext {
 /// 表上下文信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableContext {
-    pub structure: TableInfo,
+    pub structure: TableSchema,
     pub business_type: String,
     pub relationships: Vec<String>,
     pub analysis: TableAnalysis,
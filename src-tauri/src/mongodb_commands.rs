@@ -0,0 +1,763 @@
+// MongoDB 特定命令
+
+use crate::commands::AppState;
+use crate::database::{
+    DatabaseAdapter,
+    mongodb::MongoDBConnection,
+    mongodb_admin::{CreateCollectionRequest, CreateUserRequest, DatabaseInfo, RoleInfo, RoleRequest, UpdateUserRequest, UserInfo},
+    mongodb_aggregate::{AggregateRequest, AggregateResult, PipelinePreview},
+    mongodb_bulk::{BulkWriteOptions, BulkWriteRequest, BulkWriteResult},
+    mongodb_change_streams::{self, ChangeStreamBatch, ChangeStreamInfo, ChangeStreamRequest, ChangeStreamSink},
+    mongodb_connection::TopologyInfo,
+    mongodb_documents::ExtendedJsonMode,
+    mongodb_explain::{ExplainResult, ExplainVerbosity, ProfileEntry, ProfileQuery, ProfilerStatus},
+    mongodb_gridfs::{
+        self, GridFSDownloadRequest, GridFSFilePage, GridFSFileQuery, GridFSTransferProgress, GridFSTransferResult,
+        GridFSTransferSink, GridFSUploadRequest,
+    },
+    mongodb_indexes::{CreateIndexRequest, IndexUsage},
+    mongodb_schema::{CollectionSchema, DEFAULT_SCHEMA_SAMPLE_SIZE},
+    mongodb_sql::{self, SqlTranslation},
+    mongodb_validation::{CollectionValidation, ValidationDryRun},
+    enhanced_types::{EnhancedQueryResult, MongoIndexInfo, TableSchema},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use uuid::Uuid;
+
+/// 变更流事件批次
+pub const MONGODB_CHANGE_EVENT: &str = "mongodb-change-event";
+/// 变更流结束事件，携带最后的 resume token
+pub const MONGODB_CHANGE_CLOSED_EVENT: &str = "mongodb-change-closed";
+/// GridFS 传输进度事件
+pub const GRIDFS_TRANSFER_PROGRESS_EVENT: &str = "gridfs-transfer-progress";
+/// GridFS 传输完成事件（包括取消和失败）
+pub const GRIDFS_TRANSFER_COMPLETE_EVENT: &str = "gridfs-transfer-complete";
+
+// 辅助函数：获取MongoDB连接
+fn mongodb_connection<'a>(
+    connections: &'a HashMap<String, Box<dyn DatabaseAdapter>>,
+    connection_id: &str,
+) -> Result<&'a MongoDBConnection, String> {
+    connections.get(connection_id)
+        .ok_or("连接未找到")?
+        .as_mongodb()
+        .ok_or_else(|| "该连接不是MongoDB连接".to_string())
+}
+
+/// 执行聚合管道
+#[tauri::command]
+pub async fn mongodb_aggregate(
+    connection_id: String,
+    request: AggregateRequest,
+    state: State<'_, AppState>,
+) -> Result<AggregateResult, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .aggregate(&request).await
+        .map_err(|e| e.to_string())
+}
+
+/// 逐阶段预览聚合管道
+#[tauri::command]
+pub async fn mongodb_preview_pipeline(
+    connection_id: String,
+    request: AggregateRequest,
+    sample_size: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<PipelinePreview, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .preview_pipeline(&request, sample_size).await
+        .map_err(|e| e.to_string())
+}
+
+/// 抽样推断集合结构
+#[tauri::command]
+pub async fn mongodb_infer_schema(
+    connection_id: String,
+    collection: String,
+    sample_size: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<CollectionSchema, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .infer_collection_schema(&collection, sample_size.unwrap_or(DEFAULT_SCHEMA_SAMPLE_SIZE)).await
+        .map_err(|e| e.to_string())
+}
+
+/// 抽样推断集合结构，返回增强的表结构
+#[tauri::command]
+pub async fn mongodb_table_schema(
+    connection_id: String,
+    collection: String,
+    sample_size: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<TableSchema, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .infer_collection_schema(&collection, sample_size.unwrap_or(DEFAULT_SCHEMA_SAMPLE_SIZE)).await
+        .map(|schema| schema.to_table_schema())
+        .map_err(|e| e.to_string())
+}
+
+/// 按 _id 替换文档，文档使用规范模式查询结果中的扩展 JSON
+#[tauri::command]
+pub async fn mongodb_replace_document(
+    connection_id: String,
+    collection: String,
+    document: serde_json::Value,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .replace_document(&collection, document).await
+        .map_err(|e| e.to_string())
+}
+
+/// 列出集合索引
+#[tauri::command]
+pub async fn mongodb_list_indexes(
+    connection_id: String,
+    collection: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<MongoIndexInfo>, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .list_collection_indexes(&collection).await
+        .map_err(|e| e.to_string())
+}
+
+/// 创建索引，返回索引名
+#[tauri::command]
+pub async fn mongodb_create_index(
+    connection_id: String,
+    request: CreateIndexRequest,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .create_collection_index(&request).await
+        .map_err(|e| e.to_string())
+}
+
+/// 删除索引
+#[tauri::command]
+pub async fn mongodb_drop_index(
+    connection_id: String,
+    collection: String,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .drop_collection_index(&collection, &name).await
+        .map_err(|e| e.to_string())
+}
+
+/// 隐藏或取消隐藏索引
+#[tauri::command]
+pub async fn mongodb_set_index_hidden(
+    connection_id: String,
+    collection: String,
+    name: String,
+    hidden: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .set_index_hidden(&collection, &name, hidden).await
+        .map_err(|e| e.to_string())
+}
+
+/// 索引使用统计
+#[tauri::command]
+pub async fn mongodb_index_stats(
+    connection_id: String,
+    collection: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<IndexUsage>, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .index_usage(&collection).await
+        .map_err(|e| e.to_string())
+}
+
+/// 读取集合校验规则
+#[tauri::command]
+pub async fn mongodb_get_validation(
+    connection_id: String,
+    collection: String,
+    state: State<'_, AppState>,
+) -> Result<CollectionValidation, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .collection_validation(&collection).await
+        .map_err(|e| e.to_string())
+}
+
+/// 修改集合校验规则
+#[tauri::command]
+pub async fn mongodb_update_validation(
+    connection_id: String,
+    collection: String,
+    validation: CollectionValidation,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .update_validation(&collection, &validation).await
+        .map_err(|e| e.to_string())
+}
+
+/// 根据抽样文档生成 $jsonSchema
+#[tauri::command]
+pub async fn mongodb_generate_json_schema(
+    connection_id: String,
+    collection: String,
+    sample_size: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .generate_json_schema(&collection, sample_size.unwrap_or(DEFAULT_SCHEMA_SAMPLE_SIZE)).await
+        .map_err(|e| e.to_string())
+}
+
+/// 试运行校验规则
+#[tauri::command]
+pub async fn mongodb_dry_run_validator(
+    connection_id: String,
+    collection: String,
+    validator: serde_json::Value,
+    sample_size: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<ValidationDryRun, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .dry_run_validator(&collection, validator, sample_size).await
+        .map_err(|e| e.to_string())
+}
+
+/// 将变更流转发为 Tauri 事件
+struct TauriChangeStreamSink {
+    app: AppHandle,
+}
+
+impl ChangeStreamSink for TauriChangeStreamSink {
+    fn on_batch(&self, batch: ChangeStreamBatch) {
+        if let Err(e) = self.app.emit(MONGODB_CHANGE_EVENT, batch) {
+            log::warn!("推送MongoDB变更事件失败: {}", e);
+        }
+    }
+
+    fn on_closed(&self, watch_id: &str, error: Option<String>, resume_token: Option<serde_json::Value>) {
+        let payload = serde_json::json!({
+            "watch_id": watch_id,
+            "error": error,
+            "resume_token": resume_token,
+        });
+        if let Err(e) = self.app.emit(MONGODB_CHANGE_CLOSED_EVENT, payload) {
+            log::warn!("推送MongoDB变更流结束事件失败: {}", e);
+        }
+    }
+}
+
+/// 开启变更流，变更以 `mongodb-change-event` 事件推送，返回监听ID
+#[tauri::command]
+pub async fn mongodb_watch(
+    connection_id: String,
+    request: ChangeStreamRequest,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let target = {
+        let connections = state.connections.lock().await;
+        mongodb_connection(&connections, &connection_id)?
+            .watch_target(&request)
+            .map_err(|e| e.to_string())?
+    };
+
+    let info = ChangeStreamInfo {
+        watch_id: Uuid::new_v4().to_string(),
+        connection_id,
+        scope: request.scope,
+        database: target.database_name(),
+        collection: request.collection.clone(),
+        started_at: chrono::Utc::now(),
+    };
+    let watch_id = info.watch_id.clone();
+
+    let handle = mongodb_change_streams::start_watch(
+        target,
+        info,
+        request,
+        Arc::new(TauriChangeStreamSink { app }),
+    )
+    .await
+    .map_err(|e| format!("开启变更流失败: {}", e))?;
+
+    let mut watches = state.mongodb_watches.lock().await;
+    watches.retain(|_, watch| !watch.is_finished());
+    watches.insert(watch_id.clone(), handle);
+
+    Ok(watch_id)
+}
+
+/// 停止变更流，返回最后的 resume token
+#[tauri::command]
+pub async fn mongodb_unwatch(
+    watch_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<serde_json::Value>, String> {
+    let handle = state.mongodb_watches.lock().await
+        .remove(&watch_id)
+        .ok_or("变更流未找到")?;
+    let resume_token = handle.resume_token();
+    handle.stop();
+    Ok(resume_token)
+}
+
+/// 列出运行中的变更流
+#[tauri::command]
+pub async fn mongodb_list_watches(
+    connection_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ChangeStreamInfo>, String> {
+    let mut watches = state.mongodb_watches.lock().await;
+    watches.retain(|_, watch| !watch.is_finished());
+    Ok(watches.values()
+        .filter(|handle| connection_id.as_ref().is_none_or(|id| &handle.info.connection_id == id))
+        .map(|handle| handle.info.clone())
+        .collect())
+}
+
+// ===== GridFS =====
+
+/// 将传输进度转发为 Tauri 事件
+struct TauriGridFSTransferSink {
+    app: AppHandle,
+}
+
+impl GridFSTransferSink for TauriGridFSTransferSink {
+    fn on_progress(&self, progress: GridFSTransferProgress) {
+        if let Err(e) = self.app.emit(GRIDFS_TRANSFER_PROGRESS_EVENT, progress) {
+            log::warn!("推送GridFS传输进度失败: {}", e);
+        }
+    }
+
+    fn on_complete(&self, transfer_id: &str, result: Result<GridFSTransferResult, String>) {
+        let payload = match result {
+            Ok(result) => serde_json::json!({ "transfer_id": transfer_id, "result": result, "error": null }),
+            Err(error) => serde_json::json!({ "transfer_id": transfer_id, "result": null, "error": error }),
+        };
+        if let Err(e) = self.app.emit(GRIDFS_TRANSFER_COMPLETE_EVENT, payload) {
+            log::warn!("推送GridFS传输完成事件失败: {}", e);
+        }
+    }
+}
+
+/// 分页列出GridFS文件
+#[tauri::command]
+pub async fn mongodb_gridfs_list(
+    connection_id: String,
+    query: GridFSFileQuery,
+    state: State<'_, AppState>,
+) -> Result<GridFSFilePage, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .list_gridfs_files(&query).await
+        .map_err(|e| e.to_string())
+}
+
+/// 上传本地文件，进度以 `gridfs-transfer-progress` 事件推送，返回传输ID
+#[tauri::command]
+pub async fn mongodb_gridfs_upload(
+    connection_id: String,
+    request: GridFSUploadRequest,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let bucket = {
+        let connections = state.connections.lock().await;
+        mongodb_connection(&connections, &connection_id)?
+            .gridfs_bucket(&request.bucket)
+            .map_err(|e| e.to_string())?
+    };
+
+    let transfer_id = Uuid::new_v4().to_string();
+    let handle = mongodb_gridfs::start_upload(
+        bucket,
+        transfer_id.clone(),
+        connection_id,
+        request,
+        Arc::new(TauriGridFSTransferSink { app }),
+    );
+
+    let mut transfers = state.gridfs_transfers.lock().await;
+    transfers.retain(|_, transfer| !transfer.is_finished());
+    transfers.insert(transfer_id.clone(), handle);
+
+    Ok(transfer_id)
+}
+
+/// 下载文件到本地路径，进度以 `gridfs-transfer-progress` 事件推送，返回传输ID
+#[tauri::command]
+pub async fn mongodb_gridfs_download(
+    connection_id: String,
+    request: GridFSDownloadRequest,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let bucket = {
+        let connections = state.connections.lock().await;
+        mongodb_connection(&connections, &connection_id)?
+            .gridfs_bucket(&request.bucket)
+            .map_err(|e| e.to_string())?
+    };
+
+    let transfer_id = Uuid::new_v4().to_string();
+    let handle = mongodb_gridfs::start_download(
+        bucket,
+        transfer_id.clone(),
+        connection_id,
+        request,
+        Arc::new(TauriGridFSTransferSink { app }),
+    );
+
+    let mut transfers = state.gridfs_transfers.lock().await;
+    transfers.retain(|_, transfer| !transfer.is_finished());
+    transfers.insert(transfer_id.clone(), handle);
+
+    Ok(transfer_id)
+}
+
+/// 取消上传或下载，结果仍通过完成事件推送
+#[tauri::command]
+pub async fn mongodb_gridfs_cancel_transfer(
+    transfer_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut transfers = state.gridfs_transfers.lock().await;
+    let transfer = transfers.remove(&transfer_id).ok_or("传输任务未找到")?;
+    transfer.cancel();
+    Ok(())
+}
+
+/// 删除GridFS文件
+#[tauri::command]
+pub async fn mongodb_gridfs_delete(
+    connection_id: String,
+    bucket: String,
+    file_id: serde_json::Value,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .delete_gridfs_file(&bucket, file_id).await
+        .map_err(|e| e.to_string())
+}
+
+/// 获取查询的执行计划
+#[tauri::command]
+pub async fn mongodb_explain(
+    connection_id: String,
+    query: String,
+    verbosity: Option<ExplainVerbosity>,
+    state: State<'_, AppState>,
+) -> Result<ExplainResult, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .explain(&query, verbosity).await
+        .map_err(|e| e.to_string())
+}
+
+/// 读取数据库分析器设置
+#[tauri::command]
+pub async fn mongodb_get_profiler(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<ProfilerStatus, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .profiler_status().await
+        .map_err(|e| e.to_string())
+}
+
+/// 修改数据库分析器设置，返回修改前的设置
+#[tauri::command]
+pub async fn mongodb_set_profiler(
+    connection_id: String,
+    level: i64,
+    slow_ms: Option<i64>,
+    sample_rate: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<ProfilerStatus, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .set_profiler(level, slow_ms, sample_rate).await
+        .map_err(|e| e.to_string())
+}
+
+/// 列出 system.profile 中的慢操作记录
+#[tauri::command]
+pub async fn mongodb_profile_entries(
+    connection_id: String,
+    query: Option<ProfileQuery>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ProfileEntry>, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .profile_entries(&query.unwrap_or_default()).await
+        .map_err(|e| e.to_string())
+}
+
+/// 批量插入文档，单个文档的写入错误在结果中逐条返回
+#[tauri::command]
+pub async fn mongodb_insert_many(
+    connection_id: String,
+    collection: String,
+    documents: Vec<serde_json::Value>,
+    options: Option<BulkWriteOptions>,
+    state: State<'_, AppState>,
+) -> Result<BulkWriteResult, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .insert_documents(&collection, documents, &options.unwrap_or_default()).await
+        .map_err(|e| e.to_string())
+}
+
+/// 执行混合插入、更新、替换和删除的批量写入
+#[tauri::command]
+pub async fn mongodb_bulk_write(
+    connection_id: String,
+    request: BulkWriteRequest,
+    state: State<'_, AppState>,
+) -> Result<BulkWriteResult, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .execute_bulk_write(request).await
+        .map_err(|e| e.to_string())
+}
+
+/// 列出数据库及其占用空间
+#[tauri::command]
+pub async fn mongodb_list_databases(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<DatabaseInfo>, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .list_database_infos().await
+        .map_err(|e| e.to_string())
+}
+
+/// 创建数据库及其第一个集合
+#[tauri::command]
+pub async fn mongodb_create_database(
+    connection_id: String,
+    database: String,
+    collection: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .create_database(&database, &collection).await
+        .map_err(|e| e.to_string())
+}
+
+/// 删除数据库
+#[tauri::command]
+pub async fn mongodb_drop_database(
+    connection_id: String,
+    database: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .drop_database(&database).await
+        .map_err(|e| e.to_string())
+}
+
+/// 创建集合、固定集合、时间序列集合或视图
+#[tauri::command]
+pub async fn mongodb_create_collection(
+    connection_id: String,
+    request: CreateCollectionRequest,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .create_collection(&request).await
+        .map_err(|e| e.to_string())
+}
+
+/// 删除集合或视图
+#[tauri::command]
+pub async fn mongodb_drop_collection(
+    connection_id: String,
+    database: Option<String>,
+    collection: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .drop_collection(database.as_deref(), &collection).await
+        .map_err(|e| e.to_string())
+}
+
+/// 重命名集合
+#[tauri::command]
+pub async fn mongodb_rename_collection(
+    connection_id: String,
+    database: Option<String>,
+    collection: String,
+    new_name: String,
+    drop_target: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .rename_collection(database.as_deref(), &collection, &new_name, drop_target.unwrap_or(false)).await
+        .map_err(|e| e.to_string())
+}
+
+/// 列出用户及其权限
+#[tauri::command]
+pub async fn mongodb_list_users(
+    connection_id: String,
+    database: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<UserInfo>, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .list_users(database.as_deref()).await
+        .map_err(|e| e.to_string())
+}
+
+/// 创建用户
+#[tauri::command]
+pub async fn mongodb_create_user(
+    connection_id: String,
+    request: CreateUserRequest,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .create_user(&request).await
+        .map_err(|e| e.to_string())
+}
+
+/// 修改用户密码、角色或自定义数据
+#[tauri::command]
+pub async fn mongodb_update_user(
+    connection_id: String,
+    request: UpdateUserRequest,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .update_user(&request).await
+        .map_err(|e| e.to_string())
+}
+
+/// 删除用户
+#[tauri::command]
+pub async fn mongodb_drop_user(
+    connection_id: String,
+    database: Option<String>,
+    user: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .drop_user(database.as_deref(), &user).await
+        .map_err(|e| e.to_string())
+}
+
+/// 列出角色及其权限
+#[tauri::command]
+pub async fn mongodb_list_roles(
+    connection_id: String,
+    database: Option<String>,
+    show_builtin: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<RoleInfo>, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .list_roles(database.as_deref(), show_builtin.unwrap_or(false)).await
+        .map_err(|e| e.to_string())
+}
+
+/// 创建角色
+#[tauri::command]
+pub async fn mongodb_create_role(
+    connection_id: String,
+    request: RoleRequest,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .create_role(&request).await
+        .map_err(|e| e.to_string())
+}
+
+/// 修改角色的权限或继承的角色
+#[tauri::command]
+pub async fn mongodb_update_role(
+    connection_id: String,
+    request: RoleRequest,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .update_role(&request).await
+        .map_err(|e| e.to_string())
+}
+
+/// 删除角色
+#[tauri::command]
+pub async fn mongodb_drop_role(
+    connection_id: String,
+    database: Option<String>,
+    role: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .drop_role(database.as_deref(), &role).await
+        .map_err(|e| e.to_string())
+}
+
+/// 获取连接的拓扑信息（单机、副本集成员或分片路由）
+#[tauri::command]
+pub async fn mongodb_topology(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<TopologyInfo, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .topology().await
+        .map_err(|e| e.to_string())
+}
+
+/// 把 SQL SELECT 翻译为 find 或 aggregate，供执行前预览生成的 mongosh 语句
+#[tauri::command]
+pub async fn mongodb_translate_sql(sql: String) -> Result<SqlTranslation, String> {
+    mongodb_sql::translate_sql(&sql).map_err(|e| e.to_string())
+}
+
+/// 翻译并执行 SQL SELECT
+#[tauri::command]
+pub async fn mongodb_query_sql(
+    connection_id: String,
+    sql: String,
+    extended_json: Option<ExtendedJsonMode>,
+    state: State<'_, AppState>,
+) -> Result<EnhancedQueryResult, String> {
+    let connections = state.connections.lock().await;
+    mongodb_connection(&connections, &connection_id)?
+        .query_sql(&sql, extended_json.unwrap_or_default()).await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,982 @@
+// Redis 特定命令
+
+use crate::commands::AppState;
+use crate::database::{
+    DatabaseAdapter,
+    redis::RedisConnection,
+    redis_pubsub::{
+        self, RedisStreamBatch, RedisStreamKind, RedisStreamOptions, RedisStreamSink,
+        RedisSubscriptionInfo,
+    },
+    redis_streams::{
+        PendingEntry, PendingQuery, PendingSummary, StreamConsumerInfo, StreamGroupInfo,
+        StreamInfo, StreamPage, StreamTrimStrategy,
+    },
+    redis_memory::{
+        self, MemoryAnalysisOptions, MemoryAnalysisProgress, MemoryAnalysisReport, MemoryAnalysisSink,
+    },
+    redis_scripting::{CachedScript, RedisFunctionLibrary, ScriptResult},
+    redis_batch::{RedisBatchRequest, RedisBatchResult, RedisTransactionInfo},
+    redis_admin::{ClientInfo, ClientKillFilter, ConfigDiff, ConfigEntry, InfoSection, SlowlogEntry},
+    redis_value::RedisReply,
+    redis_codec::{DecodedValue, ValueFormat, ValueLayer},
+    redis_keyspace::{KeyspaceExport, KeyspaceLevel, KeyspaceQuery},
+    redis_modules::{RedisModuleSupport, SearchIndexInfo, TimeSeriesAggregation, TimeSeriesInfo},
+    enhanced_types::{EnhancedQueryResult, RedisMemoryStats, StreamEntry},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use uuid::Uuid;
+
+/// 订阅/MONITOR 消息批次事件
+pub const REDIS_STREAM_MESSAGE_EVENT: &str = "redis-stream-message";
+/// 订阅/MONITOR 结束事件
+pub const REDIS_STREAM_CLOSED_EVENT: &str = "redis-stream-closed";
+/// 内存分析进度事件
+pub const REDIS_MEMORY_PROGRESS_EVENT: &str = "redis-memory-analysis-progress";
+/// 内存分析完成事件（包括取消和失败）
+pub const REDIS_MEMORY_COMPLETE_EVENT: &str = "redis-memory-analysis-complete";
+
+// 辅助函数：获取Redis连接
+fn redis_connection<'a>(
+    connections: &'a HashMap<String, Box<dyn DatabaseAdapter>>,
+    connection_id: &str,
+) -> Result<&'a RedisConnection, String> {
+    connections.get(connection_id)
+        .ok_or("连接未找到")?
+        .as_redis()
+        .ok_or_else(|| "该连接不是Redis连接".to_string())
+}
+
+/// 将订阅消息转发为 Tauri 事件
+struct TauriRedisStreamSink {
+    app: AppHandle,
+}
+
+impl RedisStreamSink for TauriRedisStreamSink {
+    fn on_batch(&self, batch: RedisStreamBatch) {
+        if let Err(e) = self.app.emit(REDIS_STREAM_MESSAGE_EVENT, batch) {
+            log::warn!("推送Redis订阅消息失败: {}", e);
+        }
+    }
+
+    fn on_closed(&self, subscription_id: &str, error: Option<String>, dropped: u64) {
+        let payload = serde_json::json!({
+            "subscription_id": subscription_id,
+            "error": error,
+            "dropped": dropped,
+        });
+        if let Err(e) = self.app.emit(REDIS_STREAM_CLOSED_EVENT, payload) {
+            log::warn!("推送Redis订阅结束事件失败: {}", e);
+        }
+    }
+}
+
+/// 订阅频道和模式，消息以 `redis-stream-message` 事件推送
+#[tauri::command]
+pub async fn redis_subscribe(
+    connection_id: String,
+    channels: Vec<String>,
+    patterns: Vec<String>,
+    options: Option<RedisStreamOptions>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let client = {
+        let connections = state.connections.lock().await;
+        redis_connection(&connections, &connection_id)?
+            .client()
+            .map_err(|e| e.to_string())?
+    };
+
+    let info = RedisSubscriptionInfo {
+        subscription_id: Uuid::new_v4().to_string(),
+        connection_id,
+        kind: RedisStreamKind::PubSub,
+        channels,
+        patterns,
+        started_at: chrono::Utc::now(),
+    };
+    let subscription_id = info.subscription_id.clone();
+
+    let handle = redis_pubsub::start_pubsub(
+        client,
+        info,
+        options.unwrap_or_default(),
+        Arc::new(TauriRedisStreamSink { app }),
+    )
+    .await
+    .map_err(|e| format!("订阅失败: {}", e))?;
+
+    let mut streams = state.redis_streams.lock().await;
+    streams.retain(|_, stream| !stream.is_finished());
+    streams.insert(subscription_id.clone(), handle);
+
+    Ok(subscription_id)
+}
+
+/// 开启 MONITOR，服务端命令以 `redis-stream-message` 事件推送
+#[tauri::command]
+pub async fn redis_start_monitor(
+    connection_id: String,
+    options: Option<RedisStreamOptions>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let client = {
+        let connections = state.connections.lock().await;
+        redis_connection(&connections, &connection_id)?
+            .client()
+            .map_err(|e| e.to_string())?
+    };
+
+    let info = RedisSubscriptionInfo {
+        subscription_id: Uuid::new_v4().to_string(),
+        connection_id,
+        kind: RedisStreamKind::Monitor,
+        channels: vec![],
+        patterns: vec![],
+        started_at: chrono::Utc::now(),
+    };
+    let subscription_id = info.subscription_id.clone();
+
+    let handle = redis_pubsub::start_monitor(
+        client,
+        info,
+        options.unwrap_or_default(),
+        Arc::new(TauriRedisStreamSink { app }),
+    )
+    .await
+    .map_err(|e| format!("开启监控失败: {}", e))?;
+
+    let mut streams = state.redis_streams.lock().await;
+    streams.retain(|_, stream| !stream.is_finished());
+    streams.insert(subscription_id.clone(), handle);
+
+    Ok(subscription_id)
+}
+
+/// 停止订阅或 MONITOR
+#[tauri::command]
+pub async fn redis_stop_stream(
+    subscription_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let handle = state.redis_streams.lock().await
+        .remove(&subscription_id)
+        .ok_or("订阅未找到")?;
+    handle.stop();
+    Ok(())
+}
+
+/// 列出运行中的订阅和 MONITOR
+#[tauri::command]
+pub async fn redis_list_streams(
+    connection_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<RedisSubscriptionInfo>, String> {
+    let mut streams = state.redis_streams.lock().await;
+    streams.retain(|_, stream| !stream.is_finished());
+    Ok(streams.values()
+        .filter(|handle| connection_id.as_ref().is_none_or(|id| &handle.info.connection_id == id))
+        .map(|handle| handle.info.clone())
+        .collect())
+}
+
+// ===== Redis Streams =====
+
+/// 分页浏览Stream条目
+#[tauri::command]
+pub async fn redis_stream_range(
+    connection_id: String,
+    key: String,
+    start: Option<String>,
+    count: usize,
+    reverse: bool,
+    state: State<'_, AppState>,
+) -> Result<StreamPage, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .stream_range(&key, start.as_deref(), count, reverse).await
+        .map_err(|e| e.to_string())
+}
+
+/// 获取Stream统计信息
+#[tauri::command]
+pub async fn redis_stream_info(
+    connection_id: String,
+    key: String,
+    state: State<'_, AppState>,
+) -> Result<StreamInfo, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .stream_info(&key).await
+        .map_err(|e| e.to_string())
+}
+
+/// 获取消费者组列表
+#[tauri::command]
+pub async fn redis_stream_groups(
+    connection_id: String,
+    key: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<StreamGroupInfo>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .stream_groups(&key).await
+        .map_err(|e| e.to_string())
+}
+
+/// 获取消费者组内的消费者列表
+#[tauri::command]
+pub async fn redis_stream_consumers(
+    connection_id: String,
+    key: String,
+    group: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<StreamConsumerInfo>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .stream_consumers(&key, &group).await
+        .map_err(|e| e.to_string())
+}
+
+/// 获取待处理条目汇总
+#[tauri::command]
+pub async fn redis_stream_pending_summary(
+    connection_id: String,
+    key: String,
+    group: String,
+    state: State<'_, AppState>,
+) -> Result<PendingSummary, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .stream_pending_summary(&key, &group).await
+        .map_err(|e| e.to_string())
+}
+
+/// 获取待处理条目明细
+#[tauri::command]
+pub async fn redis_stream_pending(
+    connection_id: String,
+    key: String,
+    group: String,
+    query: PendingQuery,
+    state: State<'_, AppState>,
+) -> Result<Vec<PendingEntry>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .stream_pending(&key, &group, &query).await
+        .map_err(|e| e.to_string())
+}
+
+/// 确认条目
+#[tauri::command]
+pub async fn redis_stream_ack(
+    connection_id: String,
+    key: String,
+    group: String,
+    ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .stream_ack(&key, &group, &ids).await
+        .map_err(|e| e.to_string())
+}
+
+/// 将待处理条目转给其他消费者
+#[tauri::command]
+pub async fn redis_stream_claim(
+    connection_id: String,
+    key: String,
+    group: String,
+    consumer: String,
+    min_idle_ms: u64,
+    ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<StreamEntry>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .stream_claim(&key, &group, &consumer, min_idle_ms, &ids).await
+        .map_err(|e| e.to_string())
+}
+
+/// 删除条目
+#[tauri::command]
+pub async fn redis_stream_delete(
+    connection_id: String,
+    key: String,
+    ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .stream_delete(&key, &ids).await
+        .map_err(|e| e.to_string())
+}
+
+/// 裁剪Stream
+#[tauri::command]
+pub async fn redis_stream_trim(
+    connection_id: String,
+    key: String,
+    strategy: StreamTrimStrategy,
+    approximate: bool,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .stream_trim(&key, &strategy, approximate).await
+        .map_err(|e| e.to_string())
+}
+
+// ===== 内存分析 =====
+
+/// 将内存分析进度和结果转发为 Tauri 事件
+struct TauriMemoryAnalysisSink {
+    app: AppHandle,
+}
+
+impl MemoryAnalysisSink for TauriMemoryAnalysisSink {
+    fn on_progress(&self, progress: MemoryAnalysisProgress) {
+        if let Err(e) = self.app.emit(REDIS_MEMORY_PROGRESS_EVENT, progress) {
+            log::warn!("推送内存分析进度失败: {}", e);
+        }
+    }
+
+    fn on_complete(&self, job_id: &str, result: Result<MemoryAnalysisReport, String>) {
+        let payload = match result {
+            Ok(report) => serde_json::json!({ "job_id": job_id, "report": report }),
+            Err(error) => serde_json::json!({ "job_id": job_id, "error": error }),
+        };
+        if let Err(e) = self.app.emit(REDIS_MEMORY_COMPLETE_EVENT, payload) {
+            log::warn!("推送内存分析结果失败: {}", e);
+        }
+    }
+}
+
+/// 从 INFO memory 获取内存统计
+#[tauri::command]
+pub async fn redis_get_memory_stats(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<RedisMemoryStats, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .memory_stats().await
+        .map_err(|e| e.to_string())
+}
+
+/// 启动后台大键与内存分析，返回任务ID
+#[tauri::command]
+pub async fn redis_start_memory_analysis(
+    connection_id: String,
+    options: Option<MemoryAnalysisOptions>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    let (client, database) = {
+        let connections = state.connections.lock().await;
+        let redis = redis_connection(&connections, &connection_id)?;
+        let client = redis.client().map_err(|e| e.to_string())?;
+        (client, options.database.unwrap_or_else(|| redis.current_database()))
+    };
+
+    let job_id = Uuid::new_v4().to_string();
+    let handle = redis_memory::start_analysis(
+        client,
+        job_id.clone(),
+        connection_id,
+        database,
+        options,
+        Arc::new(TauriMemoryAnalysisSink { app }),
+    );
+
+    let mut jobs = state.redis_analysis_jobs.lock().await;
+    jobs.retain(|_, job| !job.is_finished());
+    jobs.insert(job_id.clone(), handle);
+
+    Ok(job_id)
+}
+
+/// 取消内存分析，已扫描部分的报告仍会通过完成事件推送
+#[tauri::command]
+pub async fn redis_cancel_memory_analysis(
+    job_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut jobs = state.redis_analysis_jobs.lock().await;
+    let job = jobs.remove(&job_id).ok_or("分析任务未找到")?;
+    job.cancel();
+    Ok(())
+}
+
+// ===== Lua 脚本与 Functions =====
+
+/// 执行Lua脚本（SCRIPT LOAD + EVALSHA）
+#[tauri::command]
+pub async fn redis_eval_script(
+    connection_id: String,
+    script: String,
+    keys: Vec<String>,
+    args: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<ScriptResult, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .eval_script(&script, &keys, &args).await
+        .map_err(|e| e.to_string())
+}
+
+/// 按SHA执行已缓存的脚本
+#[tauri::command]
+pub async fn redis_eval_sha(
+    connection_id: String,
+    sha: String,
+    keys: Vec<String>,
+    args: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<ScriptResult, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .eval_sha(&sha, &keys, &args).await
+        .map_err(|e| e.to_string())
+}
+
+/// 加载脚本，返回SHA
+#[tauri::command]
+pub async fn redis_script_load(
+    connection_id: String,
+    script: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .script_load(&script).await
+        .map_err(|e| e.to_string())
+}
+
+/// 检查脚本是否已缓存在服务端
+#[tauri::command]
+pub async fn redis_script_exists(
+    connection_id: String,
+    shas: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<bool>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .script_exists(&shas).await
+        .map_err(|e| e.to_string())
+}
+
+/// 清空服务端脚本缓存
+#[tauri::command]
+pub async fn redis_script_flush(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .script_flush().await
+        .map_err(|e| e.to_string())
+}
+
+/// 列出本连接加载过的脚本
+#[tauri::command]
+pub async fn redis_list_scripts(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<CachedScript>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .list_cached_scripts().await
+        .map_err(|e| e.to_string())
+}
+
+/// 列出函数库
+#[tauri::command]
+pub async fn redis_function_list(
+    connection_id: String,
+    library_pattern: Option<String>,
+    with_code: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<RedisFunctionLibrary>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .function_list(library_pattern.as_deref(), with_code).await
+        .map_err(|e| e.to_string())
+}
+
+/// 加载函数库，返回库名
+#[tauri::command]
+pub async fn redis_function_load(
+    connection_id: String,
+    code: String,
+    replace: bool,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .function_load(&code, replace).await
+        .map_err(|e| e.to_string())
+}
+
+/// 删除函数库
+#[tauri::command]
+pub async fn redis_function_delete(
+    connection_id: String,
+    library: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .function_delete(&library).await
+        .map_err(|e| e.to_string())
+}
+
+/// 调用函数
+#[tauri::command]
+pub async fn redis_function_call(
+    connection_id: String,
+    function: String,
+    keys: Vec<String>,
+    args: Vec<String>,
+    read_only: bool,
+    state: State<'_, AppState>,
+) -> Result<ScriptResult, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .function_call(&function, &keys, &args, read_only).await
+        .map_err(|e| e.to_string())
+}
+
+/// 以管道或 MULTI/EXEC 事务方式批量执行命令
+#[tauri::command]
+pub async fn redis_execute_batch(
+    connection_id: String,
+    request: RedisBatchRequest,
+    state: State<'_, AppState>,
+) -> Result<RedisBatchResult, String> {
+    let target = {
+        let connections = state.connections.lock().await;
+        redis_connection(&connections, &connection_id)?
+            .batch_target()
+            .map_err(|e| e.to_string())?
+    };
+    target.execute(&request).await
+        .map_err(|e| e.to_string())
+}
+
+/// 开启事务会话并 WATCH 指定的键，返回会话ID
+#[tauri::command]
+pub async fn redis_begin_transaction(
+    connection_id: String,
+    watch_keys: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let target = {
+        let connections = state.connections.lock().await;
+        redis_connection(&connections, &connection_id)?
+            .batch_target()
+            .map_err(|e| e.to_string())?
+    };
+
+    let info = RedisTransactionInfo {
+        transaction_id: Uuid::new_v4().to_string(),
+        connection_id,
+        watch_keys,
+        started_at: chrono::Utc::now(),
+    };
+    let transaction_id = info.transaction_id.clone();
+
+    let session = target.begin_transaction(info).await
+        .map_err(|e| format!("开启事务失败: {}", e))?;
+    state.redis_transactions.lock().await
+        .insert(transaction_id.clone(), session);
+
+    Ok(transaction_id)
+}
+
+/// 在事务会话的连接上执行命令，用于 MULTI 之前读取被监视的键
+#[tauri::command]
+pub async fn redis_transaction_query(
+    transaction_id: String,
+    command: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<RedisReply, String> {
+    let session = state.redis_transactions.lock().await
+        .get(&transaction_id)
+        .cloned()
+        .ok_or("事务会话未找到")?;
+    session.query(&command).await
+        .map_err(|e| e.to_string())
+}
+
+/// 执行事务会话的 MULTI/EXEC，会话随之结束
+#[tauri::command]
+pub async fn redis_exec_transaction(
+    transaction_id: String,
+    commands: Vec<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<RedisBatchResult, String> {
+    let session = state.redis_transactions.lock().await
+        .remove(&transaction_id)
+        .ok_or("事务会话未找到")?;
+    session.exec(&commands).await
+        .map_err(|e| e.to_string())
+}
+
+/// 放弃事务会话，关闭连接并取消 WATCH
+#[tauri::command]
+pub async fn redis_discard_transaction(
+    transaction_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.redis_transactions.lock().await
+        .remove(&transaction_id)
+        .ok_or("事务会话未找到")?;
+    Ok(())
+}
+
+/// 列出进行中的事务会话
+#[tauri::command]
+pub async fn redis_list_transactions(
+    connection_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<RedisTransactionInfo>, String> {
+    let transactions = state.redis_transactions.lock().await;
+    Ok(transactions.values()
+        .filter(|session| connection_id.as_ref().is_none_or(|id| &session.info.connection_id == id))
+        .map(|session| session.info.clone())
+        .collect())
+}
+
+/// 展开键空间命名空间树的一个节点
+#[tauri::command]
+pub async fn redis_keyspace_children(
+    connection_id: String,
+    query: KeyspaceQuery,
+    state: State<'_, AppState>,
+) -> Result<KeyspaceLevel, String> {
+    let target = {
+        let connections = state.connections.lock().await;
+        redis_connection(&connections, &connection_id)?
+            .keyspace_target()
+            .map_err(|e| e.to_string())?
+    };
+    target.children(&query).await
+        .map_err(|e| e.to_string())
+}
+
+/// 删除命名空间下的所有键
+#[tauri::command]
+pub async fn redis_keyspace_delete(
+    connection_id: String,
+    prefix: String,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let target = {
+        let connections = state.connections.lock().await;
+        redis_connection(&connections, &connection_id)?
+            .keyspace_target()
+            .map_err(|e| e.to_string())?
+    };
+    target.delete(&prefix).await
+        .map_err(|e| e.to_string())
+}
+
+/// 设置命名空间下所有键的过期时间，ttl 为空时移除过期时间
+#[tauri::command]
+pub async fn redis_keyspace_expire(
+    connection_id: String,
+    prefix: String,
+    ttl: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let target = {
+        let connections = state.connections.lock().await;
+        redis_connection(&connections, &connection_id)?
+            .keyspace_target()
+            .map_err(|e| e.to_string())?
+    };
+    target.expire(&prefix, ttl).await
+        .map_err(|e| e.to_string())
+}
+
+/// 导出命名空间下的键
+#[tauri::command]
+pub async fn redis_keyspace_export(
+    connection_id: String,
+    prefix: String,
+    max_keys: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<KeyspaceExport, String> {
+    let target = {
+        let connections = state.connections.lock().await;
+        redis_connection(&connections, &connection_id)?
+            .keyspace_target()
+            .map_err(|e| e.to_string())?
+    };
+    target.export(&prefix, max_keys.unwrap_or(10_000)).await
+        .map_err(|e| e.to_string())
+}
+
+/// 读取字符串值并自动识别编码
+#[tauri::command]
+pub async fn redis_get_decoded_value(
+    connection_id: String,
+    key: String,
+    state: State<'_, AppState>,
+) -> Result<DecodedValue, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .get_decoded_value(&key).await
+        .map_err(|e| e.to_string())
+}
+
+/// 按读取时识别的编码写回字符串值
+#[tauri::command]
+pub async fn redis_set_encoded_value(
+    connection_id: String,
+    key: String,
+    text: String,
+    layers: Vec<ValueLayer>,
+    format: ValueFormat,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .set_encoded_value(&key, &text, &layers, format).await
+        .map_err(|e| e.to_string())
+}
+
+/// INFO，按分节解析为带类型的字段
+#[tauri::command]
+pub async fn redis_server_info(
+    connection_id: String,
+    section: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<InfoSection>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .server_info(section.as_deref()).await
+        .map_err(|e| e.to_string())
+}
+
+/// CONFIG GET
+#[tauri::command]
+pub async fn redis_config_get(
+    connection_id: String,
+    pattern: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ConfigEntry>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .config_get(pattern.as_deref().unwrap_or("*")).await
+        .map_err(|e| e.to_string())
+}
+
+/// CONFIG SET
+#[tauri::command]
+pub async fn redis_config_set(
+    connection_id: String,
+    name: String,
+    value: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .config_set(&name, &value).await
+        .map_err(|e| e.to_string())
+}
+
+/// 与服务端版本默认值不同的配置项，默认值未知的单独列出
+#[tauri::command]
+pub async fn redis_config_diff(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<ConfigDiff, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .config_diff().await
+        .map_err(|e| e.to_string())
+}
+
+/// SLOWLOG GET
+#[tauri::command]
+pub async fn redis_slowlog_get(
+    connection_id: String,
+    count: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SlowlogEntry>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .slowlog_get(count).await
+        .map_err(|e| e.to_string())
+}
+
+/// SLOWLOG RESET
+#[tauri::command]
+pub async fn redis_slowlog_reset(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .slowlog_reset().await
+        .map_err(|e| e.to_string())
+}
+
+/// CLIENT LIST
+#[tauri::command]
+pub async fn redis_client_list(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ClientInfo>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .client_list().await
+        .map_err(|e| e.to_string())
+}
+
+/// CLIENT KILL
+#[tauri::command]
+pub async fn redis_client_kill(
+    connection_id: String,
+    filter: ClientKillFilter,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .client_kill(&filter).await
+        .map_err(|e| e.to_string())
+}
+
+/// 检测 Redis Stack 模块
+#[tauri::command]
+pub async fn redis_detect_modules(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<RedisModuleSupport, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .detect_modules().await
+        .map_err(|e| e.to_string())
+}
+
+/// JSON.GET
+#[tauri::command]
+pub async fn redis_json_get(
+    connection_id: String,
+    key: String,
+    paths: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .json_get(&key, &paths.unwrap_or_default()).await
+        .map_err(|e| e.to_string())
+}
+
+/// JSON.SET
+#[tauri::command]
+pub async fn redis_json_set(
+    connection_id: String,
+    key: String,
+    path: String,
+    json: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .json_set(&key, &path, &json).await
+        .map_err(|e| e.to_string())
+}
+
+/// JSON.DEL
+#[tauri::command]
+pub async fn redis_json_del(
+    connection_id: String,
+    key: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .json_del(&key, &path).await
+        .map_err(|e| e.to_string())
+}
+
+/// FT._LIST
+#[tauri::command]
+pub async fn redis_search_indexes(
+    connection_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .search_indexes().await
+        .map_err(|e| e.to_string())
+}
+
+/// FT.INFO
+#[tauri::command]
+pub async fn redis_search_index_info(
+    connection_id: String,
+    index: String,
+    state: State<'_, AppState>,
+) -> Result<SearchIndexInfo, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .search_index_info(&index).await
+        .map_err(|e| e.to_string())
+}
+
+/// FT.SEARCH，默认返回前 50 条
+#[tauri::command]
+pub async fn redis_search(
+    connection_id: String,
+    index: String,
+    query: String,
+    offset: Option<u64>,
+    limit: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<EnhancedQueryResult, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .search(&index, &query, offset.unwrap_or(0), limit.unwrap_or(50)).await
+        .map_err(|e| e.to_string())
+}
+
+/// TS.RANGE
+#[tauri::command]
+pub async fn redis_ts_range(
+    connection_id: String,
+    key: String,
+    from: Option<i64>,
+    to: Option<i64>,
+    count: Option<u64>,
+    aggregation: Option<TimeSeriesAggregation>,
+    state: State<'_, AppState>,
+) -> Result<EnhancedQueryResult, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .ts_range(&key, from, to, count, aggregation.as_ref()).await
+        .map_err(|e| e.to_string())
+}
+
+/// TS.INFO
+#[tauri::command]
+pub async fn redis_ts_info(
+    connection_id: String,
+    key: String,
+    state: State<'_, AppState>,
+) -> Result<TimeSeriesInfo, String> {
+    let connections = state.connections.lock().await;
+    redis_connection(&connections, &connection_id)?
+        .ts_info(&key).await
+        .map_err(|e| e.to_string())
+}
@@ -2,26 +2,24 @@
 
 use std::collections::HashMap;
 use qusc_db::database::{
+    adapter::DatabaseAdapterFactory,
     enhanced_types::*,
-    test_adapter::{AdapterConverter, TestDatabaseAdapter},
-    ConnectionConfig as LegacyConnectionConfig,
-    QueryResult as LegacyQueryResult,
-    DatabaseConnection,
-    DatabaseType as LegacyDatabaseType,
-    TableInfo as LegacyTableInfo,
-    ColumnInfo as LegacyColumnInfo,
 };
 use anyhow::Result;
 
 // 创建模拟的MySQL连接用于集成测试
 struct IntegrationMockConnection {
     connected: bool,
-    db_type: LegacyDatabaseType,
+    db_type: DatabaseType,
 }
 
 #[async_trait::async_trait]
-impl DatabaseConnection for IntegrationMockConnection {
-    async fn connect(&mut self, _config: &LegacyConnectionConfig) -> Result<()> {
+impl DatabaseAdapter for IntegrationMockConnection {
+    fn db_type(&self) -> DatabaseType {
+        self.db_type.clone()
+    }
+
+    async fn connect(&mut self, _config: &ConnectionConfig) -> Result<()> {
         self.connected = true;
         Ok(())
     }
@@ -31,11 +29,11 @@ impl DatabaseConnection for IntegrationMockConnection {
         Ok(())
     }
 
-    async fn execute(&self, query: &str) -> Result<LegacyQueryResult> {
+    async fn execute_raw(&self, query: &str) -> Result<QueryResult> {
         // 根据查询类型返回不同的模拟结果
         match query.to_uppercase().as_str() {
             q if q.starts_with("SELECT") => {
-                Ok(LegacyQueryResult {
+                Ok(QueryResult {
                     columns: vec!["id".to_string(), "name".to_string(), "email".to_string()],
                     rows: vec![
                         vec!["1".to_string(), "Alice".to_string(), "alice@example.com".to_string()],
@@ -47,7 +45,7 @@ impl DatabaseConnection for IntegrationMockConnection {
                 })
             }
             q if q.starts_with("INSERT") => {
-                Ok(LegacyQueryResult {
+                Ok(QueryResult {
                     columns: vec![],
                     rows: vec![],
                     affected_rows: 1,
@@ -55,7 +53,7 @@ impl DatabaseConnection for IntegrationMockConnection {
                 })
             }
             q if q.starts_with("UPDATE") => {
-                Ok(LegacyQueryResult {
+                Ok(QueryResult {
                     columns: vec![],
                     rows: vec![],
                     affected_rows: 2,
@@ -63,7 +61,7 @@ impl DatabaseConnection for IntegrationMockConnection {
                 })
             }
             q if q.starts_with("DELETE") => {
-                Ok(LegacyQueryResult {
+                Ok(QueryResult {
                     columns: vec![],
                     rows: vec![],
                     affected_rows: 1,
@@ -71,7 +69,7 @@ impl DatabaseConnection for IntegrationMockConnection {
                 })
             }
             _ => {
-                Ok(LegacyQueryResult {
+                Ok(QueryResult {
                     columns: vec!["result".to_string()],
                     rows: vec![vec!["OK".to_string()]],
                     affected_rows: 0,
@@ -81,55 +79,19 @@ impl DatabaseConnection for IntegrationMockConnection {
         }
     }
 
-    async fn get_schema(&self) -> Result<Vec<LegacyTableInfo>> {
-        Ok(vec![
-            LegacyTableInfo {
-                name: "users".to_string(),
-                columns: vec![
-                    LegacyColumnInfo {
-                        name: "id".to_string(),
-                        data_type: "INT AUTO_INCREMENT".to_string(),
-                        nullable: false,
-                        primary_key: true,
-                    },
-                    LegacyColumnInfo {
-                        name: "name".to_string(),
-                        data_type: "VARCHAR(255)".to_string(),
-                        nullable: false,
-                        primary_key: false,
-                    },
-                    LegacyColumnInfo {
-                        name: "email".to_string(),
-                        data_type: "VARCHAR(255)".to_string(),
-                        nullable: true,
-                        primary_key: false,
-                    },
-                ],
-            },
-            LegacyTableInfo {
-                name: "orders".to_string(),
-                columns: vec![
-                    LegacyColumnInfo {
-                        name: "id".to_string(),
-                        data_type: "INT AUTO_INCREMENT".to_string(),
-                        nullable: false,
-                        primary_key: true,
-                    },
-                    LegacyColumnInfo {
-                        name: "user_id".to_string(),
-                        data_type: "INT".to_string(),
-                        nullable: false,
-                        primary_key: false,
-                    },
-                    LegacyColumnInfo {
-                        name: "amount".to_string(),
-                        data_type: "DECIMAL(10,2)".to_string(),
-                        nullable: false,
-                        primary_key: false,
-                    },
-                ],
-            },
-        ])
+    async fn get_schema(&self) -> Result<DatabaseSchema> {
+        Ok(DatabaseSchema::new("test_db", vec![
+            TableSchema::new("users", vec![
+                ColumnInfo::new("id", "INT AUTO_INCREMENT", false, true),
+                ColumnInfo::new("name", "VARCHAR(255)", false, false),
+                ColumnInfo::new("email", "VARCHAR(255)", true, false),
+            ]),
+            TableSchema::new("orders", vec![
+                ColumnInfo::new("id", "INT AUTO_INCREMENT", false, true),
+                ColumnInfo::new("user_id", "INT", false, false),
+                ColumnInfo::new("amount", "DECIMAL(10,2)", false, false),
+            ]),
+        ]))
     }
 
     async fn get_databases(&self) -> Result<Vec<String>> {
@@ -153,11 +115,22 @@ impl DatabaseConnection for IntegrationMockConnection {
 #[tokio::test]
 async fn test_full_mysql_workflow() {
     // 测试完整的MySQL工作流程
-    let mock_conn = Box::new(IntegrationMockConnection {
+    let mut adapter = IntegrationMockConnection {
         connected: false,
-        db_type: LegacyDatabaseType::MySQL,
-    });
-    let adapter = TestDatabaseAdapter::new(mock_conn, DatabaseType::MySQL);
+        db_type: DatabaseType::MySQL,
+    };
+    let config = ConnectionConfig {
+        db_type: DatabaseType::MySQL,
+        host: "localhost".to_string(),
+        port: 3306,
+        username: Some("root".to_string()),
+        password: None,
+        database: Some("test_db".to_string()),
+        options: HashMap::new(),
+    };
+    adapter.connect(&config).await.unwrap();
+    assert!(adapter.is_connected());
+    assert_eq!(adapter.get_databases().await.unwrap().len(), 3);
 
     // 1. 测试查询执行
     let select_result = adapter.execute("SELECT * FROM users").await.unwrap();
@@ -234,8 +207,13 @@ async fn test_full_mysql_workflow() {
     // 6. 测试查询建议
     let suggestions = adapter.get_query_suggestions("SELECT");
     assert!(!suggestions.is_empty());
-    assert_eq!(suggestions[0].text, "SELECT");
+    assert_eq!(suggestions[0].text, "SELECT * FROM");
     assert_eq!(suggestions[0].category, SuggestionCategory::Keyword);
+
+    // 7. 测试实时统计和断开连接
+    assert!(adapter.get_real_time_stats().await.unwrap().is_none());
+    adapter.disconnect().await.unwrap();
+    assert!(!adapter.is_connected());
 }
 
 #[tokio::test]
@@ -243,7 +221,7 @@ async fn test_database_type_configurations() {
     // 测试不同数据库类型的配置
 
     // MySQL配置测试
-    let mysql_config = AdapterConverter::get_ui_config_for_db_type(DatabaseType::MySQL);
+    let mysql_config = DatabaseUIConfig::for_db_type(&DatabaseType::MySQL);
     assert_eq!(mysql_config.display_mode, DisplayMode::Table);
     assert_eq!(mysql_config.editor_config.language, "mysql");
     assert!(!mysql_config.monitoring_capable);
@@ -251,7 +229,7 @@ async fn test_database_type_configurations() {
     assert!(mysql_config.export_formats.contains(&ExportFormat::Excel));
 
     // PostgreSQL配置测试
-    let pg_config = AdapterConverter::get_ui_config_for_db_type(DatabaseType::PostgreSQL);
+    let pg_config = DatabaseUIConfig::for_db_type(&DatabaseType::PostgreSQL);
     assert_eq!(pg_config.display_mode, DisplayMode::Table);
     assert_eq!(pg_config.editor_config.language, "postgresql");
     assert!(!pg_config.monitoring_capable);
@@ -260,7 +238,7 @@ async fn test_database_type_configurations() {
     assert_eq!(pg_theme.icon, "🐘");
 
     // Redis配置测试
-    let redis_config = AdapterConverter::get_ui_config_for_db_type(DatabaseType::Redis);
+    let redis_config = DatabaseUIConfig::for_db_type(&DatabaseType::Redis);
     assert_eq!(redis_config.display_mode, DisplayMode::KeyValue);
     assert_eq!(redis_config.editor_config.language, "redis");
     assert!(redis_config.monitoring_capable);
@@ -273,7 +251,7 @@ async fn test_database_type_configurations() {
     assert_eq!(redis_theme.icon, "🔴");
 
     // MongoDB配置测试
-    let mongo_config = AdapterConverter::get_ui_config_for_db_type(DatabaseType::MongoDB);
+    let mongo_config = DatabaseUIConfig::for_db_type(&DatabaseType::MongoDB);
     assert_eq!(mongo_config.display_mode, DisplayMode::Document);
     assert_eq!(mongo_config.editor_config.language, "mongodb");
     assert!(!mongo_config.monitoring_capable);
@@ -286,7 +264,7 @@ async fn test_database_type_configurations() {
     assert_eq!(mongo_theme.icon, "🍃");
 
     // SQLite配置测试  
-    let sqlite_config = AdapterConverter::get_ui_config_for_db_type(DatabaseType::SQLite);
+    let sqlite_config = DatabaseUIConfig::for_db_type(&DatabaseType::SQLite);
     assert_eq!(sqlite_config.display_mode, DisplayMode::Table);
     assert_eq!(sqlite_config.editor_config.language, "sqlite");
     assert!(!sqlite_config.editor_config.auto_complete);
@@ -297,13 +275,13 @@ async fn test_database_type_configurations() {
 }
 
 #[tokio::test]
-async fn test_connection_config_conversion() {
-    // 测试连接配置转换
+async fn test_connection_config_and_factory() {
+    // 测试连接配置的序列化以及适配器工厂
     let mut options = HashMap::new();
     options.insert("charset".to_string(), "utf8mb4".to_string());
     options.insert("timeout".to_string(), "30".to_string());
 
-    let enhanced_config = ConnectionConfig {
+    let config = ConnectionConfig {
         db_type: DatabaseType::PostgreSQL,
         host: "localhost".to_string(),
         port: 5432,
@@ -313,16 +291,23 @@ async fn test_connection_config_conversion() {
         options,
     };
 
-    let legacy_config = AdapterConverter::to_legacy_config(&enhanced_config);
-
-    // 验证转换结果
-    assert_eq!(legacy_config.host, "localhost");
-    assert_eq!(legacy_config.port, 5432);
-    assert_eq!(legacy_config.username, Some("postgres".to_string()));
-    assert_eq!(legacy_config.password, Some("secret123".to_string()));
-    assert_eq!(legacy_config.database, Some("app_db".to_string()));
-    assert_eq!(legacy_config.options.get("charset"), Some(&"utf8mb4".to_string()));
-    assert_eq!(legacy_config.options.get("timeout"), Some(&"30".to_string()));
+    let json = serde_json::to_string(&config).unwrap();
+    let deserialized: ConnectionConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.db_type, DatabaseType::PostgreSQL);
+    assert_eq!(deserialized.host, "localhost");
+    assert_eq!(deserialized.port, 5432);
+    assert_eq!(deserialized.username, Some("postgres".to_string()));
+    assert_eq!(deserialized.database, Some("app_db".to_string()));
+    assert_eq!(deserialized.options.get("charset"), Some(&"utf8mb4".to_string()));
+
+    // 工厂按配置中的数据库类型创建未连接的适配器
+    let adapter = DatabaseAdapterFactory::create_adapter(&deserialized.db_type).unwrap();
+    assert_eq!(adapter.db_type(), DatabaseType::PostgreSQL);
+    assert!(!adapter.is_connected());
+    assert_eq!(adapter.get_ui_config().editor_config.language, "postgresql");
+
+    // 暂不支持的类型直接返回错误
+    assert!(DatabaseAdapterFactory::create_adapter(&DatabaseType::SQLite).is_err());
 }
 
 #[test]
@@ -360,7 +345,7 @@ fn test_query_result_serialization() {
             warnings: vec![],
         },
         execution_time: 100,
-        ui_config: AdapterConverter::get_ui_config_for_db_type(DatabaseType::MySQL),
+        ui_config: DatabaseUIConfig::for_db_type(&DatabaseType::MySQL),
     };
 
     // 测试序列化